@@ -64,6 +64,14 @@ impl From<JsProp> for JsValue {
                 serde_wasm_bindgen::to_value(&v).unwrap()
             }
             Prop::Document(doc) => JSDocumentProp(doc).into(),
+            Prop::I8(v) => v.into(),
+            Prop::I16(v) => v.into(),
+            Prop::Decimal(v) => v.to_string().into(),
+            Prop::Bytes(v) => js_sys::Uint8Array::from(v.as_ref()).into(),
+            Prop::Array(v) => {
+                let v: Array = v.iter_prop().map(|v| JsValue::from(JsProp(v))).collect();
+                v.into()
+            }
         }
     }
 }
@@ -137,6 +137,7 @@ fn raphtory(py: Python<'_>, m: &PyModule) -> PyResult<()> {
         reddit_hyperlink_graph,
         reddit_hyperlink_graph_local,
         karate_club_graph,
+        edge_list_graph,
     );
     m.add_submodule(graph_loader_module)?;
 
@@ -146,6 +147,12 @@ fn raphtory(py: Python<'_>, m: &PyModule) -> PyResult<()> {
         graph_gen_module,
         random_attachment,
         ba_preferential_attachment,
+        erdos_renyi,
+        watts_strogatz,
+        stochastic_block_model,
+        configuration_model,
+        activity_driven,
+        hawkes,
     );
     m.add_submodule(graph_gen_module)?;
 
@@ -2551,6 +2551,41 @@ def test_bincode_has_not_changed(datadir):
         )
 
 
+def test_memory_stats():
+    g = Graph()
+    for t in range(100):
+        g.add_edge(t, 1, 2, properties={"weight": float(t)}, layer="a")
+    g.add_node(0, 1, properties={"name": "node one"})
+
+    stats = g.memory_stats()
+    assert stats["total_bytes"] > 0
+    assert stats["nodes"]["count"] == 2
+    assert stats["edge_timestamps"]["count"] == 100
+    assert stats["layers"]["a"]["edge_updates"]["count"] == 100
+    weight = stats["properties"][0]
+    assert weight["entity"] == "edge"
+    assert weight["name"] == "weight"
+    assert weight["count"] == 100
+    assert g.window(0, 10).memory_stats() == stats
+
+
+def test_to_parquet():
+    g = Graph()
+    g.add_edge(1, 1, 2, properties={"weight": 1.0}, layer="a")
+    g.add_edge(5, 2, 3, properties={"weight": 2.0})
+    g.add_node(1, 1, properties={"name": "node one"}, node_type="person")
+
+    tmpdirname = tempfile.TemporaryDirectory()
+    g.window(0, 3).to_parquet(tmpdirname.name)
+    edges = pd.read_parquet(os.path.join(tmpdirname.name, "edges.parquet"))
+    assert edges.to_dict("records") == [
+        {"src": 1, "dst": 2, "time": 1, "layer": "a", "weight": 1.0}
+    ]
+    nodes = pd.read_parquet(os.path.join(tmpdirname.name, "nodes.parquet"))
+    assert set(nodes["id"]) == {1, 2}
+    assert list(nodes[nodes["id"] == 1]["name"]) == ["node one"]
+
+
 # def currently_broken_fuzzy_search(): #TODO: Fix fuzzy searching for properties
 # g = Graph()
 # g.add_edge(2,"haaroon","hamza", properties={"value":60,"value_f":31.3,"value_str":"abc123"})
@@ -2577,3 +2612,26 @@ def test_bincode_has_not_changed(datadir):
 # l_index = l_g.index()
 # assert len(index.search_edges("value_str:ddddd")) == 1
 # assert len(index.search_edges("value:>60")) == 2
+
+
+def test_load_with_mapping():
+    with tempfile.TemporaryDirectory() as tmp:
+        data = os.path.join(tmp, "transfers.csv")
+        with open(data, "w") as f:
+            f.write("ts,from,to,amount\n2024-01-01 00:00,a,b,1.5\n2024-01-01 00:01,b,c,2\n")
+        spec = """
+time: ts
+time_format: "%Y-%m-%d %H:%M"
+casts:
+  amount: f64
+edge:
+  src: from
+  dst: to
+  layer_name: transfers
+  properties: [amount]
+"""
+        g = Graph()
+        assert g.load_with_mapping(data, spec)["loaded"] == 2
+        assert g.edge("b", "c").properties["amount"] == 2.0
+        assert g.edge("a", "b").layer_names == ["transfers"]
+        assert g.earliest_time == 1704067200000
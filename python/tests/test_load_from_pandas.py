@@ -9,6 +9,7 @@ import pytest
 from raphtory import Graph, PersistentGraph, PyDirection
 from raphtory import algorithms
 from raphtory import graph_loader
+import os
 import tempfile
 from math import isclose
 import datetime
@@ -1061,3 +1062,61 @@ def test_load_edge_deletions_from_pandas():
     assert g.window(10, 12).edges.src.id.collect() == [1, 2, 3, 4, 5]
     g.load_edges_deletions_from_pandas(edge_dels_df, "src", "dst", "time")
     assert g.window(10, 12).edges.src.id.collect() == [1, 2, 5]
+
+
+def test_load_from_pandas_error_policies():
+    g = Graph()
+    g.add_node(1, "a")
+    df = pd.DataFrame({"id": ["a", "missing"], "x": [1, 2]})
+
+    with pytest.raises(Exception, match="id=missing"):
+        g.load_node_props_from_pandas(df, "id", const_properties=["x"])
+
+    with tempfile.TemporaryDirectory() as tmp:
+        dead_letter_file = os.path.join(tmp, "rejected.jsonl")
+        report = g.load_node_props_from_pandas(
+            df,
+            "id",
+            const_properties=["x"],
+            error_policy="collect",
+            dead_letter_file=dead_letter_file,
+        )
+        assert report["loaded"] == 1
+        assert report["skipped"] == 1
+        assert report["rejected"][0]["line"] == 1
+        assert report["rejected"][0]["record"] == "id=missing"
+        with open(dead_letter_file) as f:
+            assert len(f.readlines()) == 1
+    assert g.node("a").properties.constant.get("x") == 1
+
+    with pytest.raises(Exception, match="Invalid error policy"):
+        g.load_node_props_from_pandas(df, "id", error_policy="ignore")
+
+
+def test_load_edges_from_pandas_deduplicated():
+    df = pd.DataFrame(
+        {
+            "src": [1, 1, 2],
+            "dst": [2, 2, 3],
+            "time": [1, 1, 2],
+            "event": ["e1", "e1", "e2"],
+        }
+    )
+    g = Graph()
+    report = g.load_edges_from_pandas(
+        df, "src", "dst", "time", properties=["event"], event_id="event"
+    )
+    assert report["loaded"] == 2
+    assert report["duplicates"] == 1
+    report = g.load_edges_from_pandas(
+        df, "src", "dst", "time", properties=["event"], event_id="event"
+    )
+    assert report["loaded"] == 0
+    assert report["duplicates"] == 3
+    assert g.edge(1, 2).history() == [1]
+
+    g = PersistentGraph()
+    g.load_edges_from_pandas(df, "src", "dst", "time", deduplicate=True)
+    g.load_edges_from_pandas(df, "src", "dst", "time", deduplicate=True)
+    assert g.edge(1, 2).history() == [1]
+    assert g.edge(2, 3).history() == [2]
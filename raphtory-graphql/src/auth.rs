@@ -0,0 +1,587 @@
+//! Locally configured authentication and role-based access control
+//!
+//! Requests authenticate with a static API key in the `X-API-Key` header or with a JWT signed
+//! with the configured secret (HS256) in an `Authorization: Bearer` header. The claims of a JWT
+//! name the principal (`sub`) and its roles (`roles`). A role grants read or write access to
+//! graphs by path, `teamA/*` matching every graph in the namespace `teamA` and `*` matching every
+//! graph, and may hide sensitive properties. Administrators may also call mutations that read
+//! from the server's file system.
+//!
+//! The resulting [`Access`] is attached to the GraphQL request and checked by the resolvers.
+//! Requests without an [`Access`], i.e. when no [`AuthConfig`] is set on the server, are
+//! unrestricted.
+//!
+//! Properties are hidden by name in the property resolvers of graphs, nodes and edges. These
+//! find the graph they belong to from the `name` argument of the root field they are nested in,
+//! e.g. `graph(name: "teamA/fraud")`. Where there is no such argument, as below `graphs`, a
+//! property hidden on any graph is hidden.
+//! Graphs with hidden properties cannot be exported or copied as a whole, e.g. by `receiveGraph`
+//! or `saveGraph`, nor searched as vectorised graphs, except by administrators.
+//!
+//! ```toml
+//! jwt_secret = "change me"
+//! anonymous_role = "public"
+//!
+//! [[api_keys]]
+//! key = "4d1f0c"
+//! principal = "etl"
+//! roles = ["writer"]
+//!
+//! [roles.public.graphs."*"]
+//! read = true
+//! hidden_properties = ["iban"]
+//!
+//! [roles.writer]
+//! admin = true
+//! [roles.writer.graphs."*"]
+//! write = true
+//! ```
+
+use crate::{
+    cache::QueryCache,
+    limits::{execute, QueryLimits},
+    namespace::ancestors,
+    observability::metrics::METRICS,
+};
+use async_graphql::{
+    parser::types::{Field, FragmentDefinition, Selection, SelectionSet},
+    Context, Name, Positioned, QueryPathSegment,
+};
+use async_graphql_poem::{GraphQLRequest, GraphQLResponse};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use poem::{
+    handler,
+    http::{HeaderMap, StatusCode},
+    web,
+};
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::Arc,
+};
+use subtle::ConstantTimeEq;
+
+const ALL_GRAPHS: &str = "*";
+/// Principal of requests without credentials
+pub(crate) const ANONYMOUS: &str = "anonymous";
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuthError {
+    #[error("Failed to read auth config: {0}")]
+    InvalidConfig(String),
+    #[error("Authentication required")]
+    Unauthenticated,
+    #[error("Invalid credentials")]
+    InvalidCredentials,
+    #[error("Unknown role '{0}'")]
+    UnknownRole(String),
+    #[error("Access to graph '{0}' denied")]
+    ReadDenied(String),
+    #[error("Write access to graph '{0}' denied")]
+    WriteDenied(String),
+    #[error("Administrator access required")]
+    AdminRequired,
+    #[error("Properties of graph '{0}' are hidden, administrator access required")]
+    PropertiesHidden(String),
+}
+
+/// Permissions of a role on one graph, on all graphs of a namespace for `namespace/*`, or on
+/// all graphs for `*`
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct GraphPermission {
+    #[serde(default)]
+    pub read: bool,
+    /// Write access, which implies read access
+    #[serde(default)]
+    pub write: bool,
+    #[serde(default)]
+    pub hidden_properties: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct Role {
+    /// Allows mutations reading graphs or data from the server's file system
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(default)]
+    pub graphs: HashMap<String, GraphPermission>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ApiKey {
+    pub key: String,
+    pub principal: String,
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+pub struct AuthConfig {
+    /// Secret JWTs are signed with, JWTs are rejected if not set
+    pub jwt_secret: Option<String>,
+    /// Role of requests without credentials, which are rejected if not set
+    pub anonymous_role: Option<String>,
+    #[serde(default)]
+    pub api_keys: Vec<ApiKey>,
+    #[serde(default)]
+    pub roles: HashMap<String, Role>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+impl AuthConfig {
+    /// Read the config from a TOML file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AuthError> {
+        let content =
+            fs::read_to_string(path).map_err(|err| AuthError::InvalidConfig(err.to_string()))?;
+        toml::from_str(&content).map_err(|err| AuthError::InvalidConfig(err.to_string()))
+    }
+
+    /// The access of a principal with `roles`
+    pub fn access(&self, principal: &str, roles: &[String]) -> Result<Access, AuthError> {
+        let mut access = Access {
+            principal: principal.to_owned(),
+            admin: false,
+            graphs: HashMap::new(),
+        };
+        for name in roles {
+            let role = self
+                .roles
+                .get(name)
+                .ok_or_else(|| AuthError::UnknownRole(name.clone()))?;
+            access.admin |= role.admin;
+            for (graph, permission) in &role.graphs {
+                let merged = access.graphs.entry(graph.clone()).or_default();
+                merged.read |= permission.read || permission.write;
+                merged.write |= permission.write;
+                merged
+                    .hidden_properties
+                    .extend(permission.hidden_properties.iter().cloned());
+            }
+        }
+        Ok(access)
+    }
+
+    /// The access of the request with `headers`
+    pub fn authenticate(&self, headers: &HeaderMap) -> Result<Access, AuthError> {
+        if let Some(key) = headers.get("x-api-key") {
+            let key = key.to_str().map_err(|_| AuthError::InvalidCredentials)?;
+            let api_key = self
+                .api_keys
+                .iter()
+                .find(|api_key| api_key.key.as_bytes().ct_eq(key.as_bytes()).into())
+                .ok_or(AuthError::InvalidCredentials)?;
+            return self.access(&api_key.principal, &api_key.roles);
+        }
+        if let Some(authorization) = headers.get("authorization") {
+            let token = authorization
+                .to_str()
+                .ok()
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .ok_or(AuthError::InvalidCredentials)?;
+            let secret = self
+                .jwt_secret
+                .as_ref()
+                .ok_or(AuthError::InvalidCredentials)?;
+            let claims = decode::<Claims>(
+                token,
+                &DecodingKey::from_secret(secret.as_bytes()),
+                &Validation::new(Algorithm::HS256),
+            )
+            .map_err(|_| AuthError::InvalidCredentials)?
+            .claims;
+            return self.access(&claims.sub, &claims.roles);
+        }
+        match &self.anonymous_role {
+            Some(role) => self.access(ANONYMOUS, std::slice::from_ref(role)),
+            None => Err(AuthError::Unauthenticated),
+        }
+    }
+}
+
+/// What the principal of a request may do
+#[derive(Debug, Clone, PartialEq)]
+pub struct Access {
+    principal: String,
+    admin: bool,
+    graphs: HashMap<String, GraphPermission>,
+}
+
+impl Access {
+    pub fn principal(&self) -> &str {
+        &self.principal
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.admin
+    }
+
+    fn permissions<'a>(&'a self, graph: &'a str) -> impl Iterator<Item = &'a GraphPermission> {
+        let namespaces = ancestors(graph).map(|namespace| format!("{namespace}/{ALL_GRAPHS}"));
+        std::iter::once(graph.to_owned())
+            .chain(namespaces)
+            .chain(std::iter::once(ALL_GRAPHS.to_owned()))
+            .filter_map(|name| self.graphs.get(&name))
+    }
+
+    pub fn can_read(&self, graph: &str) -> bool {
+        self.permissions(graph).any(|permission| permission.read)
+    }
+
+    pub fn can_write(&self, graph: &str) -> bool {
+        self.permissions(graph).any(|permission| permission.write)
+    }
+
+    /// Whether the principal can read every graph, including graphs added later
+    pub fn can_read_all(&self) -> bool {
+        self.graphs
+            .get(ALL_GRAPHS)
+            .is_some_and(|permission| permission.read)
+    }
+
+    /// Properties hidden from the principal on `graph`
+    pub fn hidden_properties<'a>(&'a self, graph: &'a str) -> HashSet<&'a str> {
+        self.permissions(graph)
+            .flat_map(|permission| &permission.hidden_properties)
+            .map(|name| name.as_str())
+            .collect()
+    }
+
+    /// Whether the property `name` is hidden from the principal on any graph
+    fn is_hidden_on_any(&self, name: &str) -> bool {
+        self.graphs
+            .values()
+            .any(|permission| permission.hidden_properties.iter().any(|p| p == name))
+    }
+}
+
+pub(crate) fn require_read(ctx: &Context<'_>, graph: &str) -> Result<(), AuthError> {
+    match ctx.data_opt::<Access>() {
+        Some(access) if !access.can_read(graph) => Err(AuthError::ReadDenied(graph.to_owned())),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn require_write(ctx: &Context<'_>, graph: &str) -> Result<(), AuthError> {
+    match ctx.data_opt::<Access>() {
+        Some(access) if !access.can_write(graph) => Err(AuthError::WriteDenied(graph.to_owned())),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn require_admin(ctx: &Context<'_>) -> Result<(), AuthError> {
+    match ctx.data_opt::<Access>() {
+        Some(access) if !access.is_admin() => Err(AuthError::AdminRequired),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn require_read_all(ctx: &Context<'_>) -> Result<(), AuthError> {
+    match ctx.data_opt::<Access>() {
+        Some(access) if !access.can_read_all() => Err(AuthError::ReadDenied(ALL_GRAPHS.to_owned())),
+        _ => Ok(()),
+    }
+}
+
+/// Fails if properties are hidden from the principal of the request on `graph`, for resolvers
+/// exporting or copying a graph as a whole, which cannot leave them out. Administrators are not
+/// restricted.
+pub(crate) fn require_unfiltered(ctx: &Context<'_>, graph: &str) -> Result<(), AuthError> {
+    if may_hide_properties(ctx, graph) {
+        Err(AuthError::PropertiesHidden(graph.to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether properties are hidden from the principal of the request on `graph`, excluding
+/// administrators
+pub(crate) fn may_hide_properties(ctx: &Context<'_>, graph: &str) -> bool {
+    ctx.data_opt::<Access>()
+        .is_some_and(|access| !access.is_admin() && !access.hidden_properties(graph).is_empty())
+}
+
+pub(crate) fn can_read(ctx: &Context<'_>, graph: &str) -> bool {
+    require_read(ctx, graph).is_ok()
+}
+
+/// Whether the property `name` is hidden from the principal of the request on the graph
+/// being resolved, or on any graph if that is not known
+pub(crate) fn is_hidden(ctx: &Context<'_>, name: &str) -> bool {
+    ctx.data_opt::<Access>()
+        .is_some_and(|access| match resolved_graph(ctx) {
+            Some(graph) => access.hidden_properties(&graph).contains(name),
+            None => access.is_hidden_on_any(name),
+        })
+}
+
+/// The `name` argument of the root field the field being resolved is nested in
+fn resolved_graph(ctx: &Context<'_>) -> Option<String> {
+    let mut path = ctx.path_node.as_ref()?;
+    while let Some(parent) = path.parent {
+        path = parent;
+    }
+    let QueryPathSegment::Name(key) = path.segment else {
+        return None;
+    };
+    let env = ctx.query_env;
+    let field = find_field(&env.operation.node.selection_set.node, key, &env.fragments)?;
+    let value = field.get_argument("name")?.node.clone();
+    match value.into_const_with(|var| env.variables.get(&var).cloned().ok_or(())) {
+        Ok(async_graphql::Value::String(graph)) => Some(graph),
+        _ => None,
+    }
+}
+
+fn find_field<'a>(
+    selection_set: &'a SelectionSet,
+    key: &str,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+) -> Option<&'a Field> {
+    selection_set
+        .items
+        .iter()
+        .find_map(|selection| match &selection.node {
+            Selection::Field(field) => {
+                (field.node.response_key().node == key).then_some(&field.node)
+            }
+            Selection::FragmentSpread(spread) => find_field(
+                &fragments
+                    .get(&spread.node.fragment_name.node)?
+                    .node
+                    .selection_set
+                    .node,
+                key,
+                fragments,
+            ),
+            Selection::InlineFragment(fragment) => {
+                find_field(&fragment.node.selection_set.node, key, fragments)
+            }
+        })
+}
+
+/// GraphQL endpoint attaching the [`Access`] of every request, rejecting unauthenticated ones
+#[handler]
+pub(crate) async fn graphql_with_access(
+    schema: web::Data<&async_graphql::dynamic::Schema>,
+    config: web::Data<&Arc<AuthConfig>>,
+    limits: web::Data<&QueryLimits>,
+    cache: web::Data<&Arc<QueryCache>>,
+    headers: &HeaderMap,
+    request: GraphQLRequest,
+) -> poem::Result<GraphQLResponse> {
+    let access = config
+        .authenticate(headers)
+        .map_err(|err| poem::Error::new(err, StatusCode::UNAUTHORIZED))?;
+    let scope = access.principal().to_owned();
+    let operation = request.0.operation_name.clone();
+    let response = cache.execute(&scope, request.0.data(access), |request| {
+        execute(&schema, &limits, request)
+    });
+    Ok(METRICS
+        .observe_request(operation.as_deref(), response)
+        .await
+        .into())
+}
+
+#[cfg(test)]
+mod auth_test {
+    use super::*;
+    use crate::{data::Data, model::App};
+    use dynamic_graphql::{Request, Variables};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use raphtory::{db::api::view::MaterializedGraph, prelude::*};
+    use serde_json::json;
+
+    const CONFIG: &str = r#"
+        jwt_secret = "secret"
+
+        [[api_keys]]
+        key = "reader-key"
+        principal = "alice"
+        roles = ["reader"]
+
+        [roles.reader.graphs.public]
+        read = true
+        hidden_properties = ["iban"]
+
+        [roles.reader.graphs.shared]
+        read = true
+
+        [roles.writer.graphs."*"]
+        write = true
+
+        [roles.fraud.graphs."teamA/fraud/*"]
+        write = true
+    "#;
+
+    #[test]
+    fn api_keys_and_jwts() {
+        let config: AuthConfig = toml::from_str(CONFIG).unwrap();
+
+        let mut headers = HeaderMap::new();
+        assert!(matches!(
+            config.authenticate(&headers),
+            Err(AuthError::Unauthenticated)
+        ));
+
+        headers.insert("x-api-key", "reader-key".parse().unwrap());
+        let access = config.authenticate(&headers).unwrap();
+        assert_eq!(access.principal(), "alice");
+        assert!(access.can_read("public"));
+        assert!(!access.can_read("private"));
+        assert!(!access.can_write("public"));
+
+        let token = encode(
+            &Header::default(),
+            &json!({"sub": "bob", "roles": ["writer"], "exp": 4102444800u64}),
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", format!("Bearer {token}").parse().unwrap());
+        let access = config.authenticate(&headers).unwrap();
+        assert_eq!(access.principal(), "bob");
+        assert!(access.can_write("private") && access.can_read("private"));
+        assert!(!access.is_admin());
+
+        let access = config.access("carol", &["fraud".to_owned()]).unwrap();
+        assert!(access.can_write("teamA/fraud/2024-06"));
+        assert!(access.can_read("teamA/fraud/archive/2023"));
+        assert!(!access.can_read("teamA/fraud"));
+        assert!(!access.can_read("teamA/sales/2024-06"));
+
+        let forged = encode(
+            &Header::default(),
+            &json!({"sub": "bob", "roles": ["writer"], "exp": 4102444800u64}),
+            &EncodingKey::from_secret(b"guess"),
+        )
+        .unwrap();
+        headers.insert("authorization", format!("Bearer {forged}").parse().unwrap());
+        assert!(matches!(
+            config.authenticate(&headers),
+            Err(AuthError::InvalidCredentials)
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolvers_check_access() {
+        let config: AuthConfig = toml::from_str(CONFIG).unwrap();
+        let access = config.access("alice", &["reader".to_owned()]).unwrap();
+
+        let graph = Graph::new();
+        graph
+            .add_node(0, "a", [("iban", "DE00"), ("kind", "bank")], None)
+            .unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([
+            ("public".to_string(), graph.clone()),
+            ("shared".to_string(), graph.clone()),
+            ("private".to_string(), graph),
+        ]);
+        let schema = App::create_schema()
+            .data(Data::from_map(graphs))
+            .finish()
+            .unwrap();
+
+        let query = r#"{
+          graph(name: "public") {
+            nodes { list { properties { keys get(key: "iban") { value } } } }
+          }
+          graphs { name }
+        }"#;
+        let res = schema
+            .execute(Request::new(query).data(access.clone()))
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({
+                "graph": {"nodes": {"list": [{"properties": {"keys": ["kind"], "get": null}}]}},
+                "graphs": [{"name": "public"}, {"name": "shared"}]
+            })
+        );
+
+        let query = r#"query($name: String!) {
+          public: graph(name: "public") { ...iban }
+          shared: graph(name: $name) { ...iban }
+        }
+        fragment iban on GqlGraph { node(name: "a") { properties { get(key: "iban") { value } } } }"#;
+        let request = Request::new(query)
+            .variables(Variables::from_json(json!({"name": "shared"})))
+            .data(access.clone());
+        let res = schema.execute(request).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({
+                "public": {"node": {"properties": {"get": null}}},
+                "shared": {"node": {"properties": {"get": {"value": "DE00"}}}}
+            })
+        );
+
+        let query = r#"{ graph(name: "private") { name } }"#;
+        let res = schema
+            .execute(Request::new(query).data(access.clone()))
+            .await;
+        assert_eq!(res.errors[0].message, "Access to graph 'private' denied");
+
+        let mutation = r#"mutation { sendGraph(name: "public", graph: "") }"#;
+        let res = schema.execute(Request::new(mutation).data(access)).await;
+        assert_eq!(
+            res.errors[0].message,
+            "Write access to graph 'public' denied"
+        );
+    }
+
+    #[tokio::test]
+    async fn graphs_with_hidden_properties_are_not_exported() {
+        let config: AuthConfig = toml::from_str(CONFIG).unwrap();
+        let access = config
+            .access("alice", &["reader".to_owned(), "writer".to_owned()])
+            .unwrap();
+
+        let graph = Graph::new();
+        graph.add_node(0, "a", [("iban", "DE00")], None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([
+            ("public".to_string(), graph.clone()),
+            ("shared".to_string(), graph),
+        ]);
+        let schema = App::create_schema()
+            .data(Data::from_map(graphs))
+            .finish()
+            .unwrap();
+        let denied = "Properties of graph 'public' are hidden, administrator access required";
+
+        let query = r#"{ receiveGraph(name: "public") }"#;
+        let res = schema
+            .execute(Request::new(query).data(access.clone()))
+            .await;
+        assert_eq!(res.errors[0].message, denied);
+        let query = r#"{ receiveGraph(name: "shared") }"#;
+        let res = schema
+            .execute(Request::new(query).data(access.clone()))
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        let mutation = r#"mutation {
+          saveGraph(parentGraphName: "public", graphName: "public", newGraphName: "copy",
+                    props: "", isArchive: 0, graphNodes: "{\"a\": {}}")
+        }"#;
+        let res = schema
+            .execute(Request::new(mutation).data(access.clone()))
+            .await;
+        assert_eq!(res.errors[0].message, denied);
+
+        let query = r#"{ vectorisedGraph(name: "public") { algorithms { __typename } } }"#;
+        let res = schema.execute(Request::new(query).data(access)).await;
+        assert_eq!(res.errors[0].message, denied);
+    }
+}
@@ -0,0 +1,459 @@
+//! Server-side caching of query results and materialised views
+//!
+//! Queries whose root fields all select a `graph(name: ...)` are cached by the names and
+//! versions of those graphs, the normalised query, its operation name and variables, and the
+//! principal sending it. The version of a graph changes with every update to it and with every
+//! mutation run by the server, so cached results are never stale. Entries expire after the TTL
+//! and the least recently used entries are evicted once the cache exceeds its memory limit.
+//! Only responses without errors are cached.
+//!
+//! Materialised views are layered, windowed or subgraph views of a graph, computed once when
+//! they are registered and served under their own name like any other graph.
+//!
+//! ```toml
+//! ttl_secs = 300
+//! max_bytes = 104857600
+//!
+//! [[views]]
+//! name = "recent_transfers"
+//! graph = "transactions"
+//! layers = ["transfer"]
+//! start = 1700000000000
+//! ```
+
+use crate::data::Data;
+use async_graphql::{
+    parser::{
+        parse_query,
+        types::{DocumentOperations, OperationDefinition, OperationType, Selection},
+    },
+    Request, Response, Value,
+};
+use parking_lot::{Mutex, RwLock};
+use raphtory::{
+    core::utils::errors::GraphError,
+    db::api::{
+        mutation::internal::InternalAdditionOps,
+        view::{IntoDynamic, MaterializedGraph},
+    },
+    prelude::*,
+    search::IndexedGraph,
+};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    future::Future,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum CacheError {
+    #[error("Failed to read cache config: {0}")]
+    InvalidConfig(String),
+    #[error("View '{0}' is defined on unknown graph '{1}'")]
+    UnknownGraph(String, String),
+    #[error("View '{0}' would replace an existing graph")]
+    DuplicateView(String),
+    #[error("Failed to materialise view: {0}")]
+    Graph(#[from] GraphError),
+}
+
+/// A named view of a graph, materialised once and served as a graph
+#[derive(Clone, Debug, Deserialize)]
+pub struct MaterialisedView {
+    /// The name the view is served under
+    pub name: String,
+    /// The name of the graph the view is computed from
+    pub graph: String,
+    /// Only keep these layers
+    pub layers: Option<Vec<String>>,
+    /// Only keep updates at or after this time
+    pub start: Option<i64>,
+    /// Only keep updates before this time
+    pub end: Option<i64>,
+    /// Only keep nodes of these types
+    pub node_types: Option<Vec<String>>,
+    /// Only keep these nodes
+    pub nodes: Option<Vec<String>>,
+}
+
+impl MaterialisedView {
+    pub fn materialise(&self, graph: &MaterializedGraph) -> Result<MaterializedGraph, GraphError> {
+        let mut view = graph.clone().into_dynamic();
+        if let Some(layers) = &self.layers {
+            view = view.layers(layers.clone())?.into_dynamic();
+        }
+        if self.start.is_some() || self.end.is_some() {
+            let start = self.start.unwrap_or(i64::MIN);
+            let end = self.end.unwrap_or(i64::MAX);
+            view = view.window(start, end).into_dynamic();
+        }
+        if let Some(node_types) = &self.node_types {
+            view = view.subgraph_node_types(node_types.clone()).into_dynamic();
+        }
+        if let Some(nodes) = &self.nodes {
+            view = view.subgraph(nodes.clone()).into_dynamic();
+        }
+        view.materialize()
+    }
+}
+
+/// Configuration of the result cache and the materialised views of a server
+#[derive(Clone, Debug, Deserialize)]
+pub struct CacheConfig {
+    /// Seconds after which cached results expire
+    pub ttl_secs: u64,
+    /// Approximate upper bound on the memory used by cached results
+    pub max_bytes: usize,
+    #[serde(default)]
+    pub views: Vec<MaterialisedView>,
+}
+
+impl CacheConfig {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CacheError> {
+        let content =
+            fs::read_to_string(path).map_err(|err| CacheError::InvalidConfig(err.to_string()))?;
+        toml::from_str(&content).map_err(|err| CacheError::InvalidConfig(err.to_string()))
+    }
+
+    /// Materialise the views and add them to the graphs of `data`
+    pub(crate) fn register_views(&self, data: &Data) -> Result<(), CacheError> {
+        for view in &self.views {
+            data.load_graphs(&[&view.graph])?;
+        }
+        let mut graphs = data.graphs.write();
+        for view in &self.views {
+            if graphs.contains_key(&view.name) || data.unloaded.read().contains_key(&view.name) {
+                return Err(CacheError::DuplicateView(view.name.clone()));
+            }
+            let graph = graphs
+                .get(&view.graph)
+                .ok_or_else(|| CacheError::UnknownGraph(view.name.clone(), view.graph.clone()))?;
+            let materialised = view.materialise(&graph.graph)?;
+            let indexed = IndexedGraph::from_graph(&materialised).map_err(GraphError::from)?;
+            graphs.insert(view.name.clone(), indexed);
+        }
+        Ok(())
+    }
+}
+
+struct CacheEntry {
+    data: Value,
+    size: usize,
+    inserted: Instant,
+    last_used: Instant,
+}
+
+/// Cache of query results, which does nothing if it has no config
+pub(crate) struct QueryCache {
+    config: Option<CacheConfig>,
+    graphs: Arc<RwLock<HashMap<String, IndexedGraph<MaterializedGraph>>>>,
+    /// Incremented by every mutation, as mutations may replace graphs
+    generation: AtomicU64,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl QueryCache {
+    pub(crate) fn new(config: Option<CacheConfig>, data: &Data) -> Self {
+        Self {
+            config,
+            graphs: data.graphs.clone(),
+            generation: AtomicU64::new(0),
+            entries: Default::default(),
+        }
+    }
+
+    /// Run `request` with `execute`, serving it from the cache if possible. `scope` separates
+    /// the results of requests that may see different data, e.g. those of different principals.
+    pub(crate) async fn execute<F, Fut>(
+        &self,
+        scope: &str,
+        request: Request,
+        execute: F,
+    ) -> Response
+    where
+        F: FnOnce(Request) -> Fut,
+        Fut: Future<Output = Response>,
+    {
+        let Some(config) = &self.config else {
+            return execute(request).await;
+        };
+        let operation = match parse_query(&request.query) {
+            Ok(document) => match document.operations {
+                DocumentOperations::Single(operation) => Some(operation.node),
+                DocumentOperations::Multiple(mut operations) => request
+                    .operation_name
+                    .as_deref()
+                    .and_then(|name| operations.remove(name))
+                    .map(|operation| operation.node),
+            },
+            Err(_) => None,
+        };
+        let Some(operation) = operation else {
+            return execute(request).await;
+        };
+        if operation.ty == OperationType::Mutation {
+            let response = execute(request).await;
+            self.generation.fetch_add(1, Ordering::Relaxed);
+            return response;
+        }
+        let Some(key) = self.key(scope, &request, &operation) else {
+            return execute(request).await;
+        };
+
+        let ttl = Duration::from_secs(config.ttl_secs);
+        if let Some(entry) = self.entries.lock().get_mut(&key) {
+            if entry.inserted.elapsed() < ttl {
+                entry.last_used = Instant::now();
+                return Response::new(entry.data.clone());
+            }
+        }
+
+        let response = execute(request).await;
+        if response.errors.is_empty() {
+            self.insert(config, key, response.data.clone());
+        }
+        response
+    }
+
+    /// The cache key of `operation`, if all its root fields select graphs by name
+    fn key(
+        &self,
+        scope: &str,
+        request: &Request,
+        operation: &OperationDefinition,
+    ) -> Option<String> {
+        if operation.ty != OperationType::Query || !operation.directives.is_empty() {
+            return None;
+        }
+        let mut versions = vec![];
+        for selection in &operation.selection_set.node.items {
+            let Selection::Field(field) = &selection.node else {
+                return None;
+            };
+            if field.node.name.node.as_str() != "graph" {
+                return None;
+            }
+            let name = field.node.get_argument("name")?.node.clone();
+            let name = name
+                .into_const_with(|variable| request.variables.get(&variable).cloned().ok_or(()))
+                .ok()?;
+            let Value::String(name) = name else {
+                return None;
+            };
+            let version = self.graphs.read().get(&name).map(|g| g.read_generation());
+            versions.push((name, version));
+        }
+        versions.sort();
+        let generation = self.generation.load(Ordering::Relaxed);
+        let variables = serde_json::to_string(&request.variables).ok()?;
+        Some(format!(
+            "{scope}\n{generation}\n{versions:?}\n{}\n{variables}\n{}",
+            request.operation_name.as_deref().unwrap_or_default(),
+            normalise(&request.query)
+        ))
+    }
+
+    fn insert(&self, config: &CacheConfig, key: String, data: Value) {
+        let size = key.len() + data.to_string().len();
+        if size > config.max_bytes {
+            return;
+        }
+        let ttl = Duration::from_secs(config.ttl_secs);
+        let mut entries = self.entries.lock();
+        entries.retain(|_, entry| entry.inserted.elapsed() < ttl);
+        let mut used: usize = entries.values().map(|entry| entry.size).sum::<usize>() + size;
+        while used > config.max_bytes {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(entry) = entries.remove(&oldest) {
+                used -= entry.size;
+            }
+        }
+        let now = Instant::now();
+        entries.insert(
+            key,
+            CacheEntry {
+                data,
+                size,
+                inserted: now,
+                last_used: now,
+            },
+        );
+    }
+}
+
+/// Drop whitespace, commas and comments outside of strings, which are insignificant in
+/// GraphQL, keeping a single space only where it separates two names or numbers
+fn normalise(query: &str) -> String {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut normalised = String::with_capacity(query.len());
+    let mut chars = query.chars();
+    let mut pending_space = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                pending_space = false;
+                normalised.push(c);
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    normalised.push(c);
+                    match c {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => break,
+                        _ => escaped = false,
+                    }
+                }
+            }
+            '#' => {
+                pending_space = true;
+                for c in chars.by_ref() {
+                    if c == '\n' || c == '\r' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() || c == ',' || c == '\u{feff}' => pending_space = true,
+            c => {
+                if pending_space && is_name_char(c) && normalised.ends_with(is_name_char) {
+                    normalised.push(' ');
+                }
+                pending_space = false;
+                normalised.push(c);
+            }
+        }
+    }
+    normalised
+}
+
+#[cfg(test)]
+mod cache_test {
+    use super::*;
+    use crate::model::App;
+    use async_graphql::dynamic::Schema;
+    use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+
+    fn data() -> Data {
+        let graph = Graph::new();
+        graph
+            .add_edge(1, "a", "b", NO_PROPS, Some("follows"))
+            .unwrap();
+        graph
+            .add_edge(5, "b", "c", NO_PROPS, Some("likes"))
+            .unwrap();
+        let graph: MaterializedGraph = graph.into();
+        Data::from_map(HashMap::from([("g".to_string(), graph)]))
+    }
+
+    async fn run(
+        cache: &QueryCache,
+        schema: &Schema,
+        executions: &AtomicUsize,
+        query: &str,
+    ) -> serde_json::Value {
+        let response = cache
+            .execute("", Request::new(query), |request| {
+                executions.fetch_add(1, Ordering::Relaxed);
+                schema.execute(request)
+            })
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[test]
+    fn queries_are_normalised() {
+        assert_eq!(
+            normalise("{\n  graph(name: \"a  b\", x: 1) { # comment\n nodes }\n}"),
+            "{graph(name:\"a  b\"x:1){nodes}}"
+        );
+        assert_eq!(
+            normalise("query Q($n: [Int!] = [1, -2]) { g( name: \"g\" ) { ... on G { x } } }"),
+            normalise("query Q($n:[Int!]=[1 -2]){g(name:\"g\"){...on G{x}}}")
+        );
+    }
+
+    #[tokio::test]
+    async fn results_are_cached_until_the_graph_changes() {
+        let data = data();
+        let config = CacheConfig {
+            ttl_secs: 60,
+            max_bytes: 1 << 20,
+            views: vec![],
+        };
+        let cache = QueryCache::new(Some(config), &data);
+        let graphs = data.graphs.clone();
+        let schema = App::create_schema().data(data).finish().unwrap();
+        let executions = AtomicUsize::new(0);
+
+        let query = r#"{ graph(name: "g") { countEdges } }"#;
+        let result = run(&cache, &schema, &executions, query).await;
+        assert_eq!(result, json!({"graph": {"countEdges": 2}}));
+        let spaced = "{ graph( name: \"g\" ) {\n countEdges } }";
+        assert_eq!(run(&cache, &schema, &executions, spaced).await, result);
+        assert_eq!(executions.load(Ordering::Relaxed), 1);
+
+        graphs.read()["g"]
+            .graph
+            .add_edge(7, "c", "d", NO_PROPS, None)
+            .unwrap();
+        let result = run(&cache, &schema, &executions, query).await;
+        assert_eq!(result, json!({"graph": {"countEdges": 3}}));
+        assert_eq!(executions.load(Ordering::Relaxed), 2);
+
+        // updates without events change the graph too
+        graphs.read()["g"]
+            .graph
+            .add_constant_properties([("owner", "ops")])
+            .unwrap();
+        run(&cache, &schema, &executions, query).await;
+        assert_eq!(executions.load(Ordering::Relaxed), 3);
+
+        run(&cache, &schema, &executions, "{ graphs { name } }").await;
+        run(&cache, &schema, &executions, "{ graphs { name } }").await;
+        assert_eq!(executions.load(Ordering::Relaxed), 5);
+    }
+
+    #[tokio::test]
+    async fn views_are_served_as_graphs() {
+        let data = data();
+        let config: CacheConfig = toml::from_str(
+            r#"
+            ttl_secs = 60
+            max_bytes = 1024
+
+            [[views]]
+            name = "early_follows"
+            graph = "g"
+            layers = ["follows"]
+            end = 3
+            "#,
+        )
+        .unwrap();
+        config.register_views(&data).unwrap();
+        assert!(matches!(
+            config.register_views(&data),
+            Err(CacheError::DuplicateView(_))
+        ));
+
+        let schema = App::create_schema().data(data).finish().unwrap();
+        let query = r#"{ graph(name: "early_follows") { edges { list { src { name } } } } }"#;
+        let response = schema.execute(Request::new(query)).await;
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({"graph": {"edges": {"list": [{"src": {"name": "a"}}]}}})
+        );
+    }
+}
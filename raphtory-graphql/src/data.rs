@@ -1,20 +1,109 @@
-use parking_lot::RwLock;
+use crate::{
+    model::jobs::Jobs,
+    namespace::{join, NamespaceConfig, NamespaceError},
+};
+use parking_lot::{Mutex, RwLock};
 #[cfg(feature = "storage")]
 use raphtory::disk_graph::graph_impl::DiskGraph;
 use raphtory::{
-    core::Prop,
-    db::api::view::MaterializedGraph,
-    prelude::{GraphViewOps, PropUnwrap, PropertyAdditionOps},
+    core::{utils::errors::GraphError, Prop},
+    db::api::{mutation::internal::InternalAdditionOps, view::MaterializedGraph},
+    prelude::{GraphViewOps, PropertyAdditionOps},
     search::IndexedGraph,
     vectors::vectorised_graph::DynamicVectorisedGraph,
 };
-use std::{collections::HashMap, fs, path::Path, sync::Arc};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
 use walkdir::WalkDir;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Data {
     pub(crate) graphs: Arc<RwLock<HashMap<String, IndexedGraph<MaterializedGraph>>>>,
     pub(crate) vector_stores: Arc<RwLock<HashMap<String, DynamicVectorisedGraph>>>,
+    pub(crate) jobs: Arc<Jobs>,
+    /// The directory graphs were loaded from, where new graphs are saved in the subdirectory of
+    /// their namespace
+    pub(crate) work_dir: Option<PathBuf>,
+    pub(crate) namespaces: Arc<NamespaceConfig>,
+    /// The graphs of the graph directory that are not in memory, by path. They are loaded on
+    /// first access. Lock after `graphs` when locking both.
+    pub(crate) unloaded: Arc<RwLock<HashMap<String, UnloadedGraph>>>,
+    pub(crate) residency: Arc<Mutex<Residency>>,
+    /// The estimated bytes the graphs in memory may use before the least recently used graphs
+    /// of the graph directory are unloaded again
+    pub(crate) memory_budget: Option<usize>,
+}
+
+/// A graph of the graph directory that is not in memory
+#[derive(Clone, Debug)]
+pub(crate) struct UnloadedGraph {
+    file: PathBuf,
+    /// The number of nodes of the graph, counted when it is unloaded or first needed for a
+    /// quota
+    nodes: Option<usize>,
+}
+
+impl UnloadedGraph {
+    fn new(file: PathBuf) -> Self {
+        Self { file, nodes: None }
+    }
+}
+
+/// A graph of the graph directory that is in memory
+struct Resident {
+    file: PathBuf,
+    last_access: u64,
+    /// The generation of the graph when it was loaded or last saved to `file`
+    saved_at: usize,
+}
+
+/// The recency of access to the graphs of the graph directory that are in memory
+#[derive(Default)]
+pub(crate) struct Residency {
+    clock: u64,
+    graphs: HashMap<String, Resident>,
+    /// The estimated bytes of every graph in memory with the generation they were estimated at
+    sizes: HashMap<String, (usize, usize)>,
+}
+
+impl Residency {
+    /// The estimated bytes of `graph` at `name`, only estimated again if it changed since
+    fn bytes(&mut self, name: &str, graph: &IndexedGraph<MaterializedGraph>) -> usize {
+        let generation = graph.read_generation();
+        match self.sizes.get(name) {
+            Some(&(estimated_at, bytes)) if estimated_at == generation => bytes,
+            _ => {
+                let bytes = graph.memory_stats().map_or(0, |stats| stats.total_bytes());
+                self.sizes.insert(name.to_owned(), (generation, bytes));
+                bytes
+            }
+        }
+    }
+
+    fn touch(&mut self, name: &str) {
+        self.clock += 1;
+        if let Some(resident) = self.graphs.get_mut(name) {
+            resident.last_access = self.clock;
+        }
+    }
+
+    fn insert(&mut self, name: String, file: PathBuf, saved_at: usize) {
+        self.clock += 1;
+        let last_access = self.clock;
+        self.graphs.insert(
+            name,
+            Resident {
+                file,
+                last_access,
+                saved_at,
+            },
+        );
+    }
 }
 
 impl Data {
@@ -24,15 +113,17 @@ impl Data {
         Self {
             graphs,
             vector_stores,
+            ..Default::default()
         }
     }
 
+    /// Serve the graphs of `directory_path`, loading each of them on first access
     pub fn from_directory(directory_path: &str) -> Self {
-        let graphs = Arc::new(RwLock::new(Self::load_from_file(directory_path)));
-        let vector_stores = Arc::new(RwLock::new(HashMap::new()));
+        let unloaded = Self::scan_unloaded(directory_path);
         Self {
-            graphs,
-            vector_stores,
+            unloaded: Arc::new(RwLock::new(unloaded)),
+            work_dir: Some(directory_path.into()),
+            ..Default::default()
         }
     }
 
@@ -40,14 +131,218 @@ impl Data {
         graphs: HashMap<String, G>,
         directory_path: &str,
     ) -> Self {
+        let unloaded = Self::scan_unloaded(directory_path);
         let mut graphs = Self::convert_graphs(graphs);
-        graphs.extend(Self::load_from_file(directory_path));
-        let graphs = Arc::new(RwLock::new(graphs));
-        let vector_stores = Arc::new(RwLock::new(HashMap::new()));
+        graphs.retain(|name, _| !unloaded.contains_key(name));
         Self {
-            graphs,
-            vector_stores,
+            graphs: Arc::new(RwLock::new(graphs)),
+            unloaded: Arc::new(RwLock::new(unloaded)),
+            work_dir: Some(directory_path.into()),
+            ..Default::default()
+        }
+    }
+
+    /// The paths of all graphs, in memory or not
+    pub(crate) fn graph_names(&self) -> Vec<String> {
+        let graphs = self.graphs.read();
+        let unloaded = self.unloaded.read();
+        graphs.keys().chain(unloaded.keys()).cloned().collect()
+    }
+
+    pub(crate) fn contains_graph(&self, name: &str) -> bool {
+        self.graphs.read().contains_key(name) || self.unloaded.read().contains_key(name)
+    }
+
+    /// The graph at `name`, loaded from the graph directory if it is not in memory
+    pub(crate) fn get_graph(
+        &self,
+        name: &str,
+    ) -> Result<Option<IndexedGraph<MaterializedGraph>>, GraphError> {
+        self.load_graphs(&[name])?;
+        Ok(self.graphs.read().get(name).cloned())
+    }
+
+    /// Load the graphs at `names` that are not in memory and unload others if the memory budget
+    /// is exceeded
+    pub(crate) fn load_graphs(&self, names: &[&str]) -> Result<(), GraphError> {
+        let mut loaded_any = false;
+        for name in names {
+            if self.graphs.read().contains_key(*name) {
+                self.residency.lock().touch(name);
+                continue;
+            }
+            let Some(file) = self.unloaded.read().get(*name).map(|g| g.file.clone()) else {
+                continue;
+            };
+            let graph = IndexedGraph::from_graph(&read_graph(&file)?)?;
+            let saved_at = graph.read_generation();
+            let mut graphs = self.graphs.write();
+            if self.unloaded.write().remove(*name).is_some() {
+                info!(graph = %file.display(), "Graph loaded");
+                graphs.insert(name.to_string(), graph);
+                self.residency
+                    .lock()
+                    .insert(name.to_string(), file, saved_at);
+                loaded_any = true;
+            }
+        }
+        if loaded_any {
+            self.unload_least_recently_used(names)?;
+        }
+        Ok(())
+    }
+
+    /// Unload the least recently used graphs of the graph directory, except `keep`, until the
+    /// graphs in memory fit the memory budget. Graphs changed since they were loaded are saved
+    /// to their file first, without holding the locks. Graphs used or changed while being saved
+    /// stay in memory.
+    fn unload_least_recently_used(&self, keep: &[&str]) -> Result<(), GraphError> {
+        let Some(budget) = self.memory_budget else {
+            return Ok(());
+        };
+        for (name, graph, last_access) in self.choose_unloaded(budget, keep) {
+            let generation = graph.read_generation();
+            let (file, saved_at) = match self.residency.lock().graphs.get(&name) {
+                Some(resident) => (resident.file.clone(), resident.saved_at),
+                None => continue,
+            };
+            if generation != saved_at {
+                info!(graph = %file.display(), "Saving graph");
+                graph.save_to_file(&file)?;
+            }
+            let mut graphs = self.graphs.write();
+            let mut residency = self.residency.lock();
+            let Some(resident) = residency.graphs.get_mut(&name) else {
+                continue;
+            };
+            resident.saved_at = generation;
+            if resident.last_access != last_access || graph.read_generation() != generation {
+                continue;
+            }
+            graphs.remove(&name);
+            residency.graphs.remove(&name);
+            residency.sizes.remove(&name);
+            info!(graph = %file.display(), "Graph unloaded");
+            let unloaded = UnloadedGraph {
+                file,
+                nodes: Some(graph.count_nodes()),
+            };
+            self.unloaded.write().insert(name, unloaded);
+        }
+        Ok(())
+    }
+
+    /// The least recently used graphs of the graph directory, except `keep`, to unload for the
+    /// graphs in memory to fit `budget`, with the time they were last accessed
+    fn choose_unloaded(
+        &self,
+        budget: usize,
+        keep: &[&str],
+    ) -> Vec<(String, IndexedGraph<MaterializedGraph>, u64)> {
+        let graphs = self.graphs.read();
+        let mut residency = self.residency.lock();
+        residency.graphs.retain(|name, _| graphs.contains_key(name));
+        residency.sizes.retain(|name, _| graphs.contains_key(name));
+        let mut used: usize = graphs
+            .iter()
+            .map(|(name, graph)| residency.bytes(name, graph))
+            .sum();
+        let mut candidates: Vec<_> = residency
+            .graphs
+            .iter()
+            .filter(|(name, _)| !keep.contains(&name.as_str()))
+            .map(|(name, resident)| (resident.last_access, name.clone()))
+            .collect();
+        candidates.sort();
+        let mut chosen = vec![];
+        for (last_access, name) in candidates {
+            if used <= budget {
+                break;
+            }
+            let graph = graphs[&name].clone();
+            used = used.saturating_sub(residency.bytes(&name, &graph));
+            chosen.push((name, graph, last_access));
+        }
+        chosen
+    }
+
+    /// Check that storing a graph with `nodes` nodes at `path` keeps every namespace within its
+    /// quota, with the graphs in memory locked as `graphs`
+    pub(crate) fn check_quota(
+        &self,
+        graphs: &HashMap<String, IndexedGraph<MaterializedGraph>>,
+        path: &str,
+        nodes: usize,
+    ) -> Result<(), NamespaceError> {
+        let mut unloaded = self.unloaded.write();
+        for (name, graph) in unloaded.iter_mut() {
+            if graph.nodes.is_none() && self.namespaces.limits_nodes_of(path, name) {
+                let count = read_graph(&graph.file)
+                    .map_err(|err| NamespaceError::UnreadableGraph(name.clone(), err.to_string()))?
+                    .count_nodes();
+                graph.nodes = Some(count);
+            }
+        }
+        let unloaded = unloaded.iter().map(|(name, graph)| (name, graph.nodes));
+        self.namespaces.check_quota(graphs, unloaded, path, nodes)
+    }
+
+    /// Add `graph` at `name`, replacing any graph stored there, with the graphs in memory locked
+    /// as `graphs`
+    pub(crate) fn insert_graph(
+        &self,
+        graphs: &mut HashMap<String, IndexedGraph<MaterializedGraph>>,
+        name: String,
+        graph: IndexedGraph<MaterializedGraph>,
+    ) {
+        self.unloaded.write().remove(&name);
+        self.residency.lock().graphs.remove(&name);
+        graphs.insert(name, graph);
+    }
+
+    /// Add `graph`, just saved to `file`, at `name` like [`Data::insert_graph`] and unload it
+    /// again when it is not used and the memory budget is exceeded
+    pub(crate) fn insert_saved_graph(
+        &self,
+        graphs: &mut HashMap<String, IndexedGraph<MaterializedGraph>>,
+        name: String,
+        graph: IndexedGraph<MaterializedGraph>,
+        file: PathBuf,
+    ) {
+        let saved_at = graph.read_generation();
+        self.insert_graph(graphs, name.clone(), graph);
+        self.residency.lock().insert(name, file, saved_at);
+    }
+
+    fn scan_unloaded(path: &str) -> HashMap<String, UnloadedGraph> {
+        Self::scan_directory(path)
+            .into_iter()
+            .map(|(name, file)| (name, UnloadedGraph::new(file)))
+            .collect()
+    }
+
+    /// The files of the graphs in `path` and its subdirectories by graph path, named after the
+    /// files and placed in the namespaces of their subdirectories
+    pub fn scan_directory(path: &str) -> HashMap<String, PathBuf> {
+        fn scan_dir(files: &mut HashMap<String, PathBuf>, dir: &Path, namespace: &str) {
+            for entry in fs::read_dir(dir).unwrap() {
+                let entry = entry.unwrap();
+                let path = entry.path();
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if file_name.starts_with('.') {
+                    continue;
+                }
+                if path.is_dir() && !is_disk_graph_dir(&path) {
+                    scan_dir(files, &path, &join(namespace, &file_name));
+                } else if path.is_file() || cfg!(feature = "storage") {
+                    files.insert(join(namespace, &file_name), path);
+                }
+            }
         }
+
+        let mut files = HashMap::new();
+        scan_dir(&mut files, Path::new(path), "");
+        files
     }
 
     fn convert_graphs<G: Into<MaterializedGraph>>(
@@ -81,98 +376,49 @@ impl Data {
             .map(move |entry| {
                 let path = entry.path();
                 let path_string = path.display().to_string();
-                println!("loading from {path_string}");
+                info!(path = %path_string, "Loading graph");
                 loader(path)
             })
     }
 
+    /// Load the graphs of `path` and its subdirectories, named like [`Self::scan_directory`]
     pub fn load_from_file(path: &str) -> HashMap<String, IndexedGraph<MaterializedGraph>> {
-        fn get_graph_name(path: &Path, graph: &MaterializedGraph) -> String {
-            graph
-                .properties()
-                .get("name")
-                .into_str()
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| path.file_name().unwrap().to_str().unwrap().to_owned())
-        }
-
-        fn is_disk_graph_dir(path: &Path) -> bool {
-            // Check if the directory contains files specific to disk_graph graphs
-            let files = fs::read_dir(path).unwrap();
-            let mut has_disk_graph_files = false;
-            for file in files {
-                let file_name = file.unwrap().file_name().into_string().unwrap();
-                if file_name.ends_with(".ipc") {
-                    has_disk_graph_files = true;
-                    break;
-                }
-            }
-            has_disk_graph_files
-        }
-
-        fn load_bincode_graph(path: &Path) -> (String, MaterializedGraph) {
-            let path_string = path.display().to_string();
-            let graph =
-                MaterializedGraph::load_from_file(path, false).expect("Unable to load from graph");
-            let graph_name = get_graph_name(path, &graph);
-            graph
-                .update_constant_properties([("path".to_string(), Prop::str(path_string.clone()))])
-                .expect("Failed to add static property");
-
-            (graph_name, graph)
-        }
-
-        #[cfg(feature = "storage")]
-        fn load_disk_graph(path: &Path) -> (String, MaterializedGraph) {
-            let disk_graph =
-                DiskGraph::load_from_dir(path).expect("Unable to load from disk_graph graph");
-            let graph: MaterializedGraph = disk_graph.into();
-            let graph_name = get_graph_name(path, &graph);
-
-            (graph_name, graph)
-        }
-        #[allow(unused_variables)]
-        #[cfg(not(feature = "storage"))]
-        fn load_disk_graph(path: &Path) -> (String, MaterializedGraph) {
-            unimplemented!("Storage feature not enabled, cannot load from disk graph")
-        }
-
-        fn add_to_graphs(
-            graphs: &mut HashMap<String, IndexedGraph<MaterializedGraph>>,
-            graph_name: &str,
-            graph: &MaterializedGraph,
-        ) {
-            if let Some(old_graph) = graphs.insert(
-                graph_name.to_string(),
-                IndexedGraph::from_graph(graph).expect("Unable to index graph"),
-            ) {
-                let old_path = old_graph.properties().get("path").unwrap_str();
-                let name = old_graph.properties().get("name").unwrap_str();
-                panic!(
-                    "Graph with name {} defined multiple times, first file: {}, second file: {}",
-                    name, old_path, graph_name
-                );
-            }
-        }
-
-        let mut graphs: HashMap<String, IndexedGraph<MaterializedGraph>> = HashMap::default();
+        Self::scan_directory(path)
+            .into_iter()
+            .map(|(name, file)| {
+                let graph = read_graph(&file).expect("Unable to load graph");
+                info!(graph = %file.display(), "Graph loaded");
+                let graph = IndexedGraph::from_graph(&graph).expect("Unable to index graph");
+                (name, graph)
+            })
+            .collect()
+    }
+}
 
-        for entry in fs::read_dir(path).unwrap() {
-            let entry = entry.unwrap();
-            let path = entry.path();
-            if path.is_dir() {
-                println!("Disk Graph loaded = {}", path.display());
-                if is_disk_graph_dir(&path) {
-                    let (graph_name, graph) = load_disk_graph(&path);
-                    add_to_graphs(&mut graphs, &graph_name, &graph);
-                }
-            } else {
-                println!("Graph loaded = {}", path.display());
-                let (graph_name, graph) = load_bincode_graph(&path);
-                add_to_graphs(&mut graphs, &graph_name, &graph);
-            }
+/// Whether `path` is a directory of a disk graph
+fn is_disk_graph_dir(path: &Path) -> bool {
+    // Check if the directory contains files specific to disk_graph graphs
+    let files = fs::read_dir(path).unwrap();
+    let mut has_disk_graph_files = false;
+    for file in files {
+        let file_name = file.unwrap().file_name().into_string().unwrap();
+        if file_name.ends_with(".ipc") {
+            has_disk_graph_files = true;
+            break;
         }
+    }
+    has_disk_graph_files
+}
 
-        graphs
+/// Read the graph stored in the file or disk graph directory `path`
+fn read_graph(path: &Path) -> Result<MaterializedGraph, GraphError> {
+    #[cfg(feature = "storage")]
+    if path.is_dir() {
+        let graph = DiskGraph::load_from_dir(path)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?;
+        return Ok(graph.into());
     }
+    let graph = MaterializedGraph::load_from_file(path, false)?;
+    graph.update_constant_properties([("path", Prop::str(path.display().to_string()))])?;
+    Ok(graph)
 }
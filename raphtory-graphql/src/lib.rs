@@ -1,4 +1,4 @@
-pub use crate::server::RaphtoryServer;
+pub use crate::{observability::metrics::MetricsConfig, server::RaphtoryServer};
 use base64::{prelude::BASE64_URL_SAFE_NO_PAD, DecodeError, Engine};
 use raphtory::{core::utils::errors::GraphError, db::api::view::MaterializedGraph};
 
@@ -7,7 +7,11 @@ mod observability;
 mod routes;
 pub mod server;
 
+pub mod auth;
 pub mod azure_auth;
+pub mod cache;
+pub mod limits;
+pub mod namespace;
 
 mod data;
 
@@ -1182,4 +1186,365 @@ mod graphql_test {
         let expected_error_message = "Disk Graph is immutable";
         assert_eq!(error_message, expected_error_message);
     }
+
+    #[tokio::test]
+    async fn test_sorted_filtered_connection() {
+        let graph = Graph::new();
+        for (id, kind) in [(1, "bank"), (2, "bank"), (3, "bank"), (4, "shop")] {
+            graph
+                .add_node(0, id, [("kind", Prop::str(kind))], None)
+                .unwrap();
+        }
+        for (src, dst) in [(1u64, 2u64), (1, 3), (1, 4), (2, 3)] {
+            graph
+                .add_edge(src as i64, src, dst, NO_PROPS, None)
+                .unwrap();
+        }
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let data = Data::from_map(graphs);
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = |after: &str| {
+            format!(
+                r#"{{
+                  graph(name: "graph") {{
+                    nodes {{
+                      filter(where: {{properties: [{{name: "kind", operator: EQUAL, value: "bank"}}]}}) {{
+                        sorted(by: [{{field: DEGREE, reverse: true}}]) {{
+                          list {{ name }}
+                          connection(first: 2{after}) {{
+                            totalCount
+                            edges {{ cursor node {{ name }} }}
+                            pageInfo {{ hasNextPage hasPreviousPage }}
+                          }}
+                        }}
+                      }}
+                    }}
+                  }}
+                }}"#
+            )
+        };
+
+        let res = schema.execute(Request::new(query(""))).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let sorted = &data["graph"]["nodes"]["filter"]["sorted"];
+        assert_eq!(
+            sorted["list"],
+            json!([{"name": "1"}, {"name": "2"}, {"name": "3"}])
+        );
+        let connection = &sorted["connection"];
+        assert_eq!(connection["totalCount"], json!(3));
+        assert_eq!(
+            connection["pageInfo"],
+            json!({"hasNextPage": true, "hasPreviousPage": false})
+        );
+        let cursor = connection["edges"][1]["cursor"].as_str().unwrap();
+
+        let res = schema
+            .execute(Request::new(query(&format!(r#", after: "{cursor}""#))))
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let connection = &data["graph"]["nodes"]["filter"]["sorted"]["connection"];
+        assert_eq!(connection["edges"][0]["node"], json!({"name": "3"}));
+        assert_eq!(
+            connection["pageInfo"],
+            json!({"hasNextPage": false, "hasPreviousPage": true})
+        );
+
+        let invalid = r#"{
+          graph(name: "graph") {
+            nodes { sorted(by: [{field: PROPERTY}]) { list { name } } }
+          }
+        }"#;
+        let res = schema.execute(Request::new(invalid)).await;
+        assert_eq!(
+            res.errors[0].message,
+            "Invalid sort: sorting by Property needs a property name"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_algorithm_library() {
+        let graph = Graph::new();
+        for (t, src, dst) in [(1, 1, 2), (2, 2, 3), (3, 3, 1), (4, 3, 4), (5, 5, 6)] {
+            graph.add_edge(t, src, dst, NO_PROPS, None).unwrap();
+        }
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let data = Data::from_map(graphs);
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"{
+          graph(name: "graph") {
+            algorithms {
+              triangle_count
+              k_core(k: 2)
+              hits(topK: 2) { name hub authority }
+              degree_centrality(limit: 2, offset: 1) { name }
+              local_triangle_count(topK: 1) { name count }
+              weakly_connected_components { name community }
+              temporal_motifs(deltas: [10]) { delta counts }
+            }
+          }
+        }"#;
+        let res = schema.execute(Request::new(query)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let algorithms = &data["graph"]["algorithms"];
+        assert_eq!(algorithms["triangle_count"], json!(1));
+        assert_eq!(algorithms["k_core"], json!(["1", "2", "3"]));
+        assert_eq!(algorithms["hits"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            algorithms["degree_centrality"],
+            json!([{"name": "2"}, {"name": "4"}])
+        );
+        assert_eq!(
+            algorithms["local_triangle_count"],
+            json!([{"name": "1", "count": 1}])
+        );
+        let components = algorithms["weakly_connected_components"]
+            .as_array()
+            .unwrap();
+        let names: Vec<_> = components
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["1", "2", "3", "4", "5", "6"]);
+        assert_eq!(components[0]["community"], components[3]["community"]);
+        assert_eq!(components[4]["community"], components[5]["community"]);
+        assert_ne!(components[0]["community"], components[4]["community"]);
+        let motifs = &algorithms["temporal_motifs"][0];
+        assert_eq!(motifs["delta"], json!(10));
+        assert_eq!(motifs["counts"].as_array().unwrap().len(), 40);
+    }
+
+    #[tokio::test]
+    async fn test_namespaces() {
+        let test_dir = tempdir().unwrap();
+        let fraud_dir = test_dir.path().join("teamA").join("fraud");
+        std::fs::create_dir_all(&fraud_dir).unwrap();
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        graph.save_to_file(test_dir.path().join("g0")).unwrap();
+        graph.save_to_file(fraud_dir.join("g1")).unwrap();
+
+        let mut data = Data::from_directory(test_dir.path().to_str().unwrap());
+        data.namespaces =
+            std::sync::Arc::new(toml::from_str("[quotas.teamB]\nmax_graphs = 1").unwrap());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"{
+          graphs(namespace: "teamA") { name path namespace }
+          namespaces
+        }"#;
+        let res = schema.execute(Request::new(query)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({
+                "graphs": [{"name": "g1", "path": "teamA/fraud/g1", "namespace": "teamA/fraud"}],
+                "namespaces": ["teamA", "teamA/fraud"]
+            })
+        );
+
+        let mutation = r#"mutation { moveGraph(path: "teamA/fraud/g1", newPath: "teamB/g1") }"#;
+        let res = schema.execute(Request::new(mutation)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert!(test_dir.path().join("teamB").join("g1").exists());
+        assert!(!fraud_dir.join("g1").exists());
+
+        let query = r#"{ graph(name: "teamB/g1") { countNodes } namespaces(parent: "teamB") }"#;
+        let res = schema.execute(Request::new(query)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"countNodes": 2}, "namespaces": []})
+        );
+
+        let encoded = url_encode_graph(Graph::new()).unwrap();
+        let mutation = format!(r#"mutation {{ sendGraph(name: "teamB/g2", graph: "{encoded}") }}"#);
+        let res = schema.execute(Request::new(mutation)).await;
+        assert_eq!(
+            res.errors[0].message,
+            "Namespace 'teamB' may contain at most 1 graphs"
+        );
+
+        let mutation = r#"mutation { sendGraph(name: "teamB//g2", graph: "") }"#;
+        let res = schema.execute(Request::new(mutation)).await;
+        assert_eq!(
+            res.errors[0].message,
+            "Invalid graph path 'teamB//g2': empty segment"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphs_are_named_after_their_files() {
+        let test_dir = tempdir().unwrap();
+        let team_dir = test_dir.path().join("teamA");
+        std::fs::create_dir_all(&team_dir).unwrap();
+        let graph = Graph::new();
+        graph.add_constant_properties([("name", "other")]).unwrap();
+        graph.save_to_file(team_dir.join("g1")).unwrap();
+        let path = test_dir.path().to_str().unwrap();
+
+        let data = Data::from_directory(path);
+        assert_eq!(data.graph_names(), ["teamA/g1"]);
+
+        let schema = App::create_schema().data(Data::default()).finish().unwrap();
+        let path = path.replace(r#"\"#, r#"\\"#);
+        let mutation = format!(r#"mutation {{ loadGraphsFromPath(path: "{path}") }}"#);
+        let res = schema.execute(Request::new(mutation)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"loadGraphsFromPath": ["teamA/g1"]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lazy_loading() {
+        let test_dir = tempdir().unwrap();
+        for name in ["g1", "g2"] {
+            let graph = Graph::new();
+            graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+            graph.save_to_file(test_dir.path().join(name)).unwrap();
+        }
+        let mut data = Data::from_directory(test_dir.path().to_str().unwrap());
+        data.memory_budget = Some(1);
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        let list_graphs = r#"{ graphs { name loaded } }"#;
+        let count_nodes = |name: &str| format!(r#"{{ graph(name: "{name}") {{ countNodes }} }}"#);
+
+        let res = schema.execute(Request::new(list_graphs)).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graphs": [{"name": "g1", "loaded": false}, {"name": "g2", "loaded": false}]})
+        );
+
+        let res = schema.execute(Request::new(count_nodes("g1"))).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"countNodes": 2}})
+        );
+        let res = schema.execute(Request::new(list_graphs)).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graphs": [{"name": "g1", "loaded": true}, {"name": "g2", "loaded": false}]})
+        );
+
+        // loading g2 exceeds the budget, so g1 is saved with its changes and unloaded
+        let g1 = data.get_graph("g1").unwrap().unwrap();
+        g1.graph.add_node(1, 3, NO_PROPS, None).unwrap();
+        let res = schema.execute(Request::new(count_nodes("g2"))).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"countNodes": 2}})
+        );
+        let res = schema.execute(Request::new(list_graphs)).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graphs": [{"name": "g1", "loaded": false}, {"name": "g2", "loaded": true}]})
+        );
+        let saved = MaterializedGraph::load_from_file(test_dir.path().join("g1"), false).unwrap();
+        assert_eq!(saved.count_nodes(), 3);
+
+        let res = schema.execute(Request::new(count_nodes("g1"))).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"countNodes": 3}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rolling_windows() {
+        let graph = Graph::new();
+        graph
+            .add_edge(1, "a", "b", [("amount", 10i64)], None)
+            .unwrap();
+        graph
+            .add_edge(2, "a", "c", [("amount", 20i64)], None)
+            .unwrap();
+        graph
+            .add_edge(3, "b", "c", [("amount", 30i64)], None)
+            .unwrap();
+        graph
+            .add_edge(4, "a", "b", [("amount", 40i64)], None)
+            .unwrap();
+        graph
+            .add_edge(4, "a", "b", [("amount", 50i64)], None)
+            .unwrap();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let data = Data::from_map(graphs);
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"{
+          graph(name: "graph") {
+            rolling(window: "2") { start end countEdges }
+            expanding(step: "2") { end countEdges }
+            node(name: "a") { rolling(window: "2", step: "1") { start degree } }
+            subgraph(nodes: ["a", "b"]) {
+              edges {
+                list {
+                  rolling(window: "2") {
+                    properties { temporal { get(key: "amount") { sum mean min max } } }
+                  }
+                }
+              }
+            }
+          }
+        }"#;
+        let res = schema.execute(Request::new(query)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let amount = |sum: i64, mean: f64, min: i64, max: i64| json!({"properties": {"temporal": {"get": {"sum": sum, "mean": mean, "min": min, "max": max}}}});
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({
+                "graph": {
+                    "rolling": [
+                        {"start": 1, "end": 3, "countEdges": 2},
+                        {"start": 3, "end": 5, "countEdges": 2},
+                    ],
+                    "expanding": [
+                        {"end": 3, "countEdges": 2},
+                        {"end": 5, "countEdges": 3},
+                    ],
+                    "node": {
+                        "rolling": [
+                            {"start": 0, "degree": 1},
+                            {"start": 1, "degree": 2},
+                            {"start": 2, "degree": 1},
+                            {"start": 3, "degree": 1},
+                        ]
+                    },
+                    "subgraph": {
+                        "edges": {
+                            "list": [{"rolling": [amount(10, 10.0, 10, 10), amount(90, 45.0, 40, 50)]}]
+                        }
+                    }
+                }
+            })
+        );
+
+        let query = r#"{ graph(name: "graph") { rolling(window: "2 fortnights") { end } } }"#;
+        let res = schema.execute(Request::new(query)).await;
+        assert!(res.errors[0].message.starts_with("Invalid interval: "));
+
+        for window in ["0", "0 days", "-1"] {
+            let query = format!(
+                r#"{{ graph(name: "graph") {{ rolling(window: "{window}") {{ end }} }} }}"#
+            );
+            let res = schema.execute(Request::new(query)).await;
+            assert!(
+                res.errors[0].message.starts_with("Invalid interval: "),
+                "{window}"
+            );
+        }
+        let query = r#"{ graph(name: "graph") { expanding(step: "1") { end } } }"#;
+        let res = schema.execute(Request::new(query)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+    }
 }
@@ -0,0 +1,469 @@
+//! Limits protecting a shared server from expensive queries
+//!
+//! The depth and complexity of a query are checked before it runs, where every field counts
+//! one towards the complexity. Queries running longer than the timeout are cancelled:
+//! algorithms stop at their next node or iteration and lists stop being collected, after which
+//! the query fails. Cancellation is cooperative, as resolvers run synchronously and cannot be
+//! interrupted, so a query only fails once the resolver running at the timeout returns.
+//!
+//! Lists larger than the maximum list size fail instead of being returned. Every client may
+//! send a limited number of requests per period, where clients are identified by their
+//! authenticated principal or else by their address.
+//!
+//! ```toml
+//! max_depth = 10
+//! max_complexity = 1000
+//! timeout_ms = 30000
+//! max_list_size = 10000
+//!
+//! [rate_limit]
+//! requests = 100
+//! period_secs = 60
+//! ```
+
+use crate::{
+    auth::{AuthConfig, ANONYMOUS},
+    cache::QueryCache,
+    model::GqlGraphError,
+    observability::metrics::METRICS,
+};
+use async_graphql::{
+    dynamic::{Schema, SchemaBuilder},
+    Context, Request, Response, ServerError,
+};
+use async_graphql_poem::{GraphQLRequest, GraphQLResponse};
+use parking_lot::Mutex;
+use poem::{
+    endpoint::BoxEndpoint, handler, http::StatusCode, web, Endpoint, EndpointExt, IntoEndpoint,
+    IntoResponse,
+};
+use raphtory::db::task::cancellation::{with_cancellation, CancellationToken};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+/// Maximum number of clients tracked by a [`RateLimiter`]
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(thiserror::Error, Debug)]
+pub enum LimitsError {
+    #[error("Failed to read limits config: {0}")]
+    InvalidConfig(String),
+    #[error("Too many requests, at most {0} are allowed every {1:?}")]
+    TooManyRequests(u32, Duration),
+}
+
+/// The number of `requests` a client may send every `period_secs` seconds
+#[derive(Clone, Debug, Deserialize)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub period_secs: u64,
+}
+
+/// Limits on the cost of the queries a server runs, unlimited by default
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryLimits {
+    /// Maximum nesting depth of a query
+    pub max_depth: Option<usize>,
+    /// Maximum number of fields selected by a query, counting nested fields
+    pub max_complexity: Option<usize>,
+    /// Maximum running time of a query in milliseconds
+    pub timeout_ms: Option<u64>,
+    /// Maximum number of items in a returned list
+    pub max_list_size: Option<usize>,
+    /// Maximum request rate of every client
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl QueryLimits {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, LimitsError> {
+        let content =
+            fs::read_to_string(path).map_err(|err| LimitsError::InvalidConfig(err.to_string()))?;
+        toml::from_str(&content).map_err(|err| LimitsError::InvalidConfig(err.to_string()))
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.map(Duration::from_millis)
+    }
+
+    /// Apply the depth and complexity limits to `schema` and make the limits available to
+    /// resolvers
+    pub(crate) fn configure(&self, schema: SchemaBuilder) -> SchemaBuilder {
+        let schema = match self.max_depth {
+            Some(depth) => schema.limit_depth(depth),
+            None => schema,
+        };
+        let schema = match self.max_complexity {
+            Some(complexity) => schema.limit_complexity(complexity),
+            None => schema,
+        };
+        schema.data(self.clone())
+    }
+}
+
+/// Execute `request`, cancelling it once it exceeds the timeout
+pub(crate) async fn execute(schema: &Schema, limits: &QueryLimits, request: Request) -> Response {
+    let token = match limits.timeout() {
+        Some(timeout) => CancellationToken::with_deadline(Instant::now() + timeout),
+        None => CancellationToken::new(),
+    };
+    let request = request.data(token.clone());
+    let response = match limits.timeout() {
+        Some(timeout) => match tokio::time::timeout(timeout, schema.execute(request)).await {
+            Ok(response) => response,
+            Err(_) => {
+                token.cancel();
+                let error = ServerError::new(GqlGraphError::Timeout.to_string(), None);
+                Response::from_errors(vec![error])
+            }
+        },
+        None => schema.execute(request).await,
+    };
+    for error in &response.errors {
+        warn!(error = %error.message, "GraphQL request failed");
+    }
+    response
+}
+
+/// GraphQL endpoint enforcing the [`QueryLimits`] of the server
+#[handler]
+pub(crate) async fn graphql_with_limits(
+    schema: web::Data<&Schema>,
+    limits: web::Data<&QueryLimits>,
+    cache: web::Data<&Arc<QueryCache>>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    let operation = request.0.operation_name.clone();
+    let response = cache.execute("", request.0, |request| execute(&schema, &limits, request));
+    METRICS
+        .observe_request(operation.as_deref(), response)
+        .await
+        .into()
+}
+
+/// Maximum number of windows returned by `rolling` and `expanding`, also if no maximum list
+/// size is configured
+pub(crate) const MAX_WINDOWS: usize = 100_000;
+
+fn max_list_size(ctx: &Context<'_>) -> Option<usize> {
+    ctx.data_opt::<QueryLimits>()
+        .and_then(|limits| limits.max_list_size)
+}
+
+/// Collect `items` into a list, failing if it would exceed the maximum list size or if the
+/// query has been cancelled
+pub(crate) fn collect_limited<T>(
+    ctx: &Context<'_>,
+    items: impl IntoIterator<Item = T>,
+) -> Result<Vec<T>, GqlGraphError> {
+    collect_at_most(
+        ctx,
+        items,
+        max_list_size(ctx),
+        GqlGraphError::ResultTooLarge,
+    )
+}
+
+/// Collect `windows` into a list, failing if there are more than [`MAX_WINDOWS`] or than the
+/// maximum list size or if the query has been cancelled
+pub(crate) fn collect_windows<T>(
+    ctx: &Context<'_>,
+    windows: impl IntoIterator<Item = T>,
+) -> Result<Vec<T>, GqlGraphError> {
+    match max_list_size(ctx).filter(|&max| max < MAX_WINDOWS) {
+        Some(max) => collect_at_most(ctx, windows, Some(max), GqlGraphError::ResultTooLarge),
+        None => collect_at_most(
+            ctx,
+            windows,
+            Some(MAX_WINDOWS),
+            GqlGraphError::TooManyWindows,
+        ),
+    }
+}
+
+fn collect_at_most<T>(
+    ctx: &Context<'_>,
+    items: impl IntoIterator<Item = T>,
+    max: Option<usize>,
+    too_large: impl FnOnce(usize) -> GqlGraphError,
+) -> Result<Vec<T>, GqlGraphError> {
+    let token = ctx.data_opt::<CancellationToken>();
+    let mut list = vec![];
+    for item in items {
+        if token.is_some_and(CancellationToken::is_cancelled) {
+            return Err(GqlGraphError::Timeout);
+        }
+        if let Some(max) = max.filter(|&max| list.len() >= max) {
+            return Err(too_large(max));
+        }
+        list.push(item);
+    }
+    Ok(list)
+}
+
+/// Run `f` so that algorithms stop once the query is cancelled, failing if it was
+///
+/// Only algorithms checking the token, see [`raphtory::db::task::cancellation`], stop early.
+pub(crate) fn run_cancellable<T>(
+    ctx: &Context<'_>,
+    f: impl FnOnce() -> T,
+) -> Result<T, GqlGraphError> {
+    match ctx.data_opt::<CancellationToken>() {
+        Some(token) => {
+            let result = with_cancellation(token, f);
+            if token.is_cancelled() {
+                return Err(GqlGraphError::Timeout);
+            }
+            Ok(result)
+        }
+        None => Ok(f()),
+    }
+}
+
+/// Counts the requests of every client in fixed windows
+///
+/// Once `max_clients` are tracked, clients whose window has ended are dropped and, if all
+/// windows are still running, the client whose window started first.
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    max_clients: usize,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            max_clients: MAX_TRACKED_CLIENTS,
+            windows: Default::default(),
+        }
+    }
+
+    fn period(&self) -> Duration {
+        Duration::from_secs(self.limit.period_secs)
+    }
+
+    /// Record a request of `client`, failing if it exceeds the rate limit
+    pub(crate) fn check(&self, client: &str) -> Result<(), LimitsError> {
+        let now = Instant::now();
+        let period = self.period();
+        let mut windows = self.windows.lock();
+        if !windows.contains_key(client) && windows.len() >= self.max_clients {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < period);
+            if windows.len() >= self.max_clients {
+                let oldest = windows
+                    .iter()
+                    .min_by_key(|(_, (start, _))| *start)
+                    .map(|(client, _)| client.clone());
+                if let Some(oldest) = oldest {
+                    windows.remove(&oldest);
+                }
+            }
+        }
+        let (start, count) = windows.entry(client.to_owned()).or_insert((now, 0));
+        if now.duration_since(*start) >= period {
+            *start = now;
+            *count = 0;
+        }
+        *count += 1;
+        if *count > self.limit.requests {
+            return Err(LimitsError::TooManyRequests(self.limit.requests, period));
+        }
+        Ok(())
+    }
+}
+
+/// Apply the rate limit of `limits`, if any, to `endpoint`, identifying clients by the
+/// principal they authenticate as with `auth`
+pub(crate) fn with_rate_limit<E: IntoEndpoint>(
+    endpoint: E,
+    limits: &QueryLimits,
+    auth: Option<Arc<AuthConfig>>,
+) -> BoxEndpoint<'static>
+where
+    E::Endpoint: 'static,
+{
+    let endpoint = endpoint.into_endpoint();
+    match limits.rate_limit.clone() {
+        Some(rate_limit) => {
+            let limiter = Arc::new(RateLimiter::new(rate_limit));
+            endpoint
+                .around(move |endpoint, request| {
+                    rate_limited(endpoint, request, limiter.clone(), auth.clone())
+                })
+                .boxed()
+        }
+        None => endpoint.map_to_response().boxed(),
+    }
+}
+
+/// The client sending `request` for rate limiting, the principal it authenticates as or else
+/// its address
+fn client(request: &poem::Request, auth: Option<&AuthConfig>) -> String {
+    auth.and_then(|config| config.authenticate(request.headers()).ok())
+        .filter(|access| access.principal() != ANONYMOUS)
+        .map(|access| format!("principal:{}", access.principal()))
+        .unwrap_or_else(|| format!("address:{}", request.remote_addr()))
+}
+
+/// Reject requests of clients exceeding the rate limit of `limiter`
+async fn rate_limited<E: Endpoint>(
+    endpoint: Arc<E>,
+    request: poem::Request,
+    limiter: Arc<RateLimiter>,
+    auth: Option<Arc<AuthConfig>>,
+) -> poem::Result<poem::Response> {
+    let client = client(&request, auth.as_deref());
+    if let Err(err) = limiter.check(&client) {
+        warn!(client = %request.remote_addr(), "{err}");
+        return Err(poem::Error::new(err, StatusCode::TOO_MANY_REQUESTS));
+    }
+    Ok(endpoint.call(request).await?.into_response())
+}
+
+#[cfg(test)]
+mod limits_test {
+    use super::*;
+    use crate::{data::Data, model::App};
+    use raphtory::{db::api::view::MaterializedGraph, prelude::*};
+
+    fn schema(limits: &QueryLimits) -> Schema {
+        let graph = Graph::new();
+        for i in 0..10u64 {
+            graph.add_edge(0, i, (i + 1) % 10, NO_PROPS, None).unwrap();
+        }
+        let graph: MaterializedGraph = graph.into();
+        let data = Data::from_map(HashMap::from([("g".to_string(), graph)]));
+        limits
+            .configure(App::create_schema().data(data))
+            .finish()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn depth_complexity_and_list_size() {
+        let limits = QueryLimits {
+            max_depth: Some(6),
+            max_complexity: Some(20),
+            max_list_size: Some(5),
+            ..Default::default()
+        };
+        let schema = schema(&limits);
+
+        let query = r#"{ graph(name: "g") { nodes { list { neighbours { list {
+            neighbours { list { name } } } } } } } }"#;
+        let res = execute(&schema, &limits, Request::new(query)).await;
+        // the wording of the message differs between versions of async-graphql
+        assert!(
+            res.errors[0].message.contains("nested too deep"),
+            "{:?}",
+            res.errors
+        );
+
+        let query = r#"{ graph(name: "g") { nodes { page(limit: 5, offset: 0) { name } } } }"#;
+        let res = execute(&schema, &limits, Request::new(query)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        let query = r#"{ graph(name: "g") { nodes { list { name } } } }"#;
+        let res = execute(&schema, &limits, Request::new(query)).await;
+        assert_eq!(
+            res.errors[0].message,
+            GqlGraphError::ResultTooLarge(5).to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn algorithms_are_cancelled() {
+        let limits = QueryLimits {
+            timeout_ms: Some(0),
+            ..Default::default()
+        };
+        let schema = schema(&limits);
+        let query = r#"{ graph(name: "g") { algorithms { pagerank(iterCount: 100000000, tol: -1.0) {
+            name rank } } } }"#;
+        let res = execute(&schema, &limits, Request::new(query)).await;
+        assert_eq!(res.errors[0].message, GqlGraphError::Timeout.to_string());
+    }
+
+    #[tokio::test]
+    async fn windows_are_capped_without_limits() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        graph
+            .add_edge(2 * MAX_WINDOWS as i64, 1, 2, NO_PROPS, None)
+            .unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let data = Data::from_map(HashMap::from([("g".to_string(), graph)]));
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let query = r#"{ graph(name: "g") { expanding(step: "1") { end } } }"#;
+        let res = schema.execute(Request::new(query)).await;
+        assert_eq!(
+            res.errors[0].message,
+            GqlGraphError::TooManyWindows(MAX_WINDOWS).to_string()
+        );
+    }
+
+    #[test]
+    fn rate_limits_are_per_client() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests: 2,
+            period_secs: 60,
+        });
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("b").is_ok());
+        assert!(matches!(
+            limiter.check("a"),
+            Err(LimitsError::TooManyRequests(2, _))
+        ));
+    }
+
+    #[test]
+    fn rate_limiter_tracks_a_bounded_number_of_clients() {
+        let mut limiter = RateLimiter::new(RateLimit {
+            requests: 1,
+            period_secs: 60,
+        });
+        limiter.max_clients = 2;
+        assert!(limiter.check("a").is_ok());
+        assert!(limiter.check("b").is_ok());
+        assert!(limiter.check("c").is_ok());
+        assert_eq!(limiter.windows.lock().len(), 2);
+        assert!(!limiter.windows.lock().contains_key("a"));
+        assert!(limiter.check("c").is_err());
+    }
+
+    #[test]
+    fn clients_are_identified_by_principal() {
+        let config: AuthConfig = toml::from_str(
+            r#"
+            anonymous_role = "public"
+            [[api_keys]]
+            key = "key"
+            principal = "etl"
+            roles = []
+            [roles.public]
+            "#,
+        )
+        .unwrap();
+        let request = |key: &str| poem::Request::builder().header("x-api-key", key).finish();
+        assert_eq!(client(&request("key"), Some(&config)), "principal:etl");
+        // invalid credentials do not give clients a fresh limit
+        assert_eq!(
+            client(&request("random"), Some(&config)),
+            client(&request("other"), Some(&config))
+        );
+        assert_eq!(
+            client(&poem::Request::default(), Some(&config)),
+            client(&request("key"), None)
+        );
+    }
+}
@@ -1,9 +1,16 @@
-use crate::server::RaphtoryServer;
+use crate::{
+    auth::AuthConfig, cache::CacheConfig, limits::QueryLimits, namespace::NamespaceConfig,
+    server::RaphtoryServer,
+};
 use std::env;
 
+mod auth;
 mod azure_auth;
+mod cache;
 mod data;
+mod limits;
 mod model;
+mod namespace;
 mod observability;
 mod routes;
 mod server;
@@ -24,9 +31,33 @@ async fn main() {
             .await
             .unwrap();
     } else {
-        RaphtoryServer::from_directory(&graph_directory)
-            .run(config_path, false)
-            .await
-            .unwrap();
+        let mut server = RaphtoryServer::from_directory(&graph_directory);
+        if let Ok(auth_config) = env::var("RAPHTORY_AUTH_CONFIG") {
+            let auth = AuthConfig::from_file(&auth_config).expect("Failed to load auth config");
+            server = server.with_auth(auth);
+        }
+        if let Ok(limits_config) = env::var("RAPHTORY_LIMITS_CONFIG") {
+            let limits =
+                QueryLimits::from_file(&limits_config).expect("Failed to load limits config");
+            server = server.with_limits(limits);
+        }
+        if let Ok(namespaces_config) = env::var("RAPHTORY_NAMESPACES_CONFIG") {
+            let namespaces = NamespaceConfig::from_file(&namespaces_config)
+                .expect("Failed to load namespaces config");
+            server = server.with_namespaces(namespaces);
+        }
+        if let Ok(memory_budget) = env::var("RAPHTORY_MEMORY_BUDGET") {
+            let bytes = memory_budget
+                .parse()
+                .expect("RAPHTORY_MEMORY_BUDGET must be a number of bytes");
+            server = server.with_memory_budget(bytes);
+        }
+        if let Ok(cache_config) = env::var("RAPHTORY_CACHE_CONFIG") {
+            let cache = CacheConfig::from_file(&cache_config).expect("Failed to load cache config");
+            server = server
+                .with_cache(cache)
+                .expect("Failed to materialise views");
+        }
+        server.run(config_path, false).await.unwrap();
     }
 }
@@ -1,5 +1,9 @@
-use crate::model::algorithms::{
-    algorithm_entry_point::AlgorithmEntryPoint, graph_algorithms::GraphAlgorithms,
+use crate::{
+    limits::run_cancellable,
+    model::algorithms::{
+        algorithm_entry_point::AlgorithmEntryPoint, graph_algorithms::GraphAlgorithms,
+    },
+    observability::metrics::METRICS,
 };
 use async_graphql::{
     dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, ResolverContext, TypeRef},
@@ -31,10 +35,14 @@ pub trait Algorithm<'a, A: AlgorithmEntryPoint<'a> + 'static> {
     ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>>;
     fn register_algo(name: &str, registry: Registry, parent: Object) -> (Registry, Object) {
         let registry = registry.register::<Self::OutputType>();
-        let mut field = Field::new(name, Self::output_type(), |ctx| {
+        let algorithm = name.to_owned();
+        let mut field = Field::new(name, Self::output_type(), move |ctx| {
+            let algorithm = algorithm.clone();
             FieldFuture::new(async move {
+                let _timer = METRICS.algorithm_timer(&algorithm);
                 let algos: &A = ctx.parent_value.downcast_ref().unwrap();
-                Self::apply_algo(&algos, ctx).await
+                let query = ctx.ctx;
+                run_cancellable(query, || Self::apply_algo(algos, ctx))?.await
             })
         });
         for (name, type_ref) in Self::args() {
@@ -1,5 +1,8 @@
-use crate::model::algorithms::{
-    algorithm::Algorithm, document::GqlDocument, global_plugins::GlobalPlugins,
+use crate::{
+    auth::may_hide_properties,
+    model::algorithms::{
+        algorithm::Algorithm, document::GqlDocument, global_plugins::GlobalPlugins,
+    },
 };
 use async_graphql::{
     dynamic::{FieldValue, ResolverContext, TypeRef},
@@ -8,7 +11,7 @@ use async_graphql::{
 use dynamic_graphql::internal::TypeName;
 use futures_util::future::BoxFuture;
 use raphtory::vectors::{embeddings::openai_embedding, vectorised_cluster::VectorisedCluster};
-use std::ops::Deref;
+use std::collections::HashMap;
 
 pub(crate) struct GlobalSearch;
 
@@ -35,15 +38,20 @@ impl<'a> Algorithm<'a, GlobalPlugins> for GlobalSearch {
             .unwrap()
             .to_owned();
         let limit = ctx.args.try_get("limit").unwrap().u64().unwrap() as usize;
-        let vectorised_graphs = entry_point.vectorised_graphs.clone();
+        // documents are rendered from properties, so graphs with hidden properties are left out
+        let vectorised_graphs: HashMap<_, _> = entry_point
+            .vectorised_graphs
+            .read()
+            .iter()
+            .filter(|(name, _)| !may_hide_properties(ctx.ctx, name))
+            .map(|(name, graph)| (name.clone(), graph.clone()))
+            .collect();
 
         Box::pin(async move {
             let embedding = openai_embedding(vec![query.clone()]).await.remove(0);
             println!("running global search for {query}");
 
-            let graphs = vectorised_graphs.read();
-
-            let cluster = VectorisedCluster::new(graphs.deref());
+            let cluster = VectorisedCluster::new(&vectorised_graphs);
             let documents = cluster.search_graph_documents(&embedding, limit, None); // TODO: add window
 
             let gql_documents = documents
@@ -1,6 +1,12 @@
 use crate::model::algorithms::{
     algorithm::{Algorithm, Pagerank},
     algorithm_entry_point::AlgorithmEntryPoint,
+    library::{
+        BetweennessCentrality, ClusteringCoefficient, DegreeCentrality, GlobalReciprocity, Hits,
+        KCore, LabelPropagation, LocalClusteringCoefficient, LocalReciprocity, LocalTemporalMotifs,
+        LocalTriangleCount, Louvain, StronglyConnectedComponents, TemporalMotifs,
+        TemporalReachability, TriangleCount, WeaklyConnectedComponents,
+    },
     RegisterFunction,
 };
 use async_graphql::{dynamic::FieldValue, Context};
@@ -39,6 +45,68 @@ impl<'a> AlgorithmEntryPoint<'a> for GraphAlgorithms {
                 "shortest_path",
                 Box::new(ShortestPath::register_algo) as RegisterFunction,
             ),
+            ("hits", Box::new(Hits::register_algo) as RegisterFunction),
+            (
+                "betweenness_centrality",
+                Box::new(BetweennessCentrality::register_algo) as RegisterFunction,
+            ),
+            (
+                "degree_centrality",
+                Box::new(DegreeCentrality::register_algo) as RegisterFunction,
+            ),
+            (
+                "louvain",
+                Box::new(Louvain::register_algo) as RegisterFunction,
+            ),
+            (
+                "label_propagation",
+                Box::new(LabelPropagation::register_algo) as RegisterFunction,
+            ),
+            (
+                "weakly_connected_components",
+                Box::new(WeaklyConnectedComponents::register_algo) as RegisterFunction,
+            ),
+            (
+                "strongly_connected_components",
+                Box::new(StronglyConnectedComponents::register_algo) as RegisterFunction,
+            ),
+            ("k_core", Box::new(KCore::register_algo) as RegisterFunction),
+            (
+                "triangle_count",
+                Box::new(TriangleCount::register_algo) as RegisterFunction,
+            ),
+            (
+                "local_triangle_count",
+                Box::new(LocalTriangleCount::register_algo) as RegisterFunction,
+            ),
+            (
+                "clustering_coefficient",
+                Box::new(ClusteringCoefficient::register_algo) as RegisterFunction,
+            ),
+            (
+                "local_clustering_coefficient",
+                Box::new(LocalClusteringCoefficient::register_algo) as RegisterFunction,
+            ),
+            (
+                "global_reciprocity",
+                Box::new(GlobalReciprocity::register_algo) as RegisterFunction,
+            ),
+            (
+                "local_reciprocity",
+                Box::new(LocalReciprocity::register_algo) as RegisterFunction,
+            ),
+            (
+                "temporal_motifs",
+                Box::new(TemporalMotifs::register_algo) as RegisterFunction,
+            ),
+            (
+                "local_temporal_motifs",
+                Box::new(LocalTemporalMotifs::register_algo) as RegisterFunction,
+            ),
+            (
+                "temporal_reachability",
+                Box::new(TemporalReachability::register_algo) as RegisterFunction,
+            ),
         ])
     }
     fn lock_plugins() -> MutexGuard<'static, HashMap<String, RegisterFunction>> {
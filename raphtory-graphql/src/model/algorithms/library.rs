@@ -0,0 +1,708 @@
+//! The algorithms of the core library exposed on `GraphAlgorithms`
+//!
+//! Algorithms computing a value per node return lists that are sorted by name, or by
+//! descending score for scores, and take `limit` and `offset` arguments with the same meaning as
+//! `page` on node lists. Scores additionally take a `topK` argument keeping only the highest.
+
+use crate::model::algorithms::{algorithm::Algorithm, graph_algorithms::GraphAlgorithms};
+use async_graphql::{
+    dynamic::{FieldValue, ResolverContext, TypeRef},
+    Error, FieldResult,
+};
+use dynamic_graphql::{internal::TypeName, SimpleObject};
+use futures_util::future::BoxFuture;
+use itertools::Itertools;
+use raphtory::{
+    algorithms::{
+        centrality::{
+            betweenness::betweenness_centrality, degree_centrality::degree_centrality, hits::hits,
+        },
+        community_detection::{
+            label_propagation::label_propagation, louvain::louvain, modularity::ModularityUnDir,
+        },
+        components::{strongly_connected_components, weakly_connected_components},
+        cores::k_core::k_core_set,
+        metrics::{
+            clustering_coefficient::clustering_coefficient,
+            local_clustering_coefficient::local_clustering_coefficient,
+            reciprocity::{all_local_reciprocity, global_reciprocity},
+        },
+        motifs::{
+            global_temporal_three_node_motifs::temporal_three_node_motif_multi,
+            local_temporal_three_node_motifs::temporal_three_node_motif,
+            local_triangle_count::local_triangle_count, triangle_count::triangle_count,
+        },
+        pathing::temporal_reachability::temporally_reachable_nodes,
+    },
+    prelude::*,
+};
+
+#[derive(SimpleObject)]
+pub(crate) struct NodeScore {
+    name: String,
+    score: f64,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct HitsScore {
+    name: String,
+    hub: f64,
+    authority: f64,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct NodeCommunity {
+    name: String,
+    community: usize,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct NodeCount {
+    name: String,
+    count: usize,
+}
+
+/// The counts of the 40 temporal three-node motifs completing within `delta`, in the order of
+/// the Python documentation of `global_temporal_three_node_motif`
+#[derive(SimpleObject)]
+pub(crate) struct MotifCounts {
+    delta: i64,
+    counts: Vec<usize>,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct NodeMotifCounts {
+    name: String,
+    motifs: Vec<MotifCounts>,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct ReachedAt {
+    time: i64,
+    from: String,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct NodeReachability {
+    name: String,
+    reached: Vec<ReachedAt>,
+}
+
+fn page_args(mut args: Vec<(&str, TypeRef)>) -> Vec<(&str, TypeRef)> {
+    args.push(("limit", TypeRef::named(TypeRef::INT)));
+    args.push(("offset", TypeRef::named(TypeRef::INT)));
+    args
+}
+
+fn score_args(args: Vec<(&str, TypeRef)>) -> Vec<(&str, TypeRef)> {
+    let mut args = page_args(args);
+    args.push(("topK", TypeRef::named(TypeRef::INT)));
+    args
+}
+
+fn ready<'b>(
+    result: FieldResult<Option<FieldValue<'b>>>,
+) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+    Box::pin(async move { result })
+}
+
+fn usize_arg(ctx: &ResolverContext, name: &str) -> FieldResult<Option<usize>> {
+    Ok(ctx
+        .args
+        .get(name)
+        .map(|v| v.u64())
+        .transpose()?
+        .map(|v| v as usize))
+}
+
+fn strings_arg(ctx: &ResolverContext, name: &str) -> FieldResult<Option<Vec<String>>> {
+    ctx.args
+        .get(name)
+        .map(|v| {
+            v.list()?
+                .iter()
+                .map(|v| v.string().map(str::to_owned))
+                .collect()
+        })
+        .transpose()
+}
+
+fn deltas_arg(ctx: &ResolverContext) -> FieldResult<Vec<i64>> {
+    ctx.args
+        .try_get("deltas")?
+        .list()?
+        .iter()
+        .map(|v| v.i64())
+        .collect()
+}
+
+fn paged<T>(ctx: &ResolverContext, items: Vec<T>) -> FieldResult<Vec<T>> {
+    let offset = usize_arg(ctx, "offset")?.unwrap_or(0);
+    Ok(match usize_arg(ctx, "limit")? {
+        Some(limit) => items.into_iter().skip(offset * limit).take(limit).collect(),
+        None => items,
+    })
+}
+
+/// Sort `items` by name and page them
+fn by_name<T>(ctx: &ResolverContext, mut items: Vec<(String, T)>) -> FieldResult<Vec<(String, T)>> {
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+    paged(ctx, items)
+}
+
+/// Sort `items` by descending score, ties by name, keep the `topK` first and page them
+fn ranked<T>(
+    ctx: &ResolverContext,
+    mut items: Vec<(String, T)>,
+    score: impl Fn(&T) -> f64,
+) -> FieldResult<Vec<(String, T)>> {
+    items.sort_by(|(a_name, a), (b_name, b)| {
+        score(b)
+            .total_cmp(&score(a))
+            .then_with(|| a_name.cmp(b_name))
+    });
+    if let Some(k) = usize_arg(ctx, "topK")? {
+        items.truncate(k);
+    }
+    paged(ctx, items)
+}
+
+fn list<'b, T: Send + Sync + 'static>(
+    items: impl IntoIterator<Item = T>,
+) -> Option<FieldValue<'b>> {
+    Some(FieldValue::list(
+        items.into_iter().map(FieldValue::owned_any),
+    ))
+}
+
+fn scores<'b>(
+    ctx: &ResolverContext,
+    scores: impl IntoIterator<Item = (String, f64)>,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let scores = ranked(ctx, scores.into_iter().collect(), |score| *score)?;
+    Ok(list(
+        scores
+            .into_iter()
+            .map(|(name, score)| NodeScore { name, score }),
+    ))
+}
+
+fn communities<'b>(
+    ctx: &ResolverContext,
+    communities: impl IntoIterator<Item = (String, usize)>,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let communities = by_name(ctx, communities.into_iter().collect())?;
+    Ok(list(communities.into_iter().map(|(name, community)| {
+        NodeCommunity { name, community }
+    })))
+}
+
+pub(crate) struct Hits;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for Hits {
+    type OutputType = HitsScore;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(HitsScore::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        score_args(vec![("iterCount", TypeRef::named(TypeRef::INT))])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_hits(entry_point, &ctx))
+    }
+}
+
+fn apply_hits<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let iter_count = usize_arg(ctx, "iterCount")?.unwrap_or(20);
+    let result = hits(&entry_point.graph, iter_count, None).get_all_with_names();
+    let result = ranked(ctx, result.into_iter().collect(), |(hub, _)| *hub as f64)?;
+    Ok(list(result.into_iter().map(|(name, (hub, authority))| {
+        HitsScore {
+            name,
+            hub: hub as f64,
+            authority: authority as f64,
+        }
+    })))
+}
+
+pub(crate) struct BetweennessCentrality;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for BetweennessCentrality {
+    type OutputType = NodeScore;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeScore::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        score_args(vec![
+            ("k", TypeRef::named(TypeRef::INT)),
+            ("normalized", TypeRef::named(TypeRef::BOOLEAN)),
+        ])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_betweenness_centrality(entry_point, &ctx))
+    }
+}
+
+fn apply_betweenness_centrality<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let k = usize_arg(ctx, "k")?;
+    let normalized = ctx
+        .args
+        .get("normalized")
+        .map(|v| v.boolean())
+        .transpose()?;
+    let result = betweenness_centrality(&entry_point.graph, k, normalized);
+    scores(ctx, result.get_all_with_names())
+}
+
+pub(crate) struct DegreeCentrality;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for DegreeCentrality {
+    type OutputType = NodeScore;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeScore::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        score_args(vec![])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        let result = degree_centrality(&entry_point.graph, None);
+        ready(scores(&ctx, result.get_all_with_names()))
+    }
+}
+
+pub(crate) struct LocalClusteringCoefficient;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for LocalClusteringCoefficient {
+    type OutputType = NodeScore;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeScore::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        score_args(vec![])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        let graph = &entry_point.graph;
+        let result = graph.nodes().iter().map(|node| {
+            let coefficient = local_clustering_coefficient(graph, &node);
+            (node.name(), coefficient.unwrap_or_default() as f64)
+        });
+        ready(scores(&ctx, result))
+    }
+}
+
+pub(crate) struct LocalReciprocity;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for LocalReciprocity {
+    type OutputType = NodeScore;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeScore::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        score_args(vec![])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        let result = all_local_reciprocity(&entry_point.graph, None);
+        ready(scores(&ctx, result.get_all_with_names()))
+    }
+}
+
+pub(crate) struct Louvain;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for Louvain {
+    type OutputType = NodeCommunity;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeCommunity::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        page_args(vec![
+            ("resolution", TypeRef::named(TypeRef::FLOAT)),
+            ("weightProperty", TypeRef::named(TypeRef::STRING)),
+            ("tol", TypeRef::named(TypeRef::FLOAT)),
+        ])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_louvain(entry_point, &ctx))
+    }
+}
+
+fn apply_louvain<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let resolution = ctx.args.get("resolution").map(|v| v.f64()).transpose()?;
+    let weight = ctx
+        .args
+        .get("weightProperty")
+        .map(|v| v.string())
+        .transpose()?;
+    let tol = ctx.args.get("tol").map(|v| v.f64()).transpose()?;
+    let result =
+        louvain::<ModularityUnDir, _>(&entry_point.graph, resolution.unwrap_or(1.0), weight, tol);
+    communities(ctx, result.get_all_with_names())
+}
+
+pub(crate) struct LabelPropagation;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for LabelPropagation {
+    type OutputType = NodeCommunity;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeCommunity::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        page_args(vec![("seed", TypeRef::named(TypeRef::INT))])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_label_propagation(entry_point, &ctx))
+    }
+}
+
+fn apply_label_propagation<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let seed = ctx.args.get("seed").map(|v| v.u64()).transpose()?;
+    let seed = seed.map(|seed| {
+        let mut bytes = [0u8; 32];
+        bytes[..8].copy_from_slice(&seed.to_le_bytes());
+        bytes
+    });
+    let groups = label_propagation(&entry_point.graph, seed).map_err(Error::new)?;
+    let result = groups
+        .into_iter()
+        .enumerate()
+        .flat_map(|(community, nodes)| nodes.into_iter().map(move |node| (node.name(), community)));
+    communities(ctx, result)
+}
+
+pub(crate) struct WeaklyConnectedComponents;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for WeaklyConnectedComponents {
+    type OutputType = NodeCommunity;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeCommunity::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        page_args(vec![("iterCount", TypeRef::named(TypeRef::INT))])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_weakly_connected_components(entry_point, &ctx))
+    }
+}
+
+fn apply_weakly_connected_components<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let iter_count = usize_arg(ctx, "iterCount")?.unwrap_or(usize::MAX);
+    let result = weakly_connected_components(&entry_point.graph, iter_count, None);
+    let result = result
+        .get_all_with_names()
+        .into_iter()
+        .map(|(name, component)| (name, component as usize));
+    communities(ctx, result)
+}
+
+pub(crate) struct StronglyConnectedComponents;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for StronglyConnectedComponents {
+    type OutputType = NodeCommunity;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeCommunity::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        page_args(vec![])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        let result = strongly_connected_components(&entry_point.graph, None);
+        ready(communities(&ctx, result.get_all_with_names()))
+    }
+}
+
+pub(crate) struct KCore;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for KCore {
+    type OutputType = String;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(TypeRef::STRING)
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        page_args(vec![
+            ("k", TypeRef::named_nn(TypeRef::INT)),
+            ("iterCount", TypeRef::named(TypeRef::INT)),
+        ])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_k_core(entry_point, &ctx))
+    }
+}
+
+fn apply_k_core<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let graph = &entry_point.graph;
+    let k = ctx.args.try_get("k")?.u64()? as usize;
+    let iter_count = usize_arg(ctx, "iterCount")?.unwrap_or(usize::MAX);
+    let names = k_core_set(graph, k, iter_count, None)
+        .into_iter()
+        .filter_map(|node| graph.node(node).map(|node| node.name()))
+        .sorted()
+        .collect();
+    let names = paged(ctx, names)?;
+    Ok(Some(FieldValue::list(
+        names.into_iter().map(FieldValue::value),
+    )))
+}
+
+pub(crate) struct TriangleCount;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for TriangleCount {
+    type OutputType = usize;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn(TypeRef::INT)
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        vec![]
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        _ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        let count = triangle_count(&entry_point.graph, None);
+        ready(Ok(Some(FieldValue::value(count))))
+    }
+}
+
+pub(crate) struct LocalTriangleCount;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for LocalTriangleCount {
+    type OutputType = NodeCount;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeCount::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        score_args(vec![])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_local_triangle_count(entry_point, &ctx))
+    }
+}
+
+fn apply_local_triangle_count<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let graph = &entry_point.graph;
+    let counts = graph
+        .nodes()
+        .iter()
+        .map(|node| {
+            let count = local_triangle_count(graph, &node).unwrap_or_default();
+            (node.name(), count)
+        })
+        .collect();
+    let counts = ranked(ctx, counts, |count| *count as f64)?;
+    Ok(list(
+        counts
+            .into_iter()
+            .map(|(name, count)| NodeCount { name, count }),
+    ))
+}
+
+pub(crate) struct ClusteringCoefficient;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for ClusteringCoefficient {
+    type OutputType = f64;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn(TypeRef::FLOAT)
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        vec![]
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        _ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        let coefficient = clustering_coefficient(&entry_point.graph);
+        ready(Ok(Some(FieldValue::value(coefficient))))
+    }
+}
+
+pub(crate) struct GlobalReciprocity;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for GlobalReciprocity {
+    type OutputType = f64;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn(TypeRef::FLOAT)
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        vec![]
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        _ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        let reciprocity = global_reciprocity(&entry_point.graph, None);
+        ready(Ok(Some(FieldValue::value(reciprocity))))
+    }
+}
+
+pub(crate) struct TemporalMotifs;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for TemporalMotifs {
+    type OutputType = MotifCounts;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(MotifCounts::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        vec![("deltas", TypeRef::named_nn_list_nn(TypeRef::INT))]
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_temporal_motifs(entry_point, &ctx))
+    }
+}
+
+fn apply_temporal_motifs<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let deltas = deltas_arg(ctx)?;
+    let counts = temporal_three_node_motif_multi(&entry_point.graph, deltas.clone(), None);
+    Ok(list(deltas.into_iter().zip(counts).map(
+        |(delta, counts)| MotifCounts {
+            delta,
+            counts: counts.to_vec(),
+        },
+    )))
+}
+
+pub(crate) struct LocalTemporalMotifs;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for LocalTemporalMotifs {
+    type OutputType = NodeMotifCounts;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeMotifCounts::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        page_args(vec![("deltas", TypeRef::named_nn_list_nn(TypeRef::INT))])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_local_temporal_motifs(entry_point, &ctx))
+    }
+}
+
+fn apply_local_temporal_motifs<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let deltas = deltas_arg(ctx)?;
+    let counts = temporal_three_node_motif(&entry_point.graph, deltas.clone(), None);
+    let counts = by_name(ctx, counts.into_iter().collect())?;
+    Ok(list(counts.into_iter().map(|(name, counts)| {
+        let motifs = deltas
+            .iter()
+            .zip(counts)
+            .map(|(&delta, counts)| MotifCounts { delta, counts })
+            .collect();
+        NodeMotifCounts { name, motifs }
+    })))
+}
+
+pub(crate) struct TemporalReachability;
+
+impl<'a> Algorithm<'a, GraphAlgorithms> for TemporalReachability {
+    type OutputType = NodeReachability;
+    fn output_type() -> TypeRef {
+        TypeRef::named_nn_list_nn(NodeReachability::get_type_name())
+    }
+    fn args<'b>() -> Vec<(&'b str, TypeRef)> {
+        page_args(vec![
+            ("seeds", TypeRef::named_nn_list_nn(TypeRef::STRING)),
+            ("startTime", TypeRef::named_nn(TypeRef::INT)),
+            ("maxHops", TypeRef::named_nn(TypeRef::INT)),
+            ("stopNodes", TypeRef::named_list_nn(TypeRef::STRING)),
+        ])
+    }
+    fn apply_algo<'b>(
+        entry_point: &GraphAlgorithms,
+        ctx: ResolverContext,
+    ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
+        ready(apply_temporal_reachability(entry_point, &ctx))
+    }
+}
+
+fn apply_temporal_reachability<'b>(
+    entry_point: &GraphAlgorithms,
+    ctx: &ResolverContext,
+) -> FieldResult<Option<FieldValue<'b>>> {
+    let seeds = strings_arg(ctx, "seeds")?.unwrap_or_default();
+    let start_time = ctx.args.try_get("startTime")?.i64()?;
+    let max_hops = ctx.args.try_get("maxHops")?.u64()? as usize;
+    let stop_nodes = strings_arg(ctx, "stopNodes")?;
+    let result = temporally_reachable_nodes(
+        &entry_point.graph,
+        None,
+        max_hops,
+        start_time,
+        seeds,
+        stop_nodes,
+    );
+    let reached = result
+        .get_all_with_names()
+        .into_iter()
+        .filter(|(_, reached)| !reached.is_empty())
+        .collect();
+    let reached = by_name(ctx, reached)?;
+    Ok(list(reached.into_iter().map(|(name, reached)| {
+        let reached = reached
+            .into_iter()
+            .map(|(time, from)| ReachedAt { time, from })
+            .collect();
+        NodeReachability { name, reached }
+    })))
+}
@@ -7,6 +7,7 @@ pub mod document;
 pub mod global_plugins;
 mod global_search;
 pub mod graph_algorithms;
+pub mod library;
 pub mod similarity_search;
 pub mod vector_algorithms;
 
@@ -1,4 +1,11 @@
-use crate::model::graph::{node::Node, property::GqlProperties};
+use crate::{
+    limits::collect_windows,
+    model::{
+        graph::{interval::GqlInterval, node::Node, property::GqlProperties},
+        GqlGraphError,
+    },
+};
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use itertools::Itertools;
 use raphtory::{
@@ -71,6 +78,28 @@ impl Edge {
         self.ee.shrink_window(start, end).into()
     }
 
+    /// Return the edge in consecutive windows `window` wide, each starting `step` (by default
+    /// `window`) after the previous one, over the timeline of the graph. Intervals are
+    /// milliseconds or durations such as `1 day`.
+    async fn rolling(
+        &self,
+        ctx: &Context<'_>,
+        window: String,
+        step: Option<String>,
+    ) -> Result<Vec<Edge>, GqlGraphError> {
+        let windows = self
+            .ee
+            .rolling(GqlInterval(window), step.map(GqlInterval))?;
+        collect_windows(ctx, windows.map(|ee| ee.into()))
+    }
+
+    /// Return the edge in windows from the start of the graph, each ending `step` later than
+    /// the previous one, over the timeline of the graph
+    async fn expanding(&self, ctx: &Context<'_>, step: String) -> Result<Vec<Edge>, GqlGraphError> {
+        let windows = self.ee.expanding(GqlInterval(step))?;
+        collect_windows(ctx, windows.map(|ee| ee.into()))
+    }
+
     async fn shrink_start(&self, start: i64) -> Self {
         self.ee.shrink_start(start).into()
     }
@@ -1,29 +1,62 @@
-use crate::model::graph::edge::Edge;
+use crate::{
+    limits::collect_limited,
+    model::{
+        graph::{
+            edge::Edge,
+            selection::{sort_items, EdgeConnection, EdgeFilter, EdgeSortBy, PageArgs, SortBy},
+        },
+        GqlGraphError,
+    },
+};
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use raphtory::{
-    db::{api::view::DynamicGraph, graph::edges::Edges},
-    prelude::{LayerOps, TimeOps},
+    db::{
+        api::view::DynamicGraph,
+        graph::{edge::EdgeView, edges::Edges},
+    },
+    prelude::{EdgeViewOps, LayerOps, TimeOps},
 };
 
 #[derive(ResolvedObject)]
 pub(crate) struct GqlEdges {
     pub(crate) ee: Edges<'static, DynamicGraph>,
+    filters: Vec<EdgeFilter>,
+    sort: Vec<EdgeSortBy>,
 }
 
 impl GqlEdges {
     fn update<E: Into<Edges<'static, DynamicGraph>>>(&self, edges: E) -> Self {
-        Self::new(edges)
+        Self {
+            ee: edges.into(),
+            filters: self.filters.clone(),
+            sort: self.sort.clone(),
+        }
     }
 }
 
 impl GqlEdges {
     pub(crate) fn new<E: Into<Edges<'static, DynamicGraph>>>(edges: E) -> Self {
-        Self { ee: edges.into() }
+        Self {
+            ee: edges.into(),
+            filters: vec![],
+            sort: vec![],
+        }
+    }
+
+    fn filtered(&self) -> impl Iterator<Item = EdgeView<DynamicGraph>> + '_ {
+        self.ee
+            .iter()
+            .filter(|edge| self.filters.iter().all(|filter| filter.matches(edge)))
     }
 
     fn iter(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
-        let iter = self.ee.iter().map(Edge::from);
-        Box::new(iter)
+        if self.sort.is_empty() {
+            Box::new(self.filtered().map(Edge::from))
+        } else {
+            let sorted = sort_items(&self.sort, self.filtered(), |edge| edge.id());
+            Box::new(sorted.into_iter().map(|(_, edge)| Edge::from(edge)))
+        }
     }
 }
 
@@ -77,6 +110,31 @@ impl GqlEdges {
         self.update(self.ee.shrink_end(end))
     }
 
+    ////////////////////////
+    // FILTER AND SORT /////
+    ////////////////////////
+
+    /// Only keep the edges satisfying all conditions of `where`
+    async fn filter(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "where")] condition: EdgeFilter,
+    ) -> Result<Self, GqlGraphError> {
+        condition.validate(ctx)?;
+        let mut edges = self.update(self.ee.clone());
+        edges.filters.push(condition);
+        Ok(edges)
+    }
+
+    /// Sort the edges by the first order in `by`, breaking ties with the following ones and
+    /// finally by the ids of their endpoints
+    async fn sorted(&self, ctx: &Context<'_>, by: Vec<EdgeSortBy>) -> Result<Self, GqlGraphError> {
+        by.iter().try_for_each(|by| by.validate(ctx))?;
+        let mut edges = self.update(self.ee.clone());
+        edges.sort = by;
+        Ok(edges)
+    }
+
     ////////////////////////
     //// TIME QUERIES //////
     ////////////////////////
@@ -97,12 +155,35 @@ impl GqlEdges {
         self.iter().count()
     }
 
-    async fn page(&self, limit: usize, offset: usize) -> Vec<Edge> {
+    async fn page(
+        &self,
+        ctx: &Context<'_>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Edge>, GqlGraphError> {
         let start = offset * limit;
-        self.iter().skip(start).take(limit).collect()
-    }
-
-    async fn list(&self) -> Vec<Edge> {
-        self.iter().collect()
+        collect_limited(ctx, self.iter().skip(start).take(limit))
+    }
+
+    async fn list(&self, ctx: &Context<'_>) -> Result<Vec<Edge>, GqlGraphError> {
+        collect_limited(ctx, self.iter())
+    }
+
+    /// Relay-style page of the edges in sorted order, or by the ids of their endpoints if they
+    /// are not sorted
+    async fn connection(
+        &self,
+        first: Option<usize>,
+        after: Option<String>,
+        last: Option<usize>,
+        before: Option<String>,
+    ) -> Result<EdgeConnection, GqlGraphError> {
+        let args = PageArgs {
+            first,
+            after,
+            last,
+            before,
+        };
+        EdgeConnection::new(&self.sort, self.filtered(), args)
     }
 }
@@ -0,0 +1,52 @@
+use crate::{
+    data::Data,
+    model::graph::graph::GqlGraph,
+    namespace::{leaf, namespace},
+};
+use async_graphql::Context;
+use dynamic_graphql::{ResolvedObject, ResolvedObjectFields, Result};
+
+/// A graph served by the server, which need not be in memory
+#[derive(ResolvedObject)]
+pub(crate) struct GraphEntry {
+    path: String,
+    loaded: bool,
+}
+
+impl GraphEntry {
+    pub(crate) fn new(path: String, loaded: bool) -> Self {
+        Self { path, loaded }
+    }
+}
+
+#[ResolvedObjectFields]
+impl GraphEntry {
+    /// The name of the graph within its namespace
+    async fn name(&self) -> String {
+        leaf(&self.path).to_owned()
+    }
+
+    /// The full path of the graph, including its namespace
+    async fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    /// The namespace of the graph, empty at the root
+    async fn namespace(&self) -> String {
+        namespace(&self.path).to_owned()
+    }
+
+    /// Whether the graph was in memory when it was listed. Graphs of the graph directory are
+    /// loaded on first access and unloaded again when the memory budget of the server is
+    /// exceeded.
+    async fn loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// The graph, loading it if it is not in memory
+    async fn graph<'a>(&self, ctx: &Context<'a>) -> Result<Option<GqlGraph>> {
+        let data = ctx.data_unchecked::<Data>();
+        let graph = data.get_graph(&self.path)?;
+        Ok(graph.map(|graph| GqlGraph::new(self.path.clone(), graph)))
+    }
+}
@@ -1,8 +1,17 @@
-use crate::model::{
-    algorithms::graph_algorithms::GraphAlgorithms,
-    graph::{edge::Edge, edges::GqlEdges, node::Node, nodes::GqlNodes, property::GqlProperties},
-    schema::graph_schema::GraphSchema,
+use crate::{
+    limits::collect_windows,
+    model::{
+        algorithms::graph_algorithms::GraphAlgorithms,
+        graph::{
+            edge::Edge, edges::GqlEdges, interval::GqlInterval, memory_stats::GraphMemoryStats,
+            node::Node, nodes::GqlNodes, property::GqlProperties,
+        },
+        schema::graph_schema::GraphSchema,
+        GqlGraphError,
+    },
+    namespace::{leaf, namespace},
 };
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use itertools::Itertools;
 use raphtory::{
@@ -113,6 +122,32 @@ impl GqlGraph {
         GqlGraph::new(self.name.clone(), self.graph.shrink_window(start, end))
     }
 
+    /// Return the graph in consecutive windows `window` wide, each starting `step` (by default
+    /// `window`) after the previous one, over the timeline of the graph. Intervals are
+    /// milliseconds or durations such as `1 day`.
+    async fn rolling(
+        &self,
+        ctx: &Context<'_>,
+        window: String,
+        step: Option<String>,
+    ) -> Result<Vec<GqlGraph>, GqlGraphError> {
+        let windows = self
+            .graph
+            .rolling(GqlInterval(window), step.map(GqlInterval))?;
+        collect_windows(ctx, windows.map(|g| GqlGraph::new(self.name.clone(), g)))
+    }
+
+    /// Return the graph in windows from its start, each ending `step` later than the previous
+    /// one, over the timeline of the graph
+    async fn expanding(
+        &self,
+        ctx: &Context<'_>,
+        step: String,
+    ) -> Result<Vec<GqlGraph>, GqlGraphError> {
+        let windows = self.graph.expanding(GqlInterval(step))?;
+        collect_windows(ctx, windows.map(|g| GqlGraph::new(self.name.clone(), g)))
+    }
+
     async fn shrink_start(&self, start: i64) -> Self {
         GqlGraph::new(self.name.clone(), self.graph.shrink_start(start))
     }
@@ -308,14 +343,32 @@ impl GqlGraph {
     // GRAPHQL SPECIFIC ////
     ////////////////////////
 
+    /// The name of the graph within its namespace
     async fn name(&self) -> String {
+        leaf(&self.name).to_owned()
+    }
+
+    /// The full path of the graph, including its namespace
+    async fn path(&self) -> String {
         self.name.clone()
     }
 
+    /// The namespace of the graph, empty at the root
+    async fn namespace(&self) -> String {
+        namespace(&self.name).to_owned()
+    }
+
     async fn schema(&self) -> GraphSchema {
         GraphSchema::new(self.graph.graph())
     }
 
+    /// Estimated memory used by the graph, broken down by storage component, layer and
+    /// property key. Views report the memory of the whole underlying graph. Returns null for
+    /// graphs that are not stored in memory.
+    async fn memory_stats(&self) -> Option<GraphMemoryStats> {
+        self.graph.memory_stats().map(|stats| stats.into())
+    }
+
     async fn algorithms(&self) -> GraphAlgorithms {
         self.graph.graph().clone().into()
     }
@@ -0,0 +1,20 @@
+use raphtory::core::utils::time::{error::ParseTimeError, Interval};
+
+/// The `window` and `step` arguments of `rolling` and `expanding`, a number of milliseconds or a
+/// duration such as `1 day`, which must not be zero
+pub(crate) struct GqlInterval(pub(crate) String);
+
+impl TryFrom<GqlInterval> for Interval {
+    type Error = ParseTimeError;
+
+    fn try_from(value: GqlInterval) -> Result<Self, Self::Error> {
+        let interval: Interval = match value.0.trim().parse::<i64>() {
+            Ok(millis) => millis.try_into()?,
+            Err(_) => value.0.as_str().try_into()?,
+        };
+        if interval.to_millis() == Some(0) {
+            return Err(ParseTimeError::ZeroInterval);
+        }
+        Ok(interval)
+    }
+}
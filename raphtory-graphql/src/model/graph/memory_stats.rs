@@ -0,0 +1,89 @@
+use dynamic_graphql::SimpleObject;
+use raphtory::core::entities::graph::memory::{self, MemoryStats};
+
+#[derive(SimpleObject)]
+pub(crate) struct MemoryUsage {
+    bytes: usize,
+    count: usize,
+}
+
+impl From<memory::MemoryUsage> for MemoryUsage {
+    fn from(value: memory::MemoryUsage) -> Self {
+        Self {
+            bytes: value.bytes,
+            count: value.count,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct ComponentMemory {
+    name: String,
+    bytes: usize,
+    count: usize,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct LayerMemory {
+    name: String,
+    total_bytes: usize,
+    adjacency: MemoryUsage,
+    edge_updates: MemoryUsage,
+    edge_properties: MemoryUsage,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct PropertyMemory {
+    entity: String,
+    name: String,
+    constant: bool,
+    bytes: usize,
+    count: usize,
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct GraphMemoryStats {
+    total_bytes: usize,
+    components: Vec<ComponentMemory>,
+    layers: Vec<LayerMemory>,
+    properties: Vec<PropertyMemory>,
+}
+
+impl From<MemoryStats> for GraphMemoryStats {
+    fn from(stats: MemoryStats) -> Self {
+        Self {
+            total_bytes: stats.total_bytes(),
+            components: stats
+                .components()
+                .into_iter()
+                .map(|(name, usage)| ComponentMemory {
+                    name: name.to_owned(),
+                    bytes: usage.bytes,
+                    count: usage.count,
+                })
+                .collect(),
+            layers: stats
+                .layers
+                .into_iter()
+                .map(|layer| LayerMemory {
+                    total_bytes: layer.total_bytes(),
+                    name: layer.name.to_string(),
+                    adjacency: layer.adjacency.into(),
+                    edge_updates: layer.edge_updates.into(),
+                    edge_properties: layer.edge_properties.into(),
+                })
+                .collect(),
+            properties: stats
+                .properties
+                .into_iter()
+                .map(|property| PropertyMemory {
+                    entity: property.entity.to_string(),
+                    name: property.name.to_string(),
+                    constant: property.constant,
+                    bytes: property.usage.bytes,
+                    count: property.usage.count,
+                })
+                .collect(),
+        }
+    }
+}
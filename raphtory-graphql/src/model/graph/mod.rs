@@ -1,8 +1,12 @@
 pub(crate) mod edge;
 mod edges;
+pub(crate) mod entry;
 pub(crate) mod graph;
+mod interval;
+pub(crate) mod memory_stats;
 pub(crate) mod node;
 mod nodes;
 mod path_from_node;
 pub(crate) mod property;
+mod selection;
 pub(crate) mod vectorised_graph;
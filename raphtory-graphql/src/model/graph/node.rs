@@ -1,6 +1,14 @@
-use crate::model::graph::{
-    edges::GqlEdges, path_from_node::GqlPathFromNode, property::GqlProperties,
+use crate::{
+    limits::collect_windows,
+    model::{
+        graph::{
+            edges::GqlEdges, interval::GqlInterval, path_from_node::GqlPathFromNode,
+            property::GqlProperties,
+        },
+        GqlGraphError,
+    },
 };
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use raphtory::db::{
     api::{properties::dyn_props::DynProperties, view::*},
@@ -75,6 +83,28 @@ impl Node {
         self.vv.shrink_window(start, end).into()
     }
 
+    /// Return the node in consecutive windows `window` wide, each starting `step` (by default
+    /// `window`) after the previous one, over the timeline of the graph. Intervals are
+    /// milliseconds or durations such as `1 day`.
+    async fn rolling(
+        &self,
+        ctx: &Context<'_>,
+        window: String,
+        step: Option<String>,
+    ) -> Result<Vec<Node>, GqlGraphError> {
+        let windows = self
+            .vv
+            .rolling(GqlInterval(window), step.map(GqlInterval))?;
+        collect_windows(ctx, windows.map(|vv| vv.into()))
+    }
+
+    /// Return the node in windows from the start of the graph, each ending `step` later than
+    /// the previous one, over the timeline of the graph
+    async fn expanding(&self, ctx: &Context<'_>, step: String) -> Result<Vec<Node>, GqlGraphError> {
+        let windows = self.vv.expanding(GqlInterval(step))?;
+        collect_windows(ctx, windows.map(|vv| vv.into()))
+    }
+
     async fn shrink_start(&self, start: i64) -> Self {
         self.vv.shrink_start(start).into()
     }
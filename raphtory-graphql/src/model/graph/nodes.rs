@@ -1,29 +1,62 @@
-use crate::model::graph::node::Node;
+use crate::{
+    limits::collect_limited,
+    model::{
+        graph::{
+            node::Node,
+            selection::{sort_items, NodeConnection, NodeFilter, NodeSortBy, PageArgs, SortBy},
+        },
+        GqlGraphError,
+    },
+};
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use raphtory::{
-    db::{api::view::DynamicGraph, graph::nodes::Nodes},
+    db::{
+        api::view::DynamicGraph,
+        graph::{node::NodeView, nodes::Nodes},
+    },
     prelude::*,
 };
 
 #[derive(ResolvedObject)]
 pub(crate) struct GqlNodes {
     pub(crate) nn: Nodes<'static, DynamicGraph>,
+    filters: Vec<NodeFilter>,
+    sort: Vec<NodeSortBy>,
 }
 
 impl GqlNodes {
     fn update<N: Into<Nodes<'static, DynamicGraph>>>(&self, nodes: N) -> Self {
-        GqlNodes::new(nodes)
+        Self {
+            nn: nodes.into(),
+            filters: self.filters.clone(),
+            sort: self.sort.clone(),
+        }
     }
 }
 
 impl GqlNodes {
     pub(crate) fn new<N: Into<Nodes<'static, DynamicGraph>>>(nodes: N) -> Self {
-        Self { nn: nodes.into() }
+        Self {
+            nn: nodes.into(),
+            filters: vec![],
+            sort: vec![],
+        }
+    }
+
+    fn filtered(&self) -> impl Iterator<Item = NodeView<DynamicGraph>> + '_ {
+        self.nn
+            .iter()
+            .filter(|node| self.filters.iter().all(|filter| filter.matches(node)))
     }
 
     fn iter(&self) -> Box<dyn Iterator<Item = Node> + '_> {
-        let iter = self.nn.iter().map(Node::from);
-        Box::new(iter)
+        if self.sort.is_empty() {
+            Box::new(self.filtered().map(Node::from))
+        } else {
+            let sorted = sort_items(&self.sort, self.filtered(), |node| (node.id(), 0));
+            Box::new(sorted.into_iter().map(|(_, node)| Node::from(node)))
+        }
     }
 }
 
@@ -81,6 +114,31 @@ impl GqlNodes {
         self.update(self.nn.type_filter(&node_types))
     }
 
+    ////////////////////////
+    // FILTER AND SORT /////
+    ////////////////////////
+
+    /// Only keep the nodes satisfying all conditions of `where`
+    async fn filter(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "where")] condition: NodeFilter,
+    ) -> Result<Self, GqlGraphError> {
+        condition.validate(ctx)?;
+        let mut nodes = self.update(self.nn.clone());
+        nodes.filters.push(condition);
+        Ok(nodes)
+    }
+
+    /// Sort the nodes by the first order in `by`, breaking ties with the following ones and
+    /// finally by id
+    async fn sorted(&self, ctx: &Context<'_>, by: Vec<NodeSortBy>) -> Result<Self, GqlGraphError> {
+        by.iter().try_for_each(|by| by.validate(ctx))?;
+        let mut nodes = self.update(self.nn.clone());
+        nodes.sort = by;
+        Ok(nodes)
+    }
+
     ////////////////////////
     //// TIME QUERIES //////
     ////////////////////////
@@ -101,13 +159,35 @@ impl GqlNodes {
         self.iter().count()
     }
 
-    async fn page(&self, limit: usize, offset: usize) -> Vec<Node> {
+    async fn page(
+        &self,
+        ctx: &Context<'_>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Node>, GqlGraphError> {
         let start = offset * limit;
-        self.iter().skip(start).take(limit).collect()
-    }
-
-    async fn list(&self) -> Vec<Node> {
-        self.iter().collect()
+        collect_limited(ctx, self.iter().skip(start).take(limit))
+    }
+
+    async fn list(&self, ctx: &Context<'_>) -> Result<Vec<Node>, GqlGraphError> {
+        collect_limited(ctx, self.iter())
+    }
+
+    /// Relay-style page of the nodes in sorted order, or by id if they are not sorted
+    async fn connection(
+        &self,
+        first: Option<usize>,
+        after: Option<String>,
+        last: Option<usize>,
+        before: Option<String>,
+    ) -> Result<NodeConnection, GqlGraphError> {
+        let args = PageArgs {
+            first,
+            after,
+            last,
+            before,
+        };
+        NodeConnection::new(&self.sort, self.filtered(), args)
     }
 
     async fn ids(&self) -> Vec<String> {
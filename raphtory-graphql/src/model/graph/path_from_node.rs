@@ -1,4 +1,8 @@
-use crate::model::graph::node::Node;
+use crate::{
+    limits::collect_limited,
+    model::{graph::node::Node, GqlGraphError},
+};
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use raphtory::{
     db::{api::view::DynamicGraph, graph::path::PathFromNode},
@@ -102,13 +106,18 @@ impl GqlPathFromNode {
         self.iter().count()
     }
 
-    async fn page(&self, limit: usize, offset: usize) -> Vec<Node> {
+    async fn page(
+        &self,
+        ctx: &Context<'_>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Node>, GqlGraphError> {
         let start = offset * limit;
-        self.iter().skip(start).take(limit).collect()
+        collect_limited(ctx, self.iter().skip(start).take(limit))
     }
 
-    async fn list(&self) -> Vec<Node> {
-        self.iter().collect()
+    async fn list(&self, ctx: &Context<'_>) -> Result<Vec<Node>, GqlGraphError> {
+        collect_limited(ctx, self.iter())
     }
 
     async fn ids(&self) -> Vec<String> {
@@ -1,4 +1,5 @@
-use async_graphql::{Error, Name, Value as GqlValue};
+use crate::auth::is_hidden;
+use async_graphql::{Context, Error, Name, Value as GqlValue};
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields, Scalar, ScalarValue};
 use itertools::Itertools;
 use raphtory::{
@@ -75,6 +76,16 @@ fn prop_to_gql(prop: &Prop) -> GqlValue {
         Prop::Graph(g) => GqlValue::String(g.to_string()),
         Prop::PersistentGraph(g) => GqlValue::String(g.to_string()),
         Prop::Document(d) => GqlValue::String(d.content.to_owned()), // TODO: return GqlValue::Object ??
+        Prop::I8(u) => GqlValue::Number(Number::from(*u)),
+        Prop::I16(u) => GqlValue::Number(Number::from(*u)),
+        // decimals are returned as strings to not lose precision
+        Prop::Decimal(d) => GqlValue::String(d.to_string()),
+        Prop::Bytes(b) => GqlValue::List(
+            b.iter()
+                .map(|u| GqlValue::Number(Number::from(*u)))
+                .collect(),
+        ),
+        Prop::Array(a) => GqlValue::List(a.iter_prop().map(|pp| prop_to_gql(&pp)).collect()),
     }
 }
 
@@ -177,6 +188,34 @@ impl GqlTemporalProp {
             .map(|x| x.to_string())
             .collect_vec()
     }
+    /// The sum of the values, null if there are none or they cannot be added
+    async fn sum(&self) -> Option<GqlPropValue> {
+        let mut values = self.prop.values().into_iter();
+        let first = values.next()?;
+        values.try_fold(first, Prop::add).map(GqlPropValue)
+    }
+    /// The mean of the values, null if there are none or they are not numbers
+    async fn mean(&self) -> Option<f64> {
+        let values: Vec<f64> = self
+            .prop
+            .values()
+            .iter()
+            .map(Prop::as_f64)
+            .collect::<Option<_>>()?;
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    }
+    /// The smallest value, null if there are none or they cannot be compared
+    async fn min(&self) -> Option<GqlPropValue> {
+        let mut values = self.prop.values().into_iter();
+        let first = values.next()?;
+        values.try_fold(first, Prop::min).map(GqlPropValue)
+    }
+    /// The largest value, null if there are none or they cannot be compared
+    async fn max(&self) -> Option<GqlPropValue> {
+        let mut values = self.prop.values().into_iter();
+        let first = values.next()?;
+        values.try_fold(first, Prop::max).map(GqlPropValue)
+    }
     async fn ordered_dedupe(&self, latest_time: bool) -> Vec<GqlPropTuple> {
         self.prop
             .ordered_dedupe(latest_time)
@@ -237,36 +276,35 @@ impl From<DynConstProperties> for GqlConstantProperties {
 
 #[ResolvedObjectFields]
 impl GqlProperties {
-    async fn get(&self, key: &str) -> Option<GqlProp> {
+    async fn get(&self, ctx: &Context<'_>, key: &str) -> Option<GqlProp> {
+        if is_hidden(ctx, key) {
+            return None;
+        }
         self.props.get(key).map(|p| (key.to_string(), p).into())
     }
-    async fn contains(&self, key: &str) -> bool {
-        self.props.contains(key)
+    async fn contains(&self, ctx: &Context<'_>, key: &str) -> bool {
+        !is_hidden(ctx, key) && self.props.contains(key)
     }
-    async fn keys(&self) -> Vec<String> {
-        self.props.keys().map(|k| k.into()).collect()
+    async fn keys(&self, ctx: &Context<'_>) -> Vec<String> {
+        self.props
+            .keys()
+            .filter(|k| !is_hidden(ctx, k))
+            .map(|k| k.into())
+            .collect()
     }
 
-    async fn values(&self, keys: Option<Vec<String>>) -> Vec<GqlProp> {
-        match keys {
-            Some(keys) => self
-                .props
-                .iter()
-                .filter_map(|(k, p)| {
-                    let key = k.to_string();
-                    if keys.contains(&key) {
-                        Some((key, p).into())
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            None => self
-                .props
-                .iter()
-                .map(|(k, p)| (k.to_string(), p).into())
-                .collect(),
-        }
+    async fn values(&self, ctx: &Context<'_>, keys: Option<Vec<String>>) -> Vec<GqlProp> {
+        self.props
+            .iter()
+            .filter(|(k, _)| !is_hidden(ctx, k))
+            .filter_map(|(k, p)| {
+                let key = k.to_string();
+                match &keys {
+                    Some(keys) if !keys.contains(&key) => None,
+                    _ => Some((key, p).into()),
+                }
+            })
+            .collect()
     }
 
     async fn temporal(&self) -> GqlTemporalProperties {
@@ -280,69 +318,68 @@ impl GqlProperties {
 
 #[ResolvedObjectFields]
 impl GqlConstantProperties {
-    async fn get(&self, key: &str) -> Option<GqlProp> {
+    async fn get(&self, ctx: &Context<'_>, key: &str) -> Option<GqlProp> {
+        if is_hidden(ctx, key) {
+            return None;
+        }
         self.props.get(key).map(|p| (key.to_string(), p).into())
     }
-    async fn contains(&self, key: &str) -> bool {
-        self.props.contains(key)
+    async fn contains(&self, ctx: &Context<'_>, key: &str) -> bool {
+        !is_hidden(ctx, key) && self.props.contains(key)
     }
-    async fn keys(&self) -> Vec<String> {
-        self.props.keys().iter().map(|k| k.clone().into()).collect()
+    async fn keys(&self, ctx: &Context<'_>) -> Vec<String> {
+        self.props
+            .keys()
+            .iter()
+            .filter(|k| !is_hidden(ctx, k))
+            .map(|k| k.clone().into())
+            .collect()
     }
 
-    async fn values(&self, keys: Option<Vec<String>>) -> Vec<GqlProp> {
-        match keys {
-            Some(keys) => self
-                .props
-                .iter()
-                .filter_map(|(k, p)| {
-                    let key = k.to_string();
-                    if keys.contains(&key) {
-                        Some((key, p).into())
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            None => self
-                .props
-                .iter()
-                .map(|(k, p)| (k.to_string(), p).into())
-                .collect(),
-        }
+    async fn values(&self, ctx: &Context<'_>, keys: Option<Vec<String>>) -> Vec<GqlProp> {
+        self.props
+            .iter()
+            .filter(|(k, _)| !is_hidden(ctx, k))
+            .filter_map(|(k, p)| {
+                let key = k.to_string();
+                match &keys {
+                    Some(keys) if !keys.contains(&key) => None,
+                    _ => Some((key, p).into()),
+                }
+            })
+            .collect()
     }
 }
 
 #[ResolvedObjectFields]
 impl GqlTemporalProperties {
-    async fn get(&self, key: &str) -> Option<GqlTemporalProp> {
+    async fn get(&self, ctx: &Context<'_>, key: &str) -> Option<GqlTemporalProp> {
+        if is_hidden(ctx, key) {
+            return None;
+        }
         self.props.get(key).map(|p| (key.to_string(), p).into())
     }
-    async fn contains(&self, key: &str) -> bool {
-        self.props.contains(key)
-    }
-    async fn keys(&self) -> Vec<String> {
-        self.props.keys().map(|k| k.into()).collect()
-    }
-    async fn values(&self, keys: Option<Vec<String>>) -> Vec<GqlTemporalProp> {
-        match keys {
-            Some(keys) => self
-                .props
-                .iter()
-                .filter_map(|(k, p)| {
-                    let key = k.to_string();
-                    if keys.contains(&key) {
-                        Some((key, p).into())
-                    } else {
-                        None
-                    }
-                })
-                .collect(),
-            None => self
-                .props
-                .iter()
-                .map(|(k, p)| (k.to_string(), p).into())
-                .collect(),
-        }
+    async fn contains(&self, ctx: &Context<'_>, key: &str) -> bool {
+        !is_hidden(ctx, key) && self.props.contains(key)
+    }
+    async fn keys(&self, ctx: &Context<'_>) -> Vec<String> {
+        self.props
+            .keys()
+            .filter(|k| !is_hidden(ctx, k))
+            .map(|k| k.into())
+            .collect()
+    }
+    async fn values(&self, ctx: &Context<'_>, keys: Option<Vec<String>>) -> Vec<GqlTemporalProp> {
+        self.props
+            .iter()
+            .filter(|(k, _)| !is_hidden(ctx, k))
+            .filter_map(|(k, p)| {
+                let key = k.to_string();
+                match &keys {
+                    Some(keys) if !keys.contains(&key) => None,
+                    _ => Some((key, p).into()),
+                }
+            })
+            .collect()
     }
 }
@@ -0,0 +1,641 @@
+//! Sorting, filtering and cursor pagination of node and edge collections
+//!
+//! Filters and sort orders are stored on the collection and only evaluated when it is listed,
+//! so they always apply to the final view, whatever windows or layers are selected after them.
+//!
+//! Cursors encode the sort values and id of an item rather than its position. Paginating with
+//! `after` or `before` continues from the same point in the order even if items were added to
+//! the graph between requests.
+
+use crate::{
+    auth::is_hidden,
+    model::{
+        graph::{edge::Edge, node::Node, property::GqlPropValue},
+        GqlGraphError,
+    },
+};
+use async_graphql::Context;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use dynamic_graphql::{Enum, InputObject, ResolvedObject, ResolvedObjectFields, SimpleObject};
+use raphtory::{
+    core::Prop,
+    db::{
+        api::{
+            properties::{internal::PropertiesOps, Properties},
+            view::DynamicGraph,
+        },
+        graph::{edge::EdgeView, node::NodeView},
+    },
+    prelude::*,
+};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+
+/// Order of two values, comparing numbers of different types by value
+fn compare(a: &Prop, b: &Prop) -> Option<Ordering> {
+    a.partial_cmp(b)
+        .or_else(|| a.as_f64()?.partial_cmp(&b.as_f64()?))
+}
+
+/// Total order of two values used for sorting, placing values of different types by type
+fn total_cmp(a: &Prop, b: &Prop) -> Ordering {
+    fn rank(prop: &Prop) -> u8 {
+        match prop {
+            Prop::Str(_) => 1,
+            Prop::Bool(_) => 2,
+            _ if prop.as_f64().is_some() => 0,
+            _ => 3,
+        }
+    }
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => a.partial_cmp(b).unwrap_or_else(|| x.total_cmp(&y)),
+        _ => a.partial_cmp(b).unwrap_or_else(|| rank(a).cmp(&rank(b))),
+    }
+}
+
+/// Comparison of a property, degree or time with a value
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    IsSome,
+    IsNone,
+}
+
+impl Operator {
+    fn needs_value(self) -> bool {
+        !matches!(self, Operator::IsSome | Operator::IsNone)
+    }
+
+    /// Whether `value` satisfies the comparison with `target`. Missing values only satisfy
+    /// `IS_NONE`.
+    fn matches(self, value: Option<&Prop>, target: Option<&Prop>) -> bool {
+        let ordering = || compare(value?, target?);
+        match self {
+            Operator::Equal => ordering() == Some(Ordering::Equal),
+            Operator::NotEqual => value.is_some() && ordering() != Some(Ordering::Equal),
+            Operator::GreaterThan => ordering() == Some(Ordering::Greater),
+            Operator::GreaterThanOrEqual => {
+                matches!(ordering(), Some(Ordering::Greater | Ordering::Equal))
+            }
+            Operator::LessThan => ordering() == Some(Ordering::Less),
+            Operator::LessThanOrEqual => {
+                matches!(ordering(), Some(Ordering::Less | Ordering::Equal))
+            }
+            Operator::IsSome => value.is_some(),
+            Operator::IsNone => value.is_none(),
+        }
+    }
+}
+
+/// Condition on the value of a property, the latest temporal value or else the constant value
+#[derive(InputObject, Clone, Debug)]
+pub(crate) struct PropertyPredicate {
+    name: String,
+    operator: Operator,
+    value: Option<GqlPropValue>,
+}
+
+impl PropertyPredicate {
+    fn validate(&self, ctx: &Context<'_>) -> Result<(), GqlGraphError> {
+        if is_hidden(ctx, &self.name) {
+            return Err(GqlGraphError::InvalidFilter(format!(
+                "unknown property '{}'",
+                self.name
+            )));
+        }
+        if self.operator.needs_value() && self.value.is_none() {
+            return Err(GqlGraphError::InvalidFilter(format!(
+                "{:?} on property '{}' needs a value",
+                self.operator, self.name
+            )));
+        }
+        Ok(())
+    }
+
+    fn matches<P: PropertiesOps + Clone>(&self, properties: &Properties<P>) -> bool {
+        let value = properties.get(&self.name);
+        self.operator
+            .matches(value.as_ref(), self.value.as_ref().map(|value| &value.0))
+    }
+}
+
+/// Condition on a degree or time
+#[derive(InputObject, Clone, Debug)]
+pub(crate) struct NumberPredicate {
+    operator: Operator,
+    value: Option<i64>,
+}
+
+impl NumberPredicate {
+    fn validate(&self, field: &str) -> Result<(), GqlGraphError> {
+        if self.operator.needs_value() && self.value.is_none() {
+            return Err(GqlGraphError::InvalidFilter(format!(
+                "{:?} on {field} needs a value",
+                self.operator
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn validate_numbers(
+    predicates: &Option<Vec<NumberPredicate>>,
+    field: &str,
+) -> Result<(), GqlGraphError> {
+    predicates
+        .iter()
+        .flatten()
+        .try_for_each(|predicate| predicate.validate(field))
+}
+
+fn validate_properties(
+    ctx: &Context<'_>,
+    predicates: &Option<Vec<PropertyPredicate>>,
+) -> Result<(), GqlGraphError> {
+    predicates
+        .iter()
+        .flatten()
+        .try_for_each(|predicate| predicate.validate(ctx))
+}
+
+/// Whether `value` satisfies all `predicates`, only computing it if there are any
+fn numbers_match(
+    predicates: &Option<Vec<NumberPredicate>>,
+    value: impl FnOnce() -> Option<i64>,
+) -> bool {
+    match predicates {
+        None => true,
+        Some(predicates) => {
+            let value = value().map(Prop::I64);
+            predicates.iter().all(|predicate| {
+                let target = predicate.value.map(Prop::I64);
+                predicate.operator.matches(value.as_ref(), target.as_ref())
+            })
+        }
+    }
+}
+
+fn properties_match<P: PropertiesOps + Clone>(
+    predicates: &Option<Vec<PropertyPredicate>>,
+    properties: impl FnOnce() -> Properties<P>,
+) -> bool {
+    match predicates {
+        None => true,
+        Some(predicates) => {
+            let properties = properties();
+            predicates
+                .iter()
+                .all(|predicate| predicate.matches(&properties))
+        }
+    }
+}
+
+/// Conditions on nodes, all of which have to hold
+#[derive(InputObject, Clone, Debug)]
+pub(crate) struct NodeFilter {
+    properties: Option<Vec<PropertyPredicate>>,
+    degree: Option<Vec<NumberPredicate>>,
+    in_degree: Option<Vec<NumberPredicate>>,
+    out_degree: Option<Vec<NumberPredicate>>,
+    earliest_time: Option<Vec<NumberPredicate>>,
+    latest_time: Option<Vec<NumberPredicate>>,
+}
+
+impl NodeFilter {
+    pub(crate) fn validate(&self, ctx: &Context<'_>) -> Result<(), GqlGraphError> {
+        validate_properties(ctx, &self.properties)?;
+        validate_numbers(&self.degree, "degree")?;
+        validate_numbers(&self.in_degree, "inDegree")?;
+        validate_numbers(&self.out_degree, "outDegree")?;
+        validate_numbers(&self.earliest_time, "earliestTime")?;
+        validate_numbers(&self.latest_time, "latestTime")
+    }
+
+    pub(crate) fn matches(&self, node: &NodeView<DynamicGraph>) -> bool {
+        numbers_match(&self.degree, || Some(node.degree() as i64))
+            && numbers_match(&self.in_degree, || Some(node.in_degree() as i64))
+            && numbers_match(&self.out_degree, || Some(node.out_degree() as i64))
+            && numbers_match(&self.earliest_time, || node.earliest_time())
+            && numbers_match(&self.latest_time, || node.latest_time())
+            && properties_match(&self.properties, || node.properties())
+    }
+}
+
+/// Conditions on edges, all of which have to hold
+#[derive(InputObject, Clone, Debug)]
+pub(crate) struct EdgeFilter {
+    properties: Option<Vec<PropertyPredicate>>,
+    earliest_time: Option<Vec<NumberPredicate>>,
+    latest_time: Option<Vec<NumberPredicate>>,
+}
+
+impl EdgeFilter {
+    pub(crate) fn validate(&self, ctx: &Context<'_>) -> Result<(), GqlGraphError> {
+        validate_properties(ctx, &self.properties)?;
+        validate_numbers(&self.earliest_time, "earliestTime")?;
+        validate_numbers(&self.latest_time, "latestTime")
+    }
+
+    pub(crate) fn matches(&self, edge: &EdgeView<DynamicGraph>) -> bool {
+        numbers_match(&self.earliest_time, || edge.earliest_time())
+            && numbers_match(&self.latest_time, || edge.latest_time())
+            && properties_match(&self.properties, || edge.properties())
+    }
+}
+
+/// A value items can be sorted by
+pub(crate) trait SortBy<V> {
+    fn validate(&self, ctx: &Context<'_>) -> Result<(), GqlGraphError>;
+
+    fn value(&self, item: &V) -> Option<Prop>;
+
+    /// Whether to sort in descending order
+    fn reverse(&self) -> bool;
+}
+
+fn validate_property<F: std::fmt::Debug>(
+    ctx: &Context<'_>,
+    field: F,
+    property: Option<&str>,
+) -> Result<(), GqlGraphError> {
+    match property {
+        None => Err(GqlGraphError::InvalidSort(format!(
+            "sorting by {field:?} needs a property name"
+        ))),
+        Some(name) if is_hidden(ctx, name) => Err(GqlGraphError::InvalidSort(format!(
+            "unknown property '{name}'"
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NodeField {
+    Id,
+    Name,
+    NodeType,
+    EarliestTime,
+    LatestTime,
+    Degree,
+    InDegree,
+    OutDegree,
+    Property,
+}
+
+/// A sort order of nodes. Nodes without a value come last.
+#[derive(InputObject, Clone, Debug)]
+pub(crate) struct NodeSortBy {
+    field: NodeField,
+    /// The property to sort by when `field` is `PROPERTY`
+    property: Option<String>,
+    /// Sort in descending order
+    reverse: Option<bool>,
+}
+
+impl SortBy<NodeView<DynamicGraph>> for NodeSortBy {
+    fn validate(&self, ctx: &Context<'_>) -> Result<(), GqlGraphError> {
+        if self.field == NodeField::Property {
+            validate_property(ctx, self.field, self.property.as_deref())?;
+        }
+        Ok(())
+    }
+
+    fn value(&self, node: &NodeView<DynamicGraph>) -> Option<Prop> {
+        match self.field {
+            NodeField::Id => Some(Prop::U64(node.id())),
+            NodeField::Name => Some(Prop::from(node.name())),
+            NodeField::NodeType => node.node_type().map(Prop::Str),
+            NodeField::EarliestTime => node.earliest_time().map(Prop::I64),
+            NodeField::LatestTime => node.latest_time().map(Prop::I64),
+            NodeField::Degree => Some(Prop::U64(node.degree() as u64)),
+            NodeField::InDegree => Some(Prop::U64(node.in_degree() as u64)),
+            NodeField::OutDegree => Some(Prop::U64(node.out_degree() as u64)),
+            NodeField::Property => node.properties().get(self.property.as_deref()?),
+        }
+    }
+
+    fn reverse(&self) -> bool {
+        self.reverse.unwrap_or(false)
+    }
+}
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EdgeField {
+    Src,
+    Dst,
+    EarliestTime,
+    LatestTime,
+    Property,
+}
+
+/// A sort order of edges. Edges without a value come last.
+#[derive(InputObject, Clone, Debug)]
+pub(crate) struct EdgeSortBy {
+    field: EdgeField,
+    /// The property to sort by when `field` is `PROPERTY`
+    property: Option<String>,
+    /// Sort in descending order
+    reverse: Option<bool>,
+}
+
+impl SortBy<EdgeView<DynamicGraph>> for EdgeSortBy {
+    fn validate(&self, ctx: &Context<'_>) -> Result<(), GqlGraphError> {
+        if self.field == EdgeField::Property {
+            validate_property(ctx, self.field, self.property.as_deref())?;
+        }
+        Ok(())
+    }
+
+    fn value(&self, edge: &EdgeView<DynamicGraph>) -> Option<Prop> {
+        match self.field {
+            EdgeField::Src => Some(Prop::from(edge.src().name())),
+            EdgeField::Dst => Some(Prop::from(edge.dst().name())),
+            EdgeField::EarliestTime => edge.earliest_time().map(Prop::I64),
+            EdgeField::LatestTime => edge.latest_time().map(Prop::I64),
+            EdgeField::Property => edge.properties().get(self.property.as_deref()?),
+        }
+    }
+
+    fn reverse(&self) -> bool {
+        self.reverse.unwrap_or(false)
+    }
+}
+
+/// Position of an item in a sorted collection, encoded in its cursor
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SortKey {
+    values: Vec<Option<Prop>>,
+    id: (u64, u64),
+}
+
+impl SortKey {
+    fn cmp(&self, other: &Self, reverse: &[bool]) -> Ordering {
+        for ((a, b), &reverse) in self.values.iter().zip(&other.values).zip(reverse) {
+            let ordering = match (a, b) {
+                (Some(a), Some(b)) if reverse => total_cmp(b, a),
+                (Some(a), Some(b)) => total_cmp(a, b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        self.id.cmp(&other.id)
+    }
+
+    fn cursor(&self) -> String {
+        let values: Vec<_> = self
+            .values
+            .iter()
+            .map(|value| value.as_ref().map_or(Value::Null, Prop::to_json))
+            .collect();
+        let cursor = json!({"values": values, "id": [self.id.0, self.id.1]});
+        URL_SAFE_NO_PAD.encode(cursor.to_string())
+    }
+
+    fn from_cursor(cursor: &str, len: usize) -> Result<Self, GqlGraphError> {
+        let cursor = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+            .ok_or(GqlGraphError::InvalidCursor)?;
+        let values = cursor["values"]
+            .as_array()
+            .filter(|values| values.len() == len)
+            .ok_or(GqlGraphError::InvalidCursor)?
+            .iter()
+            .map(|value| match value {
+                Value::Null => Ok(None),
+                Value::Bool(value) => Ok(Some(Prop::Bool(*value))),
+                Value::String(value) => Ok(Some(Prop::from(value.as_str()))),
+                Value::Number(value) => value
+                    .as_i64()
+                    .map(Prop::I64)
+                    .or_else(|| value.as_u64().map(Prop::U64))
+                    .or_else(|| value.as_f64().map(Prop::F64))
+                    .map(Some)
+                    .ok_or(GqlGraphError::InvalidCursor),
+                _ => Err(GqlGraphError::InvalidCursor),
+            })
+            .collect::<Result<_, _>>()?;
+        let id = match cursor["id"].as_array().map(|id| id.as_slice()) {
+            Some([src, dst]) => src.as_u64().zip(dst.as_u64()),
+            _ => None,
+        }
+        .ok_or(GqlGraphError::InvalidCursor)?;
+        Ok(Self { values, id })
+    }
+}
+
+/// Sort `items` by `sort`, breaking ties by their ids
+pub(crate) fn sort_items<V, S: SortBy<V>>(
+    sort: &[S],
+    items: impl Iterator<Item = V>,
+    id: impl Fn(&V) -> (u64, u64),
+) -> Vec<(SortKey, V)> {
+    let reverse: Vec<_> = sort.iter().map(|by| by.reverse()).collect();
+    let mut items: Vec<_> = items
+        .map(|item| {
+            let key = SortKey {
+                values: sort.iter().map(|by| by.value(&item)).collect(),
+                id: id(&item),
+            };
+            (key, item)
+        })
+        .collect();
+    items.sort_by(|(a, _), (b, _)| a.cmp(b, &reverse));
+    items
+}
+
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+pub(crate) struct PageInfo {
+    has_next_page: bool,
+    has_previous_page: bool,
+    start_cursor: Option<String>,
+    end_cursor: Option<String>,
+}
+
+/// Arguments of a Relay-style connection
+pub(crate) struct PageArgs {
+    pub(crate) first: Option<usize>,
+    pub(crate) after: Option<String>,
+    pub(crate) last: Option<usize>,
+    pub(crate) before: Option<String>,
+}
+
+/// The page of sorted `items` selected by `args`, with their cursors
+fn paginate<V>(
+    mut items: Vec<(SortKey, V)>,
+    reverse: &[bool],
+    args: PageArgs,
+) -> Result<(Vec<(String, V)>, PageInfo, usize), GqlGraphError> {
+    let total = items.len();
+    let position = |cursor: &str, inclusive: bool| {
+        let cursor = SortKey::from_cursor(cursor, reverse.len())?;
+        Ok::<_, GqlGraphError>(
+            items.partition_point(|(key, _)| match key.cmp(&cursor, reverse) {
+                Ordering::Less => true,
+                Ordering::Equal => inclusive,
+                Ordering::Greater => false,
+            }),
+        )
+    };
+    let mut start = match &args.after {
+        Some(cursor) => position(cursor, true)?,
+        None => 0,
+    };
+    let mut end = match &args.before {
+        Some(cursor) => position(cursor, false)?.max(start),
+        None => total,
+    };
+    if let Some(first) = args.first {
+        end = end.min(start + first);
+    }
+    if let Some(last) = args.last {
+        start = start.max(end.saturating_sub(last));
+    }
+    let page: Vec<_> = items
+        .drain(start..end)
+        .map(|(key, item)| (key.cursor(), item))
+        .collect();
+    let info = PageInfo {
+        has_next_page: end < total,
+        has_previous_page: start > 0,
+        start_cursor: page.first().map(|(cursor, _)| cursor.clone()),
+        end_cursor: page.last().map(|(cursor, _)| cursor.clone()),
+    };
+    Ok((page, info, total))
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct NodeConnection {
+    edges: Vec<(String, NodeView<DynamicGraph>)>,
+    page_info: PageInfo,
+    total_count: usize,
+}
+
+impl NodeConnection {
+    pub(crate) fn new(
+        sort: &[NodeSortBy],
+        nodes: impl Iterator<Item = NodeView<DynamicGraph>>,
+        args: PageArgs,
+    ) -> Result<Self, GqlGraphError> {
+        let reverse: Vec<_> = sort.iter().map(|by| by.reverse()).collect();
+        let items = sort_items(sort, nodes, |node| (node.id(), 0));
+        let (edges, page_info, total_count) = paginate(items, &reverse, args)?;
+        Ok(Self {
+            edges,
+            page_info,
+            total_count,
+        })
+    }
+}
+
+#[ResolvedObjectFields]
+impl NodeConnection {
+    async fn edges(&self) -> Vec<NodeConnectionEdge> {
+        self.edges
+            .iter()
+            .map(|(cursor, node)| NodeConnectionEdge {
+                cursor: cursor.clone(),
+                node: node.clone(),
+            })
+            .collect()
+    }
+
+    async fn page_info(&self) -> PageInfo {
+        self.page_info.clone()
+    }
+
+    /// Number of nodes in the collection, across all pages
+    async fn total_count(&self) -> usize {
+        self.total_count
+    }
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct NodeConnectionEdge {
+    cursor: String,
+    node: NodeView<DynamicGraph>,
+}
+
+#[ResolvedObjectFields]
+impl NodeConnectionEdge {
+    async fn cursor(&self) -> String {
+        self.cursor.clone()
+    }
+
+    async fn node(&self) -> Node {
+        self.node.clone().into()
+    }
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct EdgeConnection {
+    edges: Vec<(String, EdgeView<DynamicGraph>)>,
+    page_info: PageInfo,
+    total_count: usize,
+}
+
+impl EdgeConnection {
+    pub(crate) fn new(
+        sort: &[EdgeSortBy],
+        edges: impl Iterator<Item = EdgeView<DynamicGraph>>,
+        args: PageArgs,
+    ) -> Result<Self, GqlGraphError> {
+        let reverse: Vec<_> = sort.iter().map(|by| by.reverse()).collect();
+        let items = sort_items(sort, edges, |edge| edge.id());
+        let (edges, page_info, total_count) = paginate(items, &reverse, args)?;
+        Ok(Self {
+            edges,
+            page_info,
+            total_count,
+        })
+    }
+}
+
+#[ResolvedObjectFields]
+impl EdgeConnection {
+    async fn edges(&self) -> Vec<EdgeConnectionEdge> {
+        self.edges
+            .iter()
+            .map(|(cursor, edge)| EdgeConnectionEdge {
+                cursor: cursor.clone(),
+                edge: edge.clone(),
+            })
+            .collect()
+    }
+
+    async fn page_info(&self) -> PageInfo {
+        self.page_info.clone()
+    }
+
+    /// Number of edges in the collection, across all pages
+    async fn total_count(&self) -> usize {
+        self.total_count
+    }
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct EdgeConnectionEdge {
+    cursor: String,
+    edge: EdgeView<DynamicGraph>,
+}
+
+#[ResolvedObjectFields]
+impl EdgeConnectionEdge {
+    async fn cursor(&self) -> String {
+        self.cursor.clone()
+    }
+
+    async fn node(&self) -> Edge {
+        self.edge.clone().into()
+    }
+}
@@ -0,0 +1,719 @@
+//! Algorithm runs in the background
+//!
+//! A job runs an algorithm on a view of a graph on one of a fixed number of worker threads, so it
+//! keeps running after the request submitting it returned. Jobs submitted while all workers are
+//! busy are queued. Submissions are rejected once the queue is full or the principal submitting
+//! them already has too many jobs queued or running. Its status, progress and results are polled
+//! with the `job` query. Progress counts the supersteps completed by algorithms built on the task runner and
+//! stays at zero for the others. Cancelling a job stops it at its next superstep and discards
+//! its results.
+
+use crate::{
+    auth::{can_read, require_read, require_write, Access},
+    data::Data,
+    model::{graph::property::GqlPropValue, GqlGraphError, MissingGraph},
+    observability::metrics::METRICS,
+};
+use async_graphql::Context;
+use chrono::Utc;
+use dynamic_graphql::{Enum, InputObject, ResolvedObject, ResolvedObjectFields, SimpleObject};
+use once_cell::sync::OnceCell;
+use parking_lot::{Mutex, RwLock};
+use raphtory::{
+    algorithms::{
+        centrality::{
+            betweenness::betweenness_centrality, degree_centrality::degree_centrality, hits::hits,
+            pagerank::unweighted_page_rank,
+        },
+        community_detection::{
+            label_propagation::label_propagation, louvain::louvain, modularity::ModularityUnDir,
+        },
+        components::{strongly_connected_components, weakly_connected_components},
+        metrics::local_clustering_coefficient::local_clustering_coefficient,
+        motifs::{
+            local_temporal_three_node_motifs::temporal_three_node_motif,
+            local_triangle_count::local_triangle_count,
+        },
+    },
+    core::{utils::errors::GraphError, IntoPropList, IntoPropMap, Prop},
+    db::{
+        api::view::{DynamicGraph, IntoDynamic, MaterializedGraph},
+        task::cancellation::{with_cancellation, CancellationToken},
+    },
+    prelude::*,
+    search::IndexedGraph,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        mpsc::{sync_channel, SyncSender},
+        Arc,
+    },
+    thread,
+};
+use uuid::Uuid;
+
+/// Finished jobs kept before the oldest are forgotten
+const MAX_FINISHED_JOBS: usize = 1000;
+
+/// Number of worker threads running jobs
+const WORKERS: usize = 4;
+
+/// Jobs waiting for a worker before submissions are rejected
+const MAX_QUEUED_JOBS: usize = 64;
+
+/// Jobs a principal may have queued or running at the same time
+const MAX_JOBS_PER_PRINCIPAL: usize = 8;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum JobAlgorithm {
+    Pagerank,
+    Hits,
+    BetweennessCentrality,
+    DegreeCentrality,
+    Louvain,
+    LabelPropagation,
+    WeaklyConnectedComponents,
+    StronglyConnectedComponents,
+    LocalTriangleCount,
+    LocalClusteringCoefficient,
+    LocalTemporalMotifs,
+}
+
+impl JobAlgorithm {
+    fn name(self) -> &'static str {
+        match self {
+            JobAlgorithm::Pagerank => "pagerank",
+            JobAlgorithm::Hits => "hits",
+            JobAlgorithm::BetweennessCentrality => "betweenness_centrality",
+            JobAlgorithm::DegreeCentrality => "degree_centrality",
+            JobAlgorithm::Louvain => "louvain",
+            JobAlgorithm::LabelPropagation => "label_propagation",
+            JobAlgorithm::WeaklyConnectedComponents => "weakly_connected_components",
+            JobAlgorithm::StronglyConnectedComponents => "strongly_connected_components",
+            JobAlgorithm::LocalTriangleCount => "local_triangle_count",
+            JobAlgorithm::LocalClusteringCoefficient => "local_clustering_coefficient",
+            JobAlgorithm::LocalTemporalMotifs => "local_temporal_motifs",
+        }
+    }
+}
+
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// An algorithm to run on a view of a graph. Parameters the algorithm does not take are ignored.
+#[derive(InputObject, Clone, Debug)]
+pub(crate) struct JobInput {
+    graph: String,
+    algorithm: JobAlgorithm,
+    /// Only use these layers
+    layers: Option<Vec<String>>,
+    /// Only use updates at or after this time
+    start: Option<i64>,
+    /// Only use updates before this time
+    end: Option<i64>,
+    /// Maximum number of iterations of pagerank, hits and weakly connected components
+    iter_count: Option<usize>,
+    /// Convergence tolerance of pagerank and louvain
+    tol: Option<f64>,
+    /// Resolution of louvain
+    resolution: Option<f64>,
+    /// Edge property used as weight by louvain
+    weight_property: Option<String>,
+    /// Number of nodes sampled by betweenness centrality
+    k: Option<usize>,
+    /// Normalise betweenness centrality
+    normalized: Option<bool>,
+    /// Seed of label propagation
+    seed: Option<u64>,
+    /// Time windows of local temporal motifs
+    deltas: Option<Vec<i64>>,
+    /// Store the result of every node in this constant property once the job completes. The
+    /// graph is changed in memory only and needs to be saved to keep the results.
+    persist_as: Option<String>,
+}
+
+impl JobInput {
+    fn view(&self, graph: &MaterializedGraph) -> Result<DynamicGraph, GraphError> {
+        let mut view = graph.clone().into_dynamic();
+        if let Some(layers) = &self.layers {
+            view = view.layers(layers.clone())?.into_dynamic();
+        }
+        if self.start.is_some() || self.end.is_some() {
+            let start = self.start.unwrap_or(i64::MIN);
+            let end = self.end.unwrap_or(i64::MAX);
+            view = view.window(start, end).into_dynamic();
+        }
+        Ok(view)
+    }
+
+    /// Run the algorithm on `graph`, returning the result of every node
+    fn run(&self, graph: &DynamicGraph) -> Result<Vec<(String, Prop)>, String> {
+        let to_prop = |value: usize| Prop::U64(value as u64);
+        let results: Vec<(String, Prop)> = match self.algorithm {
+            JobAlgorithm::Pagerank => {
+                unweighted_page_rank(graph, self.iter_count, None, self.tol, true, None)
+                    .get_all_with_names()
+                    .into_iter()
+                    .map(|(name, rank)| (name, Prop::F64(rank)))
+                    .collect()
+            }
+            JobAlgorithm::Hits => hits(graph, self.iter_count.unwrap_or(20), None)
+                .get_all_with_names()
+                .into_iter()
+                .map(|(name, (hub, authority))| {
+                    let scores = [("hub", Prop::F32(hub)), ("authority", Prop::F32(authority))];
+                    (name, scores.into_prop_map())
+                })
+                .collect(),
+            JobAlgorithm::BetweennessCentrality => {
+                betweenness_centrality(graph, self.k, self.normalized)
+                    .get_all_with_names()
+                    .into_iter()
+                    .map(|(name, score)| (name, Prop::F64(score)))
+                    .collect()
+            }
+            JobAlgorithm::DegreeCentrality => degree_centrality(graph, None)
+                .get_all_with_names()
+                .into_iter()
+                .map(|(name, score)| (name, Prop::F64(score)))
+                .collect(),
+            JobAlgorithm::Louvain => louvain::<ModularityUnDir, _>(
+                graph,
+                self.resolution.unwrap_or(1.0),
+                self.weight_property.as_deref(),
+                self.tol,
+            )
+            .get_all_with_names()
+            .into_iter()
+            .map(|(name, community)| (name, to_prop(community)))
+            .collect(),
+            JobAlgorithm::LabelPropagation => {
+                let seed = self.seed.map(|seed| {
+                    let mut bytes = [0u8; 32];
+                    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+                    bytes
+                });
+                label_propagation(graph, seed)?
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(community, nodes)| {
+                        nodes
+                            .into_iter()
+                            .map(move |node| (node.name(), to_prop(community)))
+                    })
+                    .collect()
+            }
+            JobAlgorithm::WeaklyConnectedComponents => {
+                weakly_connected_components(graph, self.iter_count.unwrap_or(usize::MAX), None)
+                    .get_all_with_names()
+                    .into_iter()
+                    .map(|(name, component)| (name, Prop::U64(component)))
+                    .collect()
+            }
+            JobAlgorithm::StronglyConnectedComponents => strongly_connected_components(graph, None)
+                .get_all_with_names()
+                .into_iter()
+                .map(|(name, component)| (name, to_prop(component)))
+                .collect(),
+            JobAlgorithm::LocalTriangleCount => graph
+                .nodes()
+                .iter()
+                .map(|node| {
+                    let count = local_triangle_count(graph, &node).unwrap_or_default();
+                    (node.name(), to_prop(count))
+                })
+                .collect(),
+            JobAlgorithm::LocalClusteringCoefficient => graph
+                .nodes()
+                .iter()
+                .map(|node| {
+                    let coefficient = local_clustering_coefficient(graph, &node);
+                    (node.name(), Prop::F32(coefficient.unwrap_or_default()))
+                })
+                .collect(),
+            JobAlgorithm::LocalTemporalMotifs => {
+                let deltas = self
+                    .deltas
+                    .clone()
+                    .ok_or("local temporal motifs need deltas")?;
+                temporal_three_node_motif(graph, deltas, None)
+                    .into_iter()
+                    .map(|(name, counts)| {
+                        let counts = counts
+                            .into_iter()
+                            .map(|counts| counts.into_iter().map(to_prop).into_prop_list());
+                        (name, counts.into_prop_list())
+                    })
+                    .collect()
+            }
+        };
+        Ok(results)
+    }
+}
+
+#[derive(Clone)]
+struct JobState {
+    status: JobStatus,
+    error: Option<String>,
+    finished_at: Option<i64>,
+    results: Arc<Vec<(String, Prop)>>,
+}
+
+/// A submitted algorithm run
+pub(crate) struct Job {
+    id: String,
+    input: JobInput,
+    principal: Option<String>,
+    submitted_at: i64,
+    token: CancellationToken,
+    state: RwLock<JobState>,
+}
+
+impl Job {
+    fn is_finished(&self) -> bool {
+        self.state.read().finished_at.is_some()
+    }
+
+    fn finish(&self, result: Result<Vec<(String, Prop)>, String>) {
+        let mut state = self.state.write();
+        state.finished_at = Some(Utc::now().timestamp_millis());
+        match result {
+            _ if self.token.is_cancelled() => state.status = JobStatus::Cancelled,
+            Ok(mut results) => {
+                results.sort_by(|(a, _), (b, _)| a.cmp(b));
+                state.status = JobStatus::Completed;
+                state.results = Arc::new(results);
+            }
+            Err(error) => {
+                state.status = JobStatus::Failed;
+                state.error = Some(error);
+            }
+        }
+    }
+}
+
+/// The jobs of a server
+#[derive(Default)]
+pub(crate) struct Jobs {
+    jobs: RwLock<HashMap<String, Arc<Job>>>,
+    /// Queue of the workers, started with the first job
+    queue: OnceCell<SyncSender<Task>>,
+}
+
+impl Jobs {
+    fn queue(&self) -> &SyncSender<Task> {
+        self.queue.get_or_init(|| {
+            let (sender, receiver) = sync_channel::<Task>(MAX_QUEUED_JOBS);
+            let receiver = Arc::new(Mutex::new(receiver));
+            for _ in 0..WORKERS {
+                let receiver = receiver.clone();
+                thread::spawn(move || loop {
+                    // the lock is released before running the task
+                    let task = receiver.lock().recv();
+                    match task {
+                        Ok(task) => task(),
+                        Err(_) => break,
+                    }
+                });
+            }
+            sender
+        })
+    }
+
+    /// Queue running `input` on `view` of `graph` and return the job
+    fn submit(
+        &self,
+        graph: IndexedGraph<MaterializedGraph>,
+        view: DynamicGraph,
+        input: JobInput,
+        principal: Option<String>,
+    ) -> Result<Arc<Job>, GqlGraphError> {
+        let job = Arc::new(Job {
+            id: Uuid::new_v4().hyphenated().to_string(),
+            input,
+            principal,
+            submitted_at: Utc::now().timestamp_millis(),
+            token: CancellationToken::new(),
+            state: RwLock::new(JobState {
+                status: JobStatus::Queued,
+                error: None,
+                finished_at: None,
+                results: Default::default(),
+            }),
+        });
+        self.forget_finished();
+        {
+            let mut jobs = self.jobs.write();
+            if let Some(principal) = &job.principal {
+                let active = jobs
+                    .values()
+                    .filter(|other| other.principal.as_ref() == Some(principal))
+                    .filter(|other| !other.is_finished())
+                    .count();
+                if active >= MAX_JOBS_PER_PRINCIPAL {
+                    return Err(GqlGraphError::TooManyJobsOfPrincipal(
+                        MAX_JOBS_PER_PRINCIPAL,
+                    ));
+                }
+            }
+            jobs.insert(job.id.clone(), job.clone());
+        }
+
+        let running = job.clone();
+        let task: Task = Box::new(move || {
+            if running.token.is_cancelled() {
+                running.finish(Ok(vec![]));
+                return;
+            }
+            running.state.write().status = JobStatus::Running;
+            let result = with_cancellation(&running.token, || {
+                let _timer = METRICS.algorithm_timer(running.input.algorithm.name());
+                running.input.run(&view)
+            });
+            let result = result.and_then(|results| {
+                match &running.input.persist_as {
+                    Some(name) if !running.token.is_cancelled() => {
+                        persist(&graph, name, &results).map_err(|err| err.to_string())?
+                    }
+                    _ => {}
+                }
+                Ok(results)
+            });
+            running.finish(result);
+        });
+        match self.queue().try_send(task) {
+            Ok(()) => Ok(job),
+            Err(_) => {
+                self.jobs.write().remove(&job.id);
+                Err(GqlGraphError::TooManyJobs(MAX_QUEUED_JOBS))
+            }
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<Arc<Job>> {
+        self.jobs.read().get(id).cloned()
+    }
+
+    /// Drop the oldest finished jobs once there are too many
+    fn forget_finished(&self) {
+        let mut jobs = self.jobs.write();
+        let mut finished: Vec<_> = jobs
+            .values()
+            .filter_map(|job| Some((job.state.read().finished_at?, job.id.clone())))
+            .collect();
+        if finished.len() >= MAX_FINISHED_JOBS {
+            finished.sort();
+            for (_, id) in &finished[..=finished.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+    }
+}
+
+/// Store `results` as the constant property `name` of the nodes of `graph`
+fn persist(
+    graph: &IndexedGraph<MaterializedGraph>,
+    name: &str,
+    results: &[(String, Prop)],
+) -> Result<(), GraphError> {
+    for (node, value) in results {
+        if let Some(node) = graph.node(node) {
+            node.update_constant_properties([(name, value.clone())])?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct NodeResult {
+    name: String,
+    value: GqlPropValue,
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct GqlJob {
+    job: Arc<Job>,
+}
+
+impl From<Arc<Job>> for GqlJob {
+    fn from(job: Arc<Job>) -> Self {
+        Self { job }
+    }
+}
+
+#[ResolvedObjectFields]
+impl GqlJob {
+    async fn id(&self) -> String {
+        self.job.id.clone()
+    }
+
+    async fn graph(&self) -> String {
+        self.job.input.graph.clone()
+    }
+
+    async fn algorithm(&self) -> JobAlgorithm {
+        self.job.input.algorithm
+    }
+
+    async fn status(&self) -> JobStatus {
+        self.job.state.read().status
+    }
+
+    /// Supersteps completed so far
+    async fn supersteps(&self) -> usize {
+        self.job.token.supersteps()
+    }
+
+    async fn error(&self) -> Option<String> {
+        self.job.state.read().error.clone()
+    }
+
+    /// Milliseconds since the epoch at which the job was submitted
+    async fn submitted_at(&self) -> i64 {
+        self.job.submitted_at
+    }
+
+    /// Milliseconds since the epoch at which the job finished
+    async fn finished_at(&self) -> Option<i64> {
+        self.job.state.read().finished_at
+    }
+
+    async fn count(&self) -> usize {
+        self.job.state.read().results.len()
+    }
+
+    /// The result of every node sorted by name, empty until the job completed
+    async fn results(&self, limit: Option<usize>, offset: Option<usize>) -> Vec<NodeResult> {
+        let results = self.job.state.read().results.clone();
+        let limit = limit.unwrap_or(results.len());
+        let start = offset.unwrap_or(0) * limit;
+        results
+            .iter()
+            .skip(start)
+            .take(limit)
+            .map(|(name, value)| NodeResult {
+                name: name.clone(),
+                value: GqlPropValue(value.clone()),
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn submit_job(ctx: &Context<'_>, input: JobInput) -> async_graphql::Result<GqlJob> {
+    require_read(ctx, &input.graph)?;
+    if input.persist_as.is_some() {
+        require_write(ctx, &input.graph)?;
+    }
+    let data = ctx.data_unchecked::<Data>();
+    let graph = data.get_graph(&input.graph)?.ok_or(MissingGraph)?;
+    #[cfg(feature = "storage")]
+    if input.persist_as.is_some() && graph.clone().graph.into_disk_graph().is_some() {
+        return Err(GqlGraphError::ImmutableDiskGraph.into());
+    }
+    let view = input.view(&graph.graph)?;
+    let principal = ctx
+        .data_opt::<Access>()
+        .map(|access| access.principal().to_owned());
+    Ok(data.jobs.submit(graph, view, input, principal)?.into())
+}
+
+pub(crate) fn job(ctx: &Context<'_>, id: &str) -> Option<GqlJob> {
+    let job = ctx.data_unchecked::<Data>().jobs.get(id)?;
+    can_read(ctx, &job.input.graph).then(|| job.into())
+}
+
+pub(crate) fn jobs(ctx: &Context<'_>) -> Vec<GqlJob> {
+    let mut jobs: Vec<_> = ctx
+        .data_unchecked::<Data>()
+        .jobs
+        .jobs
+        .read()
+        .values()
+        .filter(|job| can_read(ctx, &job.input.graph))
+        .cloned()
+        .collect();
+    jobs.sort_by_key(|job| job.submitted_at);
+    jobs.into_iter().map(GqlJob::from).collect()
+}
+
+pub(crate) fn cancel_job(ctx: &Context<'_>, id: &str) -> async_graphql::Result<bool> {
+    let job = ctx
+        .data_unchecked::<Data>()
+        .jobs
+        .get(id)
+        .filter(|job| can_read(ctx, &job.input.graph))
+        .ok_or(GqlGraphError::UnknownJob(id.to_owned()))?;
+    let running = !job.is_finished();
+    job.token.cancel();
+    Ok(running)
+}
+
+#[cfg(test)]
+mod jobs_test {
+    use super::MAX_JOBS_PER_PRINCIPAL;
+    use crate::{
+        auth::AuthConfig,
+        data::Data,
+        model::{App, GqlGraphError},
+    };
+    use async_graphql::Request;
+    use raphtory::{db::api::view::MaterializedGraph, prelude::*};
+    use serde_json::{json, Value};
+    use std::{collections::HashMap, time::Duration};
+    use tempfile::tempdir;
+
+    async fn run(schema: &async_graphql::dynamic::Schema, query: &str) -> Value {
+        let res = schema.execute(Request::new(query)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        res.data.into_json().unwrap()
+    }
+
+    async fn wait(schema: &async_graphql::dynamic::Schema, id: &str) -> Value {
+        let query = format!(
+            r#"{{ job(id: "{id}") {{ status supersteps error results {{ name value }} }} }}"#
+        );
+        loop {
+            let data = run(schema, &query).await;
+            if data["job"]["status"] != json!("RUNNING") && data["job"]["status"] != json!("QUEUED")
+            {
+                return data["job"].clone();
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn jobs_run_in_the_background() {
+        let graph = Graph::new();
+        for (src, dst) in [(1, 2), (2, 3), (3, 1), (5, 6)] {
+            graph.add_edge(0, src, dst, NO_PROPS, None).unwrap();
+        }
+        let graph: MaterializedGraph = graph.into();
+        let data = Data::from_map(HashMap::from([("graph".to_string(), graph)]));
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        let submit = r#"mutation { submitJob(job: {graph: "graph",
+            algorithm: WEAKLY_CONNECTED_COMPONENTS, persistAs: "component"}) { id } }"#;
+        let id = run(&schema, submit).await["submitJob"]["id"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+        let job = wait(&schema, &id).await;
+        assert_eq!(job["status"], json!("COMPLETED"));
+        assert!(job["supersteps"].as_u64().unwrap() > 0);
+        let results = job["results"].as_array().unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0]["value"], results[2]["value"]);
+        assert_ne!(results[0]["value"], results[3]["value"]);
+
+        let graph = data.graphs.read().get("graph").cloned().unwrap();
+        let component = graph.node(6).unwrap().properties().get("component");
+        assert_eq!(
+            component,
+            graph.node(5).unwrap().properties().get("component")
+        );
+        assert!(component.is_some());
+
+        let submit = r#"mutation { submitJob(job: {graph: "graph",
+            algorithm: PAGERANK, iterCount: 100000000, tol: -1.0}) { id } }"#;
+        let id = run(&schema, submit).await["submitJob"]["id"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+        let cancel = format!(r#"mutation {{ cancelJob(id: "{id}") }}"#);
+        assert_eq!(run(&schema, &cancel).await["cancelJob"], json!(true));
+        let job = wait(&schema, &id).await;
+        assert_eq!(job["status"], json!("CANCELLED"));
+        assert_eq!(job["results"], json!([]));
+
+        let jobs = run(&schema, "{ jobs { algorithm status } }").await;
+        let jobs = jobs["jobs"].as_array().unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs.contains(&json!({"algorithm": "PAGERANK", "status": "CANCELLED"})));
+    }
+
+    #[tokio::test]
+    async fn jobs_of_a_principal_are_limited() {
+        let config: AuthConfig = toml::from_str(
+            r#"
+            [roles.reader.graphs."*"]
+            read = true
+            "#,
+        )
+        .unwrap();
+        let access = |principal| config.access(principal, &["reader".to_owned()]).unwrap();
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let data = Data::from_map(HashMap::from([("graph".to_string(), graph)]));
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let submit = r#"mutation { submitJob(job: {graph: "graph",
+            algorithm: PAGERANK, iterCount: 100000000, tol: -1.0}) { id } }"#;
+        let mut ids = vec![];
+        for _ in 0..MAX_JOBS_PER_PRINCIPAL {
+            let res = schema
+                .execute(Request::new(submit).data(access("alice")))
+                .await;
+            assert!(res.errors.is_empty(), "{:?}", res.errors);
+            ids.push(res.data.into_json().unwrap()["submitJob"]["id"].clone());
+        }
+        let res = schema
+            .execute(Request::new(submit).data(access("alice")))
+            .await;
+        assert_eq!(
+            res.errors[0].message,
+            GqlGraphError::TooManyJobsOfPrincipal(MAX_JOBS_PER_PRINCIPAL).to_string()
+        );
+        let res = schema
+            .execute(Request::new(submit).data(access("bob")))
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        ids.push(res.data.into_json().unwrap()["submitJob"]["id"].clone());
+
+        for id in ids {
+            let id = id.as_str().unwrap();
+            run(&schema, &format!(r#"mutation {{ cancelJob(id: "{id}") }}"#)).await;
+            assert_eq!(wait(&schema, id).await["status"], json!("CANCELLED"));
+        }
+    }
+
+    #[tokio::test]
+    async fn persisted_results_are_saved_when_unloading() {
+        let test_dir = tempdir().unwrap();
+        for name in ["g1", "g2"] {
+            let graph = Graph::new();
+            graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+            graph.save_to_file(test_dir.path().join(name)).unwrap();
+        }
+        let mut data = Data::from_directory(test_dir.path().to_str().unwrap());
+        data.memory_budget = Some(1);
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        let submit = r#"mutation { submitJob(job: {graph: "g1",
+            algorithm: WEAKLY_CONNECTED_COMPONENTS, persistAs: "component"}) { id } }"#;
+        let id = run(&schema, submit).await["submitJob"]["id"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+        assert_eq!(wait(&schema, &id).await["status"], json!("COMPLETED"));
+
+        // loading g2 exceeds the budget, so g1 is saved with the persisted results and unloaded
+        data.get_graph("g2").unwrap().unwrap();
+        assert!(!data.graphs.read().contains_key("g1"));
+
+        let g1 = data.get_graph("g1").unwrap().unwrap();
+        let component = g1.node(1).unwrap().properties().get("component");
+        assert!(component.is_some());
+        assert_eq!(component, g1.node(2).unwrap().properties().get("component"));
+    }
+}
@@ -1,9 +1,15 @@
 use crate::{
+    auth::{
+        can_read, require_admin, require_read, require_read_all, require_unfiltered, require_write,
+    },
     data::Data,
     model::{
         algorithms::global_plugins::GlobalPlugins,
-        graph::{graph::GqlGraph, vectorised_graph::GqlVectorisedGraph},
+        graph::{entry::GraphEntry, graph::GqlGraph, vectorised_graph::GqlVectorisedGraph},
+        jobs::{GqlJob, JobInput},
     },
+    namespace::{self, in_namespace, leaf, validate_path, NamespaceError},
+    observability::metrics::METRICS,
 };
 use async_graphql::Context;
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
@@ -14,16 +20,21 @@ use dynamic_graphql::{
 };
 use itertools::Itertools;
 use raphtory::{
-    core::{utils::errors::GraphError, ArcStr, Prop},
+    core::{
+        utils::{errors::GraphError, time::error::ParseTimeError},
+        ArcStr, Prop,
+    },
     db::api::view::MaterializedGraph,
-    prelude::{GraphViewOps, ImportOps, NodeViewOps, PropertyAdditionOps},
+    graph_loader::source::mapping::{load_with_mapping, MappingSpec},
+    prelude::{Graph, GraphViewOps, ImportOps, NodeViewOps, PropertyAdditionOps},
     search::IndexedGraph,
 };
 use serde_json::Value;
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     error::Error,
     fmt::{Display, Formatter},
+    fs,
     io::Read,
     path::Path,
 };
@@ -31,6 +42,7 @@ use uuid::Uuid;
 
 pub mod algorithms;
 pub(crate) mod graph;
+pub(crate) mod jobs;
 pub(crate) mod schema;
 
 #[derive(Debug)]
@@ -48,6 +60,26 @@ impl Error for MissingGraph {}
 pub enum GqlGraphError {
     #[error("Disk Graph is immutable")]
     ImmutableDiskGraph,
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+    #[error("Invalid sort: {0}")]
+    InvalidSort(String),
+    #[error("Invalid cursor")]
+    InvalidCursor,
+    #[error("Result has more than {0} items, use page or connection instead")]
+    ResultTooLarge(usize),
+    #[error("Query exceeded its time limit")]
+    Timeout,
+    #[error("Job '{0}' does not exist")]
+    UnknownJob(String),
+    #[error("More than {0} jobs are queued, try again later")]
+    TooManyJobs(usize),
+    #[error("At most {0} jobs of a principal may be queued or running")]
+    TooManyJobsOfPrincipal(usize),
+    #[error("Invalid interval: {0}")]
+    InvalidInterval(#[from] ParseTimeError),
+    #[error("More than {0} windows, use a larger step")]
+    TooManyWindows(usize),
 }
 
 #[derive(ResolvedObject)]
@@ -60,45 +92,84 @@ impl QueryRoot {
         "Hello world from raphtory-graphql"
     }
 
-    /// Returns a graph
-    async fn graph<'a>(ctx: &Context<'a>, name: &str) -> Option<GqlGraph> {
+    /// Returns the graph at `name`, a path such as `teamA/fraud/2024-06` for graphs in namespaces
+    async fn graph<'a>(ctx: &Context<'a>, name: &str) -> Result<Option<GqlGraph>> {
+        require_read(ctx, name)?;
         let data = ctx.data_unchecked::<Data>();
-        let g = data.graphs.read().get(name).cloned()?;
-        Some(GqlGraph::new(name.to_string(), g))
+        let g = data.get_graph(name)?;
+        Ok(g.map(|g| GqlGraph::new(name.to_string(), g)))
     }
 
-    async fn vectorised_graph<'a>(ctx: &Context<'a>, name: &str) -> Option<GqlVectorisedGraph> {
+    async fn vectorised_graph<'a>(
+        ctx: &Context<'a>,
+        name: &str,
+    ) -> Result<Option<GqlVectorisedGraph>> {
+        require_read(ctx, name)?;
+        // documents are rendered from properties, hidden ones cannot be left out
+        require_unfiltered(ctx, name)?;
         let data = ctx.data_unchecked::<Data>();
-        let g = data.vector_stores.read().get(name).cloned()?;
-        Some(g.into())
+        let g = data.vector_stores.read().get(name).cloned();
+        Ok(g.map(|g| g.into()))
     }
 
-    async fn graphs<'a>(ctx: &Context<'a>) -> Vec<GqlGraph> {
+    /// Returns the graphs the caller is allowed to read, only those in `namespace` and its
+    /// nested namespaces if given, whether they are in memory or not
+    async fn graphs<'a>(ctx: &Context<'a>, namespace: Option<String>) -> Vec<GraphEntry> {
+        let namespace = namespace.unwrap_or_default();
         let data = ctx.data_unchecked::<Data>();
-        data.graphs
-            .read()
-            .iter()
-            .map(|(name, g)| GqlGraph::new(name.clone(), g.clone()))
+        let loaded = data.graphs.read();
+        let unloaded = data.unloaded.read();
+        let paths = loaded
+            .keys()
+            .map(|path| (path, true))
+            .chain(unloaded.keys().map(|path| (path, false)));
+        paths
+            .filter(|(path, _)| in_namespace(path, &namespace) && can_read(ctx, path))
+            .sorted()
+            .map(|(path, loaded)| GraphEntry::new(path.clone(), loaded))
             .collect_vec()
     }
 
-    async fn plugins<'a>(ctx: &Context<'a>) -> GlobalPlugins {
+    /// Returns the namespaces containing graphs the caller is allowed to read, only those
+    /// nested in `parent` if given
+    async fn namespaces<'a>(ctx: &Context<'a>, parent: Option<String>) -> Vec<String> {
+        let parent = parent.unwrap_or_default();
+        let data = ctx.data_unchecked::<Data>();
+        let names = data.graph_names();
+        let namespaces: BTreeSet<_> = names
+            .iter()
+            .filter(|name| can_read(ctx, name))
+            .flat_map(|name| namespace::ancestors(name))
+            .filter(|namespace| in_namespace(namespace, &parent))
+            .map(|namespace| namespace.to_owned())
+            .collect();
+        namespaces.into_iter().collect()
+    }
+
+    /// Returns a background algorithm run
+    async fn job<'a>(ctx: &Context<'a>, id: &str) -> Option<GqlJob> {
+        jobs::job(ctx, id)
+    }
+
+    /// Returns the background algorithm runs on graphs the caller is allowed to read
+    async fn jobs<'a>(ctx: &Context<'a>) -> Vec<GqlJob> {
+        jobs::jobs(ctx)
+    }
+
+    async fn plugins<'a>(ctx: &Context<'a>) -> Result<GlobalPlugins> {
+        require_read_all(ctx)?;
         let data = ctx.data_unchecked::<Data>();
-        GlobalPlugins {
+        Ok(GlobalPlugins {
             graphs: data.graphs.clone(),
             vectorised_graphs: data.vector_stores.clone(),
-        }
+        })
     }
 
     async fn receive_graph<'a>(ctx: &Context<'a>, name: &str) -> Result<String> {
+        require_read(ctx, name)?;
         let data = ctx.data_unchecked::<Data>();
-        let g = data
-            .graphs
-            .read()
-            .get(name)
-            .cloned()
-            .ok_or(MissingGraph)?
-            .materialize()?;
+        require_unfiltered(ctx, name)?;
+        let g = data.get_graph(name)?.ok_or(MissingGraph)?.materialize()?;
         let bincode = bincode::serialize(&g)?;
         Ok(URL_SAFE_NO_PAD.encode(bincode))
     }
@@ -116,12 +187,17 @@ impl Mut {
     ///
     /// Returns::
     ///   list of names for newly added graphs
-    async fn load_graphs_from_path<'a>(ctx: &Context<'a>, path: String) -> Vec<String> {
+    async fn load_graphs_from_path<'a>(ctx: &Context<'a>, path: String) -> Result<Vec<String>> {
+        require_admin(ctx)?;
+        let _timer = METRICS.load_timer("load_graphs_from_path");
         let new_graphs = Data::load_from_file(&path);
         let keys: Vec<_> = new_graphs.keys().cloned().collect();
-        let mut data = ctx.data_unchecked::<Data>().graphs.write();
-        data.extend(new_graphs);
-        keys
+        let server = ctx.data_unchecked::<Data>();
+        let mut data = server.graphs.write();
+        for (name, graph) in new_graphs {
+            server.insert_graph(&mut data, name, graph);
+        }
+        Ok(keys)
     }
 
     async fn rename_graph<'a>(
@@ -130,13 +206,22 @@ impl Mut {
         graph_name: String,
         new_graph_name: String,
     ) -> Result<bool> {
+        require_read(ctx, &parent_graph_name)?;
+        require_write(ctx, &graph_name)?;
+        require_write(ctx, &new_graph_name)?;
+        require_unfiltered(ctx, &parent_graph_name)?;
+        validate_path(&new_graph_name)?;
+        if namespace::namespace(&new_graph_name) != namespace::namespace(&graph_name) {
+            return Err(NamespaceError::NamespaceChanged(graph_name).into());
+        }
         let data = ctx.data_unchecked::<Data>();
-        if data.graphs.read().contains_key(&new_graph_name) {
+        if data.contains_graph(&new_graph_name) {
             return Err((GraphError::GraphNameAlreadyExists {
                 name: new_graph_name,
             })
             .into());
         }
+        data.load_graphs(&[&parent_graph_name, &graph_name])?;
 
         let mut data = ctx.data_unchecked::<Data>().graphs.write();
 
@@ -169,7 +254,7 @@ impl Mut {
             new_subgraph.update_constant_properties(static_props_without_name)?;
 
             new_subgraph
-                .update_constant_properties([("name", Prop::Str(new_graph_name.clone().into()))])?;
+                .update_constant_properties([("name", Prop::str(leaf(&new_graph_name)))])?;
 
             let dt = Utc::now();
             let timestamp: i64 = dt.timestamp();
@@ -189,7 +274,10 @@ impl Mut {
     }
 
     async fn update_graph_last_opened<'a>(ctx: &Context<'a>, graph_name: String) -> Result<bool> {
-        let data = ctx.data_unchecked::<Data>().graphs.write();
+        require_write(ctx, &graph_name)?;
+        let server = ctx.data_unchecked::<Data>();
+        server.load_graphs(&[&graph_name])?;
+        let data = server.graphs.write();
 
         let subgraph = data.get(&graph_name).ok_or("Graph not found")?;
 
@@ -224,7 +312,15 @@ impl Mut {
         is_archive: u8,
         graph_nodes: String,
     ) -> Result<bool> {
-        let mut data = ctx.data_unchecked::<Data>().graphs.write();
+        require_read(ctx, &parent_graph_name)?;
+        require_read(ctx, &graph_name)?;
+        require_write(ctx, &new_graph_name)?;
+        require_unfiltered(ctx, &parent_graph_name)?;
+        require_unfiltered(ctx, &graph_name)?;
+        validate_path(&new_graph_name)?;
+        let server = ctx.data_unchecked::<Data>();
+        server.load_graphs(&[&parent_graph_name, &graph_name, &new_graph_name])?;
+        let mut data = server.graphs.write();
 
         let parent_graph = data.get(&parent_graph_name).ok_or("Graph not found")?;
         let subgraph = data.get(&graph_name).ok_or("Graph not found")?;
@@ -242,20 +338,32 @@ impl Mut {
                 .ok_or("Path is missing")?
                 .to_string(),
             None => {
-                let base_path = subgraph
-                    .properties()
-                    .constant()
-                    .get("path")
-                    .ok_or("Path is missing")?
-                    .to_string();
-                let path: &Path = Path::new(base_path.as_str());
-                path.with_file_name(Uuid::new_v4().hyphenated().to_string())
-                    .to_str()
-                    .ok_or("Invalid path")?
-                    .to_string()
+                let file_name = Uuid::new_v4().hyphenated().to_string();
+                let path = match &server.work_dir {
+                    Some(work_dir) => {
+                        // graphs of the graph directory are served under their file name
+                        let dir = work_dir.join(namespace::namespace(&new_graph_name));
+                        fs::create_dir_all(&dir)?;
+                        let file = dir.join(leaf(&new_graph_name));
+                        if file.exists() {
+                            dir.join(file_name)
+                        } else {
+                            file
+                        }
+                    }
+                    None => {
+                        let base_path = subgraph
+                            .properties()
+                            .constant()
+                            .get("path")
+                            .ok_or("Path is missing")?
+                            .to_string();
+                        Path::new(base_path.as_str()).with_file_name(file_name)
+                    }
+                };
+                path.to_str().ok_or("Invalid path")?.to_string()
             }
         };
-        println!("Saving graph to path {path}");
 
         let deserialized_node_map: Value = serde_json::from_str(graph_nodes.as_str())?;
         let node_map = deserialized_node_map
@@ -264,7 +372,8 @@ impl Mut {
         let node_ids = node_map.keys().map(|key| key.as_str()).collect_vec();
 
         let _new_subgraph = parent_graph.subgraph(node_ids.clone()).materialize()?;
-        _new_subgraph.update_constant_properties([("name", Prop::str(new_graph_name.clone()))])?;
+        server.check_quota(&data, &new_graph_name, _new_subgraph.count_nodes())?;
+        _new_subgraph.update_constant_properties([("name", Prop::str(leaf(&new_graph_name)))])?;
 
         let new_subgraph = &_new_subgraph.clone().into_persistent().unwrap();
         let new_subgraph_data = subgraph.subgraph(node_ids).materialize()?;
@@ -322,12 +431,12 @@ impl Mut {
         new_subgraph.update_constant_properties([("path", Prop::Str(path.clone().into()))])?;
         new_subgraph.update_constant_properties([("isArchive", Prop::U8(is_archive))])?;
 
-        new_subgraph.save_to_file(path)?;
+        new_subgraph.save_to_file(&path)?;
 
         let m_g = new_subgraph.materialize()?;
         let gi: IndexedGraph<MaterializedGraph> = m_g.into();
 
-        data.insert(new_graph_name, gi);
+        server.insert_saved_graph(&mut data, new_graph_name, gi, path.into());
 
         Ok(true)
     }
@@ -336,15 +445,18 @@ impl Mut {
     ///
     /// Returns::
     ///   list of names for newly added graphs
-    async fn load_new_graphs_from_path<'a>(ctx: &Context<'a>, path: String) -> Vec<String> {
-        let mut data = ctx.data_unchecked::<Data>().graphs.write();
+    async fn load_new_graphs_from_path<'a>(ctx: &Context<'a>, path: String) -> Result<Vec<String>> {
+        require_admin(ctx)?;
+        let _timer = METRICS.load_timer("load_new_graphs_from_path");
+        let server = ctx.data_unchecked::<Data>();
+        let mut data = server.graphs.write();
         let new_graphs: HashMap<_, _> = Data::load_from_file(&path)
             .into_iter()
-            .filter(|(key, _)| !data.contains_key(key))
+            .filter(|(key, _)| !data.contains_key(key) && !server.unloaded.read().contains_key(key))
             .collect();
         let keys: Vec<_> = new_graphs.keys().cloned().collect();
         data.extend(new_graphs);
-        keys
+        Ok(keys)
     }
 
     /// Use GQL multipart upload to send new graphs to server
@@ -352,13 +464,18 @@ impl Mut {
     /// Returns::
     ///    name of the new graph
     async fn upload_graph<'a>(ctx: &Context<'a>, name: String, graph: Upload) -> Result<String> {
+        require_write(ctx, &name)?;
+        validate_path(&name)?;
+        let _timer = METRICS.load_timer("upload_graph");
         let mut buffer = Vec::new();
         let mut buff_read = graph.value(ctx)?.content;
         buff_read.read_to_end(&mut buffer)?;
         let g: MaterializedGraph = MaterializedGraph::from_bincode(&buffer)?;
+        let server = ctx.data_unchecked::<Data>();
+        let mut data = server.graphs.write();
+        server.check_quota(&data, &name, g.count_nodes())?;
         let gi: IndexedGraph<MaterializedGraph> = g.into();
-        let mut data = ctx.data_unchecked::<Data>().graphs.write();
-        data.insert(name.clone(), gi);
+        server.insert_graph(&mut data, name.clone(), gi);
         Ok(name)
     }
 
@@ -367,19 +484,131 @@ impl Mut {
     /// Returns::
     ///    name of the new graph
     async fn send_graph<'a>(ctx: &Context<'a>, name: String, graph: String) -> Result<String> {
+        require_write(ctx, &name)?;
+        validate_path(&name)?;
+        let _timer = METRICS.load_timer("send_graph");
         let g: MaterializedGraph = bincode::deserialize(&URL_SAFE_NO_PAD.decode(graph)?)?;
-        let mut data = ctx.data_unchecked::<Data>().graphs.write();
-        data.insert(name.clone(), g.into());
+        let server = ctx.data_unchecked::<Data>();
+        let mut data = server.graphs.write();
+        server.check_quota(&data, &name, g.count_nodes())?;
+        server.insert_graph(&mut data, name.clone(), g.into());
         Ok(name)
     }
 
+    /// Load a data file, or a directory of data files on the server, into a graph as described
+    /// by a mapping spec written as JSON, YAML or TOML (the graph is created if it does not exist)
+    ///
+    /// Returns::
+    ///    number of records loaded
+    async fn load_with_mapping<'a>(
+        ctx: &Context<'a>,
+        graph_name: String,
+        path: String,
+        spec: String,
+    ) -> Result<usize> {
+        require_admin(ctx)?;
+        require_write(ctx, &graph_name)?;
+        validate_path(&graph_name)?;
+        let _timer = METRICS.load_timer("load_with_mapping");
+        let spec = MappingSpec::from_path_or_str(&spec)?;
+        let server = ctx.data_unchecked::<Data>();
+        server.load_graphs(&[&graph_name])?;
+        let mut data = server.graphs.write();
+        if !data.contains_key(&graph_name) {
+            server.check_quota(&data, &graph_name, 0)?;
+        }
+        let graph = data
+            .entry(graph_name)
+            .or_insert_with(|| MaterializedGraph::from(Graph::new()).into());
+
+        #[cfg(feature = "storage")]
+        if graph.clone().graph.into_disk_graph().is_some() {
+            return Err(GqlGraphError::ImmutableDiskGraph.into());
+        }
+
+        Ok(load_with_mapping(path, &spec, &*graph)?.loaded)
+    }
+
+    /// Move a graph to another path, possibly in another namespace. Graphs loaded from files
+    /// are saved to the directory of the new namespace and their old file is removed.
+    async fn move_graph<'a>(ctx: &Context<'a>, path: String, new_path: String) -> Result<bool> {
+        require_write(ctx, &path)?;
+        require_write(ctx, &new_path)?;
+        validate_path(&new_path)?;
+        let server = ctx.data_unchecked::<Data>();
+        server.load_graphs(&[&path])?;
+        let mut data = server.graphs.write();
+        if data.contains_key(&new_path) || server.unloaded.read().contains_key(&new_path) {
+            return Err(GraphError::GraphNameAlreadyExists { name: new_path }.into());
+        }
+        let graph = data.get(&path).ok_or(MissingGraph)?.clone();
+
+        #[cfg(feature = "storage")]
+        if graph.clone().graph.into_disk_graph().is_some() {
+            return Err(GqlGraphError::ImmutableDiskGraph.into());
+        }
+
+        server.check_quota(&data, &new_path, graph.count_nodes())?;
+
+        let file = graph.properties().constant().get("path");
+        match file.map(|file| file.to_string()) {
+            Some(file) => {
+                let old_file = Path::new(&file);
+                let dir = match &server.work_dir {
+                    Some(work_dir) => work_dir.join(namespace::namespace(&new_path)),
+                    None => old_file.parent().unwrap_or(Path::new("")).to_path_buf(),
+                };
+                // graphs of the graph directory are served under their file name
+                let new_file = dir.join(leaf(&new_path));
+                if new_file != old_file && new_file.exists() {
+                    return Err(GraphError::GraphNameAlreadyExists { name: new_path }.into());
+                }
+                fs::create_dir_all(&dir)?;
+                graph.update_constant_properties([
+                    ("name", Prop::str(leaf(&new_path))),
+                    ("path", Prop::str(new_file.display().to_string())),
+                ])?;
+                graph.save_to_file(&new_file)?;
+                if new_file != old_file {
+                    fs::remove_file(old_file)?;
+                }
+                data.remove(&path);
+                server.insert_saved_graph(&mut data, new_path, graph, new_file);
+            }
+            None => {
+                data.remove(&path);
+                server.insert_graph(&mut data, new_path, graph);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Run an algorithm in the background
+    ///
+    /// Returns::
+    ///   the job, whose status and results are queried with `job`
+    async fn submit_job<'a>(ctx: &Context<'a>, job: JobInput) -> Result<GqlJob> {
+        jobs::submit_job(ctx, job)
+    }
+
+    /// Cancel a background algorithm run
+    ///
+    /// Returns::
+    ///   whether the job was still running
+    async fn cancel_job<'a>(ctx: &Context<'a>, id: String) -> Result<bool> {
+        jobs::cancel_job(ctx, &id)
+    }
+
     async fn archive_graph<'a>(
         ctx: &Context<'a>,
         graph_name: String,
         _parent_graph_name: String,
         is_archive: u8,
     ) -> Result<bool> {
-        let data = ctx.data_unchecked::<Data>().graphs.write();
+        require_write(ctx, &graph_name)?;
+        let server = ctx.data_unchecked::<Data>();
+        server.load_graphs(&[&graph_name])?;
+        let data = server.graphs.write();
         let subgraph = data.get(&graph_name).ok_or("Graph not found")?;
 
         #[cfg(feature = "storage")]
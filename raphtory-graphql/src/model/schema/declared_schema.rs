@@ -0,0 +1,25 @@
+use dynamic_graphql::SimpleObject;
+use raphtory::core::entities::properties::schema::PropertyDefinition;
+
+#[derive(SimpleObject)]
+pub(crate) struct DeclaredPropertySchema {
+    entity: String,
+    name: String,
+    dtype: String,
+    scope: Option<String>,
+    constant: bool,
+    required: bool,
+}
+
+impl DeclaredPropertySchema {
+    pub fn new(entity: &str, definition: PropertyDefinition) -> Self {
+        Self {
+            entity: entity.to_owned(),
+            name: definition.name.to_string(),
+            dtype: format!("{:?}", definition.dtype),
+            scope: definition.scope.map(|scope| scope.to_string()),
+            constant: definition.constant,
+            required: definition.required,
+        }
+    }
+}
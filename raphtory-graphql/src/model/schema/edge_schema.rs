@@ -1,6 +1,10 @@
-use crate::model::schema::{
-    get_node_type, merge_schemas, property_schema::PropertySchema, SchemaAggregate,
+use crate::{
+    auth::is_hidden,
+    model::schema::{
+        get_node_type, merge_schemas, property_schema::PropertySchema, SchemaAggregate,
+    },
 };
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use itertools::Itertools;
 use raphtory::{
@@ -39,7 +43,7 @@ impl<G: StaticGraphViewOps> EdgeSchema<G> {
     }
 
     /// Returns the list of property schemas for edges connecting these types of nodes
-    async fn properties(&self) -> Vec<PropertySchema> {
+    async fn properties(&self, ctx: &Context<'_>) -> Vec<PropertySchema> {
         let filter_types = |edge: &EdgeView<G>| {
             let src_type = get_node_type(edge.src());
             let dst_type = get_node_type(edge.dst());
@@ -53,7 +57,11 @@ impl<G: StaticGraphViewOps> EdgeSchema<G> {
             .reduce(merge_schemas)
             .unwrap_or_else(|| HashMap::new());
 
-        schema.into_iter().map(|prop| prop.into()).collect_vec()
+        schema
+            .into_iter()
+            .filter(|(key, _)| !is_hidden(ctx, key))
+            .map(|prop| prop.into())
+            .collect_vec()
     }
 }
 
@@ -1,4 +1,6 @@
-use crate::model::schema::{layer_schema::LayerSchema, node_schema::NodeSchema};
+use crate::model::schema::{
+    declared_schema::DeclaredPropertySchema, layer_schema::LayerSchema, node_schema::NodeSchema,
+};
 use dynamic_graphql::SimpleObject;
 use itertools::Itertools;
 use raphtory::{db::api::view::DynamicGraph, prelude::*};
@@ -7,6 +9,8 @@ use raphtory::{db::api::view::DynamicGraph, prelude::*};
 pub(crate) struct GraphSchema {
     nodes: Vec<NodeSchema>,
     layers: Vec<LayerSchema<DynamicGraph>>,
+    declared_properties: Vec<DeclaredPropertySchema>,
+    schema_enforcement: String,
 }
 
 impl GraphSchema {
@@ -24,6 +28,31 @@ impl GraphSchema {
             .map(|layer_name| graph.layers(layer_name).unwrap().into())
             .collect_vec();
 
-        GraphSchema { nodes, layers }
+        let declared = graph.declared_schema();
+        let schema_enforcement = format!("{:?}", declared.enforcement);
+        let declared_properties = declared
+            .nodes
+            .into_iter()
+            .map(|d| DeclaredPropertySchema::new("node", d))
+            .chain(
+                declared
+                    .edges
+                    .into_iter()
+                    .map(|d| DeclaredPropertySchema::new("edge", d)),
+            )
+            .chain(
+                declared
+                    .graph
+                    .into_iter()
+                    .map(|d| DeclaredPropertySchema::new("graph", d)),
+            )
+            .collect_vec();
+
+        GraphSchema {
+            nodes,
+            layers,
+            declared_properties,
+            schema_enforcement,
+        }
     }
 }
@@ -1,4 +1,8 @@
-use crate::model::schema::{merge_schemas, property_schema::PropertySchema, SchemaAggregate};
+use crate::{
+    auth::is_hidden,
+    model::schema::{merge_schemas, property_schema::PropertySchema, SchemaAggregate},
+};
+use async_graphql::Context;
 use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
 use itertools::Itertools;
 use raphtory::{
@@ -29,7 +33,7 @@ impl NodeSchema {
     }
 
     /// Returns the list of property schemas for this node
-    async fn properties(&self) -> Vec<PropertySchema> {
+    async fn properties(&self, ctx: &Context<'_>) -> Vec<PropertySchema> {
         let filter_type = |node: &NodeView<DynamicGraph>| match node.properties().get("type") {
             Some(node_type) => node_type.to_string() == self.type_name,
             None => false,
@@ -42,7 +46,11 @@ impl NodeSchema {
             .reduce(merge_schemas)
             .unwrap_or_else(|| HashMap::new());
 
-        schema.into_iter().map(|prop| prop.into()).collect_vec()
+        schema
+            .into_iter()
+            .filter(|(key, _)| !is_hidden(ctx, key))
+            .map(|prop| prop.into())
+            .collect_vec()
     }
 }
 
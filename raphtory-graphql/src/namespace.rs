@@ -0,0 +1,269 @@
+//! Hierarchical graph paths and per-namespace quotas
+//!
+//! Graphs are served under paths such as `teamA/fraud/2024-06`, where every prefix of the path
+//! ending before a `/` (`teamA`, `teamA/fraud`) is a namespace. Graphs loaded from a directory
+//! are placed in the namespace of the subdirectory they are found in. Roles grant access to all
+//! graphs of a namespace and its nested namespaces with a `teamA/*` pattern, and quotas limit the
+//! number of graphs and nodes of a namespace, counting those of its nested namespaces.
+//!
+//! ```toml
+//! [quotas.teamA]
+//! max_graphs = 100
+//!
+//! [quotas."teamA/fraud"]
+//! max_nodes = 1000000
+//! ```
+
+use raphtory::{db::api::view::MaterializedGraph, prelude::*, search::IndexedGraph};
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path};
+
+#[derive(thiserror::Error, Debug)]
+pub enum NamespaceError {
+    #[error("Failed to read namespace config: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid graph path '{0}': {1}")]
+    InvalidPath(String, &'static str),
+    #[error("Namespace '{0}' may contain at most {1} graphs")]
+    TooManyGraphs(String, usize),
+    #[error("Namespace '{0}' may contain at most {1} nodes")]
+    TooManyNodes(String, usize),
+    #[error("Graph '{0}' can only be renamed within its namespace, use moveGraph instead")]
+    NamespaceChanged(String),
+    #[error("Failed to read graph '{0}' to check quotas: {1}")]
+    UnreadableGraph(String, String),
+}
+
+/// Check that `path` is a sequence of non-empty names separated by `/`
+pub fn validate_path(path: &str) -> Result<(), NamespaceError> {
+    let invalid = |reason| Err(NamespaceError::InvalidPath(path.to_owned(), reason));
+    for segment in path.split('/') {
+        match segment {
+            "" => return invalid("empty segment"),
+            "." | ".." => return invalid("relative segment"),
+            "*" => return invalid("'*' is reserved for permissions"),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The namespace of the graph at `path`, empty at the root
+pub fn namespace(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(namespace, _)| namespace)
+}
+
+/// The name of the graph at `path` within its namespace
+pub fn leaf(path: &str) -> &str {
+    path.rsplit_once('/').map_or(path, |(_, name)| name)
+}
+
+/// The path of the graph `name` in `namespace`
+pub fn join(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{namespace}/{name}")
+    }
+}
+
+/// Whether `path` is in `namespace` or one of its nested namespaces. Every path is in the root
+/// namespace.
+pub fn in_namespace(path: &str, namespace: &str) -> bool {
+    namespace.is_empty()
+        || path
+            .strip_prefix(namespace)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The namespaces containing `path`, innermost first and excluding the root
+pub fn ancestors(path: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(namespace(path)), |namespace| {
+        namespace.rsplit_once('/').map(|(parent, _)| parent)
+    })
+    .filter(|namespace| !namespace.is_empty())
+}
+
+/// Limits on the graphs of a namespace, including those of nested namespaces
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Quota {
+    pub max_graphs: Option<usize>,
+    pub max_nodes: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct NamespaceConfig {
+    /// Quotas by namespace
+    pub quotas: HashMap<String, Quota>,
+}
+
+impl NamespaceConfig {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, NamespaceError> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| NamespaceError::InvalidConfig(err.to_string()))?;
+        toml::from_str(&content).map_err(|err| NamespaceError::InvalidConfig(err.to_string()))
+    }
+
+    /// Whether the nodes of the graph at `other` count towards a quota checked when storing a
+    /// graph at `path`
+    pub(crate) fn limits_nodes_of(&self, path: &str, other: &str) -> bool {
+        ancestors(path).any(|namespace| {
+            in_namespace(other, namespace)
+                && self
+                    .quotas
+                    .get(namespace)
+                    .is_some_and(|quota| quota.max_nodes.is_some())
+        })
+    }
+
+    /// Check that storing a graph with `nodes` nodes at `path`, replacing any graph stored
+    /// there, keeps every namespace within its quota. Graphs that are not in memory, at
+    /// `unloaded`, count with their number of nodes, which must be known for those that
+    /// [`Self::limits_nodes_of`].
+    pub(crate) fn check_quota<'a>(
+        &self,
+        graphs: &HashMap<String, IndexedGraph<MaterializedGraph>>,
+        unloaded: impl Iterator<Item = (&'a String, Option<usize>)> + Clone,
+        path: &str,
+        nodes: usize,
+    ) -> Result<(), NamespaceError> {
+        for namespace in ancestors(path) {
+            let Some(quota) = self.quotas.get(namespace) else {
+                continue;
+            };
+            let others = graphs
+                .iter()
+                .filter(|(other, _)| *other != path && in_namespace(other, namespace));
+            let (count, total_nodes) = others.fold((1, nodes), |(count, total), (_, graph)| {
+                (count + 1, total + graph.count_nodes())
+            });
+            let (count, total_nodes) = unloaded
+                .clone()
+                .filter(|(other, _)| *other != path && in_namespace(other, namespace))
+                .fold((count, total_nodes), |(count, total), (_, nodes)| {
+                    (count + 1, total + nodes.unwrap_or(0))
+                });
+            if let Some(max) = quota.max_graphs.filter(|&max| count > max) {
+                return Err(NamespaceError::TooManyGraphs(namespace.to_owned(), max));
+            }
+            if let Some(max) = quota.max_nodes.filter(|&max| total_nodes > max) {
+                return Err(NamespaceError::TooManyNodes(namespace.to_owned(), max));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod namespace_test {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        assert!(validate_path("teamA/fraud/2024-06").is_ok());
+        assert!(validate_path("graph").is_ok());
+        for invalid in [
+            "",
+            "/graph",
+            "teamA//graph",
+            "teamA/",
+            "teamA/../graph",
+            "*",
+        ] {
+            assert!(validate_path(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!(namespace("teamA/fraud/2024-06"), "teamA/fraud");
+        assert_eq!(namespace("graph"), "");
+        assert_eq!(leaf("teamA/fraud/2024-06"), "2024-06");
+        assert_eq!(join("teamA", "graph"), "teamA/graph");
+        assert_eq!(join("", "graph"), "graph");
+        assert!(in_namespace("teamA/fraud/2024-06", "teamA"));
+        assert!(!in_namespace("teamAB/graph", "teamA"));
+        assert_eq!(
+            ancestors("teamA/fraud/2024-06").collect::<Vec<_>>(),
+            ["teamA/fraud", "teamA"]
+        );
+    }
+
+    #[test]
+    fn quotas_count_nested_namespaces() {
+        let config: NamespaceConfig = toml::from_str(
+            r#"
+            [quotas.teamA]
+            max_graphs = 2
+            max_nodes = 3
+            "#,
+        )
+        .unwrap();
+        let unloaded: Vec<(&String, Option<usize>)> = vec![];
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([(
+            "teamA/fraud/g1".to_owned(),
+            IndexedGraph::from_graph(&graph).unwrap(),
+        )]);
+
+        assert!(config
+            .check_quota(&graphs, unloaded.iter().copied(), "teamA/g2", 1)
+            .is_ok());
+        assert!(config
+            .check_quota(&graphs, unloaded.iter().copied(), "teamA/fraud/g1", 3)
+            .is_ok());
+        assert!(config
+            .check_quota(&graphs, unloaded.iter().copied(), "teamB/g2", 10)
+            .is_ok());
+        assert!(matches!(
+            config.check_quota(&graphs, unloaded.iter().copied(), "teamA/g2", 2),
+            Err(NamespaceError::TooManyNodes(_, 3))
+        ));
+
+        let mut graphs = graphs;
+        let empty: MaterializedGraph = Graph::new().into();
+        graphs.insert(
+            "teamA/g2".to_owned(),
+            IndexedGraph::from_graph(&empty).unwrap(),
+        );
+        assert!(matches!(
+            config.check_quota(&graphs, unloaded.iter().copied(), "teamA/g3", 0),
+            Err(NamespaceError::TooManyGraphs(_, 2))
+        ));
+
+        graphs.remove("teamA/g2");
+        let g2 = "teamA/g2".to_owned();
+        let unloaded = [(&g2, None)];
+        assert!(matches!(
+            config.check_quota(&graphs, unloaded.iter().copied(), "teamA/g3", 0),
+            Err(NamespaceError::TooManyGraphs(_, 2))
+        ));
+    }
+
+    #[test]
+    fn quotas_count_nodes_of_unloaded_graphs() {
+        let config: NamespaceConfig = toml::from_str(
+            r#"
+            [quotas.teamA]
+            max_nodes = 3
+            "#,
+        )
+        .unwrap();
+        assert!(config.limits_nodes_of("teamA/g2", "teamA/fraud/g1"));
+        assert!(!config.limits_nodes_of("teamA/g2", "teamB/g1"));
+        assert!(!config.limits_nodes_of("teamB/g2", "teamB/g1"));
+
+        let graphs = HashMap::new();
+        let g1 = "teamA/fraud/g1".to_owned();
+        let unloaded = [(&g1, Some(3))];
+        assert!(matches!(
+            config.check_quota(&graphs, unloaded.iter().copied(), "teamA/g2", 1),
+            Err(NamespaceError::TooManyNodes(_, 3))
+        ));
+        assert!(config
+            .check_quota(&graphs, unloaded.iter().copied(), "teamA/g2", 0)
+            .is_ok());
+        assert!(config
+            .check_quota(&graphs, unloaded.iter().copied(), "teamA/fraud/g1", 3)
+            .is_ok());
+    }
+}
@@ -0,0 +1,310 @@
+//! Prometheus metrics of the GraphQL server
+//!
+//! Request, algorithm and loader durations are recorded as they happen. Graph counts and memory
+//! estimates are computed when the metrics are scraped; the memory estimate of a graph is only
+//! recomputed after the graph changed.
+
+use crate::data::Data;
+use async_graphql::Response;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use poem::{handler, web};
+use raphtory::{db::api::mutation::internal::InternalAdditionOps, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    future::Future,
+    time::Instant,
+};
+
+pub(crate) static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+/// Upper bounds in seconds of the duration histogram buckets
+const BUCKETS: [f64; 13] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Configuration of the metrics endpoint, the `[metrics]` table of `config.toml`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub path: String,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "/metrics".to_owned(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, label: &str, value: &str) {
+        let value = escape(value);
+        for (count, bound) in self.buckets.iter().zip(BUCKETS) {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{{label}=\"{value}\",le=\"{bound}\"}} {count}"
+            );
+        }
+        let count = self.count;
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{{label}=\"{value}\",le=\"+Inf\"}} {count}"
+        );
+        let _ = writeln!(out, "{name}_sum{{{label}=\"{value}\"}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{label}=\"{value}\"}} {count}");
+    }
+}
+
+type Histograms = Mutex<BTreeMap<String, Histogram>>;
+
+fn observe(histograms: &Histograms, key: &str, start: Instant) {
+    let seconds = start.elapsed().as_secs_f64();
+    histograms
+        .lock()
+        .entry(key.to_owned())
+        .or_default()
+        .observe(seconds)
+}
+
+/// Records the time until it is dropped
+pub(crate) struct Timer<'a> {
+    histograms: &'a Histograms,
+    key: &'a str,
+    start: Instant,
+}
+
+impl Drop for Timer<'_> {
+    fn drop(&mut self) {
+        observe(self.histograms, self.key, self.start)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Metrics {
+    requests: Histograms,
+    errors: Mutex<BTreeMap<String, u64>>,
+    algorithms: Histograms,
+    loads: Histograms,
+    /// The event id at which the memory of a graph was last estimated and the estimate
+    memory: Mutex<HashMap<String, (usize, usize)>>,
+}
+
+impl Metrics {
+    /// Time a run of the algorithm `name`
+    pub(crate) fn algorithm_timer<'a>(&'a self, name: &'a str) -> Timer<'a> {
+        Timer {
+            histograms: &self.algorithms,
+            key: name,
+            start: Instant::now(),
+        }
+    }
+
+    /// Time loading or uploading graphs with the mutation `kind`
+    pub(crate) fn load_timer(&self, kind: &'static str) -> Timer<'_> {
+        Timer {
+            histograms: &self.loads,
+            key: kind,
+            start: Instant::now(),
+        }
+    }
+
+    /// Record the duration and errors of the request for `operation`
+    pub(crate) async fn observe_request(
+        &self,
+        operation: Option<&str>,
+        response: impl Future<Output = Response>,
+    ) -> Response {
+        let operation = operation.unwrap_or("anonymous");
+        let start = Instant::now();
+        let response = response.await;
+        observe(&self.requests, operation, start);
+        if !response.errors.is_empty() {
+            *self.errors.lock().entry(operation.to_owned()).or_default() +=
+                response.errors.len() as u64;
+        }
+        response
+    }
+
+    pub(crate) fn render(&self, data: &Data) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP raphtory_graphql_request_duration_seconds Duration of GraphQL requests by operation\n");
+        out.push_str("# TYPE raphtory_graphql_request_duration_seconds histogram\n");
+        for (operation, histogram) in self.requests.lock().iter() {
+            histogram.render(
+                &mut out,
+                "raphtory_graphql_request_duration_seconds",
+                "operation",
+                operation,
+            );
+        }
+
+        out.push_str("# HELP raphtory_graphql_errors_total Errors returned by GraphQL requests by operation\n");
+        out.push_str("# TYPE raphtory_graphql_errors_total counter\n");
+        for (operation, count) in self.errors.lock().iter() {
+            let operation = escape(operation);
+            let _ = writeln!(
+                out,
+                "raphtory_graphql_errors_total{{operation=\"{operation}\"}} {count}"
+            );
+        }
+
+        out.push_str(
+            "# HELP raphtory_graphql_algorithm_duration_seconds Running time of algorithms\n",
+        );
+        out.push_str("# TYPE raphtory_graphql_algorithm_duration_seconds histogram\n");
+        for (algorithm, histogram) in self.algorithms.lock().iter() {
+            histogram.render(
+                &mut out,
+                "raphtory_graphql_algorithm_duration_seconds",
+                "algorithm",
+                algorithm,
+            );
+        }
+
+        out.push_str("# HELP raphtory_graphql_load_duration_seconds Duration of loading and uploading graphs\n");
+        out.push_str("# TYPE raphtory_graphql_load_duration_seconds histogram\n");
+        for (kind, histogram) in self.loads.lock().iter() {
+            histogram.render(
+                &mut out,
+                "raphtory_graphql_load_duration_seconds",
+                "mutation",
+                kind,
+            );
+        }
+
+        let graphs = data.graphs.read();
+        let mut names: Vec<_> = graphs.keys().collect();
+        names.sort();
+        out.push_str("# HELP raphtory_graphql_graphs Number of loaded graphs\n");
+        out.push_str("# TYPE raphtory_graphql_graphs gauge\n");
+        let _ = writeln!(out, "raphtory_graphql_graphs {}", graphs.len());
+
+        out.push_str("# HELP raphtory_graphql_graph_nodes Number of nodes per graph\n");
+        out.push_str("# TYPE raphtory_graphql_graph_nodes gauge\n");
+        for name in &names {
+            let nodes = graphs[*name].count_nodes();
+            let name = escape(name);
+            let _ = writeln!(
+                out,
+                "raphtory_graphql_graph_nodes{{graph=\"{name}\"}} {nodes}"
+            );
+        }
+
+        out.push_str("# HELP raphtory_graphql_graph_edges Number of edges per graph\n");
+        out.push_str("# TYPE raphtory_graphql_graph_edges gauge\n");
+        for name in &names {
+            let edges = graphs[*name].count_edges();
+            let name = escape(name);
+            let _ = writeln!(
+                out,
+                "raphtory_graphql_graph_edges{{graph=\"{name}\"}} {edges}"
+            );
+        }
+
+        out.push_str(
+            "# HELP raphtory_graphql_graph_memory_bytes Estimated memory per in-memory graph\n",
+        );
+        out.push_str("# TYPE raphtory_graphql_graph_memory_bytes gauge\n");
+        let mut memory = self.memory.lock();
+        memory.retain(|name, _| graphs.contains_key(name));
+        for name in &names {
+            let graph = &graphs[*name];
+            let version = graph.read_event_id();
+            let bytes = match memory.get(*name) {
+                Some(&(estimated_at, bytes)) if estimated_at == version => Some(bytes),
+                _ => graph.memory_stats().map(|stats| {
+                    let bytes = stats.total_bytes();
+                    memory.insert(name.to_string(), (version, bytes));
+                    bytes
+                }),
+            };
+            if let Some(bytes) = bytes {
+                let name = escape(name);
+                let _ = writeln!(
+                    out,
+                    "raphtory_graphql_graph_memory_bytes{{graph=\"{name}\"}} {bytes}"
+                );
+            }
+        }
+        out
+    }
+}
+
+fn escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[handler]
+pub(crate) async fn prometheus_metrics(data: web::Data<&Data>) -> String {
+    METRICS.render(&data)
+}
+
+#[cfg(test)]
+mod metrics_test {
+    use super::*;
+    use raphtory::db::api::view::MaterializedGraph;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn renders_prometheus_text() {
+        let graph = Graph::new();
+        graph.add_edge(0, "a", "b", NO_PROPS, None).unwrap();
+        graph.add_edge(1, "b", "c", NO_PROPS, None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let data = Data::from_map(HashMap::from([("g\"1".to_string(), graph)]));
+
+        let metrics = Metrics::default();
+        metrics
+            .observe_request(Some("Dashboard"), async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Response::from_errors(vec![async_graphql::ServerError::new("boom", None)])
+            })
+            .await;
+        drop(metrics.load_timer("upload_graph"));
+
+        let text = metrics.render(&data);
+        assert!(text.contains(
+            "raphtory_graphql_request_duration_seconds_bucket{operation=\"Dashboard\",le=\"0.01\"} 0"
+        ));
+        assert!(text.contains(
+            "raphtory_graphql_request_duration_seconds_bucket{operation=\"Dashboard\",le=\"60\"} 1"
+        ));
+        assert!(text.contains(
+            "raphtory_graphql_request_duration_seconds_count{operation=\"Dashboard\"} 1"
+        ));
+        assert!(text.contains("raphtory_graphql_errors_total{operation=\"Dashboard\"} 1"));
+        assert!(text
+            .contains("raphtory_graphql_load_duration_seconds_count{mutation=\"upload_graph\"} 1"));
+        assert!(text.contains("raphtory_graphql_graphs 1\n"));
+        assert!(text.contains("raphtory_graphql_graph_nodes{graph=\"g\\\"1\"} 3"));
+        assert!(text.contains("raphtory_graphql_graph_edges{graph=\"g\\\"1\"} 2"));
+        assert!(text.contains("raphtory_graphql_graph_memory_bytes{graph=\"g\\\"1\"} "));
+    }
+}
@@ -1 +1,2 @@
+pub(crate) mod metrics;
 pub(crate) mod tracing;
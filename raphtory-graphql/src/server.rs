@@ -1,21 +1,26 @@
 #![allow(dead_code)]
 use crate::{
+    auth::{graphql_with_access, AuthConfig},
     azure_auth::{
         common::{auth_callback, get_jwks, login, logout, verify, AppState},
         token_middleware::TokenMiddleware,
     },
+    cache::{CacheConfig, CacheError, QueryCache},
     data::Data,
+    limits::{graphql_with_limits, with_rate_limit, QueryLimits},
     model::{
         algorithms::{algorithm::Algorithm, algorithm_entry_point::AlgorithmEntryPoint},
         App,
     },
-    observability::tracing::create_tracer_from_env,
+    namespace::NamespaceConfig,
+    observability::{
+        metrics::{prometheus_metrics, MetricsConfig},
+        tracing::create_tracer_from_env,
+    },
     routes::{graphql_playground, health},
 };
 use async_graphql::extensions::ApolloTracing;
-use async_graphql_poem::GraphQL;
 use dotenv::dotenv;
-use itertools::Itertools;
 use oauth2::{basic::BasicClient, AuthUrl, ClientId, ClientSecret, RedirectUrl, TokenUrl};
 use poem::{
     get,
@@ -56,25 +61,43 @@ use tracing_subscriber::{
 /// A struct for defining and running a Raphtory GraphQL server
 pub struct RaphtoryServer {
     data: Data,
+    auth: Option<Arc<AuthConfig>>,
+    limits: QueryLimits,
+    cache: Option<CacheConfig>,
+    metrics: Option<MetricsConfig>,
 }
 
 // Define a struct for log configuration
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LogConfig {
     log_level: String,
+    #[serde(default)]
+    metrics: MetricsConfig,
 }
 
 impl RaphtoryServer {
     /// Return a server object with graphs loaded from a map `graphs`
     pub fn from_map(graphs: HashMap<String, MaterializedGraph>) -> Self {
         let data = Data::from_map(graphs);
-        Self { data }
+        Self {
+            data,
+            auth: None,
+            limits: Default::default(),
+            cache: None,
+            metrics: None,
+        }
     }
 
     /// Return a server object with graphs loaded from a directory `graph_directory`
     pub fn from_directory(graph_directory: &str) -> Self {
         let data = Data::from_directory(graph_directory);
-        Self { data }
+        Self {
+            data,
+            auth: None,
+            limits: Default::default(),
+            cache: None,
+            metrics: None,
+        }
     }
 
     /// Return a server object with graphs loaded from a map `graphs` and a directory `graph_directory`
@@ -83,7 +106,13 @@ impl RaphtoryServer {
         graph_directory: &str,
     ) -> Self {
         let data = Data::from_map_and_directory(graphs, graph_directory);
-        Self { data }
+        Self {
+            data,
+            auth: None,
+            limits: Default::default(),
+            cache: None,
+            metrics: None,
+        }
     }
 
     /// Vectorise a subset of the graphs of the server.
@@ -107,22 +136,21 @@ impl RaphtoryServer {
         F: EmbeddingFunction + Clone + 'static,
         T: DocumentTemplate<DynamicGraph> + 'static,
     {
-        let graphs = &self.data.graphs;
         let stores = &self.data.vector_stores;
 
         let template = template
             .map(|template| Arc::new(template) as Arc<dyn DocumentTemplate<DynamicGraph>>)
             .unwrap_or(Arc::new(DefaultTemplate));
 
-        let graph_names = graph_names.unwrap_or_else(|| {
-            let graphs = graphs.read();
-            let all_graph_names = graphs.iter().map(|(graph_name, _)| graph_name).cloned();
-            all_graph_names.collect_vec()
-        });
+        let graph_names = graph_names.unwrap_or_else(|| self.data.graph_names());
 
         for graph_name in graph_names {
             let graph_cache = cache.join(&graph_name);
-            let graph = graphs.read().get(&graph_name).unwrap().clone();
+            let graph = self
+                .data
+                .get_graph(&graph_name)
+                .expect("Unable to load graph")
+                .unwrap();
             println!("Loading embeddings for {graph_name} using cache from {graph_cache:?}");
             let vectorised = graph
                 .into_dynamic()
@@ -141,6 +169,48 @@ impl RaphtoryServer {
         self
     }
 
+    /// Require every GraphQL request to authenticate against `config` and
+    /// restrict it to the graphs and properties its roles grant access to.
+    pub fn with_auth(mut self, config: AuthConfig) -> Self {
+        self.auth = Some(Arc::new(config));
+        self
+    }
+
+    /// Limit the depth, complexity, running time and result sizes of queries and the request
+    /// rate of every client.
+    pub fn with_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Cache the results of queries and add the materialised views of `config` to the graphs
+    /// of the server.
+    pub fn with_cache(mut self, config: CacheConfig) -> Result<Self, CacheError> {
+        config.register_views(&self.data)?;
+        self.cache = Some(config);
+        Ok(self)
+    }
+
+    /// Unload the least recently used graphs of the graph directory, saving their changes, when
+    /// loading another graph makes the graphs in memory exceed an estimated `bytes`.
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.data.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Enforce the quotas of `config` on the namespaces of the server.
+    pub fn with_namespaces(mut self, config: NamespaceConfig) -> Self {
+        self.data.namespaces = Arc::new(config);
+        self
+    }
+
+    /// Configure the Prometheus metrics endpoint, overriding the `[metrics]` table of the
+    /// config file the server is started with.
+    pub fn with_metrics(mut self, config: MetricsConfig) -> Self {
+        self.metrics = Some(config);
+        self
+    }
+
     pub fn register_algorithm<
         'a,
         E: AlgorithmEntryPoint<'a> + 'static,
@@ -220,7 +290,22 @@ impl RaphtoryServer {
             }
         }
 
+        fn metrics_config(log_config_or_level: &str) -> MetricsConfig {
+            if parse_log_level(log_config_or_level).is_some() {
+                return MetricsConfig::default();
+            }
+            fs::read_to_string(log_config_or_level)
+                .ok()
+                .and_then(|content| toml::from_str::<LogConfig>(&content).ok())
+                .map(|config| config.metrics)
+                .unwrap_or_default()
+        }
+
         configure_logger(log_config_or_level);
+        let metrics = self
+            .metrics
+            .clone()
+            .unwrap_or_else(|| metrics_config(log_config_or_level));
 
         let registry = Registry::default().with(tracing_subscriber::fmt::layer().pretty());
         let env_filter = EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("INFO"));
@@ -238,10 +323,10 @@ impl RaphtoryServer {
 
         let app: CorsEndpoint<CookieJarManagerEndpoint<Route>> = if enable_auth {
             println!("Generating endpoint with auth");
-            self.generate_microsoft_endpoint_with_auth(enable_tracing, port)
+            self.generate_microsoft_endpoint_with_auth(enable_tracing, port, &metrics)
                 .await
         } else {
-            self.generate_endpoint(enable_tracing).await
+            self.generate_endpoint(enable_tracing, &metrics).await
         };
 
         let (signal_sender, signal_receiver) = mpsc::channel(1);
@@ -260,9 +345,12 @@ impl RaphtoryServer {
     async fn generate_endpoint(
         self,
         enable_tracing: bool,
+        metrics: &MetricsConfig,
     ) -> CorsEndpoint<CookieJarManagerEndpoint<Route>> {
+        let data = self.data.clone();
+        let cache = Arc::new(QueryCache::new(self.cache, &self.data));
         let schema_builder = App::create_schema();
-        let schema_builder = schema_builder.data(self.data);
+        let schema_builder = self.limits.configure(schema_builder.data(self.data));
         let schema = if enable_tracing {
             let schema_builder = schema_builder.extension(ApolloTracing);
             schema_builder.finish().unwrap()
@@ -270,9 +358,31 @@ impl RaphtoryServer {
             schema_builder.finish().unwrap()
         };
 
+        let limits = self.limits;
+        let graphql = match self.auth {
+            Some(config) => with_rate_limit(
+                graphql_with_access
+                    .data(schema)
+                    .data(config.clone())
+                    .data(limits.clone())
+                    .data(cache),
+                &limits,
+                Some(config),
+            ),
+            None => with_rate_limit(
+                graphql_with_limits
+                    .data(schema)
+                    .data(limits.clone())
+                    .data(cache),
+                &limits,
+                None,
+            ),
+        };
+
         let app = Route::new()
-            .at("/", get(graphql_playground).post(GraphQL::new(schema)))
-            .at("/health", get(health))
+            .at("/", get(graphql_playground).post(graphql))
+            .at("/health", get(health));
+        let app = with_metrics_endpoint(app, metrics, data)
             .with(CookieJarManager::new())
             .with(Cors::new());
         app
@@ -282,9 +392,12 @@ impl RaphtoryServer {
         self,
         enable_tracing: bool,
         port: u16,
+        metrics: &MetricsConfig,
     ) -> CorsEndpoint<CookieJarManagerEndpoint<Route>> {
+        let data = self.data.clone();
+        let cache = Arc::new(QueryCache::new(self.cache, &self.data));
         let schema_builder = App::create_schema();
-        let schema_builder = schema_builder.data(self.data);
+        let schema_builder = self.limits.configure(schema_builder.data(self.data));
         let schema = if enable_tracing {
             let schema_builder = schema_builder.extension(ApolloTracing);
             schema_builder.finish().unwrap()
@@ -292,6 +405,8 @@ impl RaphtoryServer {
             schema_builder.finish().unwrap()
         };
 
+        let limits = self.limits;
+
         dotenv().ok();
         println!("Loading env");
         let client_id_str = env::var("CLIENT_ID").expect("CLIENT_ID not set");
@@ -344,7 +459,14 @@ impl RaphtoryServer {
             .at(
                 "/",
                 get(graphql_playground)
-                    .post(GraphQL::new(schema))
+                    .post(with_rate_limit(
+                        graphql_with_limits
+                            .data(schema)
+                            .data(limits.clone())
+                            .data(cache),
+                        &limits,
+                        None,
+                    ))
                     .with(token_middleware.clone()),
             )
             .at("/health", get(health))
@@ -356,7 +478,8 @@ impl RaphtoryServer {
                     .data(app_state.clone())
                     .with(token_middleware.clone()),
             )
-            .at("/logout", logout.with(token_middleware.clone()))
+            .at("/logout", logout.with(token_middleware.clone()));
+        let app = with_metrics_endpoint(app, metrics, data)
             .with(CookieJarManager::new())
             .with(Cors::new());
         println!("App done");
@@ -421,6 +544,15 @@ impl RunningRaphtoryServer {
     }
 }
 
+/// Serve the Prometheus metrics at the configured path if they are enabled
+fn with_metrics_endpoint(app: Route, config: &MetricsConfig, data: Data) -> Route {
+    if config.enabled {
+        app.at(&config.path, get(prometheus_metrics.data(data)))
+    } else {
+        app
+    }
+}
+
 async fn server_termination(mut internal_signal: Receiver<()>) {
     let ctrl_c = async {
         signal::ctrl_c()
@@ -1,7 +1,7 @@
 use crate::{
     algorithms::algorithm_result::AlgorithmResult,
     core::entities::VID,
-    db::graph::node::NodeView,
+    db::{graph::node::NodeView, task::cancellation},
     prelude::{GraphViewOps, NodeViewOps},
 };
 use ordered_float::OrderedFloat;
@@ -33,6 +33,9 @@ pub fn betweenness_centrality<'graph, G: GraphViewOps<'graph>>(
 
     // Main loop over each node to compute betweenness centrality.
     for node in nodes.iter().take(k_sample) {
+        if cancellation::is_cancelled() {
+            break;
+        }
         let mut stack = Vec::new();
         let mut predecessors: HashMap<usize, Vec<usize>> = HashMap::new();
         let mut sigma: HashMap<usize, f64> = HashMap::new();
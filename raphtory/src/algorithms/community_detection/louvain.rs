@@ -4,6 +4,7 @@ use crate::{
         community_detection::modularity::{ModularityFunction, Partition},
     },
     core::entities::VID,
+    db::task::cancellation,
     prelude::GraphViewOps,
 };
 use rand::prelude::SliceRandom;
@@ -32,11 +33,11 @@ pub fn louvain<'graph, M: ModularityFunction, G: GraphViewOps<'graph>>(
         .collect();
 
     let mut outer_moved = true;
-    while outer_moved {
+    while outer_moved && !cancellation::is_cancelled() {
         outer_moved = false;
         let mut inner_moved = true;
         let mut nodes: Vec<_> = modularity_state.nodes().collect();
-        while inner_moved {
+        while inner_moved && !cancellation::is_cancelled() {
             inner_moved = false;
             nodes.shuffle(&mut rng);
             for v in nodes.iter() {
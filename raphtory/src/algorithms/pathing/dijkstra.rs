@@ -7,8 +7,10 @@ use std::{
 use crate::{core::entities::nodes::node_ref::AsNodeRef, db::api::view::StaticGraphViewOps};
 use crate::{
     core::{Direction, PropType},
+    db::task::cancellation,
     prelude::{EdgeViewOps, NodeViewOps, Prop},
 };
+use rust_decimal::Decimal;
 
 /// A state in the Dijkstra algorithm with a cost and a node name.
 #[derive(PartialEq)]
@@ -103,6 +105,9 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         PropType::U64 => Prop::U64(0u64),
         PropType::I32 => Prop::I32(0i32),
         PropType::I64 => Prop::I64(0i64),
+        PropType::I8 => Prop::I8(0i8),
+        PropType::I16 => Prop::I16(0i16),
+        PropType::Decimal => Prop::Decimal(Decimal::ZERO),
         PropType::Bool => return Err("Weight type: Bool, not supported"),
         PropType::List => return Err("Weight type: List, not supported"),
         PropType::Map => return Err("Weight type: Map, not supported"),
@@ -111,6 +116,8 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         PropType::Graph => return Err("Weight type: Graph, not supported"),
         PropType::PersistentGraph => return Err("Weight type: Persistent Graph, not supported"),
         PropType::Document => return Err("Weight type: Document, not supported"),
+        PropType::Bytes => return Err("Weight type: Bytes, not supported"),
+        PropType::Array(_) => return Err("Weight type: Array, not supported"),
     };
     let max_val = match weight_type.unwrap() {
         PropType::Empty => return Err("Weight type: Empty, not supported"),
@@ -123,6 +130,9 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         PropType::U64 => Prop::U64(u64::MAX),
         PropType::I32 => Prop::I32(i32::MAX),
         PropType::I64 => Prop::I64(i64::MAX),
+        PropType::I8 => Prop::I8(i8::MAX),
+        PropType::I16 => Prop::I16(i16::MAX),
+        PropType::Decimal => Prop::Decimal(Decimal::MAX),
         PropType::Bool => return Err("Weight type: Bool, not supported"),
         PropType::List => return Err("Weight type: List, not supported"),
         PropType::Map => return Err("Weight type: Map, not supported"),
@@ -131,6 +141,8 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         PropType::Graph => return Err("Weight type: Graph, not supported"),
         PropType::PersistentGraph => return Err("Weight type: Persistent Graph, not supported"),
         PropType::Document => return Err("Weight type: Document, not supported"),
+        PropType::Bytes => return Err("Weight type: Bytes, not supported"),
+        PropType::Array(_) => return Err("Weight type: Array, not supported"),
     };
     let mut heap = BinaryHeap::new();
     heap.push(State {
@@ -150,6 +162,9 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         node: node_name,
     }) = heap.pop()
     {
+        if cancellation::is_cancelled() {
+            break;
+        }
         if target_nodes.contains(&node_name) && !paths.contains_key(&node_name) {
             let mut path = vec![node_name.clone()];
             let mut current_node_name = node_name.clone();
@@ -5,6 +5,7 @@ use crate::{
             LayerIds, EID, VID,
         },
         storage::{
+            columnar::CompactionStats,
             lazy_vec::IllegalSet,
             timeindex::{TimeIndex, TimeIndexEntry, TimeIndexIntoOps, TimeIndexOps},
             ArcEntry,
@@ -88,6 +89,13 @@ impl EdgeLayer {
     pub(crate) fn temporal_property(&self, prop_id: usize) -> Option<&TProp> {
         self.props.as_ref().and_then(|ps| ps.temporal_prop(prop_id))
     }
+
+    pub(crate) fn compact_props(&mut self) -> CompactionStats {
+        self.props
+            .as_mut()
+            .map(|props| props.compact())
+            .unwrap_or_default()
+    }
 }
 
 impl EdgeStore {
@@ -120,6 +128,14 @@ impl EdgeStore {
                 .is_some()
     }
 
+    pub(crate) fn compact_props(&mut self) -> CompactionStats {
+        let mut stats = CompactionStats::default();
+        for layer in self.layers.iter_mut() {
+            stats += layer.compact_props();
+        }
+        stats
+    }
+
     pub fn layer_iter(&self) -> impl Iterator<Item = &EdgeLayer> + '_ {
         self.layers.iter()
     }
@@ -0,0 +1,504 @@
+//! Estimates of the memory used by the in-memory graph storage
+//!
+//! Sizes are estimates based on the layout of the storage containers. Values shared between
+//! entities (e.g. strings behind an `Arc`) are counted once per reference.
+
+use crate::{
+    core::{
+        entities::{
+            edges::edge_store::{EdgeLayer, EdgeStore},
+            graph::tgraph::TemporalGraph,
+            nodes::{node_store::NodeStore, structure::adj::Adj},
+            properties::props::{DictMapper, Props},
+        },
+        storage::{
+            btree_heap_size,
+            timeindex::{AsTime, TimeIndex, TimeIndexEntry},
+        },
+        ArcStr, Prop, PropArray,
+    },
+    db::api::storage::tprop_storage_ops::TPropOps,
+};
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use std::{
+    fmt::{Display, Formatter},
+    mem::size_of,
+    ops::AddAssign,
+};
+
+/// Memory used by a part of the graph and the number of entries (or updates) it stores
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub bytes: usize,
+    pub count: usize,
+}
+
+impl AddAssign for MemoryUsage {
+    fn add_assign(&mut self, rhs: Self) {
+        self.bytes += rhs.bytes;
+        self.count += rhs.count;
+    }
+}
+
+/// Memory used by a single layer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerMemoryStats {
+    pub name: ArcStr,
+    /// Adjacency lists of the nodes in the layer, the count is the number of entries
+    pub adjacency: MemoryUsage,
+    /// Edge additions and deletions in the layer
+    pub edge_updates: MemoryUsage,
+    /// Edge properties in the layer, the count is the number of property updates
+    pub edge_properties: MemoryUsage,
+}
+
+impl LayerMemoryStats {
+    pub fn total_bytes(&self) -> usize {
+        self.adjacency.bytes + self.edge_updates.bytes + self.edge_properties.bytes
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PropertyEntity {
+    Node,
+    Edge,
+    Graph,
+}
+
+impl Display for PropertyEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyEntity::Node => write!(f, "node"),
+            PropertyEntity::Edge => write!(f, "edge"),
+            PropertyEntity::Graph => write!(f, "graph"),
+        }
+    }
+}
+
+/// Memory used by all values of a property key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyMemoryStats {
+    pub entity: PropertyEntity,
+    pub name: ArcStr,
+    pub constant: bool,
+    /// The count is the number of updates (or values for constant properties)
+    pub usage: MemoryUsage,
+}
+
+/// Breakdown of the memory used by a graph
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Node records including names and adjacency lists
+    pub nodes: MemoryUsage,
+    /// Edge records
+    pub edges: MemoryUsage,
+    /// Timestamps of node updates
+    pub node_timestamps: MemoryUsage,
+    /// Timestamps of edge additions and deletions
+    pub edge_timestamps: MemoryUsage,
+    pub node_properties: MemoryUsage,
+    pub edge_properties: MemoryUsage,
+    pub graph_properties: MemoryUsage,
+    /// Mappings of property keys, layer names and node types
+    pub metadata: MemoryUsage,
+    /// Mapping from node ids to internal ids
+    pub id_mapping: MemoryUsage,
+    pub string_pool: MemoryUsage,
+    /// Memory per layer (a breakdown of part of `nodes`, `edge_timestamps` and `edge_properties`)
+    pub layers: Vec<LayerMemoryStats>,
+    /// Memory per property key, largest first
+    pub properties: Vec<PropertyMemoryStats>,
+}
+
+impl MemoryStats {
+    /// The top-level components of the graph storage
+    pub fn components(&self) -> [(&'static str, MemoryUsage); 10] {
+        [
+            ("nodes", self.nodes),
+            ("edges", self.edges),
+            ("node_timestamps", self.node_timestamps),
+            ("edge_timestamps", self.edge_timestamps),
+            ("node_properties", self.node_properties),
+            ("edge_properties", self.edge_properties),
+            ("graph_properties", self.graph_properties),
+            ("metadata", self.metadata),
+            ("id_mapping", self.id_mapping),
+            ("string_pool", self.string_pool),
+        ]
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.components().iter().map(|(_, usage)| usage.bytes).sum()
+    }
+}
+
+const ARC_HEADER: usize = 2 * size_of::<usize>();
+
+pub(crate) fn arc_str_heap_size(value: &ArcStr) -> usize {
+    ARC_HEADER + value.len()
+}
+
+// hash tables keep the load factor below 7/8 and use one control byte per bucket
+fn hash_map_heap_size<K, V>(len: usize) -> usize {
+    len * (size_of::<(K, V)>() + 1) * 8 / 7
+}
+
+/// Estimated heap memory owned by a property value
+pub(crate) fn prop_heap_size(prop: &Prop) -> usize {
+    match prop {
+        Prop::Str(value) => arc_str_heap_size(value),
+        Prop::Document(value) => value.content.capacity(),
+        Prop::List(values) => {
+            ARC_HEADER
+                + values.capacity() * size_of::<Prop>()
+                + values.iter().map(prop_heap_size).sum::<usize>()
+        }
+        Prop::Map(values) => {
+            ARC_HEADER
+                + hash_map_heap_size::<ArcStr, Prop>(values.capacity())
+                + values
+                    .iter()
+                    .map(|(k, v)| arc_str_heap_size(k) + prop_heap_size(v))
+                    .sum::<usize>()
+        }
+        Prop::Bytes(value) => ARC_HEADER + value.len(),
+        Prop::Array(value) => {
+            ARC_HEADER
+                + match value {
+                    PropArray::I32(v) => v.len() * size_of::<i32>(),
+                    PropArray::I64(v) => v.len() * size_of::<i64>(),
+                    PropArray::F32(v) => v.len() * size_of::<f32>(),
+                    PropArray::F64(v) => v.len() * size_of::<f64>(),
+                }
+        }
+        _ => 0,
+    }
+}
+
+fn time_index_usage<T: AsTime>(index: &TimeIndex<T>) -> MemoryUsage {
+    let bytes = match index {
+        TimeIndex::Set(ts) => btree_heap_size(ts.len(), size_of::<T>()),
+        _ => 0,
+    };
+    MemoryUsage {
+        bytes,
+        count: index.len(),
+    }
+}
+
+fn dict_mapper_usage(mapper: &DictMapper) -> MemoryUsage {
+    let keys = mapper.get_keys();
+    MemoryUsage {
+        bytes: hash_map_heap_size::<ArcStr, usize>(keys.len())
+            + keys.capacity() * size_of::<ArcStr>()
+            + keys.iter().map(arc_str_heap_size).sum::<usize>(),
+        count: keys.len(),
+    }
+}
+
+#[derive(Default)]
+struct LayerUsage {
+    adjacency: MemoryUsage,
+    edge_updates: MemoryUsage,
+    edge_properties: MemoryUsage,
+}
+
+/// Accumulates the memory used by nodes or edges
+#[derive(Default)]
+struct StorageWalk {
+    records: MemoryUsage,
+    timestamps: MemoryUsage,
+    properties: MemoryUsage,
+    layers: Vec<LayerUsage>,
+    keys: FxHashMap<(usize, bool), MemoryUsage>,
+}
+
+impl StorageWalk {
+    fn layer_mut(&mut self, layer_id: usize) -> &mut LayerUsage {
+        if self.layers.len() <= layer_id {
+            self.layers.resize_with(layer_id + 1, Default::default);
+        }
+        &mut self.layers[layer_id]
+    }
+
+    fn add_props(&mut self, props: &Props) -> MemoryUsage {
+        let mut usage = MemoryUsage {
+            bytes: props.constant_props.heap_size() + props.temporal_props.heap_size(),
+            count: 0,
+        };
+        for id in props.const_prop_ids() {
+            if let Some(prop) = props.const_prop(id) {
+                let key_usage = MemoryUsage {
+                    bytes: prop_heap_size(prop),
+                    count: 1,
+                };
+                *self.keys.entry((id, true)).or_default() += key_usage;
+                usage += key_usage;
+            }
+        }
+        for id in props.temporal_prop_ids() {
+            if let Some(prop) = props.temporal_prop(id) {
+                let key_usage = MemoryUsage {
+                    bytes: prop.heap_size() + prop.values_heap_size(),
+                    count: prop.len(),
+                };
+                *self.keys.entry((id, false)).or_default() += key_usage;
+                usage += key_usage;
+            }
+        }
+        self.properties += usage;
+        usage
+    }
+
+    fn add_node(mut self, node: &NodeStore) -> Self {
+        let mut bytes = size_of::<NodeStore>()
+            + node.name.as_ref().map_or(0, |name| name.capacity())
+            + node.layers.capacity() * size_of::<Adj>();
+        for (layer_id, adj) in node.layers.iter().enumerate() {
+            let adjacency = MemoryUsage {
+                bytes: adj.heap_size(),
+                count: adj.len(),
+            };
+            bytes += adjacency.bytes;
+            self.layer_mut(layer_id).adjacency += adjacency;
+        }
+        self.records += MemoryUsage { bytes, count: 1 };
+        self.timestamps += time_index_usage(node.timestamps());
+        if let Some(props) = &node.props {
+            self.add_props(props);
+        }
+        self
+    }
+
+    fn add_edge(mut self, edge: &EdgeStore) -> Self {
+        self.records += MemoryUsage {
+            bytes: size_of::<EdgeStore>()
+                + edge.layers.capacity() * size_of::<EdgeLayer>()
+                + (edge.additions.capacity() + edge.deletions.capacity())
+                    * size_of::<TimeIndex<TimeIndexEntry>>(),
+            count: 1,
+        };
+        for layer_id in 0..edge.internal_num_layers() {
+            let mut updates = MemoryUsage::default();
+            for index in [edge.additions.get(layer_id), edge.deletions.get(layer_id)]
+                .into_iter()
+                .flatten()
+            {
+                updates += time_index_usage(index);
+            }
+            self.timestamps += updates;
+            self.layer_mut(layer_id).edge_updates += updates;
+            if let Some(props) = edge.layers.get(layer_id).and_then(|layer| layer.props()) {
+                let usage = self.add_props(props);
+                self.layer_mut(layer_id).edge_properties += usage;
+            }
+        }
+        self
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        self.records += other.records;
+        self.timestamps += other.timestamps;
+        self.properties += other.properties;
+        for (layer_id, usage) in other.layers.into_iter().enumerate() {
+            let layer = self.layer_mut(layer_id);
+            layer.adjacency += usage.adjacency;
+            layer.edge_updates += usage.edge_updates;
+            layer.edge_properties += usage.edge_properties;
+        }
+        for (key, usage) in other.keys {
+            *self.keys.entry(key).or_default() += usage;
+        }
+        self
+    }
+}
+
+impl TemporalGraph {
+    /// Estimate the memory used by the graph
+    pub(crate) fn memory_stats(&self) -> MemoryStats {
+        let nodes = self.storage.nodes.read_lock();
+        let node_walk = nodes
+            .par_iter()
+            .fold(StorageWalk::default, StorageWalk::add_node)
+            .reduce(StorageWalk::default, StorageWalk::merge);
+        let edges = self.storage.edges.read_lock();
+        let edge_walk = edges
+            .par_iter()
+            .fold(StorageWalk::default, StorageWalk::add_edge)
+            .reduce(StorageWalk::default, StorageWalk::merge);
+
+        let mut properties = vec![];
+        for ((id, constant), usage) in node_walk.keys.iter() {
+            properties.push(PropertyMemoryStats {
+                entity: PropertyEntity::Node,
+                name: self.node_meta.get_prop_name(*id, *constant),
+                constant: *constant,
+                usage: *usage,
+            });
+        }
+        for ((id, constant), usage) in edge_walk.keys.iter() {
+            properties.push(PropertyMemoryStats {
+                entity: PropertyEntity::Edge,
+                name: self.edge_meta.get_prop_name(*id, *constant),
+                constant: *constant,
+                usage: *usage,
+            });
+        }
+
+        let mut graph_properties = MemoryUsage::default();
+        for id in self.graph_meta.const_prop_ids() {
+            let value = self.graph_meta.get_constant(id);
+            let usage = MemoryUsage {
+                bytes: hash_map_heap_size::<usize, Option<Prop>>(1)
+                    + value.as_ref().map_or(0, prop_heap_size),
+                count: usize::from(value.is_some()),
+            };
+            graph_properties += usage;
+            properties.push(PropertyMemoryStats {
+                entity: PropertyEntity::Graph,
+                name: self.graph_meta.get_const_prop_name(id),
+                constant: true,
+                usage,
+            });
+        }
+        for id in self.graph_meta.temporal_ids() {
+            if let Some(prop) = self.graph_meta.get_temporal_prop(id) {
+                let usage = MemoryUsage {
+                    bytes: hash_map_heap_size::<usize, Prop>(1)
+                        + prop.heap_size()
+                        + prop.values_heap_size(),
+                    count: prop.len(),
+                };
+                graph_properties += usage;
+                properties.push(PropertyMemoryStats {
+                    entity: PropertyEntity::Graph,
+                    name: self.graph_meta.get_temporal_name(id),
+                    constant: false,
+                    usage,
+                });
+            }
+        }
+        properties.sort_by(|a, b| {
+            b.usage
+                .bytes
+                .cmp(&a.usage.bytes)
+                .then_with(|| (a.entity, &a.name, a.constant).cmp(&(b.entity, &b.name, b.constant)))
+        });
+
+        let layer_names = self.edge_meta.layer_meta().get_keys();
+        let layers = layer_names
+            .iter()
+            .enumerate()
+            .map(|(layer_id, name)| {
+                let mut layer = LayerMemoryStats {
+                    name: name.clone(),
+                    adjacency: MemoryUsage::default(),
+                    edge_updates: MemoryUsage::default(),
+                    edge_properties: MemoryUsage::default(),
+                };
+                for walk in [&node_walk, &edge_walk] {
+                    if let Some(usage) = walk.layers.get(layer_id) {
+                        layer.adjacency += usage.adjacency;
+                        layer.edge_updates += usage.edge_updates;
+                        layer.edge_properties += usage.edge_properties;
+                    }
+                }
+                layer
+            })
+            .collect();
+
+        let mut metadata = MemoryUsage::default();
+        for meta in [&self.node_meta, &self.edge_meta] {
+            metadata += dict_mapper_usage(meta.const_prop_meta());
+            metadata += dict_mapper_usage(meta.temporal_prop_meta());
+            metadata += dict_mapper_usage(meta.layer_meta());
+            metadata += dict_mapper_usage(meta.node_type_meta());
+        }
+        metadata += dict_mapper_usage(self.graph_meta.const_prop_meta());
+        metadata += dict_mapper_usage(self.graph_meta.temporal_prop_meta());
+
+        let num_ids = self.logical_to_physical.len();
+        let id_mapping = MemoryUsage {
+            bytes: hash_map_heap_size::<u64, usize>(num_ids),
+            count: num_ids,
+        };
+        let string_pool = MemoryUsage {
+            bytes: hash_map_heap_size::<ArcStr, ()>(self.string_pool.len())
+                + self
+                    .string_pool
+                    .iter()
+                    .map(|s| arc_str_heap_size(&s))
+                    .sum::<usize>(),
+            count: self.string_pool.len(),
+        };
+
+        MemoryStats {
+            nodes: node_walk.records,
+            edges: edge_walk.records,
+            node_timestamps: node_walk.timestamps,
+            edge_timestamps: edge_walk.timestamps,
+            node_properties: node_walk.properties,
+            edge_properties: edge_walk.properties,
+            graph_properties,
+            metadata,
+            id_mapping,
+            string_pool,
+            layers,
+            properties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn memory_stats_grow_with_updates() {
+        let g = Graph::new();
+        g.add_edge(0, 1, 2, [("weight", 1.0)], Some("a")).unwrap();
+        let small = g.memory_stats().unwrap();
+
+        for t in 1..1000 {
+            g.add_edge(t, 1, 2, [("weight", t as f64)], Some("a"))
+                .unwrap();
+            g.add_edge(t, 2, 3, NO_PROPS, Some("b")).unwrap();
+        }
+        g.add_node(0, 1, [("name", "a long string value")], None)
+            .unwrap();
+        g.add_constant_properties([("owner", "me")]).unwrap();
+        let stats = g.memory_stats().unwrap();
+
+        assert!(stats.total_bytes() > small.total_bytes());
+        assert_eq!(stats.nodes.count, 3);
+        assert_eq!(stats.edges.count, 2);
+        assert_eq!(stats.edge_timestamps.count, 1999);
+        assert_eq!(stats.edge_properties.count, 1000);
+        assert_eq!(stats.node_properties.count, 1);
+        assert_eq!(stats.graph_properties.count, 1);
+        assert!(stats.edge_timestamps.bytes > 0);
+
+        let layer = |name: &str| {
+            stats
+                .layers
+                .iter()
+                .find(|layer| layer.name == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(layer("a").edge_updates.count, 1000);
+        assert_eq!(layer("b").edge_updates.count, 999);
+        assert_eq!(layer("a").edge_properties.count, 1000);
+        assert_eq!(layer("b").edge_properties.count, 0);
+        assert_eq!(layer("_default").total_bytes(), 0);
+
+        // the largest property key comes first
+        let weight = &stats.properties[0];
+        assert_eq!(weight.entity, PropertyEntity::Edge);
+        assert_eq!(weight.name, "weight");
+        assert!(!weight.constant);
+        assert_eq!(weight.usage.count, 1000);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod memory;
 pub mod tgraph;
 pub mod tgraph_storage;
 pub(crate) mod timer;
@@ -15,6 +15,7 @@ use crate::{
             LayerIds, EID, VID,
         },
         storage::{
+            columnar::CompactionStats,
             locked_view::LockedView,
             timeindex::{AsTime, TimeIndexEntry},
             Entry, EntryMut,
@@ -64,6 +65,7 @@ impl InternalGraph {
             string_pool: Default::default(),
             storage: GraphStorage::new(num_locks),
             event_counter: AtomicUsize::new(0),
+            generation: AtomicUsize::new(0),
             earliest_time: MinCounter::new(),
             latest_time: MaxCounter::new(),
             node_meta: Arc::new(Meta::new()),
@@ -78,13 +80,17 @@ impl InternalGraph {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TemporalGraph {
     // mapping between logical and physical ids
-    logical_to_physical: FxDashMap<u64, VID>,
-    string_pool: FxDashSet<ArcStr>,
+    pub(in crate::core) logical_to_physical: FxDashMap<u64, VID>,
+    pub(in crate::core) string_pool: FxDashSet<ArcStr>,
 
     pub(crate) storage: GraphStorage,
 
     pub(crate) event_counter: AtomicUsize,
 
+    // number of mutations since the graph was created or loaded, including those without events
+    #[serde(skip)]
+    pub(crate) generation: AtomicUsize,
+
     //earliest time seen in this graph
     pub(in crate::core) earliest_time: MinCounter,
 
@@ -119,6 +125,30 @@ impl Default for InternalGraph {
 }
 
 impl TemporalGraph {
+    /// Compact the temporal property histories of all nodes, edges and the graph itself
+    pub(crate) fn compact(&self) -> CompactionStats {
+        let mut stats = CompactionStats::default();
+        let node_stats = self.storage.nodes.par_map_buckets_mut(|nodes| {
+            let mut stats = CompactionStats::default();
+            for node in nodes {
+                stats += node.compact_props();
+            }
+            stats
+        });
+        let edge_stats = self.storage.edges.par_map_buckets_mut(|edges| {
+            let mut stats = CompactionStats::default();
+            for edge in edges {
+                stats += edge.compact_props();
+            }
+            stats
+        });
+        for bucket_stats in node_stats.into_iter().chain(edge_stats) {
+            stats += bucket_stats;
+        }
+        stats += self.graph_meta.compact();
+        stats
+    }
+
     pub(crate) fn num_layers(&self) -> usize {
         self.edge_meta.layer_meta().len()
     }
@@ -317,6 +347,20 @@ impl TemporalGraph {
         }
     }
 
+    /// overwrite the type of a node, allocating a new node type if needed
+    pub(crate) fn set_node_type(&self, v_id: VID, node_type: &str) -> Result<usize, GraphError> {
+        if node_type == "_default" {
+            return Err(GraphError::NodeTypeError(
+                "_default type is not allowed to be used on nodes".to_owned(),
+            ));
+        }
+        let node_type_id = self.node_meta.get_or_create_node_type_id(node_type);
+        Ok(self
+            .storage
+            .get_node_mut(v_id)
+            .update_node_type(node_type_id))
+    }
+
     #[inline]
     pub(crate) fn add_node_no_props(
         &self,
@@ -339,6 +383,17 @@ impl TemporalGraph {
         props: Vec<(usize, Prop)>,
         node_type_id: usize,
     ) -> Result<(), GraphError> {
+        let props = self.node_meta.coerce_props(props, false)?;
+        // an update without a type keeps the type the node already has
+        let node_type_id = match node_type_id {
+            0 => self.node_type_id(v_id),
+            node_type_id => node_type_id,
+        };
+        self.node_meta.check_required_props(
+            || self.node_meta.get_node_type_name_by_id(node_type_id),
+            &props,
+            || format!("node {}", self.node_name(v_id)),
+        )?;
         let mut node = self.add_node_no_props(time, v_id, node_type_id);
         for (id, prop) in props {
             node.add_prop(time, id, prop)?;
@@ -350,6 +405,7 @@ impl TemporalGraph {
         &self,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        let props = self.graph_meta.coerce_props(props, true)?;
         for (id, prop) in props {
             self.graph_meta.add_constant_prop(id, prop)?;
         }
@@ -360,6 +416,7 @@ impl TemporalGraph {
         &self,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        let props = self.graph_meta.coerce_props(props, true)?;
         for (id, prop) in props {
             self.graph_meta.update_constant_prop(id, prop)?;
         }
@@ -371,6 +428,7 @@ impl TemporalGraph {
         t: TimeIndexEntry,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        let props = self.graph_meta.coerce_props(props, false)?;
         for (prop_id, prop) in props {
             self.graph_meta.add_prop(t, prop_id, prop)?;
         }
@@ -444,6 +502,18 @@ impl TemporalGraph {
         props: Vec<(usize, Prop)>,
         layer: usize,
     ) -> Result<EID, GraphError> {
+        let props = self.edge_meta.coerce_props(props, false)?;
+        self.edge_meta.check_required_props(
+            || Some(self.get_layer_name(layer)),
+            &props,
+            || {
+                format!(
+                    "edge {} -> {}",
+                    self.node_name(src_id),
+                    self.node_name(dst_id)
+                )
+            },
+        )?;
         // get the entries for the src and dst nodes
         self.link_nodes(src_id, dst_id, t, layer, move |edge| {
             edge.additions_mut(layer).insert(t);
@@ -6,6 +6,7 @@ use crate::core::{
         LayerIds, EID, VID,
     },
     storage::{
+        columnar::CompactionStats,
         lazy_vec::IllegalSet,
         timeindex::{AsTime, TimeIndex, TimeIndexEntry},
         ArcEntry,
@@ -337,6 +338,13 @@ impl NodeStore {
             .flat_map(|ps| ps.const_prop_ids())
     }
 
+    pub(crate) fn compact_props(&mut self) -> CompactionStats {
+        self.props
+            .as_mut()
+            .map(|props| props.compact())
+            .unwrap_or_default()
+    }
+
     pub(crate) fn temporal_property(&self, prop_id: usize) -> Option<&TProp> {
         self.props.as_ref().and_then(|ps| ps.temporal_prop(prop_id))
     }
@@ -30,6 +30,22 @@ impl Adj {
         }
     }
 
+    /// Estimated heap memory used by the adjacency sets
+    pub(crate) fn heap_size(&self) -> usize {
+        match self {
+            Adj::Solo => 0,
+            Adj::List { out, into } => out.heap_size() + into.heap_size(),
+        }
+    }
+
+    /// Number of entries in the adjacency sets
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Adj::Solo => 0,
+            Adj::List { out, into } => out.len() + into.len(),
+        }
+    }
+
     pub(crate) fn new_out(v: VID, e: EID) -> Self {
         Adj::List {
             out: AdjSet::new(v, e),
@@ -1,6 +1,7 @@
 //! A data structure for efficiently storing and querying the temporal adjacency set of a node in a temporal graph.
+use crate::core::storage::btree_heap_size;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, hash::Hash};
+use std::{collections::BTreeMap, hash::Hash, mem::size_of};
 
 const SMALL_SET: usize = 1024;
 
@@ -35,6 +36,17 @@ impl<K: Ord + Copy + Hash + Send + Sync, V: Into<usize> + Copy + Send + Sync> Ad
         }
     }
 
+    /// Estimated heap memory used by the set
+    pub(crate) fn heap_size(&self) -> usize {
+        match self {
+            AdjSet::Empty | AdjSet::One(_, _) => 0,
+            AdjSet::Small { vs, edges } => {
+                vs.capacity() * size_of::<K>() + edges.capacity() * size_of::<V>()
+            }
+            AdjSet::Large { vs } => btree_heap_size(vs.len(), size_of::<(K, V)>()),
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         match self {
             AdjSet::Empty => true,
@@ -3,10 +3,11 @@ use crate::core::{
         graph::tgraph::FxDashMap,
         properties::{
             props::{ArcReadLockedVec, DictMapper},
+            schema::PropertyDeclarations,
             tprop::TProp,
         },
     },
-    storage::{locked_view::LockedView, timeindex::TimeIndexEntry},
+    storage::{columnar::CompactionStats, locked_view::LockedView, timeindex::TimeIndexEntry},
     utils::errors::{GraphError, MutateGraphError},
     ArcStr, Prop, PropType,
 };
@@ -19,6 +20,8 @@ pub struct GraphMeta {
     temporal_mapper: DictMapper,
     constant: FxDashMap<usize, Option<Prop>>,
     temporal: FxDashMap<usize, TProp>,
+    #[serde(skip)]
+    declarations: PropertyDeclarations,
 }
 
 impl GraphMeta {
@@ -28,9 +31,41 @@ impl GraphMeta {
             temporal_mapper: DictMapper::default(),
             constant: FxDashMap::default(),
             temporal: FxDashMap::default(),
+            declarations: PropertyDeclarations::default(),
         }
     }
 
+    #[inline]
+    pub fn declarations(&self) -> &PropertyDeclarations {
+        &self.declarations
+    }
+
+    /// Convert property values to their declared type where they differ
+    pub(crate) fn coerce_props(
+        &self,
+        props: Vec<(usize, Prop)>,
+        is_static: bool,
+    ) -> Result<Vec<(usize, Prop)>, GraphError> {
+        if self.declarations.is_empty() {
+            return Ok(props);
+        }
+        let mapper = if is_static {
+            &self.constant_mapper
+        } else {
+            &self.temporal_mapper
+        };
+        props
+            .into_iter()
+            .map(|(id, prop)| {
+                let name = mapper.get_name(id);
+                match self.declarations.dtype(&name, is_static) {
+                    Some(dtype) => Ok((id, self.declarations.coerce(&name, prop, dtype)?)),
+                    None => Ok((id, prop)),
+                }
+            })
+            .collect()
+    }
+
     #[inline]
     pub fn const_prop_meta(&self) -> &DictMapper {
         &self.constant_mapper
@@ -93,6 +128,14 @@ impl GraphMeta {
         (*prop_entry).set(t, prop)
     }
 
+    pub(crate) fn compact(&self) -> CompactionStats {
+        let mut stats = CompactionStats::default();
+        for mut prop in self.temporal.iter_mut() {
+            stats += prop.compact();
+        }
+        stats
+    }
+
     pub(crate) fn get_constant(&self, id: usize) -> Option<Prop> {
         let entry = self.constant.get(&id)?;
         entry.as_ref().cloned()
@@ -1,4 +1,5 @@
 pub mod graph_meta;
 pub mod props;
+pub mod schema;
 pub mod tcell;
 pub mod tprop;
@@ -1,7 +1,14 @@
 use crate::{
     core::{
-        entities::{graph::tgraph::FxDashMap, properties::tprop::TProp},
+        entities::{
+            graph::tgraph::FxDashMap,
+            properties::{
+                schema::{PropertyDeclarations, PropertyDefinition},
+                tprop::TProp,
+            },
+        },
         storage::{
+            columnar::CompactionStats,
             lazy_vec::{IllegalSet, LazyVec},
             timeindex::TimeIndexEntry,
         },
@@ -101,6 +108,14 @@ impl Props {
     pub fn temporal_prop_ids(&self) -> impl Iterator<Item = usize> + '_ {
         self.temporal_props.filled_ids()
     }
+
+    pub(crate) fn compact(&mut self) -> CompactionStats {
+        let mut stats = CompactionStats::default();
+        for prop in self.temporal_props.values_mut() {
+            stats += prop.compact();
+        }
+        stats
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -109,6 +124,8 @@ pub struct Meta {
     meta_prop_constant: PropMapper,
     meta_layer: DictMapper,
     meta_node_type: DictMapper,
+    #[serde(skip)]
+    declarations: PropertyDeclarations,
 }
 
 impl Default for Meta {
@@ -134,6 +151,10 @@ impl Meta {
         &self.meta_node_type
     }
 
+    pub fn declarations(&self) -> &PropertyDeclarations {
+        &self.declarations
+    }
+
     pub fn new() -> Self {
         let meta_layer = DictMapper::default();
         meta_layer.get_or_create_id("_default");
@@ -144,6 +165,7 @@ impl Meta {
             meta_prop_constant: PropMapper::default(),
             meta_layer,     // layer 0 is the default layer
             meta_node_type, // type 0 is the default type for a node
+            declarations: PropertyDeclarations::default(),
         }
     }
 
@@ -154,6 +176,7 @@ impl Meta {
         dtype: PropType,
         is_static: bool,
     ) -> Result<usize, GraphError> {
+        let dtype = self.declarations.target_dtype(prop, dtype, is_static);
         if is_static {
             self.meta_prop_constant
                 .get_or_create_and_validate(prop, dtype)
@@ -163,6 +186,63 @@ impl Meta {
         }
     }
 
+    /// Declare a property, fixing its type
+    pub(crate) fn declare_prop(&self, definition: PropertyDefinition) -> Result<usize, GraphError> {
+        let id = if definition.constant {
+            self.meta_prop_constant
+                .get_or_create_and_validate(&definition.name, definition.dtype)?
+        } else {
+            self.meta_prop_temporal
+                .get_or_create_and_validate(&definition.name, definition.dtype)?
+        };
+        self.declarations.declare(definition)?;
+        Ok(id)
+    }
+
+    /// Convert property values to the type of the property where they differ
+    pub(crate) fn coerce_props(
+        &self,
+        props: Vec<(usize, Prop)>,
+        is_static: bool,
+    ) -> Result<Vec<(usize, Prop)>, GraphError> {
+        if self.declarations.is_empty() {
+            return Ok(props);
+        }
+        let mapper = if is_static {
+            &self.meta_prop_constant
+        } else {
+            &self.meta_prop_temporal
+        };
+        props
+            .into_iter()
+            .map(|(id, prop)| match mapper.get_dtype(id) {
+                Some(dtype) if dtype != prop.dtype() => {
+                    let name = mapper.get_name(id);
+                    Ok((id, self.declarations.coerce(&name, prop, dtype)?))
+                }
+                _ => Ok((id, prop)),
+            })
+            .collect()
+    }
+
+    /// Check that all required temporal properties declared for `scope` are part of `props`
+    pub(crate) fn check_required_props(
+        &self,
+        scope: impl FnOnce() -> Option<ArcStr>,
+        props: &[(usize, Prop)],
+        entity: impl FnOnce() -> String,
+    ) -> Result<(), GraphError> {
+        self.declarations.check_required(
+            scope,
+            |name| {
+                self.meta_prop_temporal
+                    .get_id(name)
+                    .is_some_and(|id| props.iter().any(|(prop_id, _)| *prop_id == id))
+            },
+            entity,
+        )
+    }
+
     #[inline]
     pub fn get_prop_id(&self, name: &str, is_static: bool) -> Option<usize> {
         if is_static {
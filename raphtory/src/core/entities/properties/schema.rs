@@ -0,0 +1,420 @@
+//! Declared property schemas
+//!
+//! By default the type of a property is fixed by the first value that is added for it. Declaring a
+//! property upfront fixes its type before any data arrives. Updates that do not conform to the
+//! declaration are rejected or, if the enforcement is set to [`SchemaEnforcement::Coerce`],
+//! converted to the declared type where possible (e.g. `I32` to `I64` or a date string to `DTime`).
+use crate::core::{
+    utils::{errors::GraphError, time::TryIntoTime},
+    ArcStr, Prop, PropType,
+};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use serde::{Deserialize, Serialize};
+
+/// How updates that do not match the declared property type are handled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaEnforcement {
+    /// Reject updates with a `GraphError::PropertyTypeError`
+    #[default]
+    Strict,
+    /// Convert values to the declared type if possible and reject them otherwise
+    Coerce,
+}
+
+/// Declaration of a single property
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PropertyDefinition {
+    pub name: ArcStr,
+    pub dtype: PropType,
+    /// Node type (for node properties) or layer (for edge properties) the declaration applies to,
+    /// `None` applies to all nodes or edges. The type of a property is the same for all scopes.
+    pub scope: Option<ArcStr>,
+    pub constant: bool,
+    /// Required temporal properties have to be present on every update of an entity in scope
+    pub required: bool,
+}
+
+impl PropertyDefinition {
+    pub fn new<S: Into<ArcStr>>(name: S, dtype: PropType) -> Self {
+        Self {
+            name: name.into(),
+            dtype,
+            scope: None,
+            constant: false,
+            required: false,
+        }
+    }
+
+    /// Restrict the declaration to a node type or layer
+    pub fn scoped<S: Into<ArcStr>>(mut self, scope: S) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Declare a constant instead of a temporal property
+    pub fn constant(mut self) -> Self {
+        self.constant = true;
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    fn applies_to(&self, scope: Option<&str>) -> bool {
+        match &self.scope {
+            None => true,
+            Some(s) => scope.is_some_and(|scope| s == scope),
+        }
+    }
+}
+
+/// Snapshot of all declarations of a graph
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeclaredSchema {
+    pub enforcement: SchemaEnforcement,
+    pub nodes: Vec<PropertyDefinition>,
+    pub edges: Vec<PropertyDefinition>,
+    pub graph: Vec<PropertyDefinition>,
+}
+
+/// Property declarations for one kind of entity (nodes, edges or the graph itself)
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PropertyDeclarations {
+    enforcement: RwLock<SchemaEnforcement>,
+    definitions: RwLock<Vec<PropertyDefinition>>,
+}
+
+impl PropertyDeclarations {
+    pub fn enforcement(&self) -> SchemaEnforcement {
+        *self.enforcement.read()
+    }
+
+    pub(crate) fn set_enforcement(&self, enforcement: SchemaEnforcement) {
+        *self.enforcement.write() = enforcement;
+    }
+
+    pub fn definitions(&self) -> Vec<PropertyDefinition> {
+        self.definitions.read().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.read().is_empty()
+    }
+
+    /// Add a declaration, replacing an existing declaration for the same property and scope
+    pub(crate) fn declare(&self, definition: PropertyDefinition) -> Result<(), GraphError> {
+        let mut definitions = self.definitions.write();
+        if let Some(existing) = definitions.iter().find(|d| {
+            d.name == definition.name
+                && d.constant == definition.constant
+                && d.dtype != definition.dtype
+        }) {
+            return Err(GraphError::PropertyTypeError {
+                name: definition.name.to_string(),
+                expected: existing.dtype,
+                actual: definition.dtype,
+            });
+        }
+        definitions.retain(|d| {
+            !(d.name == definition.name
+                && d.constant == definition.constant
+                && d.scope == definition.scope)
+        });
+        definitions.push(definition);
+        Ok(())
+    }
+
+    /// The declared type of a property, if any
+    pub fn dtype(&self, name: &str, is_static: bool) -> Option<PropType> {
+        self.definitions
+            .read()
+            .iter()
+            .find(|d| d.name == name && d.constant == is_static)
+            .map(|d| d.dtype)
+    }
+
+    /// The type a property with values of type `dtype` should be stored as
+    pub(crate) fn target_dtype(&self, name: &str, dtype: PropType, is_static: bool) -> PropType {
+        if self.enforcement() == SchemaEnforcement::Coerce {
+            if let Some(declared) = self.dtype(name, is_static) {
+                if can_coerce(dtype, declared) {
+                    return declared;
+                }
+            }
+        }
+        dtype
+    }
+
+    /// Convert `prop` to `dtype` if allowed by the enforcement
+    pub(crate) fn coerce(
+        &self,
+        name: &str,
+        prop: Prop,
+        dtype: PropType,
+    ) -> Result<Prop, GraphError> {
+        if prop.dtype() == dtype {
+            return Ok(prop);
+        }
+        match self.enforcement() {
+            SchemaEnforcement::Strict => Err(GraphError::PropertyTypeError {
+                name: name.to_owned(),
+                expected: dtype,
+                actual: prop.dtype(),
+            }),
+            SchemaEnforcement::Coerce => {
+                coerce_prop(&prop, dtype).ok_or_else(|| GraphError::PropertyCoercionError {
+                    name: name.to_owned(),
+                    value: prop,
+                    dtype,
+                })
+            }
+        }
+    }
+
+    /// Check that all required temporal properties for `scope` are present
+    pub(crate) fn check_required(
+        &self,
+        scope: impl FnOnce() -> Option<ArcStr>,
+        is_present: impl Fn(&str) -> bool,
+        entity: impl FnOnce() -> String,
+    ) -> Result<(), GraphError> {
+        let definitions = self.definitions.read();
+        if !definitions.iter().any(|d| d.required && !d.constant) {
+            return Ok(());
+        }
+        let scope = scope();
+        let scope: Option<&str> = scope.as_ref().map(|s| s.as_ref());
+        let missing = definitions
+            .iter()
+            .find(|d| d.required && !d.constant && d.applies_to(scope) && !is_present(&d.name));
+        match missing {
+            None => Ok(()),
+            Some(d) => Err(GraphError::MissingRequiredProperty {
+                name: d.name.to_string(),
+                entity: entity(),
+            }),
+        }
+    }
+}
+
+fn integer_value(prop: &Prop) -> Option<i128> {
+    match prop {
+        Prop::U8(v) => Some(*v as i128),
+        Prop::U16(v) => Some(*v as i128),
+        Prop::I8(v) => Some(*v as i128),
+        Prop::I16(v) => Some(*v as i128),
+        Prop::I32(v) => Some(*v as i128),
+        Prop::I64(v) => Some(*v as i128),
+        Prop::U32(v) => Some(*v as i128),
+        Prop::U64(v) => Some(*v as i128),
+        _ => None,
+    }
+}
+
+fn is_integer(dtype: PropType) -> bool {
+    matches!(
+        dtype,
+        PropType::U8
+            | PropType::U16
+            | PropType::I8
+            | PropType::I16
+            | PropType::I32
+            | PropType::I64
+            | PropType::U32
+            | PropType::U64
+    )
+}
+
+/// Whether values of type `from` can in principle be converted to `to` (individual values may
+/// still fail to convert, e.g. strings that do not parse or integers that are out of range)
+pub fn can_coerce(from: PropType, to: PropType) -> bool {
+    if from == to {
+        return true;
+    }
+    match to {
+        PropType::U8
+        | PropType::U16
+        | PropType::I8
+        | PropType::I16
+        | PropType::I32
+        | PropType::I64
+        | PropType::U32
+        | PropType::U64 => is_integer(from) || from == PropType::Str,
+        PropType::F32 | PropType::F64 | PropType::Decimal => {
+            from.is_numeric() || from == PropType::Str
+        }
+        PropType::Bool => from == PropType::Str,
+        PropType::Str => from.is_numeric() || from.is_bool() || from.is_date(),
+        PropType::DTime | PropType::NDTime => {
+            from.is_date() || from == PropType::Str || from == PropType::I64
+        }
+        _ => false,
+    }
+}
+
+/// Convert a property value to a different type, returns `None` if the value cannot be
+/// represented as `dtype`
+pub fn coerce_prop(prop: &Prop, dtype: PropType) -> Option<Prop> {
+    if prop.dtype() == dtype {
+        return Some(prop.clone());
+    }
+    if let Prop::Str(s) = prop {
+        let s = s.trim();
+        return match dtype {
+            PropType::U8 => s.parse().ok().map(Prop::U8),
+            PropType::U16 => s.parse().ok().map(Prop::U16),
+            PropType::I8 => s.parse().ok().map(Prop::I8),
+            PropType::I16 => s.parse().ok().map(Prop::I16),
+            PropType::I32 => s.parse().ok().map(Prop::I32),
+            PropType::I64 => s.parse().ok().map(Prop::I64),
+            PropType::U32 => s.parse().ok().map(Prop::U32),
+            PropType::U64 => s.parse().ok().map(Prop::U64),
+            PropType::F32 => s.parse().ok().map(Prop::F32),
+            PropType::F64 => s.parse().ok().map(Prop::F64),
+            PropType::Bool => s.parse().ok().map(Prop::Bool),
+            PropType::Decimal => s.parse().ok().map(Prop::Decimal),
+            PropType::DTime => s
+                .try_into_time()
+                .ok()
+                .and_then(DateTime::<Utc>::from_timestamp_millis)
+                .map(Prop::DTime),
+            PropType::NDTime => s
+                .try_into_time()
+                .ok()
+                .and_then(DateTime::<Utc>::from_timestamp_millis)
+                .map(|dt| Prop::NDTime(dt.naive_utc())),
+            _ => None,
+        };
+    }
+    if let Some(v) = integer_value(prop) {
+        let converted = match dtype {
+            PropType::U8 => u8::try_from(v).ok().map(Prop::U8),
+            PropType::U16 => u16::try_from(v).ok().map(Prop::U16),
+            PropType::I8 => i8::try_from(v).ok().map(Prop::I8),
+            PropType::I16 => i16::try_from(v).ok().map(Prop::I16),
+            PropType::I32 => i32::try_from(v).ok().map(Prop::I32),
+            PropType::I64 => i64::try_from(v).ok().map(Prop::I64),
+            PropType::U32 => u32::try_from(v).ok().map(Prop::U32),
+            PropType::U64 => u64::try_from(v).ok().map(Prop::U64),
+            PropType::F32 => Some(Prop::F32(v as f32)),
+            PropType::F64 => Some(Prop::F64(v as f64)),
+            PropType::Decimal => Decimal::try_from_i128_with_scale(v, 0)
+                .ok()
+                .map(Prop::Decimal),
+            PropType::Str => Some(Prop::str(v.to_string())),
+            PropType::DTime => i64::try_from(v)
+                .ok()
+                .and_then(DateTime::<Utc>::from_timestamp_millis)
+                .map(Prop::DTime),
+            PropType::NDTime => i64::try_from(v)
+                .ok()
+                .and_then(DateTime::<Utc>::from_timestamp_millis)
+                .map(|dt| Prop::NDTime(dt.naive_utc())),
+            _ => None,
+        };
+        return converted;
+    }
+    match (prop, dtype) {
+        (Prop::F32(v), PropType::F64) => Some(Prop::F64(*v as f64)),
+        (Prop::F64(v), PropType::F32) => Some(Prop::F32(*v as f32)),
+        (Prop::F32(v), PropType::Decimal) => Decimal::try_from(*v).ok().map(Prop::Decimal),
+        (Prop::F64(v), PropType::Decimal) => Decimal::try_from(*v).ok().map(Prop::Decimal),
+        (Prop::Decimal(v), PropType::F32) => v.to_f32().map(Prop::F32),
+        (Prop::Decimal(v), PropType::F64) => v.to_f64().map(Prop::F64),
+        (Prop::Decimal(v), PropType::Str) => Some(Prop::str(v.to_string())),
+        (Prop::F32(v), PropType::Str) => Some(Prop::str(v.to_string())),
+        (Prop::F64(v), PropType::Str) => Some(Prop::str(v.to_string())),
+        (Prop::Bool(v), PropType::Str) => Some(Prop::str(v.to_string())),
+        (Prop::DTime(v), PropType::Str) => Some(Prop::str(v.to_rfc3339())),
+        (Prop::NDTime(v), PropType::Str) => Some(Prop::str(v.to_string())),
+        (Prop::DTime(v), PropType::NDTime) => Some(Prop::NDTime(v.naive_utc())),
+        (Prop::NDTime(v), PropType::DTime) => Some(Prop::DTime(v.and_utc())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_coerce_integers() {
+        assert_eq!(
+            coerce_prop(&Prop::I32(5), PropType::I64),
+            Some(Prop::I64(5))
+        );
+        assert_eq!(coerce_prop(&Prop::I64(-1), PropType::U64), None);
+        assert_eq!(coerce_prop(&Prop::U64(300), PropType::U8), None);
+        assert_eq!(
+            coerce_prop(&Prop::U8(3), PropType::F64),
+            Some(Prop::F64(3.0))
+        );
+        assert_eq!(
+            coerce_prop(&Prop::I64(-5), PropType::I8),
+            Some(Prop::I8(-5))
+        );
+        assert_eq!(coerce_prop(&Prop::I32(70000), PropType::I16), None);
+        assert_eq!(
+            coerce_prop(&Prop::I64(12), PropType::Decimal),
+            Some(Prop::Decimal(Decimal::new(12, 0)))
+        );
+    }
+
+    #[test]
+    fn test_coerce_strings() {
+        assert_eq!(
+            coerce_prop(&Prop::str("42"), PropType::I64),
+            Some(Prop::I64(42))
+        );
+        assert_eq!(coerce_prop(&Prop::str("abc"), PropType::I64), None);
+        assert_eq!(
+            coerce_prop(&Prop::str("19.99"), PropType::Decimal),
+            Some(Prop::Decimal(Decimal::new(1999, 2)))
+        );
+        assert_eq!(
+            coerce_prop(&Prop::str("true"), PropType::Bool),
+            Some(Prop::Bool(true))
+        );
+        let expected = NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+        assert_eq!(
+            coerce_prop(&Prop::str("2024-01-02T03:04:05Z"), PropType::DTime),
+            Some(Prop::DTime(expected.and_utc()))
+        );
+        assert_eq!(
+            coerce_prop(&Prop::str("2024-01-02"), PropType::NDTime),
+            Some(Prop::NDTime(expected.date().and_hms_opt(0, 0, 0).unwrap()))
+        );
+        assert_eq!(
+            coerce_prop(&Prop::I64(3), PropType::Str),
+            Some(Prop::str("3"))
+        );
+    }
+
+    #[test]
+    fn test_conflicting_declarations() {
+        let declarations = PropertyDeclarations::default();
+        declarations
+            .declare(PropertyDefinition::new("a", PropType::I64).scoped("x"))
+            .unwrap();
+        assert!(declarations
+            .declare(PropertyDefinition::new("a", PropType::F64).scoped("y"))
+            .is_err());
+        declarations
+            .declare(
+                PropertyDefinition::new("a", PropType::I64)
+                    .scoped("x")
+                    .required(),
+            )
+            .unwrap();
+        assert_eq!(declarations.definitions().len(), 1);
+        assert!(declarations.definitions()[0].required);
+    }
+}
@@ -1,11 +1,14 @@
 use crate::core::storage::{
+    btree_heap_size,
+    columnar::ColumnBlock,
     sorted_vec_map::SVM,
     timeindex::{AsTime, TimeIndexEntry},
 };
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt::Debug, ops::Range};
+use std::{collections::BTreeMap, fmt::Debug, mem::size_of, ops::Range};
 
-#[derive(Debug, PartialEq, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 // TCells represent a value in time that can be set at multiple times and keeps a history
 pub enum TCell<A: Clone + Debug + PartialEq> {
     #[default]
@@ -13,10 +16,25 @@ pub enum TCell<A: Clone + Debug + PartialEq> {
     TCell1(TimeIndexEntry, A),
     TCellCap(SVM<TimeIndexEntry, A>),
     TCellN(BTreeMap<TimeIndexEntry, A>),
+    TCellColumnar(Box<ColumnarTCell<A>>),
+}
+
+/// Compacted history: an immutable columnar block plus a tail that takes new updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnarTCell<A: Clone + Debug + PartialEq> {
+    block: ColumnBlock<A>,
+    tail: TCell<A>,
 }
 
 const BTREE_CUTOFF: usize = 128;
 
+impl<A: Clone + Debug + PartialEq + Send + Sync> PartialEq for TCell<A> {
+    fn eq(&self, other: &Self) -> bool {
+        // compare the history so compacted and uncompacted cells are equal
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
 impl<A: Clone + Debug + PartialEq + Send + Sync> TCell<A> {
     pub fn new(t: TimeIndexEntry, value: A) -> Self {
         TCell::TCell1(t, value)
@@ -53,6 +71,17 @@ impl<A: Clone + Debug + PartialEq + Send + Sync> TCell<A> {
             TCell::TCellN(btm) => {
                 btm.insert(t, value);
             }
+            TCell::TCellColumnar(cell) if cell.block.get(&t).is_none() => {
+                cell.tail.set(t, value);
+            }
+            TCell::TCellColumnar(_) => {
+                // the compacted block is immutable, overwriting one of its updates decompacts
+                // the history
+                let mut btm: BTreeMap<TimeIndexEntry, A> =
+                    self.iter().map(|(t, v)| (t, v.clone())).collect();
+                btm.insert(t, value);
+                *self = TCell::TCellN(btm);
+            }
         }
     }
 
@@ -62,15 +91,21 @@ impl<A: Clone + Debug + PartialEq + Send + Sync> TCell<A> {
             TCell::TCell1(t, v) => (t == ti).then_some(v),
             TCell::TCellCap(svm) => svm.get(ti),
             TCell::TCellN(btm) => btm.get(ti),
+            TCell::TCellColumnar(cell) => cell.block.get(ti).or_else(|| cell.tail.at(ti)),
         }
     }
 
-    pub fn iter(&self) -> Box<dyn Iterator<Item = (&TimeIndexEntry, &A)> + Send + '_> {
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (TimeIndexEntry, &A)> + Send + '_> {
         match self {
             TCell::Empty => Box::new(std::iter::empty()),
-            TCell::TCell1(t, value) => Box::new(std::iter::once((t, value))),
-            TCell::TCellCap(svm) => Box::new(svm.iter()),
-            TCell::TCellN(btm) => Box::new(btm.iter()),
+            TCell::TCell1(t, value) => Box::new(std::iter::once((*t, value))),
+            TCell::TCellCap(svm) => Box::new(svm.iter().map(|(t, v)| (*t, v))),
+            TCell::TCellN(btm) => Box::new(btm.iter().map(|(t, v)| (*t, v))),
+            TCell::TCellColumnar(cell) => Box::new(
+                cell.block
+                    .iter()
+                    .merge_by(cell.tail.iter(), |(t1, _), (t2, _)| t1 <= t2),
+            ),
         }
     }
 
@@ -80,24 +115,30 @@ impl<A: Clone + Debug + PartialEq + Send + Sync> TCell<A> {
             TCell::TCell1(t, value) => Box::new(std::iter::once((t.t(), value))),
             TCell::TCellCap(svm) => Box::new(svm.iter().map(|(ti, v)| (ti.t(), v))),
             TCell::TCellN(btm) => Box::new(btm.iter().map(|(ti, v)| (ti.t(), v))),
+            TCell::TCellColumnar(_) => Box::new(self.iter().map(|(ti, v)| (ti.t(), v))),
         }
     }
 
     pub fn iter_window(
         &self,
         r: Range<TimeIndexEntry>,
-    ) -> Box<dyn Iterator<Item = (&TimeIndexEntry, &A)> + Send + '_> {
+    ) -> Box<dyn Iterator<Item = (TimeIndexEntry, &A)> + Send + '_> {
         match self {
             TCell::Empty => Box::new(std::iter::empty()),
             TCell::TCell1(t, value) => {
                 if r.contains(t) {
-                    Box::new(std::iter::once((t, value)))
+                    Box::new(std::iter::once((*t, value)))
                 } else {
                     Box::new(std::iter::empty())
                 }
             }
-            TCell::TCellCap(svm) => Box::new(svm.range(r)),
-            TCell::TCellN(btm) => Box::new(btm.range(r)),
+            TCell::TCellCap(svm) => Box::new(svm.range(r).map(|(t, v)| (*t, v))),
+            TCell::TCellN(btm) => Box::new(btm.range(r).map(|(t, v)| (*t, v))),
+            TCell::TCellColumnar(cell) => Box::new(
+                cell.block
+                    .range(r.clone())
+                    .merge_by(cell.tail.iter_window(r), |(t1, _), (t2, _)| t1 <= t2),
+            ),
         }
     }
 
@@ -119,6 +160,10 @@ impl<A: Clone + Debug + PartialEq + Send + Sync> TCell<A> {
                 btm.range(TimeIndexEntry::range(r))
                     .map(|(ti, v)| (ti.t(), v)),
             ),
+            TCell::TCellColumnar(_) => Box::new(
+                self.iter_window(TimeIndexEntry::range(r))
+                    .map(|(ti, v)| (ti.t(), v)),
+            ),
         }
     }
 
@@ -134,6 +179,12 @@ impl<A: Clone + Debug + PartialEq + Send + Sync> TCell<A> {
                 .range(TimeIndexEntry::range(i64::MIN..t))
                 .last()
                 .map(|(ti, v)| (*ti, v)),
+            TCell::TCellColumnar(cell) => {
+                match (cell.block.last_before(t), cell.tail.last_before(t)) {
+                    (Some(b), Some(t)) => Some(if b.0 > t.0 { b } else { t }),
+                    (b, t) => b.or(t),
+                }
+            }
         }
     }
 
@@ -143,12 +194,53 @@ impl<A: Clone + Debug + PartialEq + Send + Sync> TCell<A> {
             TCell::TCell1(_, _) => 1,
             TCell::TCellCap(v) => v.len(),
             TCell::TCellN(v) => v.len(),
+            TCell::TCellColumnar(cell) => cell.block.len() + cell.tail.len(),
         }
     }
 
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Estimated heap memory used to store the history, not counting memory owned by the values
+    pub fn heap_size(&self) -> usize {
+        let entry_size = size_of::<(TimeIndexEntry, A)>();
+        match self {
+            TCell::Empty | TCell::TCell1(_, _) => 0,
+            TCell::TCellCap(svm) => svm.len() * entry_size,
+            TCell::TCellN(btm) => btree_heap_size(btm.len(), entry_size),
+            TCell::TCellColumnar(cell) => {
+                size_of::<ColumnarTCell<A>>() + cell.block.heap_size() + cell.tail.heap_size()
+            }
+        }
+    }
+
+    /// Convert the history into an immutable columnar block if that reduces memory usage.
+    ///
+    /// New updates are still accepted after compaction and are kept separately until the
+    /// next compaction. Returns the number of updates moved into the block.
+    pub fn compact(&mut self) -> usize {
+        let pending = match self {
+            TCell::Empty | TCell::TCell1(_, _) => 0,
+            TCell::TCellColumnar(cell) => cell.tail.len(),
+            _ => self.len(),
+        };
+        if pending == 0 {
+            return 0;
+        }
+        let block = ColumnBlock::from_sorted(self.iter().map(|(t, v)| (t, v.clone())));
+        let compacted = TCell::TCellColumnar(Box::new(ColumnarTCell {
+            block,
+            tail: TCell::Empty,
+        }));
+        // merging new updates into an existing block is always worthwhile
+        if matches!(self, TCell::TCellColumnar(_)) || compacted.heap_size() < self.heap_size() {
+            *self = compacted;
+            pending
+        } else {
+            0
+        }
+    }
 }
 
 #[cfg(test)]
@@ -200,7 +292,7 @@ mod tcell_tests {
 
         assert_eq!(
             tcell.iter().collect::<Vec<_>>(),
-            vec![(&TimeIndexEntry::start(3), &"Pometry")]
+            vec![(TimeIndexEntry::start(3), &"Pometry")]
         );
 
         assert_eq!(tcell.iter_t().collect::<Vec<_>>(), vec![(3, &"Pometry")]);
@@ -218,8 +310,8 @@ mod tcell_tests {
             // Results are ordered by time
             tcell.iter().collect::<Vec<_>>(),
             vec![
-                (&TimeIndexEntry::start(1), &"Inc. Pometry"),
-                (&TimeIndexEntry::start(2), &"Pometry")
+                (TimeIndexEntry::start(1), &"Inc. Pometry"),
+                (TimeIndexEntry::start(2), &"Pometry")
             ]
         );
 
@@ -254,7 +346,7 @@ mod tcell_tests {
             tcell
                 .iter_window(TimeIndexEntry::range(3..4))
                 .collect::<Vec<_>>(),
-            vec![(&TimeIndexEntry(3, 0), &"Pometry")]
+            vec![(TimeIndexEntry(3, 0), &"Pometry")]
         );
 
         assert_eq!(
@@ -288,9 +380,9 @@ mod tcell_tests {
                 .iter_window(TimeIndexEntry::range(1..i64::MAX))
                 .collect::<Vec<_>>(),
             vec![
-                (&one, &"Pometry Inc."),
-                (&two, &"Raphtory"),
-                (&three, &"Pometry")
+                (one, &"Pometry Inc."),
+                (two, &"Raphtory"),
+                (three, &"Pometry")
             ]
         );
 
@@ -298,14 +390,14 @@ mod tcell_tests {
             tcell
                 .iter_window(TimeIndexEntry::range(3..i64::MAX))
                 .collect::<Vec<_>>(),
-            vec![(&three, &"Pometry")]
+            vec![(three, &"Pometry")]
         );
 
         assert_eq!(
             tcell
                 .iter_window(TimeIndexEntry::range(2..i64::MAX))
                 .collect::<Vec<_>>(),
-            vec![(&two, &"Raphtory"), (&three, &"Pometry")]
+            vec![(two, &"Raphtory"), (three, &"Pometry")]
         );
 
         let expected = vec![];
@@ -321,9 +413,9 @@ mod tcell_tests {
                 .iter_window(TimeIndexEntry::range(i64::MIN..4))
                 .collect::<Vec<_>>(),
             vec![
-                (&one, &"Pometry Inc."),
-                (&two, &"Raphtory"),
-                (&three, &"Pometry")
+                (one, &"Pometry Inc."),
+                (two, &"Raphtory"),
+                (three, &"Pometry")
             ]
         );
 
@@ -349,4 +441,76 @@ mod tcell_tests {
             129
         )
     }
+
+    #[test]
+    fn compacted_tcell_keeps_history_and_accepts_updates() {
+        let mut tcell: TCell<i64> = TCell::default();
+        for n in (0..300).rev() {
+            tcell.set(TimeIndexEntry::start(n), n / 10)
+        }
+        let expected = tcell.clone();
+        let size_before = tcell.heap_size();
+
+        assert_eq!(tcell.compact(), 300);
+        assert!(matches!(tcell, TCell::TCellColumnar(_)));
+        assert!(tcell.heap_size() < size_before);
+        assert_eq!(tcell, expected);
+        assert_eq!(tcell.compact(), 0);
+
+        tcell.set(TimeIndexEntry::start(1000), 1);
+        tcell.set(TimeIndexEntry::start(-1), 2);
+        assert_eq!(tcell.len(), 302);
+        assert_eq!(tcell.at(&TimeIndexEntry::start(5)), Some(&0));
+        assert_eq!(tcell.at(&TimeIndexEntry::start(-1)), Some(&2));
+        assert_eq!(tcell.last_before(0), Some((TimeIndexEntry::start(-1), &2)));
+        assert_eq!(
+            tcell.last_before(2000),
+            Some((TimeIndexEntry::start(1000), &1))
+        );
+        assert_eq!(
+            tcell.iter_window_t(298..i64::MAX).collect::<Vec<_>>(),
+            vec![(298, &29), (299, &29), (1000, &1)]
+        );
+        assert_eq!(
+            tcell.iter_t().take(2).collect::<Vec<_>>(),
+            vec![(-1, &2), (0, &0)]
+        );
+
+        assert_eq!(tcell.compact(), 2);
+        assert_eq!(tcell.len(), 302);
+        assert_eq!(
+            tcell.iter_window_t(298..i64::MAX).collect::<Vec<_>>(),
+            vec![(298, &29), (299, &29), (1000, &1)]
+        );
+    }
+
+    #[test]
+    fn overwriting_a_compacted_update_decompacts() {
+        let mut tcell: TCell<i64> = TCell::default();
+        for n in 0..300 {
+            tcell.set(TimeIndexEntry::start(n), n / 10)
+        }
+        tcell.compact();
+        tcell.set(TimeIndexEntry::start(1000), 1);
+
+        tcell.set(TimeIndexEntry::start(5), 42);
+        assert!(matches!(tcell, TCell::TCellN(_)));
+        assert_eq!(tcell.len(), 301);
+        assert_eq!(tcell.at(&TimeIndexEntry::start(5)), Some(&42));
+        assert_eq!(tcell.at(&TimeIndexEntry::start(1000)), Some(&1));
+        assert_eq!(
+            tcell.iter_window_t(4..7).collect::<Vec<_>>(),
+            vec![(4, &0), (5, &42), (6, &0)]
+        );
+        assert_eq!(tcell.compact(), 301);
+        assert_eq!(tcell.at(&TimeIndexEntry::start(5)), Some(&42));
+    }
+
+    #[test]
+    fn small_tcells_are_not_compacted() {
+        let mut tcell = TCell::new(TimeIndexEntry::start(1), "Pometry");
+        tcell.set(TimeIndexEntry::start(2), "Pometry Inc.");
+        assert_eq!(tcell.compact(), 0);
+        assert!(matches!(tcell, TCell::TCellCap(_)));
+    }
 }
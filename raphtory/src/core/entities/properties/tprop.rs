@@ -1,7 +1,12 @@
 use crate::{
     core::{
-        entities::properties::tcell::TCell, storage::timeindex::TimeIndexEntry,
-        utils::errors::GraphError, ArcStr, DocumentInput, Prop, PropType,
+        entities::{
+            graph::memory::{arc_str_heap_size, prop_heap_size},
+            properties::tcell::TCell,
+        },
+        storage::{columnar::CompactionStats, timeindex::TimeIndexEntry},
+        utils::errors::GraphError,
+        ArcStr, DocumentInput, Prop, PropArray, PropType,
     },
     db::{
         api::storage::tprop_storage_ops::TPropOps,
@@ -9,6 +14,7 @@ use crate::{
     },
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, iter, ops::Range, sync::Arc};
 
@@ -35,6 +41,11 @@ pub enum TProp {
     Document(TCell<DocumentInput>),
     List(TCell<Arc<Vec<Prop>>>),
     Map(TCell<Arc<HashMap<ArcStr, Prop>>>),
+    I8(TCell<i8>),
+    I16(TCell<i16>),
+    Decimal(TCell<Decimal>),
+    Bytes(TCell<Arc<[u8]>>),
+    Array(TCell<PropArray>),
 }
 
 impl TProp {
@@ -58,6 +69,105 @@ impl TProp {
             TProp::List(_) => PropType::List,
             TProp::Map(_) => PropType::Map,
             TProp::DTime(_) => PropType::DTime,
+            TProp::I8(_) => PropType::I8,
+            TProp::I16(_) => PropType::I16,
+            TProp::Decimal(_) => PropType::Decimal,
+            TProp::Bytes(_) => PropType::Bytes,
+            TProp::Array(cell) => cell
+                .iter()
+                .next()
+                .map(|(_, a)| PropType::Array(a.dtype()))
+                .unwrap_or(PropType::Empty),
+        }
+    }
+
+    /// Estimated heap memory used by the history, see `TCell::heap_size`
+    pub(crate) fn heap_size(&self) -> usize {
+        match self {
+            TProp::Empty => 0,
+            TProp::Str(cell) => cell.heap_size(),
+            TProp::U8(cell) => cell.heap_size(),
+            TProp::U16(cell) => cell.heap_size(),
+            TProp::I32(cell) => cell.heap_size(),
+            TProp::I64(cell) => cell.heap_size(),
+            TProp::U32(cell) => cell.heap_size(),
+            TProp::U64(cell) => cell.heap_size(),
+            TProp::F32(cell) => cell.heap_size(),
+            TProp::F64(cell) => cell.heap_size(),
+            TProp::Bool(cell) => cell.heap_size(),
+            TProp::DTime(cell) => cell.heap_size(),
+            TProp::NDTime(cell) => cell.heap_size(),
+            TProp::Graph(cell) => cell.heap_size(),
+            TProp::PersistentGraph(cell) => cell.heap_size(),
+            TProp::Document(cell) => cell.heap_size(),
+            TProp::List(cell) => cell.heap_size(),
+            TProp::Map(cell) => cell.heap_size(),
+            TProp::I8(cell) => cell.heap_size(),
+            TProp::I16(cell) => cell.heap_size(),
+            TProp::Decimal(cell) => cell.heap_size(),
+            TProp::Bytes(cell) => cell.heap_size(),
+            TProp::Array(cell) => cell.heap_size(),
+        }
+    }
+
+    /// Estimated heap memory owned by the values in the history (e.g. string contents)
+    pub(crate) fn values_heap_size(&self) -> usize {
+        match self {
+            TProp::Str(cell) => cell.iter().map(|(_, v)| arc_str_heap_size(v)).sum(),
+            TProp::Document(cell) => cell.iter().map(|(_, v)| v.content.capacity()).sum(),
+            TProp::List(cell) => cell
+                .iter()
+                .map(|(_, v)| prop_heap_size(&Prop::List(v.clone())))
+                .sum(),
+            TProp::Map(cell) => cell
+                .iter()
+                .map(|(_, v)| prop_heap_size(&Prop::Map(v.clone())))
+                .sum(),
+            TProp::Bytes(cell) => cell
+                .iter()
+                .map(|(_, v)| prop_heap_size(&Prop::Bytes(v.clone())))
+                .sum(),
+            TProp::Array(cell) => cell
+                .iter()
+                .map(|(_, v)| prop_heap_size(&Prop::Array(v.clone())))
+                .sum(),
+            _ => 0,
+        }
+    }
+
+    /// Move the history into columnar storage where that saves memory, see `TCell::compact`
+    pub(crate) fn compact(&mut self) -> CompactionStats {
+        let bytes_before = self.heap_size();
+        let compacted_updates = match self {
+            TProp::Empty => 0,
+            TProp::Str(cell) => cell.compact(),
+            TProp::U8(cell) => cell.compact(),
+            TProp::U16(cell) => cell.compact(),
+            TProp::I32(cell) => cell.compact(),
+            TProp::I64(cell) => cell.compact(),
+            TProp::U32(cell) => cell.compact(),
+            TProp::U64(cell) => cell.compact(),
+            TProp::F32(cell) => cell.compact(),
+            TProp::F64(cell) => cell.compact(),
+            TProp::Bool(cell) => cell.compact(),
+            TProp::DTime(cell) => cell.compact(),
+            TProp::NDTime(cell) => cell.compact(),
+            TProp::Graph(cell) => cell.compact(),
+            TProp::PersistentGraph(cell) => cell.compact(),
+            TProp::Document(cell) => cell.compact(),
+            TProp::List(cell) => cell.compact(),
+            TProp::Map(cell) => cell.compact(),
+            TProp::I8(cell) => cell.compact(),
+            TProp::I16(cell) => cell.compact(),
+            TProp::Decimal(cell) => cell.compact(),
+            TProp::Bytes(cell) => cell.compact(),
+            TProp::Array(cell) => cell.compact(),
+        };
+        CompactionStats {
+            compacted_histories: usize::from(compacted_updates > 0),
+            compacted_updates,
+            bytes_before,
+            bytes_after: self.heap_size(),
         }
     }
 
@@ -80,6 +190,11 @@ impl TProp {
             Prop::Document(value) => TProp::Document(TCell::new(t, value)),
             Prop::List(value) => TProp::List(TCell::new(t, value)),
             Prop::Map(value) => TProp::Map(TCell::new(t, value)),
+            Prop::I8(value) => TProp::I8(TCell::new(t, value)),
+            Prop::I16(value) => TProp::I16(TCell::new(t, value)),
+            Prop::Decimal(value) => TProp::Decimal(TCell::new(t, value)),
+            Prop::Bytes(value) => TProp::Bytes(TCell::new(t, value)),
+            Prop::Array(value) => TProp::Array(TCell::new(t, value)),
         }
     }
 
@@ -141,6 +256,26 @@ impl TProp {
                 (TProp::Map(cell), Prop::Map(a)) => {
                     cell.set(t, a);
                 }
+                (TProp::I8(cell), Prop::I8(a)) => {
+                    cell.set(t, a);
+                }
+                (TProp::I16(cell), Prop::I16(a)) => {
+                    cell.set(t, a);
+                }
+                (TProp::Decimal(cell), Prop::Decimal(a)) => {
+                    cell.set(t, a);
+                }
+                (TProp::Bytes(cell), Prop::Bytes(a)) => {
+                    cell.set(t, a);
+                }
+                (TProp::Array(cell), Prop::Array(a))
+                    if cell
+                        .iter()
+                        .next()
+                        .map_or(true, |(_, v)| v.dtype() == a.dtype()) =>
+                {
+                    cell.set(t, a);
+                }
                 _ => return Err(GraphError::IncorrectPropertyType),
             };
         }
@@ -153,40 +288,52 @@ impl TProp {
         match self {
             TProp::Empty => Box::new(iter::empty()),
             TProp::Str(cell) => {
-                Box::new(cell.iter().map(|(t, value)| (*t, Prop::Str(value.clone()))))
+                Box::new(cell.iter().map(|(t, value)| (t, Prop::Str(value.clone()))))
             }
-            TProp::I32(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::I32(*value)))),
-            TProp::I64(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::I64(*value)))),
-            TProp::U8(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::U8(*value)))),
-            TProp::U16(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::U16(*value)))),
-            TProp::U32(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::U32(*value)))),
-            TProp::U64(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::U64(*value)))),
-            TProp::F32(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::F32(*value)))),
-            TProp::F64(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::F64(*value)))),
-            TProp::Bool(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::Bool(*value)))),
-            TProp::DTime(cell) => Box::new(cell.iter().map(|(t, value)| (*t, Prop::DTime(*value)))),
+            TProp::I32(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::I32(*value)))),
+            TProp::I64(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::I64(*value)))),
+            TProp::U8(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::U8(*value)))),
+            TProp::U16(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::U16(*value)))),
+            TProp::U32(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::U32(*value)))),
+            TProp::U64(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::U64(*value)))),
+            TProp::F32(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::F32(*value)))),
+            TProp::F64(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::F64(*value)))),
+            TProp::Bool(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::Bool(*value)))),
+            TProp::DTime(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::DTime(*value)))),
             TProp::NDTime(cell) => {
-                Box::new(cell.iter().map(|(t, value)| (*t, Prop::NDTime(*value))))
+                Box::new(cell.iter().map(|(t, value)| (t, Prop::NDTime(*value))))
             }
             TProp::Graph(cell) => Box::new(
                 cell.iter()
-                    .map(|(t, value)| (*t, Prop::Graph(value.clone()))),
+                    .map(|(t, value)| (t, Prop::Graph(value.clone()))),
             ),
             TProp::PersistentGraph(cell) => Box::new(
                 cell.iter()
-                    .map(|(t, value)| (*t, Prop::PersistentGraph(value.clone()))),
+                    .map(|(t, value)| (t, Prop::PersistentGraph(value.clone()))),
             ),
             TProp::Document(cell) => Box::new(
                 cell.iter()
-                    .map(|(t, value)| (*t, Prop::Document(value.clone()))),
-            ),
-            TProp::List(cell) => Box::new(
-                cell.iter()
-                    .map(|(t, value)| (*t, Prop::List(value.clone()))),
+                    .map(|(t, value)| (t, Prop::Document(value.clone()))),
             ),
+            TProp::List(cell) => {
+                Box::new(cell.iter().map(|(t, value)| (t, Prop::List(value.clone()))))
+            }
             TProp::Map(cell) => {
-                Box::new(cell.iter().map(|(t, value)| (*t, Prop::Map(value.clone()))))
+                Box::new(cell.iter().map(|(t, value)| (t, Prop::Map(value.clone()))))
+            }
+            TProp::I8(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::I8(*value)))),
+            TProp::I16(cell) => Box::new(cell.iter().map(|(t, value)| (t, Prop::I16(*value)))),
+            TProp::Decimal(cell) => {
+                Box::new(cell.iter().map(|(t, value)| (t, Prop::Decimal(*value))))
             }
+            TProp::Bytes(cell) => Box::new(
+                cell.iter()
+                    .map(|(t, value)| (t, Prop::Bytes(value.clone()))),
+            ),
+            TProp::Array(cell) => Box::new(
+                cell.iter()
+                    .map(|(t, value)| (t, Prop::Array(value.clone()))),
+            ),
         }
     }
 
@@ -232,6 +379,19 @@ impl TProp {
                 cell.iter_t()
                     .map(|(t, value)| (t, Prop::Map(value.clone()))),
             ),
+            TProp::I8(cell) => Box::new(cell.iter_t().map(|(t, value)| (t, Prop::I8(*value)))),
+            TProp::I16(cell) => Box::new(cell.iter_t().map(|(t, value)| (t, Prop::I16(*value)))),
+            TProp::Decimal(cell) => {
+                Box::new(cell.iter_t().map(|(t, value)| (t, Prop::Decimal(*value))))
+            }
+            TProp::Bytes(cell) => Box::new(
+                cell.iter_t()
+                    .map(|(t, value)| (t, Prop::Bytes(value.clone()))),
+            ),
+            TProp::Array(cell) => Box::new(
+                cell.iter_t()
+                    .map(|(t, value)| (t, Prop::Array(value.clone()))),
+            ),
         }
     }
 
@@ -243,70 +403,81 @@ impl TProp {
             TProp::Empty => Box::new(iter::empty()),
             TProp::Str(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::Str(value.clone()))),
-            ),
-            TProp::I32(cell) => Box::new(
-                cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::I32(*value))),
-            ),
-            TProp::I64(cell) => Box::new(
-                cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::I64(*value))),
+                    .map(|(t, value)| (t, Prop::Str(value.clone()))),
             ),
+            TProp::I32(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::I32(*value))))
+            }
+            TProp::I64(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::I64(*value))))
+            }
             TProp::U8(cell) => {
-                Box::new(cell.iter_window(r).map(|(t, value)| (*t, Prop::U8(*value))))
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::U8(*value))))
+            }
+            TProp::U16(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::U16(*value))))
+            }
+            TProp::U32(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::U32(*value))))
+            }
+            TProp::U64(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::U64(*value))))
+            }
+            TProp::F32(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::F32(*value))))
+            }
+            TProp::F64(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::F64(*value))))
             }
-            TProp::U16(cell) => Box::new(
-                cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::U16(*value))),
-            ),
-            TProp::U32(cell) => Box::new(
-                cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::U32(*value))),
-            ),
-            TProp::U64(cell) => Box::new(
-                cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::U64(*value))),
-            ),
-            TProp::F32(cell) => Box::new(
-                cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::F32(*value))),
-            ),
-            TProp::F64(cell) => Box::new(
-                cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::F64(*value))),
-            ),
             TProp::Bool(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::Bool(*value))),
+                    .map(|(t, value)| (t, Prop::Bool(*value))),
             ),
             TProp::DTime(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::DTime(*value))),
+                    .map(|(t, value)| (t, Prop::DTime(*value))),
             ),
             TProp::NDTime(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::NDTime(*value))),
+                    .map(|(t, value)| (t, Prop::NDTime(*value))),
             ),
             TProp::Graph(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::Graph(value.clone()))),
+                    .map(|(t, value)| (t, Prop::Graph(value.clone()))),
             ),
             TProp::PersistentGraph(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::PersistentGraph(value.clone()))),
+                    .map(|(t, value)| (t, Prop::PersistentGraph(value.clone()))),
             ),
             TProp::Document(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::Document(value.clone()))),
+                    .map(|(t, value)| (t, Prop::Document(value.clone()))),
             ),
             TProp::List(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::List(value.clone()))),
+                    .map(|(t, value)| (t, Prop::List(value.clone()))),
             ),
             TProp::Map(cell) => Box::new(
                 cell.iter_window(r)
-                    .map(|(t, value)| (*t, Prop::Map(value.clone()))),
+                    .map(|(t, value)| (t, Prop::Map(value.clone()))),
+            ),
+            TProp::I8(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::I8(*value))))
+            }
+            TProp::I16(cell) => {
+                Box::new(cell.iter_window(r).map(|(t, value)| (t, Prop::I16(*value))))
+            }
+            TProp::Decimal(cell) => Box::new(
+                cell.iter_window(r)
+                    .map(|(t, value)| (t, Prop::Decimal(*value))),
+            ),
+            TProp::Bytes(cell) => Box::new(
+                cell.iter_window(r)
+                    .map(|(t, value)| (t, Prop::Bytes(value.clone()))),
+            ),
+            TProp::Array(cell) => Box::new(
+                cell.iter_window(r)
+                    .map(|(t, value)| (t, Prop::Array(value.clone()))),
             ),
         }
     }
@@ -339,6 +510,15 @@ impl<'a> TPropOps<'a> for &'a TProp {
                 .map(|(t, v)| (t, Prop::Document(v.clone()))),
             TProp::List(cell) => cell.last_before(t).map(|(t, v)| (t, Prop::List(v.clone()))),
             TProp::Map(cell) => cell.last_before(t).map(|(t, v)| (t, Prop::Map(v.clone()))),
+            TProp::I8(cell) => cell.last_before(t).map(|(t, v)| (t, Prop::I8(*v))),
+            TProp::I16(cell) => cell.last_before(t).map(|(t, v)| (t, Prop::I16(*v))),
+            TProp::Decimal(cell) => cell.last_before(t).map(|(t, v)| (t, Prop::Decimal(*v))),
+            TProp::Bytes(cell) => cell
+                .last_before(t)
+                .map(|(t, v)| (t, Prop::Bytes(v.clone()))),
+            TProp::Array(cell) => cell
+                .last_before(t)
+                .map(|(t, v)| (t, Prop::Array(v.clone()))),
         }
     }
 
@@ -373,6 +553,11 @@ impl<'a> TPropOps<'a> for &'a TProp {
             TProp::Document(cell) => cell.at(ti).map(|v| Prop::Document(v.clone())),
             TProp::List(cell) => cell.at(ti).map(|v| Prop::List(v.clone())),
             TProp::Map(cell) => cell.at(ti).map(|v| Prop::Map(v.clone())),
+            TProp::I8(cell) => cell.at(ti).map(|v| Prop::I8(*v)),
+            TProp::I16(cell) => cell.at(ti).map(|v| Prop::I16(*v)),
+            TProp::Decimal(cell) => cell.at(ti).map(|v| Prop::Decimal(*v)),
+            TProp::Bytes(cell) => cell.at(ti).map(|v| Prop::Bytes(v.clone())),
+            TProp::Array(cell) => cell.at(ti).map(|v| Prop::Array(v.clone())),
         }
     }
 
@@ -396,6 +581,11 @@ impl<'a> TPropOps<'a> for &'a TProp {
             TProp::Document(v) => v.len(),
             TProp::List(v) => v.len(),
             TProp::Map(v) => v.len(),
+            TProp::I8(v) => v.len(),
+            TProp::I16(v) => v.len(),
+            TProp::Decimal(v) => v.len(),
+            TProp::Bytes(v) => v.len(),
+            TProp::Array(v) => v.len(),
         }
     }
 }
@@ -522,6 +712,55 @@ mod tprop_tests {
             tprop.iter_t().collect::<Vec<_>>(),
             vec![(1, Prop::Bool(true)), (2, Prop::Bool(true))]
         );
+
+        let mut tprop = TProp::from(1.into(), Prop::I8(-1));
+        tprop.set(2.into(), Prop::I8(2)).unwrap();
+
+        assert_eq!(
+            tprop.iter_t().collect::<Vec<_>>(),
+            vec![(1, Prop::I8(-1)), (2, Prop::I8(2))]
+        );
+
+        let mut tprop = TProp::from(1.into(), Prop::I16(-1));
+        tprop.set(2.into(), Prop::I16(2)).unwrap();
+
+        assert_eq!(
+            tprop.iter_t().collect::<Vec<_>>(),
+            vec![(1, Prop::I16(-1)), (2, Prop::I16(2))]
+        );
+
+        let mut tprop = TProp::from(1.into(), Prop::Decimal(Decimal::new(1999, 2)));
+        tprop
+            .set(2.into(), Prop::Decimal(Decimal::new(5, 0)))
+            .unwrap();
+
+        assert_eq!(
+            tprop.iter_t().collect::<Vec<_>>(),
+            vec![
+                (1, Prop::Decimal(Decimal::new(1999, 2))),
+                (2, Prop::Decimal(Decimal::new(5, 0)))
+            ]
+        );
+
+        let mut tprop = TProp::from(1.into(), Prop::bytes(vec![0u8, 1]));
+        tprop.set(2.into(), Prop::bytes(vec![2u8])).unwrap();
+
+        assert_eq!(
+            tprop.iter_t().collect::<Vec<_>>(),
+            vec![(1, Prop::bytes(vec![0u8, 1])), (2, Prop::bytes(vec![2u8]))]
+        );
+
+        let mut tprop = TProp::from(1.into(), Prop::array(vec![0.5f32, 1.5]));
+        tprop.set(2.into(), Prop::array(vec![2.5f32, 3.5])).unwrap();
+
+        assert_eq!(
+            tprop.iter_t().collect::<Vec<_>>(),
+            vec![
+                (1, Prop::array(vec![0.5f32, 1.5])),
+                (2, Prop::array(vec![2.5f32, 3.5]))
+            ]
+        );
+        assert!(tprop.set(3.into(), Prop::array(vec![1i64])).is_err());
     }
 
     #[test]
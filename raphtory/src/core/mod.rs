@@ -29,6 +29,7 @@ use crate::{
     prelude::GraphViewOps,
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
@@ -158,6 +159,123 @@ impl Display for DocumentInput {
     }
 }
 
+/// Element type of a [PropArray]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub enum ArrayType {
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+/// Dense array of numbers (e.g. an embedding or a sensor reading), stored without boxing each
+/// element as a separate `Prop`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PropArray {
+    I32(Arc<[i32]>),
+    I64(Arc<[i64]>),
+    F32(Arc<[f32]>),
+    F64(Arc<[f64]>),
+}
+
+impl PropArray {
+    pub fn dtype(&self) -> ArrayType {
+        match self {
+            PropArray::I32(_) => ArrayType::I32,
+            PropArray::I64(_) => ArrayType::I64,
+            PropArray::F32(_) => ArrayType::F32,
+            PropArray::F64(_) => ArrayType::F64,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            PropArray::I32(values) => values.len(),
+            PropArray::I64(values) => values.len(),
+            PropArray::F32(values) => values.len(),
+            PropArray::F64(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the element at position `i` as a `Prop`
+    pub fn get(&self, i: usize) -> Option<Prop> {
+        match self {
+            PropArray::I32(values) => values.get(i).copied().map(Prop::I32),
+            PropArray::I64(values) => values.get(i).copied().map(Prop::I64),
+            PropArray::F32(values) => values.get(i).copied().map(Prop::F32),
+            PropArray::F64(values) => values.get(i).copied().map(Prop::F64),
+        }
+    }
+
+    /// Iterate over the elements as `Prop`s
+    pub fn iter_prop(&self) -> impl Iterator<Item = Prop> + '_ {
+        (0..self.len()).filter_map(move |i| self.get(i))
+    }
+
+    /// Iterate over the elements converted to `f64`
+    pub fn iter_f64(&self) -> impl Iterator<Item = f64> + '_ {
+        self.iter_prop().filter_map(|v| v.as_f64())
+    }
+}
+
+impl Hash for PropArray {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            PropArray::I32(values) => values.hash(state),
+            PropArray::I64(values) => values.hash(state),
+            PropArray::F32(values) => {
+                for v in values.iter() {
+                    v.to_bits().hash(state);
+                }
+            }
+            PropArray::F64(values) => {
+                for v in values.iter() {
+                    v.to_bits().hash(state);
+                }
+            }
+        }
+    }
+}
+
+impl Display for PropArray {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PropArray::I32(values) => write!(f, "{:?}", values),
+            PropArray::I64(values) => write!(f, "{:?}", values),
+            PropArray::F32(values) => write!(f, "{:?}", values),
+            PropArray::F64(values) => write!(f, "{:?}", values),
+        }
+    }
+}
+
+impl From<Vec<i32>> for PropArray {
+    fn from(value: Vec<i32>) -> Self {
+        PropArray::I32(value.into())
+    }
+}
+
+impl From<Vec<i64>> for PropArray {
+    fn from(value: Vec<i64>) -> Self {
+        PropArray::I64(value.into())
+    }
+}
+
+impl From<Vec<f32>> for PropArray {
+    fn from(value: Vec<f32>) -> Self {
+        PropArray::F32(value.into())
+    }
+}
+
+impl From<Vec<f64>> for PropArray {
+    fn from(value: Vec<f64>) -> Self {
+        PropArray::F64(value.into())
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
 pub enum PropType {
     #[default]
@@ -179,6 +297,11 @@ pub enum PropType {
     PersistentGraph,
     Document,
     DTime,
+    I8,
+    I16,
+    Decimal,
+    Bytes,
+    Array(ArrayType),
 }
 
 impl PropType {
@@ -189,10 +312,13 @@ impl PropType {
                 | PropType::U16
                 | PropType::U32
                 | PropType::U64
+                | PropType::I8
+                | PropType::I16
                 | PropType::I32
                 | PropType::I64
                 | PropType::F32
                 | PropType::F64
+                | PropType::Decimal
         )
     }
 
@@ -229,6 +355,8 @@ impl From<&DataType> for PropType {
             DataType::LargeUtf8 => PropType::Str,
             DataType::UInt8 => PropType::U8,
             DataType::UInt16 => PropType::U16,
+            DataType::Int8 => PropType::I8,
+            DataType::Int16 => PropType::I16,
             DataType::Int32 => PropType::I32,
             DataType::Int64 => PropType::I64,
             DataType::UInt32 => PropType::U32,
@@ -236,6 +364,17 @@ impl From<&DataType> for PropType {
             DataType::Float32 => PropType::F32,
             DataType::Float64 => PropType::F64,
             DataType::Boolean => PropType::Bool,
+            DataType::Decimal(_, _) => PropType::Decimal,
+            DataType::Binary | DataType::LargeBinary => PropType::Bytes,
+            DataType::LargeList(field) | DataType::FixedSizeList(field, _) => {
+                match field.data_type() {
+                    DataType::Int32 => PropType::Array(ArrayType::I32),
+                    DataType::Int64 => PropType::Array(ArrayType::I64),
+                    DataType::Float32 => PropType::Array(ArrayType::F32),
+                    DataType::Float64 => PropType::Array(ArrayType::F64),
+                    _ => PropType::Empty,
+                }
+            }
 
             _ => PropType::Empty,
         }
@@ -262,6 +401,11 @@ pub enum Prop {
     Graph(Graph),
     PersistentGraph(PersistentGraph),
     Document(DocumentInput),
+    I8(i8),
+    I16(i16),
+    Decimal(Decimal),
+    Bytes(Arc<[u8]>),
+    Array(PropArray),
 }
 
 impl Hash for Prop {
@@ -313,6 +457,11 @@ impl Hash for Prop {
                 }
             }
             Prop::Document(d) => d.hash(state),
+            Prop::I8(i) => i.hash(state),
+            Prop::I16(i) => i.hash(state),
+            Prop::Decimal(d) => d.hash(state),
+            Prop::Bytes(b) => b.hash(state),
+            Prop::Array(a) => a.hash(state),
         }
     }
 }
@@ -325,6 +474,8 @@ impl PartialOrd for Prop {
             (Prop::Str(a), Prop::Str(b)) => a.partial_cmp(b),
             (Prop::U8(a), Prop::U8(b)) => a.partial_cmp(b),
             (Prop::U16(a), Prop::U16(b)) => a.partial_cmp(b),
+            (Prop::I8(a), Prop::I8(b)) => a.partial_cmp(b),
+            (Prop::I16(a), Prop::I16(b)) => a.partial_cmp(b),
             (Prop::I32(a), Prop::I32(b)) => a.partial_cmp(b),
             (Prop::I64(a), Prop::I64(b)) => a.partial_cmp(b),
             (Prop::U32(a), Prop::U32(b)) => a.partial_cmp(b),
@@ -334,6 +485,8 @@ impl PartialOrd for Prop {
             (Prop::Bool(a), Prop::Bool(b)) => a.partial_cmp(b),
             (Prop::NDTime(a), Prop::NDTime(b)) => a.partial_cmp(b),
             (Prop::DTime(a), Prop::DTime(b)) => a.partial_cmp(b),
+            (Prop::Decimal(a), Prop::Decimal(b)) => a.partial_cmp(b),
+            (Prop::Bytes(a), Prop::Bytes(b)) => a.partial_cmp(b),
             _ => None,
         }
     }
@@ -370,6 +523,36 @@ impl Prop {
                 Value::String("Persistent Graph cannot be converted to JSON".to_string())
             }
             Prop::Document(DocumentInput { content, .. }) => Value::String(content.to_owned()), // TODO: return Value::Object ??
+            Prop::I8(value) => Value::Number((*value).into()),
+            Prop::I16(value) => Value::Number((*value).into()),
+            // serialised as a string to not lose precision
+            Prop::Decimal(value) => Value::String(value.to_string()),
+            Prop::Bytes(value) => Value::Array(value.iter().map(|b| (*b).into()).collect()),
+            Prop::Array(value) => Value::Array(value.iter_prop().map(|v| v.to_json()).collect()),
+        }
+    }
+
+    /// Convert a JSON value to a property, integers become `I64` (or `U64` if they are too large)
+    /// and other numbers `F64`. Returns `None` for `null`.
+    pub fn from_json(value: &Value) -> Option<Prop> {
+        match value {
+            Value::Null => None,
+            Value::Bool(value) => Some(Prop::Bool(*value)),
+            Value::Number(value) => value
+                .as_i64()
+                .map(Prop::I64)
+                .or_else(|| value.as_u64().map(Prop::U64))
+                .or_else(|| value.as_f64().map(Prop::F64)),
+            Value::String(value) => Some(Prop::str(value.as_str())),
+            Value::Array(values) => Some(Prop::List(Arc::new(
+                values.iter().filter_map(Prop::from_json).collect(),
+            ))),
+            Value::Object(values) => Some(Prop::Map(Arc::new(
+                values
+                    .iter()
+                    .filter_map(|(k, v)| Some((ArcStr::from(k.as_str()), Prop::from_json(v)?)))
+                    .collect(),
+            ))),
         }
     }
 
@@ -392,6 +575,11 @@ impl Prop {
             Prop::PersistentGraph(_) => PropType::PersistentGraph,
             Prop::Document(_) => PropType::Document,
             Prop::DTime(_) => PropType::DTime,
+            Prop::I8(_) => PropType::I8,
+            Prop::I16(_) => PropType::I16,
+            Prop::Decimal(_) => PropType::Decimal,
+            Prop::Bytes(_) => PropType::Bytes,
+            Prop::Array(a) => PropType::Array(a.dtype()),
         }
     }
 
@@ -399,16 +587,27 @@ impl Prop {
         Prop::Str(s.into())
     }
 
+    pub fn bytes<B: Into<Arc<[u8]>>>(b: B) -> Prop {
+        Prop::Bytes(b.into())
+    }
+
+    pub fn array<A: Into<PropArray>>(a: A) -> Prop {
+        Prop::Array(a.into())
+    }
+
     pub fn add(self, other: Prop) -> Option<Prop> {
         match (self, other) {
             (Prop::U8(a), Prop::U8(b)) => Some(Prop::U8(a + b)),
             (Prop::U16(a), Prop::U16(b)) => Some(Prop::U16(a + b)),
+            (Prop::I8(a), Prop::I8(b)) => Some(Prop::I8(a + b)),
+            (Prop::I16(a), Prop::I16(b)) => Some(Prop::I16(a + b)),
             (Prop::I32(a), Prop::I32(b)) => Some(Prop::I32(a + b)),
             (Prop::I64(a), Prop::I64(b)) => Some(Prop::I64(a + b)),
             (Prop::U32(a), Prop::U32(b)) => Some(Prop::U32(a + b)),
             (Prop::U64(a), Prop::U64(b)) => Some(Prop::U64(a + b)),
             (Prop::F32(a), Prop::F32(b)) => Some(Prop::F32(a + b)),
             (Prop::F64(a), Prop::F64(b)) => Some(Prop::F64(a + b)),
+            (Prop::Decimal(a), Prop::Decimal(b)) => a.checked_add(b).map(Prop::Decimal),
             (Prop::Str(a), Prop::Str(b)) => Some(Prop::Str((a.to_string() + &b).into())),
             _ => None,
         }
@@ -434,12 +633,15 @@ impl Prop {
         match (self, other) {
             (Prop::U8(a), Prop::U8(b)) if b != 0 => Some(Prop::U8(a / b)),
             (Prop::U16(a), Prop::U16(b)) if b != 0 => Some(Prop::U16(a / b)),
+            (Prop::I8(a), Prop::I8(b)) if b != 0 => Some(Prop::I8(a / b)),
+            (Prop::I16(a), Prop::I16(b)) if b != 0 => Some(Prop::I16(a / b)),
             (Prop::I32(a), Prop::I32(b)) if b != 0 => Some(Prop::I32(a / b)),
             (Prop::I64(a), Prop::I64(b)) if b != 0 => Some(Prop::I64(a / b)),
             (Prop::U32(a), Prop::U32(b)) if b != 0 => Some(Prop::U32(a / b)),
             (Prop::U64(a), Prop::U64(b)) if b != 0 => Some(Prop::U64(a / b)),
             (Prop::F32(a), Prop::F32(b)) if b != 0.0 => Some(Prop::F32(a / b)),
             (Prop::F64(a), Prop::F64(b)) if b != 0.0 => Some(Prop::F64(a / b)),
+            (Prop::Decimal(a), Prop::Decimal(b)) => a.checked_div(b).map(Prop::Decimal),
             _ => None,
         }
     }
@@ -448,12 +650,15 @@ impl Prop {
         match self {
             Prop::U8(v) => Some(*v as f64),
             Prop::U16(v) => Some(*v as f64),
+            Prop::I8(v) => Some(*v as f64),
+            Prop::I16(v) => Some(*v as f64),
             Prop::I32(v) => Some(*v as f64),
             Prop::I64(v) => Some(*v as f64),
             Prop::U32(v) => Some(*v as f64),
             Prop::U64(v) => Some(*v as f64),
             Prop::F32(v) => Some(*v as f64),
             Prop::F64(v) => Some(*v),
+            Prop::Decimal(v) => v.to_f64(),
             _ => None,
         }
     }
@@ -537,6 +742,31 @@ pub trait PropUnwrap: Sized {
     fn unwrap_document(self) -> DocumentInput {
         self.into_document().unwrap()
     }
+
+    fn into_i8(self) -> Option<i8>;
+    fn unwrap_i8(self) -> i8 {
+        self.into_i8().unwrap()
+    }
+
+    fn into_i16(self) -> Option<i16>;
+    fn unwrap_i16(self) -> i16 {
+        self.into_i16().unwrap()
+    }
+
+    fn into_decimal(self) -> Option<Decimal>;
+    fn unwrap_decimal(self) -> Decimal {
+        self.into_decimal().unwrap()
+    }
+
+    fn into_bytes(self) -> Option<Arc<[u8]>>;
+    fn unwrap_bytes(self) -> Arc<[u8]> {
+        self.into_bytes().unwrap()
+    }
+
+    fn into_array(self) -> Option<PropArray>;
+    fn unwrap_array(self) -> PropArray {
+        self.into_array().unwrap()
+    }
 }
 
 impl<P: PropUnwrap> PropUnwrap for Option<P> {
@@ -603,6 +833,26 @@ impl<P: PropUnwrap> PropUnwrap for Option<P> {
     fn into_document(self) -> Option<DocumentInput> {
         self.and_then(|p| p.into_document())
     }
+
+    fn into_i8(self) -> Option<i8> {
+        self.and_then(|p| p.into_i8())
+    }
+
+    fn into_i16(self) -> Option<i16> {
+        self.and_then(|p| p.into_i16())
+    }
+
+    fn into_decimal(self) -> Option<Decimal> {
+        self.and_then(|p| p.into_decimal())
+    }
+
+    fn into_bytes(self) -> Option<Arc<[u8]>> {
+        self.and_then(|p| p.into_bytes())
+    }
+
+    fn into_array(self) -> Option<PropArray> {
+        self.and_then(|p| p.into_array())
+    }
 }
 
 impl PropUnwrap for Prop {
@@ -733,6 +983,46 @@ impl PropUnwrap for Prop {
             None
         }
     }
+
+    fn into_i8(self) -> Option<i8> {
+        if let Prop::I8(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    fn into_i16(self) -> Option<i16> {
+        if let Prop::I16(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    fn into_decimal(self) -> Option<Decimal> {
+        if let Prop::Decimal(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    fn into_bytes(self) -> Option<Arc<[u8]>> {
+        if let Prop::Bytes(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    fn into_array(self) -> Option<PropArray> {
+        if let Prop::Array(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
 
 impl Display for Prop {
@@ -769,6 +1059,11 @@ impl Display for Prop {
                 write!(f, "{:?}", value)
             }
             Prop::Document(value) => write!(f, "{}", value),
+            Prop::I8(value) => write!(f, "{}", value),
+            Prop::I16(value) => write!(f, "{}", value),
+            Prop::Decimal(value) => write!(f, "{}", value),
+            Prop::Bytes(value) => write!(f, "{:?}", value),
+            Prop::Array(value) => write!(f, "{}", value),
         }
     }
 }
@@ -822,6 +1117,18 @@ impl From<i32> for Prop {
     }
 }
 
+impl From<i8> for Prop {
+    fn from(i: i8) -> Self {
+        Prop::I8(i)
+    }
+}
+
+impl From<i16> for Prop {
+    fn from(i: i16) -> Self {
+        Prop::I16(i)
+    }
+}
+
 impl From<u8> for Prop {
     fn from(i: u8) -> Self {
         Prop::U8(i)
@@ -870,6 +1177,18 @@ impl From<DateTime<Utc>> for Prop {
     }
 }
 
+impl From<Decimal> for Prop {
+    fn from(d: Decimal) -> Self {
+        Prop::Decimal(d)
+    }
+}
+
+impl From<PropArray> for Prop {
+    fn from(a: PropArray) -> Self {
+        Prop::Array(a)
+    }
+}
+
 impl From<bool> for Prop {
     fn from(b: bool) -> Self {
         Prop::Bool(b)
@@ -997,6 +1316,36 @@ mod test_arc_str {
         assert_eq!(opt_str3, Some("test"));
     }
 
+    #[test]
+    fn test_new_prop_types() {
+        use super::{PropArray, PropType};
+        use rust_decimal::Decimal;
+
+        let a = Prop::Decimal(Decimal::new(1050, 2));
+        let b = Prop::Decimal(Decimal::new(25, 1));
+        assert_eq!(
+            a.clone().add(b.clone()),
+            Some(Prop::Decimal(Decimal::new(1300, 2)))
+        );
+        assert_eq!(a.as_f64(), Some(10.5));
+        assert_eq!(a.to_string(), "10.50");
+        assert_eq!(a.dtype(), PropType::Decimal);
+
+        let arr = Prop::array(vec![1.0f32, 2.5]);
+        assert_eq!(arr.dtype(), PropType::Array(super::ArrayType::F32));
+        assert_eq!(arr.to_string(), "[1.0, 2.5]");
+        assert_eq!(
+            PropArray::from(vec![1i64, 2])
+                .iter_prop()
+                .collect::<Vec<_>>(),
+            vec![Prop::I64(1), Prop::I64(2)]
+        );
+
+        assert_eq!(Prop::I8(-3).max(Prop::I8(2)), Some(Prop::I8(2)));
+        assert_eq!(Prop::from(-300i16).as_f64(), Some(-300.0));
+        assert_eq!(Prop::bytes(vec![1u8, 2]).dtype(), PropType::Bytes);
+    }
+
     #[test]
     fn test_prop_min_max() {
         let v1 = Prop::I64(4);
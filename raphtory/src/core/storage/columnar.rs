@@ -0,0 +1,493 @@
+use crate::core::storage::timeindex::TimeIndexEntry;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Debug, iter, mem::size_of, ops::Range};
+
+/// Immutable, time-sorted block of updates stored as separate time and value columns
+///
+/// Times are delta-encoded when all gaps fit in 32 bits. Values are run-length or dictionary
+/// encoded, whichever takes the least space, or stored directly if neither saves memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnBlock<A> {
+    times: TimeColumn,
+    values: ColumnValues<A>,
+}
+
+/// Number of times between two full (non-delta) timestamps in a delta-encoded time column
+const DELTA_ANCHOR_INTERVAL: usize = 64;
+
+/// Maximum number of distinct values in a dictionary-encoded value column
+const MAX_DICTIONARY_SIZE: usize = u8::MAX as usize + 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum TimeColumn {
+    Plain(Box<[TimeIndexEntry]>),
+    // the time at index `i` is `anchors[i / DELTA_ANCHOR_INTERVAL]` plus the `deltas` after the
+    // anchor up to and including `i`, `secondary[i]` is its secondary index
+    Delta {
+        anchors: Box<[i64]>,
+        deltas: Box<[u32]>,
+        secondary: Box<[u32]>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ColumnValues<A> {
+    Plain(Box<[A]>),
+    // `ends[i]` is the (exclusive) end index of the run of `values[i]`
+    RunLength { ends: Box<[u32]>, values: Box<[A]> },
+    // the value at index `i` is `values[codes[i]]`
+    Dictionary { codes: Box<[u8]>, values: Box<[A]> },
+}
+
+impl TimeColumn {
+    fn from_sorted(times: Vec<TimeIndexEntry>) -> Self {
+        let fits = times.windows(2).all(|w| {
+            w[1].0
+                .checked_sub(w[0].0)
+                .is_some_and(|delta| u32::try_from(delta).is_ok())
+        }) && times.iter().all(|t| u32::try_from(t.1).is_ok());
+        let delta_size = times.len().div_ceil(DELTA_ANCHOR_INTERVAL) * size_of::<i64>()
+            + times.len() * 2 * size_of::<u32>();
+        if !fits || delta_size >= times.len() * size_of::<TimeIndexEntry>() {
+            return TimeColumn::Plain(times.into());
+        }
+        let anchors: Vec<i64> = times
+            .iter()
+            .step_by(DELTA_ANCHOR_INTERVAL)
+            .map(|t| t.0)
+            .collect();
+        let deltas: Vec<u32> = iter::once(0)
+            .chain(times.windows(2).enumerate().map(|(i, w)| {
+                if (i + 1) % DELTA_ANCHOR_INTERVAL == 0 {
+                    0
+                } else {
+                    (w[1].0 - w[0].0) as u32
+                }
+            }))
+            .collect();
+        let secondary: Vec<u32> = times.iter().map(|t| t.1 as u32).collect();
+        TimeColumn::Delta {
+            anchors: anchors.into(),
+            deltas: deltas.into(),
+            secondary: secondary.into(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            TimeColumn::Plain(times) => times.len(),
+            TimeColumn::Delta { secondary, .. } => secondary.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> TimeIndexEntry {
+        match self {
+            TimeColumn::Plain(times) => times[index],
+            TimeColumn::Delta {
+                anchors,
+                deltas,
+                secondary,
+            } => {
+                let anchor = index / DELTA_ANCHOR_INTERVAL;
+                let t = deltas[anchor * DELTA_ANCHOR_INTERVAL + 1..=index]
+                    .iter()
+                    .fold(anchors[anchor], |t, &delta| t + delta as i64);
+                TimeIndexEntry(t, secondary[index] as usize)
+            }
+        }
+    }
+
+    /// Index of the first time for which `before` is false, `before` has to be monotone
+    fn partition_point(&self, before: impl Fn(&TimeIndexEntry) -> bool) -> usize {
+        match self {
+            TimeColumn::Plain(times) => times.partition_point(before),
+            TimeColumn::Delta {
+                anchors, secondary, ..
+            } => {
+                // find the last anchor that comes before, then scan the deltas that follow it
+                let (mut lo, mut hi) = (0, anchors.len());
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    let anchor = TimeIndexEntry(
+                        anchors[mid],
+                        secondary[mid * DELTA_ANCHOR_INTERVAL] as usize,
+                    );
+                    if before(&anchor) {
+                        lo = mid + 1;
+                    } else {
+                        hi = mid;
+                    }
+                }
+                match lo.checked_sub(1) {
+                    None => 0,
+                    Some(anchor) => {
+                        let start = anchor * DELTA_ANCHOR_INTERVAL;
+                        start
+                            + self
+                                .iter_from(start)
+                                .take(DELTA_ANCHOR_INTERVAL)
+                                .take_while(|t| before(t))
+                                .count()
+                    }
+                }
+            }
+        }
+    }
+
+    fn iter_from(&self, start: usize) -> Box<dyn Iterator<Item = TimeIndexEntry> + Send + '_> {
+        if start >= self.len() {
+            return Box::new(iter::empty());
+        }
+        match self {
+            TimeColumn::Plain(times) => Box::new(times[start..].iter().copied()),
+            TimeColumn::Delta {
+                anchors,
+                deltas,
+                secondary,
+            } => {
+                let mut t = self.get(start).0;
+                Box::new((start..secondary.len()).map(move |i| {
+                    if i % DELTA_ANCHOR_INTERVAL == 0 {
+                        t = anchors[i / DELTA_ANCHOR_INTERVAL];
+                    } else if i != start {
+                        t += deltas[i] as i64;
+                    }
+                    TimeIndexEntry(t, secondary[i] as usize)
+                }))
+            }
+        }
+    }
+
+    fn heap_size(&self) -> usize {
+        match self {
+            TimeColumn::Plain(times) => times.len() * size_of::<TimeIndexEntry>(),
+            TimeColumn::Delta {
+                anchors,
+                deltas,
+                secondary,
+            } => {
+                anchors.len() * size_of::<i64>()
+                    + (deltas.len() + secondary.len()) * size_of::<u32>()
+            }
+        }
+    }
+}
+
+impl<A: PartialEq> ColumnValues<A> {
+    fn encode(plain: Vec<A>) -> Self {
+        let len = plain.len();
+        let plain_size = len * size_of::<A>();
+        let num_runs = 1 + plain.windows(2).filter(|w| w[0] != w[1]).count();
+        let run_length_size = num_runs * (size_of::<A>() + size_of::<u32>());
+        let mut dictionary: Vec<&A> = Vec::new();
+        for value in plain.iter() {
+            if !dictionary.contains(&value) {
+                if dictionary.len() == MAX_DICTIONARY_SIZE {
+                    dictionary.clear();
+                    break;
+                }
+                dictionary.push(value);
+            }
+        }
+        let dictionary_size = if dictionary.is_empty() {
+            usize::MAX
+        } else {
+            len * size_of::<u8>() + dictionary.len() * size_of::<A>()
+        };
+        let dictionary_len = dictionary.len();
+
+        if len <= u32::MAX as usize
+            && run_length_size < plain_size
+            && run_length_size <= dictionary_size
+        {
+            let mut ends: Vec<u32> = Vec::with_capacity(num_runs);
+            let mut values: Vec<A> = Vec::with_capacity(num_runs);
+            for (i, value) in plain.into_iter().enumerate() {
+                match values.last() {
+                    Some(last) if last == &value => {}
+                    _ => {
+                        if !values.is_empty() {
+                            ends.push(i as u32);
+                        }
+                        values.push(value);
+                    }
+                }
+            }
+            ends.push(len as u32);
+            ColumnValues::RunLength {
+                ends: ends.into(),
+                values: values.into(),
+            }
+        } else if dictionary_size < plain_size {
+            let mut codes: Vec<u8> = Vec::with_capacity(len);
+            let mut values: Vec<A> = Vec::with_capacity(dictionary_len);
+            for value in plain {
+                match values.iter().position(|v| v == &value) {
+                    Some(code) => codes.push(code as u8),
+                    None => {
+                        codes.push(values.len() as u8);
+                        values.push(value);
+                    }
+                }
+            }
+            ColumnValues::Dictionary {
+                codes: codes.into(),
+                values: values.into(),
+            }
+        } else {
+            ColumnValues::Plain(plain.into())
+        }
+    }
+
+    fn heap_size(&self) -> usize {
+        match self {
+            ColumnValues::Plain(values) => values.len() * size_of::<A>(),
+            ColumnValues::RunLength { ends, values } => {
+                ends.len() * size_of::<u32>() + values.len() * size_of::<A>()
+            }
+            ColumnValues::Dictionary { codes, values } => {
+                codes.len() * size_of::<u8>() + values.len() * size_of::<A>()
+            }
+        }
+    }
+}
+
+impl<A: PartialEq + Send + Sync> ColumnBlock<A> {
+    /// Build a block from updates sorted by time
+    pub fn from_sorted(updates: impl IntoIterator<Item = (TimeIndexEntry, A)>) -> Self {
+        let (times, plain): (Vec<_>, Vec<_>) = updates.into_iter().unzip();
+        debug_assert!(times.windows(2).all(|w| w[0] < w[1]));
+        Self {
+            times: TimeColumn::from_sorted(times),
+            values: ColumnValues::encode(plain),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_delta_encoded(&self) -> bool {
+        matches!(self.times, TimeColumn::Delta { .. })
+    }
+
+    pub fn is_run_length_encoded(&self) -> bool {
+        matches!(self.values, ColumnValues::RunLength { .. })
+    }
+
+    pub fn is_dictionary_encoded(&self) -> bool {
+        matches!(self.values, ColumnValues::Dictionary { .. })
+    }
+
+    fn value(&self, index: usize) -> &A {
+        match &self.values {
+            ColumnValues::Plain(values) => &values[index],
+            ColumnValues::RunLength { ends, values } => {
+                &values[ends.partition_point(|&end| end as usize <= index)]
+            }
+            ColumnValues::Dictionary { codes, values } => &values[codes[index] as usize],
+        }
+    }
+
+    fn values_from(&self, start: usize) -> Box<dyn Iterator<Item = &A> + Send + '_> {
+        match &self.values {
+            ColumnValues::Plain(values) => Box::new(values[start..].iter()),
+            ColumnValues::RunLength { ends, values } => {
+                let first = ends.partition_point(|&end| end as usize <= start);
+                let mut pos = start;
+                Box::new(ends[first..].iter().zip(values[first..].iter()).flat_map(
+                    move |(&end, value)| {
+                        let n = end as usize - pos;
+                        pos = end as usize;
+                        iter::repeat(value).take(n)
+                    },
+                ))
+            }
+            ColumnValues::Dictionary { codes, values } => {
+                Box::new(codes[start..].iter().map(|&code| &values[code as usize]))
+            }
+        }
+    }
+
+    pub fn get(&self, t: &TimeIndexEntry) -> Option<&A> {
+        let index = self.times.partition_point(|ti| ti < t);
+        (index < self.len() && &self.times.get(index) == t).then(|| self.value(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (TimeIndexEntry, &A)> + Send + '_ {
+        self.times.iter_from(0).zip(self.values_from(0))
+    }
+
+    pub fn range(
+        &self,
+        r: Range<TimeIndexEntry>,
+    ) -> impl Iterator<Item = (TimeIndexEntry, &A)> + Send + '_ {
+        let start = self.times.partition_point(|t| t < &r.start);
+        let end = self.times.partition_point(|t| t < &r.end).max(start);
+        self.times
+            .iter_from(start)
+            .take(end - start)
+            .zip(self.values_from(start))
+    }
+
+    pub fn last_before(&self, t: i64) -> Option<(TimeIndexEntry, &A)> {
+        let index = self.times.partition_point(|ti| ti.0 < t).checked_sub(1)?;
+        Some((self.times.get(index), self.value(index)))
+    }
+
+    /// Heap memory used by the block, not counting memory owned by the values themselves
+    pub fn heap_size(&self) -> usize {
+        self.times.heap_size() + self.values.heap_size()
+    }
+}
+
+/// Summary of a compaction run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionStats {
+    /// Number of temporal property histories that were compacted
+    pub compacted_histories: usize,
+    /// Number of updates moved into columnar blocks
+    pub compacted_updates: usize,
+    /// Estimated heap memory of all temporal property histories before compaction
+    pub bytes_before: usize,
+    /// Estimated heap memory of all temporal property histories after compaction
+    pub bytes_after: usize,
+}
+
+impl CompactionStats {
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+impl std::ops::AddAssign for CompactionStats {
+    fn add_assign(&mut self, rhs: Self) {
+        self.compacted_histories += rhs.compacted_histories;
+        self.compacted_updates += rhs.compacted_updates;
+        self.bytes_before += rhs.bytes_before;
+        self.bytes_after += rhs.bytes_after;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use raphtory_api::core::storage::timeindex::AsTime;
+
+    fn block(values: &[i64]) -> ColumnBlock<i64> {
+        ColumnBlock::from_sorted(
+            values
+                .iter()
+                .enumerate()
+                .map(|(t, v)| (TimeIndexEntry::start(t as i64), *v)),
+        )
+    }
+
+    #[test]
+    fn repeated_values_are_run_length_encoded() {
+        let values = [1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3];
+        let block = block(&values);
+        assert!(block.is_run_length_encoded());
+        assert!(block.heap_size() < values.len() * (size_of::<TimeIndexEntry>() + 8));
+        assert_eq!(
+            block.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            values.to_vec()
+        );
+        for (t, v) in values.iter().enumerate() {
+            assert_eq!(block.get(&TimeIndexEntry::start(t as i64)), Some(v));
+        }
+        assert_eq!(
+            block
+                .range(TimeIndexEntry::range(3..9))
+                .map(|(t, v)| (t.0, *v))
+                .collect::<Vec<_>>(),
+            vec![(3, 1), (4, 2), (5, 2), (6, 2), (7, 2), (8, 3)]
+        );
+        assert_eq!(block.last_before(5), Some((TimeIndexEntry::start(4), &2)));
+        assert_eq!(block.last_before(0), None);
+    }
+
+    #[test]
+    fn distinct_values_are_stored_plain() {
+        let block = block(&[1, 2, 3, 4]);
+        assert!(!block.is_run_length_encoded());
+        assert!(!block.is_dictionary_encoded());
+        assert_eq!(
+            block
+                .range(TimeIndexEntry::range(1..3))
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(block.range(TimeIndexEntry::range(5..10)).count(), 0);
+        assert_eq!(block.get(&TimeIndexEntry::start(4)), None);
+    }
+
+    #[test]
+    fn few_alternating_values_are_dictionary_encoded() {
+        let too_many: Vec<i64> = (0..1000).map(|i| i % 300).collect();
+        assert!(!block(&too_many).is_dictionary_encoded());
+
+        let values: Vec<i64> = (0..1000).map(|i| (i * 7) % 5).collect();
+        let block = block(&values);
+        assert!(block.is_dictionary_encoded());
+        assert!(block.heap_size() < values.len() * (size_of::<TimeIndexEntry>() + 8));
+        assert_eq!(block.iter().map(|(_, v)| *v).collect::<Vec<_>>(), values);
+        assert_eq!(block.get(&TimeIndexEntry::start(998)), Some(&values[998]));
+        assert_eq!(
+            block
+                .range(TimeIndexEntry::range(500..503))
+                .map(|(t, v)| (t.0, *v))
+                .collect::<Vec<_>>(),
+            vec![(500, values[500]), (501, values[501]), (502, values[502])]
+        );
+    }
+
+    #[test]
+    fn sorted_times_are_delta_encoded() {
+        let times: Vec<TimeIndexEntry> = (0..1000)
+            .map(|i| TimeIndexEntry(i * i - 5000, i as usize % 3))
+            .collect();
+        let block = ColumnBlock::from_sorted(times.iter().map(|t| (*t, t.0)));
+        assert!(block.is_delta_encoded());
+        assert!(block.heap_size() < times.len() * (size_of::<TimeIndexEntry>() + 8));
+        assert_eq!(block.iter().map(|(t, _)| t).collect::<Vec<_>>(), times);
+        for t in times.iter() {
+            assert_eq!(block.get(t), Some(&t.0));
+        }
+        assert_eq!(block.get(&TimeIndexEntry(-4999, 0)), None);
+        assert_eq!(block.get(&TimeIndexEntry(-5000, 1)), None);
+        assert_eq!(
+            block
+                .range(TimeIndexEntry::range(3000..5000))
+                .map(|(t, _)| t)
+                .collect::<Vec<_>>(),
+            times
+                .iter()
+                .copied()
+                .filter(|t| (3000..5000).contains(&t.0))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            block.last_before(64 * 64 - 5000),
+            Some((times[63], &times[63].0))
+        );
+        assert_eq!(block.last_before(-5000), None);
+
+        let far_apart = [
+            TimeIndexEntry::start(i64::MIN),
+            TimeIndexEntry::start(0),
+            TimeIndexEntry::start(i64::MAX),
+        ];
+        let block = ColumnBlock::from_sorted(far_apart.iter().map(|t| (*t, 0)));
+        assert!(!block.is_delta_encoded());
+        assert_eq!(
+            block.iter().map(|(t, _)| t).collect::<Vec<_>>(),
+            far_apart.to_vec()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::core::utils::errors::GraphError;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Debug, iter};
+use std::{fmt::Debug, iter, mem::size_of};
 
 #[derive(thiserror::Error, Debug, PartialEq)]
 #[error("cannot set previous value '{previous_value:?}' to '{new_value:?}' in position '{index}'")]
@@ -65,6 +65,22 @@ where
         }
     }
 
+    /// Heap memory used by the slots, not counting memory owned by the values
+    pub(crate) fn heap_size(&self) -> usize {
+        match self {
+            LazyVec::LazyVecN(vector) => vector.capacity() * size_of::<A>(),
+            _ => 0,
+        }
+    }
+
+    pub(crate) fn values_mut(&mut self) -> Box<dyn Iterator<Item = &mut A> + '_> {
+        match self {
+            LazyVec::Empty => Box::new(iter::empty()),
+            LazyVec::LazyVec1(_, value) => Box::new(iter::once(value)),
+            LazyVec::LazyVecN(vector) => Box::new(vector.iter_mut()),
+        }
+    }
+
     pub(crate) fn get(&self, id: usize) -> Option<&A> {
         match self {
             LazyVec::LazyVec1(only_id, value) if *only_id == id => Some(value),
@@ -1,5 +1,6 @@
 #![allow(unused)]
 
+pub mod columnar;
 pub(crate) mod iter;
 pub mod lazy_vec;
 pub mod locked_view;
@@ -26,6 +27,15 @@ use std::{
 
 type ArcRwLockReadGuard<T> = lock_api::ArcRwLockReadGuard<parking_lot::RawRwLock, T>;
 
+// estimated number of entries per BTreeMap node (nodes hold up to 11 entries)
+const BTREE_NODE_FILL: usize = 8;
+const BTREE_NODE_CAPACITY: usize = 11;
+
+/// Estimated heap memory of a `BTreeMap` or `BTreeSet` with `len` entries of `entry_size` bytes
+pub(crate) fn btree_heap_size(len: usize, entry_size: usize) -> usize {
+    len.div_ceil(BTREE_NODE_FILL) * (BTREE_NODE_CAPACITY * entry_size + 16)
+}
+
 #[inline]
 fn resolve(index: usize, num_buckets: usize) -> (usize, usize) {
     let bucket = index % num_buckets;
@@ -158,6 +168,17 @@ where
     pub fn count_with_filter<F: Fn(&T) -> bool + Send + Sync>(&self, f: F) -> usize {
         self.read_lock().par_iter().filter(|x| f(x)).count()
     }
+
+    /// Write-lock the buckets in parallel and apply `f` to the entries of each bucket
+    pub(crate) fn par_map_buckets_mut<R: Send, F: Fn(&mut [T]) -> R + Send + Sync>(
+        &self,
+        f: F,
+    ) -> Vec<R> {
+        self.data
+            .par_iter()
+            .map(|bucket| f(&mut bucket.data.write()))
+            .collect()
+    }
 }
 
 impl<Index, T: Default> RawStorage<T, Index>
@@ -0,0 +1,259 @@
+//! Error policies for bulk loaders and reports of the records they rejected
+//!
+//! Loaders hand every record that fails to parse, or that the graph refuses, to a
+//! [`DeadLetters`] handle. Depending on its [`ErrorPolicy`] the load is aborted with the position
+//! of the offending record, or the record is skipped and optionally kept in the [`LoadReport`].
+//! Rejected records can also be appended to a dead-letter file, one JSON object per line, to be
+//! fixed and loaded again later.
+//!
+//! # Example
+//! ```
+//! use raphtory::core::utils::dead_letter::{DeadLetters, ErrorPolicy, RejectedRecord};
+//!
+//! let dead_letters = DeadLetters::new(ErrorPolicy::Collect);
+//! dead_letters.loaded(2);
+//! dead_letters
+//!     .reject(RejectedRecord::new(Some(3), "a,b,oops", "invalid time"))
+//!     .unwrap();
+//! let report = dead_letters.report();
+//! assert_eq!(report.loaded, 2);
+//! assert_eq!(report.skipped, 1);
+//! assert_eq!(report.rejected[0].line, Some(3));
+//! ```
+
+use crate::core::utils::errors::GraphError;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// What a loader does with records it cannot load
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorPolicy {
+    /// Abort the load on the first bad record
+    #[default]
+    FailFast,
+    /// Skip bad records, only counting them
+    Skip,
+    /// Skip bad records and keep them in the report
+    Collect,
+}
+
+impl FromStr for ErrorPolicy {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fail" | "fail_fast" => Ok(ErrorPolicy::FailFast),
+            "skip" => Ok(ErrorPolicy::Skip),
+            "collect" | "skip_and_collect" => Ok(ErrorPolicy::Collect),
+            other => Err(GraphError::InvalidErrorPolicy(other.to_owned())),
+        }
+    }
+}
+
+/// A record that could not be loaded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectedRecord {
+    /// File the record was read from, if any
+    pub file: Option<PathBuf>,
+    /// Line of the record in its file, or row of the record in its dataframe
+    pub line: Option<u64>,
+    /// The raw record
+    pub record: String,
+    /// Why the record was rejected
+    pub error: String,
+}
+
+impl RejectedRecord {
+    pub fn new(line: Option<u64>, record: impl Into<String>, error: impl Display) -> Self {
+        Self {
+            file: None,
+            line,
+            record: record.into(),
+            error: error.to_string(),
+        }
+    }
+
+    pub fn in_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+impl Display for RejectedRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{line}:")?;
+        }
+        if self.file.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{} (record: {})", self.error, self.record)
+    }
+}
+
+impl Error for RejectedRecord {}
+
+/// Summary of a load
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// Number of records loaded
+    pub loaded: usize,
+    /// Number of records skipped
+    pub skipped: usize,
+    /// Number of records skipped because they were loaded before
+    pub duplicates: usize,
+    /// The skipped records, only kept with [`ErrorPolicy::Collect`]
+    pub rejected: Vec<RejectedRecord>,
+}
+
+struct Inner {
+    policy: ErrorPolicy,
+    file: Option<Mutex<File>>,
+    report: Mutex<LoadReport>,
+}
+
+/// Shared handle applying an [`ErrorPolicy`] to the rejected records of one or more loads
+///
+/// Clones refer to the same report, so a handle can be given to a loader and inspected once the
+/// load is done.
+#[derive(Clone)]
+pub struct DeadLetters(Arc<Inner>);
+
+impl Default for DeadLetters {
+    fn default() -> Self {
+        Self::new(ErrorPolicy::default())
+    }
+}
+
+impl Debug for DeadLetters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeadLetters")
+            .field("policy", &self.0.policy)
+            .field("report", &*self.0.report.lock())
+            .finish()
+    }
+}
+
+impl PartialEq for DeadLetters {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl DeadLetters {
+    pub fn new(policy: ErrorPolicy) -> Self {
+        Self(Arc::new(Inner {
+            policy,
+            file: None,
+            report: Default::default(),
+        }))
+    }
+
+    /// Also append every rejected record to `path` as a line of JSON, truncating the file first
+    pub fn with_file<P: AsRef<Path>>(policy: ErrorPolicy, path: P) -> Result<Self, GraphError> {
+        Ok(Self(Arc::new(Inner {
+            policy,
+            file: Some(Mutex::new(File::create(path)?)),
+            report: Default::default(),
+        })))
+    }
+
+    pub fn policy(&self) -> ErrorPolicy {
+        self.0.policy
+    }
+
+    /// A copy of the report so far
+    pub fn report(&self) -> LoadReport {
+        self.0.report.lock().clone()
+    }
+
+    /// Count records that were loaded successfully
+    pub fn loaded(&self, records: usize) {
+        self.0.report.lock().loaded += records;
+    }
+
+    /// Count a record that was skipped as a duplicate of one loaded before
+    pub fn duplicate(&self) {
+        self.0.report.lock().duplicates += 1;
+    }
+
+    /// Handle a record that could not be loaded
+    ///
+    /// The record is written to the dead-letter file if there is one. With
+    /// [`ErrorPolicy::FailFast`] it is then returned as an error, otherwise it is counted as
+    /// skipped.
+    pub fn reject(&self, record: RejectedRecord) -> Result<(), GraphError> {
+        if let Some(file) = &self.0.file {
+            let line = serde_json::to_string(&record).map_err(std::io::Error::from)?;
+            writeln!(file.lock(), "{line}")?;
+        }
+        match self.0.policy {
+            ErrorPolicy::FailFast => Err(GraphError::RejectedRecord(Box::new(record))),
+            ErrorPolicy::Skip => {
+                self.0.report.lock().skipped += 1;
+                Ok(())
+            }
+            ErrorPolicy::Collect => {
+                let mut report = self.0.report.lock();
+                report.skipped += 1;
+                report.rejected.push(record);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn policies() {
+        let fail = DeadLetters::default();
+        let err = fail
+            .reject(RejectedRecord::new(Some(2), "x", "bad").in_file("a.csv"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to load record a.csv:2: bad (record: x)"
+        );
+
+        let skip = DeadLetters::new(ErrorPolicy::Skip);
+        skip.reject(RejectedRecord::new(None, "x", "bad")).unwrap();
+        assert_eq!(skip.report().skipped, 1);
+        assert!(skip.report().rejected.is_empty());
+        assert_eq!(
+            "skip_and_collect".parse::<ErrorPolicy>().unwrap(),
+            ErrorPolicy::Collect
+        );
+        assert!("ignore".parse::<ErrorPolicy>().is_err());
+    }
+
+    #[test]
+    fn dead_letter_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rejected.jsonl");
+        let dead_letters = DeadLetters::with_file(ErrorPolicy::Skip, &path).unwrap();
+        let record = RejectedRecord::new(Some(4), "{\"a\": }", "expected value").in_file("in.json");
+        dead_letters.clone().reject(record.clone()).unwrap();
+        let written: RejectedRecord =
+            serde_json::from_str(fs::read_to_string(&path).unwrap().trim()).unwrap();
+        assert_eq!(written, record);
+        assert_eq!(dead_letters.report().skipped, 1);
+    }
+}
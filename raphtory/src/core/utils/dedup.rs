@@ -0,0 +1,198 @@
+//! Idempotent ingestion of edge updates
+//!
+//! Adding an edge update always appends a new update, even if an identical one was loaded
+//! before, so loading overlapping files twice duplicates their updates. A [`Deduplicator`]
+//! identifies every edge update by a key and lets loaders skip updates whose key was seen before.
+//! Keys are either a user-provided event id stored in an edge property or a hash of the time,
+//! source, destination, layer and properties of the update, see [`DedupKey`].
+//!
+//! [`Deduplicator::for_graph`] seeds the seen keys from the updates already in a graph. As the
+//! keys are derived from the graph itself, replaying the files of a load that was interrupted
+//! into the saved graph produces the same graph as an uninterrupted load.
+//!
+//! # Example
+//! ```
+//! use raphtory::core::utils::dedup::{DedupKey, Deduplicator};
+//! use raphtory::prelude::*;
+//!
+//! let g = Graph::new();
+//! g.add_edge(1, "a", "b", [("amount", 10i64)], None).unwrap();
+//!
+//! let dedup = Deduplicator::for_graph(DedupKey::Content, &g);
+//! let key = dedup.edge_key(1, "a", "b", None, &[("amount", Prop::I64(10))]).unwrap();
+//! assert!(!dedup.insert(key));
+//! let key = dedup.edge_key(2, "a", "b", None, &[("amount", Prop::I64(10))]).unwrap();
+//! assert!(dedup.insert(key));
+//! ```
+
+use crate::{
+    core::utils::{errors::GraphError, hashing::calculate_hash},
+    db::api::view::StaticGraphViewOps,
+    prelude::*,
+};
+use dashmap::DashSet;
+use rustc_hash::FxHasher;
+use std::{
+    fmt::{Debug, Formatter},
+    hash::BuildHasherDefault,
+    sync::Arc,
+};
+
+/// Name of the default layer as stored in the graph
+const DEFAULT_LAYER: &str = "_default";
+
+/// How edge updates are identified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DedupKey {
+    /// A unique id of every update, stored in the edge property with this name
+    EventId(String),
+    /// A hash of the time, source, destination, layer and temporal properties of the update,
+    /// treating identical updates as one
+    Content,
+}
+
+struct Inner {
+    key: DedupKey,
+    seen: DashSet<u64, BuildHasherDefault<FxHasher>>,
+}
+
+/// Shared set of the keys of the edge updates loaded so far
+///
+/// Clones refer to the same set, so one handle can be used by parallel loaders and by
+/// consecutive loads into the same graph.
+#[derive(Clone)]
+pub struct Deduplicator(Arc<Inner>);
+
+impl Debug for Deduplicator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Deduplicator")
+            .field("key", &self.0.key)
+            .field("seen", &self.0.seen.len())
+            .finish()
+    }
+}
+
+impl PartialEq for Deduplicator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deduplicator {
+    /// A deduplicator that has not seen any updates yet
+    pub fn new(key: DedupKey) -> Self {
+        Self(Arc::new(Inner {
+            key,
+            seen: Default::default(),
+        }))
+    }
+
+    /// A deduplicator that has seen all edge updates of `graph`
+    pub fn for_graph<G: StaticGraphViewOps>(key: DedupKey, graph: &G) -> Self {
+        let dedup = Self::new(key);
+        for edge in graph.edges().explode() {
+            let (Ok(time), Ok(layer)) = (edge.time(), edge.layer_name()) else {
+                continue;
+            };
+            let layer: &str = &layer;
+            let props: Vec<_> = edge
+                .properties()
+                .temporal()
+                .iter()
+                .filter_map(|(name, view)| Some((name, view.latest()?)))
+                .collect();
+            let key = dedup.edge_key(
+                time,
+                &edge.src().name(),
+                &edge.dst().name(),
+                Some(layer),
+                &props,
+            );
+            if let Ok(key) = key {
+                dedup.insert(key);
+            }
+        }
+        dedup
+    }
+
+    pub fn key(&self) -> &DedupKey {
+        &self.0.key
+    }
+
+    /// Number of distinct updates seen
+    pub fn len(&self) -> usize {
+        self.0.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.seen.is_empty()
+    }
+
+    /// The key of an edge update
+    ///
+    /// Nodes are identified by their name and `None` is the default layer. With
+    /// [`DedupKey::EventId`], updates without the event id property are an error.
+    pub fn edge_key<S: AsRef<str>>(
+        &self,
+        time: i64,
+        src: &str,
+        dst: &str,
+        layer: Option<&str>,
+        props: &[(S, Prop)],
+    ) -> Result<u64, GraphError> {
+        match &self.0.key {
+            DedupKey::EventId(name) => props
+                .iter()
+                .find(|(key, _)| key.as_ref() == name.as_str())
+                .map(|(_, id)| calculate_hash(id))
+                .ok_or_else(|| GraphError::InvalidRecord(format!("missing event id '{name}'"))),
+            DedupKey::Content => {
+                let mut props: Vec<_> = props
+                    .iter()
+                    .map(|(name, value)| (name.as_ref(), value))
+                    .collect();
+                props.sort_by_key(|(name, _)| *name);
+                Ok(calculate_hash(&(
+                    time,
+                    src,
+                    dst,
+                    layer.unwrap_or(DEFAULT_LAYER),
+                    props,
+                )))
+            }
+        }
+    }
+
+    /// Mark `key` as seen, returning whether it is new
+    pub fn insert(&self, key: u64) -> bool {
+        self.0.seen.insert(key)
+    }
+
+    /// Forget `key` again, e.g. because its update could not be loaded after all
+    pub fn remove(&self, key: u64) {
+        self.0.seen.remove(&key);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn event_ids_are_seeded_from_the_graph() {
+        let g = Graph::new();
+        g.add_edge(1, "a", "b", [("event", "e1")], Some("pays"))
+            .unwrap();
+        g.add_edge(2, "a", "b", [("event", "e2")], None).unwrap();
+
+        let dedup = Deduplicator::for_graph(DedupKey::EventId("event".to_owned()), &g);
+        assert_eq!(dedup.len(), 2);
+        let key = dedup
+            .edge_key(5, "x", "y", None, &[("event", Prop::str("e1"))])
+            .unwrap();
+        assert!(!dedup.insert(key));
+        assert!(dedup
+            .edge_key(5, "x", "y", None, &[("other", Prop::str("e1"))])
+            .is_err());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::core::{utils::time::error::ParseTimeError, ArcStr, Prop, PropType};
+use crate::core::{
+    utils::{dead_letter::RejectedRecord, time::error::ParseTimeError},
+    ArcStr, Prop, PropType,
+};
 #[cfg(feature = "search")]
 use tantivy;
 #[cfg(feature = "search")]
@@ -29,6 +32,16 @@ pub enum GraphError {
         actual: PropType,
     },
 
+    #[error("Failed to convert value {value:?} of property {name} to {dtype:?}")]
+    PropertyCoercionError {
+        name: String,
+        value: Prop,
+        dtype: PropType,
+    },
+
+    #[error("Required property {name} is missing for {entity}")]
+    MissingRequiredProperty { name: String, entity: String },
+
     #[error("Tried to mutate constant property {name}: old value {old:?}, new value {new:?}")]
     ConstantPropertyMutationError { name: ArcStr, old: Prop, new: Prop },
 
@@ -44,6 +57,14 @@ pub enum GraphError {
     #[error("Edge already exists for nodes {0} {1}")]
     EdgeExistsError(u64, u64),
 
+    #[error("Conflicting values for {key} of {entity}: left {left:?}, right {right:?}")]
+    MergeConflict {
+        entity: String,
+        key: String,
+        left: Prop,
+        right: Prop,
+    },
+
     #[error("No Node with ID {0}")]
     NodeIdError(u64),
 
@@ -84,11 +105,17 @@ pub enum GraphError {
         source: std::io::Error,
     },
 
-    #[cfg(feature = "python")]
+    #[error("Failed to load record {0}")]
+    RejectedRecord(Box<RejectedRecord>),
+
+    #[error("Invalid error policy '{0}', expected one of 'fail', 'skip' or 'collect'")]
+    InvalidErrorPolicy(String),
+
+    #[cfg(feature = "arrow")]
     #[error("Failed to load graph: {0}")]
     LoadFailure(String),
 
-    #[cfg(feature = "python")]
+    #[cfg(feature = "arrow")]
     #[error(
         "Failed to load graph as the following columns are not present within the dataframe: {0}"
     )]
@@ -113,11 +140,54 @@ pub enum GraphError {
     )]
     BincodeVersionError(u32, u32),
 
+    #[error("Invalid generator parameter: {0}")]
+    InvalidGeneratorParameter(String),
+
     #[error("The layer_name function is only available once an edge has been exploded via .explode_layers() or .explode(). If you want to retrieve the layers for this edge you can use .layer_names")]
     LayerNameAPIError,
 
     #[error("The time function is only available once an edge has been exploded via .explode(). You may want to retrieve the history for this edge via .history(), or the earliest/latest time via earliest_time or latest_time")]
     TimeAPIError,
+
+    #[cfg(feature = "arrow")]
+    #[error("Failed to export graph: {0}")]
+    ExportFailure(String),
+
+    #[cfg(feature = "storage")]
+    #[error("Failed to convert properties for a disk graph: {0}")]
+    DiskPropertyConversion(String),
+
+    #[cfg(feature = "io")]
+    #[error("XML operation failed")]
+    XmlError {
+        #[from]
+        source: quick_xml::Error,
+    },
+
+    #[cfg(feature = "io")]
+    #[error("CSV operation failed")]
+    CsvError {
+        #[from]
+        source: csv::Error,
+    },
+
+    #[cfg(feature = "io")]
+    #[error("Invalid {format} file: {message}")]
+    InvalidFileFormat {
+        format: &'static str,
+        message: String,
+    },
+
+    #[error("Invalid record: {0}")]
+    InvalidRecord(String),
+
+    #[cfg(feature = "io")]
+    #[error("Invalid mapping: {0}")]
+    InvalidMapping(String),
+
+    #[cfg(feature = "io")]
+    #[error("Invalid time unit '{0}', expected one of raw, seconds, milliseconds or datetime")]
+    InvalidTimeUnit(String),
 }
 
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -1,3 +1,5 @@
+pub mod dead_letter;
+pub mod dedup;
 pub mod errors;
 pub mod hashing;
 pub mod time;
@@ -10,6 +10,8 @@ use crate::{
 };
 
 use super::time_from_input;
+#[cfg(any(feature = "io", feature = "arrow"))]
+use crate::core::storage::timeindex::TimeIndexEntry;
 
 pub trait DeletionOps: InternalDeletionOps + InternalAdditionOps + Sized {
     fn delete_edge<V: InputNode, T: TryIntoInputTime>(
@@ -38,3 +40,20 @@ pub trait DeletionOps: InternalDeletionOps + InternalAdditionOps + Sized {
         self.delete_edge(time, src, dst, layer)
     }
 }
+
+/// Delete an edge at `time` on any graph that can store deletions, including graphs whose
+/// views ignore them and therefore do not implement [`DeletionOps`]
+#[cfg(any(feature = "io", feature = "arrow"))]
+pub(crate) fn delete_edge_at<G: InternalAdditionOps + InternalDeletionOps, V: InputNode>(
+    graph: &G,
+    time: i64,
+    src: V,
+    dst: V,
+    layer: Option<&str>,
+) -> Result<(), GraphError> {
+    let t = TimeIndexEntry::new(time, graph.next_event_id());
+    let src = graph.resolve_node(src.id(), src.id_str());
+    let dst = graph.resolve_node(dst.id(), dst.id_str());
+    let layer = graph.resolve_layer(layer);
+    graph.internal_delete_edge(t, src, dst, layer)
+}
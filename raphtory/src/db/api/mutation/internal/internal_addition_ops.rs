@@ -14,6 +14,13 @@ pub trait InternalAdditionOps {
     /// get the sequence id for the next event
     fn next_event_id(&self) -> usize;
 
+    /// get the number of event ids handed out so far, which increases with every update
+    fn read_event_id(&self) -> usize;
+
+    /// get the number of mutations of the graph since it was created or loaded, which, unlike
+    /// the event id, also increases with constant property updates and node type changes
+    fn read_generation(&self) -> usize;
+
     /// map layer name to id and allocate a new layer if needed
     fn resolve_layer(&self, layer: Option<&str>) -> usize;
 
@@ -87,6 +94,16 @@ impl<G: DelegateAdditionOps> InternalAdditionOps for G {
         self.graph().next_event_id()
     }
 
+    #[inline(always)]
+    fn read_event_id(&self) -> usize {
+        self.graph().read_event_id()
+    }
+
+    #[inline(always)]
+    fn read_generation(&self) -> usize {
+        self.graph().read_generation()
+    }
+
     #[inline]
     fn resolve_layer(&self, layer: Option<&str>) -> usize {
         self.graph().resolve_layer(layer)
@@ -14,12 +14,16 @@ mod deletion_ops;
 mod import_ops;
 pub mod internal;
 mod property_addition_ops;
+mod schema_ops;
 
 pub use addition_ops::AdditionOps;
+#[cfg(any(feature = "io", feature = "arrow"))]
+pub(crate) use deletion_ops::delete_edge_at;
 pub use deletion_ops::DeletionOps;
 pub use import_ops::ImportOps;
 pub use property_addition_ops::PropertyAdditionOps;
 use raphtory_api::core::storage::timeindex::TimeIndexEntry;
+pub use schema_ops::SchemaOps;
 
 use self::internal::InternalAdditionOps;
 
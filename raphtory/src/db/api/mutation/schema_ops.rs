@@ -0,0 +1,225 @@
+use crate::{
+    core::{
+        entities::properties::schema::{PropertyDefinition, SchemaEnforcement},
+        utils::errors::GraphError,
+    },
+    db::api::{mutation::internal::InternalAdditionOps, view::StaticGraphViewOps},
+};
+
+/// Declare property schemas on a graph
+///
+/// Declared properties have a fixed type from the start. Updates that do not match the declared
+/// type are rejected, or converted if the enforcement is set to `SchemaEnforcement::Coerce`.
+/// The declared schema of any graph view can be read using `GraphViewOps::declared_schema`.
+pub trait SchemaOps: StaticGraphViewOps {
+    /// Declare a node property. If the definition is scoped, the scope is a node type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use raphtory::{
+    ///     core::{entities::properties::schema::PropertyDefinition, PropType},
+    ///     db::api::mutation::SchemaOps,
+    ///     prelude::*,
+    /// };
+    /// let g = Graph::new();
+    /// g.declare_node_property(
+    ///     PropertyDefinition::new("balance", PropType::F64)
+    ///         .scoped("wallet")
+    ///         .required(),
+    /// )
+    /// .unwrap();
+    /// assert!(g.add_node(0, "a", [("balance", "lots")], Some("wallet")).is_err());
+    /// assert!(g.add_node(0, "a", NO_PROPS, Some("wallet")).is_err());
+    /// g.add_node(0, "a", [("balance", 1.0)], Some("wallet")).unwrap();
+    /// ```
+    fn declare_node_property(&self, definition: PropertyDefinition) -> Result<(), GraphError>;
+
+    /// Declare an edge property. If the definition is scoped, the scope is a layer name.
+    fn declare_edge_property(&self, definition: PropertyDefinition) -> Result<(), GraphError>;
+
+    /// Declare a graph property. Scopes are ignored for graph properties.
+    fn declare_graph_property(&self, definition: PropertyDefinition) -> Result<(), GraphError>;
+
+    /// Set how non-conforming updates are handled for all declared properties
+    fn set_schema_enforcement(&self, enforcement: SchemaEnforcement);
+}
+
+impl<G: InternalAdditionOps + StaticGraphViewOps> SchemaOps for G {
+    fn declare_node_property(&self, definition: PropertyDefinition) -> Result<(), GraphError> {
+        if let Some(node_type) = &definition.scope {
+            self.node_meta().get_or_create_node_type_id(node_type);
+        }
+        self.node_meta().declare_prop(definition)?;
+        Ok(())
+    }
+
+    fn declare_edge_property(&self, definition: PropertyDefinition) -> Result<(), GraphError> {
+        if let Some(layer) = &definition.scope {
+            self.resolve_layer(Some(layer));
+        }
+        self.edge_meta().declare_prop(definition)?;
+        Ok(())
+    }
+
+    fn declare_graph_property(&self, definition: PropertyDefinition) -> Result<(), GraphError> {
+        self.resolve_graph_property(&definition.name, definition.constant);
+        self.graph_meta().declarations().declare(definition)
+    }
+
+    fn set_schema_enforcement(&self, enforcement: SchemaEnforcement) {
+        self.node_meta().declarations().set_enforcement(enforcement);
+        self.edge_meta().declarations().set_enforcement(enforcement);
+        self.graph_meta()
+            .declarations()
+            .set_enforcement(enforcement);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{core::PropType, prelude::*};
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    #[test]
+    fn declared_type_is_fixed_before_first_update() {
+        let g = Graph::new();
+        g.declare_node_property(PropertyDefinition::new("value", PropType::I64))
+            .unwrap();
+        assert!(matches!(
+            g.add_node(0, 1, [("value", "stray")], None),
+            Err(GraphError::PropertyTypeError { .. })
+        ));
+        assert!(g.add_node(0, 1, [("value", 1i32)], None).is_err());
+        g.add_node(0, 1, [("value", 1i64)], None).unwrap();
+    }
+
+    #[test]
+    fn declaring_conflicting_type_errors() {
+        let g = Graph::new();
+        g.add_edge(0, 1, 2, [("weight", 1.0)], None).unwrap();
+        assert!(g
+            .declare_edge_property(PropertyDefinition::new("weight", PropType::I64))
+            .is_err());
+        g.declare_edge_property(PropertyDefinition::new("weight", PropType::F64))
+            .unwrap();
+    }
+
+    #[test]
+    fn coercion() {
+        let g = Graph::new();
+        g.set_schema_enforcement(SchemaEnforcement::Coerce);
+        g.declare_node_property(PropertyDefinition::new("value", PropType::I64))
+            .unwrap();
+        g.declare_edge_property(PropertyDefinition::new("time", PropType::DTime))
+            .unwrap();
+        g.declare_graph_property(PropertyDefinition::new("count", PropType::U64).constant())
+            .unwrap();
+
+        g.add_node(0, 1, [("value", 1i32)], None).unwrap();
+        g.add_node(1, 1, [("value", "2")], None).unwrap();
+        assert!(matches!(
+            g.add_node(2, 1, [("value", "abc")], None),
+            Err(GraphError::PropertyCoercionError { .. })
+        ));
+        assert_eq!(
+            g.node(1)
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("value")
+                .unwrap()
+                .values(),
+            vec![Prop::I64(1), Prop::I64(2)]
+        );
+
+        g.add_edge(0, 1, 2, [("time", "2024-06-01T12:00:00Z")], None)
+            .unwrap();
+        assert_eq!(
+            g.edge(1, 2).unwrap().properties().get("time"),
+            Some(Prop::DTime(
+                Utc.from_utc_datetime(
+                    &NaiveDate::from_ymd_opt(2024, 6, 1)
+                        .unwrap()
+                        .and_hms_opt(12, 0, 0)
+                        .unwrap()
+                )
+            ))
+        );
+
+        g.add_constant_properties([("count", 3i32)]).unwrap();
+        assert_eq!(g.properties().get("count"), Some(Prop::U64(3)));
+    }
+
+    #[test]
+    fn required_properties_are_scoped() {
+        let g = Graph::new();
+        g.declare_node_property(
+            PropertyDefinition::new("balance", PropType::F64)
+                .scoped("wallet")
+                .required(),
+        )
+        .unwrap();
+        g.declare_edge_property(
+            PropertyDefinition::new("amount", PropType::F64)
+                .scoped("transfer")
+                .required(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            g.add_node(0, "a", NO_PROPS, Some("wallet")),
+            Err(GraphError::MissingRequiredProperty { .. })
+        ));
+        g.add_node(0, "a", [("balance", 1.0)], Some("wallet"))
+            .unwrap();
+        g.add_node(0, "b", NO_PROPS, Some("person")).unwrap();
+        g.add_node(0, "c", NO_PROPS, None).unwrap();
+
+        assert!(g.add_edge(0, "a", "b", NO_PROPS, Some("transfer")).is_err());
+        g.add_edge(0, "a", "b", [("amount", 2.0)], Some("transfer"))
+            .unwrap();
+        g.add_edge(0, "a", "b", NO_PROPS, Some("knows")).unwrap();
+    }
+
+    #[test]
+    fn required_properties_use_the_stored_node_type() {
+        let g = Graph::new();
+        g.declare_node_property(
+            PropertyDefinition::new("balance", PropType::F64)
+                .scoped("wallet")
+                .required(),
+        )
+        .unwrap();
+        g.add_node(0, "a", [("balance", 1.0)], Some("wallet"))
+            .unwrap();
+
+        assert!(matches!(
+            g.add_node(1, "a", NO_PROPS, None),
+            Err(GraphError::MissingRequiredProperty { .. })
+        ));
+        assert!(g.node("a").unwrap().add_updates(2, NO_PROPS).is_err());
+        g.add_node(3, "a", [("balance", 2.0)], None).unwrap();
+
+        let other = Graph::new();
+        other.add_node(4, "a", NO_PROPS, Some("person")).unwrap();
+        assert!(g.import_node(&other.node("a").unwrap(), true).is_err());
+        assert_eq!(g.node("a").unwrap().node_type(), Some("wallet".into()));
+    }
+
+    #[test]
+    fn declared_schema_is_visible_on_views() {
+        let g = Graph::new();
+        let definition = PropertyDefinition::new("name", PropType::Str)
+            .constant()
+            .required();
+        g.declare_node_property(definition.clone()).unwrap();
+        g.add_node(0, 1, NO_PROPS, None).unwrap();
+
+        let schema = g.window(0, 1).declared_schema();
+        assert_eq!(schema.nodes, vec![definition]);
+        assert!(schema.edges.is_empty());
+        assert_eq!(schema.enforcement, SchemaEnforcement::Strict);
+    }
+}
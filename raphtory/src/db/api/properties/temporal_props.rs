@@ -1,9 +1,10 @@
 use crate::{
-    core::{ArcStr, DocumentInput, Prop, PropUnwrap},
+    core::{ArcStr, DocumentInput, Prop, PropArray, PropUnwrap},
     db::{api::properties::internal::PropertiesOps, graph::views::deletion_graph::PersistentGraph},
     prelude::Graph,
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
 use std::{
     collections::{HashMap, HashSet},
     iter::Zip,
@@ -237,4 +238,24 @@ impl<P: PropertiesOps> PropUnwrap for TemporalPropertyView<P> {
     fn into_document(self) -> Option<DocumentInput> {
         self.latest().into_document()
     }
+
+    fn into_i8(self) -> Option<i8> {
+        self.latest().into_i8()
+    }
+
+    fn into_i16(self) -> Option<i16> {
+        self.latest().into_i16()
+    }
+
+    fn into_decimal(self) -> Option<Decimal> {
+        self.latest().into_decimal()
+    }
+
+    fn into_bytes(self) -> Option<Arc<[u8]>> {
+        self.latest().into_bytes()
+    }
+
+    fn into_array(self) -> Option<PropArray> {
+        self.latest().into_array()
+    }
 }
@@ -1,6 +1,11 @@
 use crate::{
     core::{
-        entities::{graph::tgraph::InternalGraph, nodes::node_ref::AsNodeRef, LayerIds, VID},
+        entities::{
+            graph::{memory::MemoryStats, tgraph::InternalGraph},
+            nodes::node_ref::AsNodeRef,
+            properties::schema::DeclaredSchema,
+            LayerIds, VID,
+        },
         storage::timeindex::AsTime,
         utils::errors::GraphError,
         ArcStr, OptionAsStr,
@@ -112,6 +117,17 @@ pub trait GraphViewOps<'graph>: BoxableGraphView + Sized + Clone + 'graph {
     ///
     /// A view of the properties of the graph
     fn properties(&self) -> Properties<Self>;
+
+    /// Get the property declarations of this graph.
+    ///
+    /// Declarations are not affected by view filters.
+    fn declared_schema(&self) -> DeclaredSchema;
+
+    /// Estimate the memory used by the graph, broken down by component, layer and property key.
+    ///
+    /// Views report the memory of the underlying graph. Returns `None` for graphs that are not
+    /// stored in memory.
+    fn memory_stats(&self) -> Option<MemoryStats>;
 }
 
 impl<'graph, G: BoxableGraphView + Sized + Clone + 'graph> GraphViewOps<'graph> for G {
@@ -436,6 +452,19 @@ impl<'graph, G: BoxableGraphView + Sized + Clone + 'graph> GraphViewOps<'graph>
     fn properties(&self) -> Properties<Self> {
         Properties::new(self.clone())
     }
+
+    fn declared_schema(&self) -> DeclaredSchema {
+        DeclaredSchema {
+            enforcement: self.node_meta().declarations().enforcement(),
+            nodes: self.node_meta().declarations().definitions(),
+            edges: self.edge_meta().declarations().definitions(),
+            graph: self.graph_meta().declarations().definitions(),
+        }
+    }
+
+    fn memory_stats(&self) -> Option<MemoryStats> {
+        self.core_memory_stats()
+    }
 }
 
 pub trait StaticGraphViewOps: for<'graph> GraphViewOps<'graph> + 'static {}
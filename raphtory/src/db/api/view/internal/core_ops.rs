@@ -2,6 +2,7 @@ use crate::{
     core::{
         entities::{
             edges::edge_ref::EdgeRef,
+            graph::memory::MemoryStats,
             nodes::node_ref::NodeRef,
             properties::{graph_meta::GraphMeta, props::Meta, tprop::TProp},
             LayerIds, ELID, VID,
@@ -61,6 +62,11 @@ pub trait CoreGraphOps {
 
     fn graph_meta(&self) -> &GraphMeta;
 
+    /// Estimate the memory used by the in-memory storage of the graph (`None` for other storage)
+    fn core_memory_stats(&self) -> Option<MemoryStats> {
+        None
+    }
+
     fn get_layer_name(&self, layer_id: usize) -> ArcStr;
 
     fn get_layer_id(&self, name: &str) -> Option<usize>;
@@ -239,6 +245,11 @@ impl<G: DelegateCoreOps + ?Sized> CoreGraphOps for G {
         self.graph().graph_meta()
     }
 
+    #[inline]
+    fn core_memory_stats(&self) -> Option<MemoryStats> {
+        self.graph().core_memory_stats()
+    }
+
     #[inline]
     fn get_layer_name(&self, layer_id: usize) -> ArcStr {
         self.graph().get_layer_name(layer_id)
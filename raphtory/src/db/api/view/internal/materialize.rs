@@ -2,9 +2,11 @@ use crate::{
     core::{
         entities::{
             edges::edge_ref::EdgeRef,
-            graph::tgraph::InternalGraph,
+            graph::{memory::MemoryStats, tgraph::InternalGraph},
             nodes::node_ref::NodeRef,
-            properties::{graph_meta::GraphMeta, props::Meta, tprop::TProp},
+            properties::{
+                graph_meta::GraphMeta, props::Meta, schema::DeclaredSchema, tprop::TProp,
+            },
             LayerIds, EID, ELID, VID,
         },
         storage::{locked_view::LockedView, timeindex::TimeIndexEntry},
@@ -38,7 +40,10 @@ use crate::{
 use chrono::{DateTime, Utc};
 use enum_dispatch::enum_dispatch;
 use serde::{de::Error, Deserialize, Deserializer, Serialize};
-use std::path::Path;
+use std::{
+    io::{Cursor, ErrorKind, Read, Write},
+    path::Path,
+};
 
 #[cfg(feature = "storage")]
 use crate::disk_graph::graph_impl::DiskGraph;
@@ -119,16 +124,15 @@ impl MaterializedGraph {
         let mut reader = std::io::BufReader::new(f);
         if force {
             let _: String = bincode::deserialize_from(&mut reader)?;
-            let data: Self = bincode::deserialize_from(&mut reader)?;
-            Ok(data)
         } else {
             let version: u32 = bincode::deserialize_from(&mut reader)?;
             if version != BINCODE_VERSION {
                 return Err(GraphError::BincodeVersionError(version, BINCODE_VERSION));
             }
-            let data: Self = bincode::deserialize_from(&mut reader)?;
-            Ok(data)
         }
+        let data: Self = bincode::deserialize_from(&mut reader)?;
+        data.read_declarations(reader)?;
+        Ok(data)
     }
 
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), GraphError> {
@@ -138,7 +142,8 @@ impl MaterializedGraph {
             version: BINCODE_VERSION,
             graph: self.clone(),
         };
-        Ok(bincode::serialize_into(&mut writer, &versioned_data)?)
+        bincode::serialize_into(&mut writer, &versioned_data)?;
+        self.write_declarations(writer)
     }
 
     pub fn bincode(&self) -> Result<Vec<u8>, GraphError> {
@@ -146,7 +151,8 @@ impl MaterializedGraph {
             version: BINCODE_VERSION,
             graph: self.clone(),
         };
-        let encoded = bincode::serialize(&versioned_data)?;
+        let mut encoded = bincode::serialize(&versioned_data)?;
+        self.write_declarations(&mut encoded)?;
         Ok(encoded)
     }
 
@@ -155,9 +161,52 @@ impl MaterializedGraph {
         if version != BINCODE_VERSION {
             return Err(GraphError::BincodeVersionError(version, BINCODE_VERSION));
         }
-        let g: VersionedGraph<MaterializedGraph> = bincode::deserialize(b)?;
+        let mut reader = Cursor::new(b);
+        let g: VersionedGraph<MaterializedGraph> = bincode::deserialize_from(&mut reader)?;
+        g.graph.read_declarations(reader)?;
         Ok(g.graph)
     }
+
+    /// Declared property schemas are appended after the graph and only if there are any, such
+    /// that graphs without declarations are stored exactly as before they existed
+    fn write_declarations(&self, writer: impl Write) -> Result<(), GraphError> {
+        let schema = self.declared_schema();
+        if schema != DeclaredSchema::default() {
+            bincode::serialize_into(writer, &schema)?;
+        }
+        Ok(())
+    }
+
+    fn read_declarations(&self, reader: impl Read) -> Result<(), GraphError> {
+        let schema: DeclaredSchema = match bincode::deserialize_from(reader) {
+            Ok(schema) => schema,
+            Err(err) => match *err {
+                bincode::ErrorKind::Io(err) if err.kind() == ErrorKind::UnexpectedEof => {
+                    return Ok(())
+                }
+                err => return Err(Box::new(err).into()),
+            },
+        };
+        for definition in schema.nodes {
+            self.node_meta().declare_prop(definition)?;
+        }
+        for definition in schema.edges {
+            self.edge_meta().declare_prop(definition)?;
+        }
+        for definition in schema.graph {
+            self.graph_meta().declarations().declare(definition)?;
+        }
+        self.node_meta()
+            .declarations()
+            .set_enforcement(schema.enforcement);
+        self.edge_meta()
+            .declarations()
+            .set_enforcement(schema.enforcement);
+        self.graph_meta()
+            .declarations()
+            .set_enforcement(schema.enforcement);
+        Ok(())
+    }
 }
 
 #[enum_dispatch]
@@ -185,13 +234,23 @@ where
 #[cfg(test)]
 mod test_materialised_graph_dispatch {
     use crate::{
-        core::entities::LayerIds,
-        db::api::view::internal::{
-            CoreGraphOps, EdgeFilterOps, InternalLayerOps, InternalMaterialize, MaterializedGraph,
-            TimeSemantics,
+        core::{
+            entities::{
+                properties::schema::{DeclaredSchema, PropertyDefinition, SchemaEnforcement},
+                LayerIds,
+            },
+            PropType,
+        },
+        db::api::{
+            mutation::SchemaOps,
+            view::internal::{
+                CoreGraphOps, EdgeFilterOps, InternalLayerOps, InternalMaterialize,
+                MaterializedGraph, TimeSemantics,
+            },
         },
         prelude::*,
     };
+    use std::path::PathBuf;
 
     #[test]
     fn materialised_graph_has_core_ops() {
@@ -238,4 +297,39 @@ mod test_materialised_graph_dispatch {
         let v = mg.add_node(0, 1, NO_PROPS, None).unwrap();
         assert_eq!(v.id(), 1)
     }
+
+    #[test]
+    fn declarations_survive_save_and_load() {
+        let g = Graph::new();
+        g.set_schema_enforcement(SchemaEnforcement::Coerce);
+        g.declare_node_property(PropertyDefinition::new("value", PropType::I64).required())
+            .unwrap();
+        g.declare_edge_property(PropertyDefinition::new("weight", PropType::F64).scoped("a"))
+            .unwrap();
+        g.add_node(0, 1, [("value", 1i64)], None).unwrap();
+        let mg = MaterializedGraph::from(g.clone());
+
+        let loaded = MaterializedGraph::from_bincode(&mg.bincode().unwrap()).unwrap();
+        assert_eq!(loaded.declared_schema(), g.declared_schema());
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("graph.bincode");
+        mg.save_to_file(&path).unwrap();
+        let loaded = MaterializedGraph::load_from_file(&path, false).unwrap();
+        assert_eq!(loaded.declared_schema(), g.declared_schema());
+        assert!(loaded.add_node(1, 1, NO_PROPS, None).is_err());
+    }
+
+    #[test]
+    fn graphs_without_declarations_keep_the_version_one_format() {
+        let path: PathBuf = [
+            env!("CARGO_MANIFEST_DIR"),
+            "../python/tests/test_graphdb/graph.bincode",
+        ]
+        .iter()
+        .collect();
+        let g = MaterializedGraph::load_from_file(&path, false).unwrap();
+        assert_eq!(g.declared_schema(), DeclaredSchema::default());
+        assert_eq!(g.bincode().unwrap(), std::fs::read(&path).unwrap());
+    }
 }
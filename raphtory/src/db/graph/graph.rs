@@ -17,7 +17,10 @@
 //!
 
 use crate::{
-    core::{entities::graph::tgraph::InternalGraph, utils::errors::GraphError},
+    core::{
+        entities::graph::tgraph::InternalGraph, storage::columnar::CompactionStats,
+        utils::errors::GraphError,
+    },
     db::api::{
         mutation::internal::InheritMutationOps,
         view::internal::{Base, InheritViewOps, MaterializedGraph, Static},
@@ -193,6 +196,34 @@ impl Graph {
     pub fn persistent_graph(&self) -> PersistentGraph {
         PersistentGraph::from_internal_graph(self.0.clone())
     }
+
+    /// Compact the temporal property histories of the graph into sorted, columnar blocks
+    ///
+    /// Histories are only converted where this reduces memory usage. Timestamps are
+    /// delta-encoded and values are run-length or dictionary encoded where that saves space.
+    /// The graph can still be updated after compaction, new updates are kept separately until
+    /// the next call to `compact`. Overwriting a compacted update decompacts its history.
+    ///
+    /// Returns:
+    ///
+    /// The number of compacted histories and updates and the estimated memory used by
+    /// temporal property histories before and after compaction
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use raphtory::prelude::*;
+    /// let g = Graph::new();
+    /// for t in 0..1000 {
+    ///     g.add_node(t, 1, [("value", t / 100)], None).unwrap();
+    /// }
+    /// let stats = g.compact();
+    /// assert_eq!(stats.compacted_updates, 1000);
+    /// assert!(stats.bytes_after < stats.bytes_before);
+    /// ```
+    pub fn compact(&self) -> CompactionStats {
+        self.0.inner().compact()
+    }
 }
 
 #[cfg(test)]
@@ -2891,4 +2922,71 @@ mod db_tests {
             ]
         );
     }
+
+    #[test]
+    fn compacted_graph_is_unchanged() {
+        let g = Graph::new();
+        for t in 0..500 {
+            g.add_node(t, 1, [("value", t / 50), ("flag", t % 2)], None)
+                .unwrap();
+            g.add_edge(t, 1, 2, [("weight", (t / 100) as f64)], Some("a"))
+                .unwrap();
+        }
+        g.add_properties(0, [("graph_prop", 1)]).unwrap();
+        let histories = |g: &Graph| {
+            let node = g.node(1).unwrap();
+            let edge = g.edge(1, 2).unwrap();
+            (
+                node.properties()
+                    .temporal()
+                    .get("value")
+                    .unwrap()
+                    .iter()
+                    .collect_vec(),
+                node.properties()
+                    .temporal()
+                    .get("flag")
+                    .unwrap()
+                    .iter()
+                    .collect_vec(),
+                edge.properties()
+                    .temporal()
+                    .get("weight")
+                    .unwrap()
+                    .iter()
+                    .collect_vec(),
+            )
+        };
+        let expected = histories(&g);
+
+        let stats = g.compact();
+        assert_eq!(stats.compacted_histories, 3);
+        assert_eq!(stats.compacted_updates, 1500);
+        assert!(stats.bytes_after < stats.bytes_before);
+        assert_eq!(histories(&g), expected);
+        assert_eq!(
+            g.window(100, 102)
+                .node(1)
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("value")
+                .unwrap()
+                .values(),
+            vec![Prop::I64(2), Prop::I64(2)]
+        );
+
+        // the graph can still be updated after compaction
+        g.add_node(1000, 1, [("value", 100i64)], None).unwrap();
+        assert_eq!(
+            g.node(1).unwrap().properties().get("value"),
+            Some(Prop::I64(100))
+        );
+        let stats = g.compact();
+        assert_eq!(stats.compacted_updates, 1);
+        assert_eq!(
+            g.before(1000).node(1).unwrap().properties().get("value"),
+            Some(Prop::I64(9))
+        );
+    }
 }
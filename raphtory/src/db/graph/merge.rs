@@ -0,0 +1,668 @@
+//! Merging of one graph (or graph view) into an existing `Graph`.
+//!
+//! All temporal history of the other graph (node updates, exploded edge updates, edge deletions and
+//! temporal graph properties) is appended to the graph. Conflicting constant properties and node
+//! types are resolved using a [`MergePolicy`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use raphtory::{db::graph::merge::MergePolicy, prelude::*};
+//!
+//! let left = Graph::new();
+//! left.add_edge(0, "Alice", "Bob", NO_PROPS, None).unwrap();
+//!
+//! let right = Graph::new();
+//! right.add_edge(1, "Alice", "Bob", NO_PROPS, Some("friends")).unwrap();
+//!
+//! let report = left.merge(&right, MergePolicy::keep_right()).unwrap();
+//! assert_eq!(report.edges_merged, 1);
+//! assert_eq!(left.count_temporal_edges(), 2);
+//! ```
+
+use crate::{
+    core::{
+        entities::LayerIds,
+        storage::timeindex::{AsTime, TimeIndexEntry},
+        utils::errors::GraphError,
+        ArcStr, OptionAsStr, Prop,
+    },
+    db::{
+        api::{
+            mutation::{
+                internal::{InternalAdditionOps, InternalDeletionOps},
+                time_from_input,
+            },
+            storage::{nodes::node_storage_ops::NodeStorageOps, tprop_storage_ops::TPropOps},
+            view::internal::CoreGraphOps,
+        },
+        graph::graph::Graph,
+    },
+    prelude::*,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    sync::Arc,
+};
+
+/// The kind of entity attribute that was in conflict during a merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    NodeType,
+    NodeProperty,
+    EdgeProperty,
+    GraphProperty,
+}
+
+/// A conflicting value encountered while merging two graphs
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub kind: ConflictKind,
+    /// Human readable description of the entity (node name, `src->dst` pair with layer, or `graph`)
+    pub entity: String,
+    /// The property key, or `node_type` for node type conflicts
+    pub key: ArcStr,
+    /// The value in the graph being merged into
+    pub left: Prop,
+    /// The value in the graph being merged from
+    pub right: Prop,
+}
+
+/// Closure used by [`ConflictResolution::Custom`] to pick the value to keep
+pub type ConflictResolver = Arc<dyn Fn(&MergeConflict) -> Result<Prop, GraphError> + Send + Sync>;
+
+/// How to resolve conflicting constant properties and node types
+#[derive(Clone, Default)]
+pub enum ConflictResolution {
+    /// Keep the value of the graph being merged into
+    KeepLeft,
+    /// Overwrite with the value of the graph being merged from
+    KeepRight,
+    /// Abort the merge with a `GraphError::MergeConflict`
+    #[default]
+    Error,
+    /// Compute the value to keep using a closure. For node type conflicts the closure has to return
+    /// a `Prop::Str` with the new node type.
+    Custom(ConflictResolver),
+}
+
+impl Debug for ConflictResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictResolution::KeepLeft => write!(f, "KeepLeft"),
+            ConflictResolution::KeepRight => write!(f, "KeepRight"),
+            ConflictResolution::Error => write!(f, "Error"),
+            ConflictResolution::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// Policy for merging graphs
+///
+/// Combines a [`ConflictResolution`] with an optional mapping of layer names which is used to
+/// reconcile layers of the other graph with the layers of the graph being merged into.
+#[derive(Debug, Clone, Default)]
+pub struct MergePolicy {
+    resolution: ConflictResolution,
+    layer_map: HashMap<ArcStr, ArcStr>,
+}
+
+impl MergePolicy {
+    pub fn new(resolution: ConflictResolution) -> Self {
+        Self {
+            resolution,
+            layer_map: HashMap::new(),
+        }
+    }
+
+    pub fn keep_left() -> Self {
+        Self::new(ConflictResolution::KeepLeft)
+    }
+
+    pub fn keep_right() -> Self {
+        Self::new(ConflictResolution::KeepRight)
+    }
+
+    pub fn error() -> Self {
+        Self::new(ConflictResolution::Error)
+    }
+
+    pub fn custom<F: Fn(&MergeConflict) -> Result<Prop, GraphError> + Send + Sync + 'static>(
+        resolver: F,
+    ) -> Self {
+        Self::new(ConflictResolution::Custom(Arc::new(resolver)))
+    }
+
+    /// Add updates of layer `from` in the other graph to layer `to` (use `_default` to refer to the
+    /// default layer)
+    pub fn map_layer<F: Into<ArcStr>, T: Into<ArcStr>>(mut self, from: F, to: T) -> Self {
+        self.layer_map.insert(from.into(), to.into());
+        self
+    }
+
+    pub fn resolution(&self) -> &ConflictResolution {
+        &self.resolution
+    }
+
+    /// Name of the target layer or `None` for the default layer
+    fn target_layer(&self, layer: ArcStr) -> Option<ArcStr> {
+        let target = self.layer_map.get(&layer).cloned().unwrap_or(layer);
+        if target == "_default" {
+            None
+        } else {
+            Some(target)
+        }
+    }
+
+    fn resolve(
+        &self,
+        conflict: MergeConflict,
+        report: &mut MergeReport,
+    ) -> Result<Prop, GraphError> {
+        let value = match &self.resolution {
+            ConflictResolution::KeepLeft => conflict.left.clone(),
+            ConflictResolution::KeepRight => conflict.right.clone(),
+            ConflictResolution::Error => {
+                return Err(GraphError::MergeConflict {
+                    entity: conflict.entity,
+                    key: conflict.key.to_string(),
+                    left: conflict.left,
+                    right: conflict.right,
+                })
+            }
+            ConflictResolution::Custom(resolver) => resolver(&conflict)?,
+        };
+        report.conflicts.push(conflict);
+        Ok(value)
+    }
+
+    fn resolve_constant(
+        &self,
+        kind: ConflictKind,
+        entity: impl Fn() -> String,
+        left: Option<Prop>,
+        right: (ArcStr, Prop),
+        report: &mut MergeReport,
+    ) -> Result<Option<(ArcStr, Prop)>, GraphError> {
+        let (key, right) = right;
+        match left {
+            None => Ok(Some((key, right))),
+            Some(left) if left == right => Ok(None),
+            Some(left) => {
+                let conflict = MergeConflict {
+                    kind,
+                    entity: entity(),
+                    key: key.clone(),
+                    left: left.clone(),
+                    right,
+                };
+                let value = self.resolve(conflict, report)?;
+                Ok((value != left).then_some((key, value)))
+            }
+        }
+    }
+}
+
+/// Summary of a merge
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    /// Number of nodes that did not exist before the merge
+    pub nodes_added: usize,
+    /// Number of nodes that existed in both graphs
+    pub nodes_merged: usize,
+    /// Number of edges that did not exist before the merge
+    pub edges_added: usize,
+    /// Number of edges that existed in both graphs
+    pub edges_merged: usize,
+    /// Number of node updates (including temporal property updates) appended
+    pub node_updates: usize,
+    /// Number of exploded edge updates appended
+    pub edge_updates: usize,
+    /// Number of edge deletions appended
+    pub edge_deletions: usize,
+    /// Number of temporal graph property updates appended
+    pub graph_updates: usize,
+    /// Layers that were created by the merge
+    pub new_layers: Vec<ArcStr>,
+    /// All conflicts that were resolved, in the order they were encountered
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl Graph {
+    /// Merge another graph (or graph view) into this graph
+    ///
+    /// All temporal history of `other` is appended to this graph. Conflicting constant properties
+    /// and node types are resolved using `policy` and layers are matched by name after applying
+    /// the layer mapping of the policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The graph to merge into this graph
+    /// * `policy` - The policy used to resolve conflicts
+    ///
+    /// Returns:
+    ///
+    /// A report of what was merged. If the merge fails, updates that were already applied are not
+    /// rolled back.
+    pub fn merge<'graph, G: GraphViewOps<'graph>>(
+        &self,
+        other: &G,
+        policy: MergePolicy,
+    ) -> Result<MergeReport, GraphError> {
+        let mut report = MergeReport::default();
+
+        // make sure we preserve all layers even if they are empty
+        for layer in other.unique_layers() {
+            if let Some(layer) = policy.target_layer(layer) {
+                if self.get_layer_id(&layer).is_none() {
+                    self.resolve_layer(Some(&layer));
+                    report.new_layers.push(layer);
+                }
+            }
+        }
+
+        for v in other.nodes().iter() {
+            let v_id = match self.node(v.id()) {
+                Some(node) => {
+                    report.nodes_merged += 1;
+                    node.node
+                }
+                None => {
+                    report.nodes_added += 1;
+                    self.resolve_node(v.id(), Some(&v.name()))
+                }
+            };
+
+            match (self.0.inner().node_type(v_id), v.node_type()) {
+                (Some(left), Some(right)) if left != right => {
+                    let conflict = MergeConflict {
+                        kind: ConflictKind::NodeType,
+                        entity: v.name(),
+                        key: "node_type".into(),
+                        left: Prop::Str(left),
+                        right: Prop::Str(right),
+                    };
+                    match policy.resolve(conflict, &mut report)? {
+                        Prop::Str(node_type) => {
+                            self.0.inner().set_node_type(v_id, &node_type)?;
+                        }
+                        value => {
+                            return Err(GraphError::NodeTypeError(format!(
+                                "Invalid node type {value:?} for node {}",
+                                v.name()
+                            )))
+                        }
+                    }
+                }
+                (None, Some(right)) => {
+                    self.resolve_node_type(v_id, Some(&right))?;
+                }
+                _ => {}
+            }
+
+            // properties added together share the time index entry of their update, which is
+            // replayed as one update, keeping the order of updates at the same time
+            let window = TimeIndexEntry::range(
+                other.view_start().unwrap_or(i64::MIN)..other.view_end().unwrap_or(i64::MAX),
+            );
+            let node_entry = other.core_node_entry(v.node);
+            let mut updates: BTreeMap<TimeIndexEntry, Vec<(ArcStr, Prop)>> = BTreeMap::new();
+            for (prop_id, name) in other
+                .node_meta()
+                .temporal_prop_meta()
+                .get_keys()
+                .iter()
+                .enumerate()
+            {
+                for (t, prop) in node_entry.tprop(prop_id).iter_window(window.clone()) {
+                    updates.entry(t).or_default().push((name.clone(), prop));
+                }
+            }
+            let mut with_props: Vec<i64> = updates.keys().map(|t| t.t()).collect();
+            with_props.dedup();
+            for t in v.history() {
+                if with_props.binary_search(&t).is_err() {
+                    updates.insert(TimeIndexEntry::start(t), vec![]);
+                }
+            }
+            for (t, props) in updates {
+                self.add_node(t.t(), v.id(), props, None)?;
+                report.node_updates += 1;
+            }
+
+            let node = self.node(v.id()).expect("node added");
+            let mut updates = vec![];
+            for prop in v.properties().constant().iter() {
+                let left = node.properties().constant().get(&prop.0);
+                if let Some(update) = policy.resolve_constant(
+                    ConflictKind::NodeProperty,
+                    || v.name(),
+                    left,
+                    prop,
+                    &mut report,
+                )? {
+                    updates.push(update);
+                }
+            }
+            node.update_constant_properties(updates)?;
+        }
+
+        for e in other.edges() {
+            if self.has_edge(e.src().id(), e.dst().id()) {
+                report.edges_merged += 1;
+            } else {
+                report.edges_added += 1;
+            }
+            for ee in e.explode_layers() {
+                let layer_id = *ee.edge.layer().expect("exploded layers");
+                let layer_name = policy.target_layer(other.get_layer_name(layer_id));
+                let layer_name = layer_name.as_str();
+
+                for ee in ee.explode() {
+                    self.add_edge(
+                        ee.time().expect("exploded edge"),
+                        ee.src().id(),
+                        ee.dst().id(),
+                        ee.properties().temporal().collect_properties(),
+                        layer_name,
+                    )?;
+                    report.edge_updates += 1;
+                }
+
+                let deletions = other.edge_deletion_history(ee.edge, &LayerIds::One(layer_id));
+                if !deletions.is_empty() {
+                    let src_id = self.resolve_node(e.src().id(), Some(&e.src().name()));
+                    let dst_id = self.resolve_node(e.dst().id(), Some(&e.dst().name()));
+                    let target_layer_id = self.resolve_layer(layer_name);
+                    for t in deletions {
+                        let ti = time_from_input(self, t)?;
+                        self.internal_delete_edge(ti, src_id, dst_id, target_layer_id)?;
+                        report.edge_deletions += 1;
+                    }
+                }
+
+                let edge = self.edge(e.src().id(), e.dst().id()).expect("edge added");
+                let target_layer_id = self.resolve_layer(layer_name);
+                let mut updates = vec![];
+                for prop in ee.properties().constant().iter() {
+                    let left = self
+                        .edge_meta()
+                        .const_prop_meta()
+                        .get_id(&prop.0)
+                        .and_then(|id| {
+                            self.get_const_edge_prop(edge.edge, id, LayerIds::One(target_layer_id))
+                        });
+                    if let Some(update) = policy.resolve_constant(
+                        ConflictKind::EdgeProperty,
+                        || {
+                            format!(
+                                "{}->{} (layer {})",
+                                e.src().name(),
+                                e.dst().name(),
+                                layer_name.unwrap_or("_default")
+                            )
+                        },
+                        left,
+                        prop,
+                        &mut report,
+                    )? {
+                        updates.push(update);
+                    }
+                }
+                edge.update_constant_properties(updates, layer_name)?;
+            }
+        }
+
+        for (name, prop_view) in other.properties().temporal().iter() {
+            for (t, prop) in prop_view.iter() {
+                self.add_properties(t, [(name.clone(), prop)])?;
+                report.graph_updates += 1;
+            }
+        }
+
+        let mut updates = vec![];
+        for prop in other.properties().constant().iter() {
+            let left = self.properties().constant().get(&prop.0);
+            if let Some(update) = policy.resolve_constant(
+                ConflictKind::GraphProperty,
+                || "graph".to_string(),
+                left,
+                prop,
+                &mut report,
+            )? {
+                updates.push(update);
+            }
+        }
+        self.update_constant_properties(updates)?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod merge_tests {
+    use super::*;
+    use crate::db::graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph};
+
+    #[test]
+    fn test_merge_appends_history() {
+        let left = Graph::new();
+        left.add_edge(0, 1, 2, [("weight", 1i64)], None).unwrap();
+        left.add_node(0, 3, NO_PROPS, None).unwrap();
+
+        let right = Graph::new();
+        right.add_edge(1, 1, 2, [("weight", 2i64)], None).unwrap();
+        right.add_edge(2, 2, 4, NO_PROPS, Some("layer")).unwrap();
+
+        let report = left.merge(&right, MergePolicy::error()).unwrap();
+
+        assert_eq!(report.nodes_added, 1);
+        assert_eq!(report.nodes_merged, 2);
+        assert_eq!(report.edges_added, 1);
+        assert_eq!(report.edges_merged, 1);
+        assert_eq!(report.edge_updates, 2);
+        assert_eq!(report.new_layers, vec![ArcStr::from("layer")]);
+        assert!(report.conflicts.is_empty());
+
+        assert_eq!(left.count_nodes(), 4);
+        assert_eq!(left.count_temporal_edges(), 3);
+        assert_eq!(
+            left.edge(1, 2)
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("weight")
+                .unwrap()
+                .values(),
+            vec![Prop::I64(1), Prop::I64(2)]
+        );
+        assert_eq!(
+            left.edge(2, 4).unwrap().layer_names().collect::<Vec<_>>(),
+            vec!["layer"]
+        );
+    }
+
+    #[test]
+    fn test_merge_replays_node_updates_once() {
+        let g = Graph::new();
+        g.add_node(1, 1, [("a", 1i64), ("b", 2i64)], None).unwrap();
+        g.add_node(1, 1, [("c", 5i64)], None).unwrap();
+        g.add_node(1, 1, [("a", 3i64)], None).unwrap();
+        g.add_node(2, 1, NO_PROPS, None).unwrap();
+
+        let merged = Graph::new();
+        let report = merged.merge(&g, MergePolicy::error()).unwrap();
+        assert_eq!(report.node_updates, 4);
+        let node = merged.node(1).unwrap();
+        assert_eq!(node.history(), g.node(1).unwrap().history());
+        assert_eq!(
+            node.properties()
+                .temporal()
+                .get("a")
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![(1, Prop::I64(1)), (1, Prop::I64(3))]
+        );
+        assert_graph_equal(&merged, &g);
+    }
+
+    #[test]
+    fn test_merge_into_empty_graph() {
+        let g = Graph::new();
+        g.add_edge(0, 1, 2, [("a", 1)], Some("x")).unwrap();
+        g.add_node(1, 3, [("b", "test")], Some("type")).unwrap();
+        g.node(3)
+            .unwrap()
+            .add_constant_properties([("c", 1u64)])
+            .unwrap();
+
+        let merged = Graph::new();
+        merged.merge(&g, MergePolicy::error()).unwrap();
+        assert_graph_equal(&merged, &g);
+        assert_eq!(merged.node(3).unwrap().node_type().unwrap(), "type");
+        assert_eq!(
+            merged.node(3).unwrap().properties().get("c"),
+            Some(Prop::U64(1))
+        );
+    }
+
+    #[test]
+    fn test_merge_constant_property_policies() {
+        let make_left = || {
+            let g = Graph::new();
+            g.add_node(0, 1, NO_PROPS, None)
+                .unwrap()
+                .add_constant_properties([("name", "left")])
+                .unwrap();
+            g
+        };
+        let right = Graph::new();
+        right
+            .add_node(1, 1, NO_PROPS, None)
+            .unwrap()
+            .add_constant_properties([("name", "right")])
+            .unwrap();
+
+        let g = make_left();
+        let report = g.merge(&right, MergePolicy::keep_left()).unwrap();
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].kind, ConflictKind::NodeProperty);
+        assert_eq!(
+            g.node(1).unwrap().properties().get("name"),
+            Some(Prop::str("left"))
+        );
+
+        let g = make_left();
+        g.merge(&right, MergePolicy::keep_right()).unwrap();
+        assert_eq!(
+            g.node(1).unwrap().properties().get("name"),
+            Some(Prop::str("right"))
+        );
+
+        let g = make_left();
+        assert!(matches!(
+            g.merge(&right, MergePolicy::error()),
+            Err(GraphError::MergeConflict { .. })
+        ));
+
+        let g = make_left();
+        g.merge(
+            &right,
+            MergePolicy::custom(|conflict| {
+                Ok(Prop::str(format!(
+                    "{}+{}",
+                    conflict.left.clone().unwrap_str(),
+                    conflict.right.clone().unwrap_str()
+                )))
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            g.node(1).unwrap().properties().get("name"),
+            Some(Prop::str("left+right"))
+        );
+    }
+
+    #[test]
+    fn test_merge_node_type_conflict() {
+        let left = Graph::new();
+        left.add_node(0, 1, NO_PROPS, Some("a")).unwrap();
+        let right = Graph::new();
+        right.add_node(1, 1, NO_PROPS, Some("b")).unwrap();
+
+        left.merge(&right, MergePolicy::keep_left()).unwrap();
+        assert_eq!(left.node(1).unwrap().node_type().unwrap(), "a");
+
+        let report = left.merge(&right, MergePolicy::keep_right()).unwrap();
+        assert_eq!(report.conflicts[0].kind, ConflictKind::NodeType);
+        assert_eq!(left.node(1).unwrap().node_type().unwrap(), "b");
+    }
+
+    #[test]
+    fn test_merge_edge_constant_properties_and_layer_mapping() {
+        let left = Graph::new();
+        left.add_edge(0, 1, 2, NO_PROPS, Some("friends"))
+            .unwrap()
+            .add_constant_properties([("since", 2000i64)], Some("friends"))
+            .unwrap();
+
+        let right = Graph::new();
+        right
+            .add_edge(1, 1, 2, NO_PROPS, Some("mates"))
+            .unwrap()
+            .add_constant_properties([("since", 2010i64)], Some("mates"))
+            .unwrap();
+
+        let report = left
+            .merge(
+                &right,
+                MergePolicy::keep_right().map_layer("mates", "friends"),
+            )
+            .unwrap();
+        assert!(report.new_layers.is_empty());
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].kind, ConflictKind::EdgeProperty);
+
+        let e = left.edge(1, 2).unwrap();
+        assert_eq!(e.layer_names().collect::<Vec<_>>(), vec!["friends"]);
+        assert_eq!(
+            e.layers("friends").unwrap().properties().get("since"),
+            Some(Prop::I64(2010))
+        );
+        assert_eq!(e.history(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_merge_graph_properties() {
+        let left = Graph::new();
+        left.add_constant_properties([("name", "left")]).unwrap();
+        left.add_properties(0, [("count", 1)]).unwrap();
+        let right = Graph::new();
+        right.add_constant_properties([("name", "right")]).unwrap();
+        right.add_properties(1, [("count", 2)]).unwrap();
+
+        let report = left.merge(&right, MergePolicy::keep_left()).unwrap();
+        assert_eq!(report.graph_updates, 1);
+        assert_eq!(report.conflicts[0].kind, ConflictKind::GraphProperty);
+        assert_eq!(left.properties().get("name"), Some(Prop::str("left")));
+        assert_eq!(
+            left.properties().temporal().get("count").unwrap().values(),
+            vec![Prop::I32(1), Prop::I32(2)]
+        );
+    }
+
+    #[test]
+    fn test_merge_persistent_deletions() {
+        let left = Graph::new();
+        let right = PersistentGraph::new();
+        right.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        right.delete_edge(5, 1, 2, None).unwrap();
+
+        let report = left.merge(&right, MergePolicy::error()).unwrap();
+        assert_eq!(report.edge_deletions, 1);
+        assert!(!left.persistent_graph().at(6).has_edge(1, 2));
+        assert!(left.persistent_graph().at(3).has_edge(1, 2));
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 pub mod edge;
 pub mod edges;
 pub mod graph;
+pub mod merge;
 pub mod node;
 pub mod nodes;
 pub mod path;
@@ -1,7 +1,10 @@
 use crate::{
     core::{
         entities::{edges::edge_ref::EdgeRef, graph::tgraph::InternalGraph, LayerIds, VID},
-        storage::timeindex::{AsTime, TimeIndexEntry, TimeIndexIntoOps, TimeIndexOps},
+        storage::{
+            columnar::CompactionStats,
+            timeindex::{AsTime, TimeIndexEntry, TimeIndexIntoOps, TimeIndexOps},
+        },
         utils::errors::GraphError,
         Prop,
     },
@@ -170,6 +173,11 @@ impl PersistentGraph {
     pub fn event_graph(&self) -> Graph {
         Graph::from_internal_graph(self.0.clone())
     }
+
+    /// Compact the temporal property histories of the graph, see `Graph::compact`
+    pub fn compact(&self) -> CompactionStats {
+        self.0.inner().compact()
+    }
 }
 
 impl<'graph, G: GraphViewOps<'graph>> PartialEq<G> for PersistentGraph {
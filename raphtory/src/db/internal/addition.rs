@@ -16,6 +16,16 @@ impl InternalAdditionOps for InternalGraph {
         self.inner().event_counter.fetch_add(1, Ordering::Relaxed)
     }
 
+    #[inline]
+    fn read_event_id(&self) -> usize {
+        self.inner().event_counter.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn read_generation(&self) -> usize {
+        self.inner().generation.load(Ordering::Relaxed)
+    }
+
     #[inline]
     fn resolve_layer(&self, layer: Option<&str>) -> usize {
         layer
@@ -25,6 +35,9 @@ impl InternalAdditionOps for InternalGraph {
 
     #[inline]
     fn resolve_node_type(&self, v_id: VID, node_type: Option<&str>) -> Result<usize, GraphError> {
+        if node_type.is_some() {
+            self.record_mutation();
+        }
         self.inner().resolve_node_type(v_id, node_type)
     }
 
@@ -78,6 +91,7 @@ impl InternalAdditionOps for InternalGraph {
         props: Vec<(usize, Prop)>,
         node_type_id: usize,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
         self.inner().add_node_internal(t, v, props, node_type_id)
     }
 
@@ -90,6 +104,15 @@ impl InternalAdditionOps for InternalGraph {
         props: Vec<(usize, Prop)>,
         layer: usize,
     ) -> Result<EID, GraphError> {
+        self.record_mutation();
         self.inner().add_edge_internal(t, src, dst, props, layer)
     }
 }
+
+impl InternalGraph {
+    /// Count a mutation towards the generation of the graph
+    #[inline]
+    pub(crate) fn record_mutation(&self) {
+        self.inner().generation.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     core::{
         entities::{
             edges::edge_ref::EdgeRef,
-            graph::tgraph::InternalGraph,
+            graph::{memory::MemoryStats, tgraph::InternalGraph},
             nodes::node_ref::NodeRef,
             properties::{graph_meta::GraphMeta, props::Meta, tprop::TProp},
             LayerIds, ELID, VID,
@@ -55,6 +55,10 @@ impl CoreGraphOps for InternalGraph {
         &self.inner().graph_meta
     }
 
+    fn core_memory_stats(&self) -> Option<MemoryStats> {
+        Some(self.inner().memory_stats())
+    }
+
     #[inline]
     fn get_layer_name(&self, layer_id: usize) -> ArcStr {
         self.inner()
@@ -15,6 +15,7 @@ impl InternalDeletionOps for InternalGraph {
         dst: VID,
         layer: usize,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
         self.inner().delete_edge(t, src, dst, layer)
     }
 }
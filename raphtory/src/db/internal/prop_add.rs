@@ -14,10 +14,12 @@ impl InternalPropertyAdditionOps for InternalGraph {
         t: TimeIndexEntry,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
         self.inner().add_properties(t, props)
     }
 
     fn internal_add_static_properties(&self, props: Vec<(usize, Prop)>) -> Result<(), GraphError> {
+        self.record_mutation();
         self.inner().add_constant_properties(props)
     }
 
@@ -25,6 +27,7 @@ impl InternalPropertyAdditionOps for InternalGraph {
         &self,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
         self.inner().update_constant_properties(props)
     }
 
@@ -33,6 +36,8 @@ impl InternalPropertyAdditionOps for InternalGraph {
         vid: VID,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
+        let props = self.node_meta().coerce_props(props, true)?;
         let mut node = self.inner().storage.get_node_mut(vid);
         for (prop_id, value) in props {
             node.add_constant_prop(prop_id, value).map_err(|err| {
@@ -54,6 +59,8 @@ impl InternalPropertyAdditionOps for InternalGraph {
         vid: VID,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
+        let props = self.node_meta().coerce_props(props, true)?;
         let mut node = self.inner().storage.get_node_mut(vid);
         for (prop_id, value) in props {
             node.update_constant_prop(prop_id, value)?;
@@ -67,6 +74,8 @@ impl InternalPropertyAdditionOps for InternalGraph {
         layer: usize,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
+        let props = self.edge_meta().coerce_props(props, true)?;
         let mut edge = self.inner().storage.get_edge_mut(eid);
         let mut edge_layer = edge.layer_mut(layer);
         for (prop_id, value) in props {
@@ -92,6 +101,8 @@ impl InternalPropertyAdditionOps for InternalGraph {
         layer: usize,
         props: Vec<(usize, Prop)>,
     ) -> Result<(), GraphError> {
+        self.record_mutation();
+        let props = self.edge_meta().coerce_props(props, true)?;
         let mut edge = self.inner().storage.get_edge_mut(eid);
         let mut edge_layer = edge.layer_mut(layer);
         for (prop_id, value) in props {
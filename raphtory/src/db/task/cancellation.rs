@@ -0,0 +1,180 @@
+//! Cooperative cancellation and progress of long-running algorithms.
+//!
+//! Algorithms built on the [`TaskRunner`](super::task_runner::TaskRunner) check the token
+//! installed with [`with_cancellation`] before every node and between supersteps and stop early
+//! once it is cancelled or its deadline has passed. Other long-running algorithms, like
+//! betweenness centrality, louvain and dijkstra, check it once per iteration of their main loop.
+//! Cancellation is cooperative: code that does not check the token runs to completion. Results
+//! computed by a cancelled run are incomplete and should be discarded by the caller. Every completed superstep is counted on the token, so the progress of
+//! a run can be followed from another thread.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// A handle that can be used to stop a running computation
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    supersteps: Arc<AtomicUsize>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token that is cancelled automatically once `deadline` has passed
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            deadline: Some(deadline),
+            ..Default::default()
+        }
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed)
+    }
+
+    /// The number of supersteps completed by algorithms running with this token
+    pub fn supersteps(&self) -> usize {
+        self.supersteps.load(Ordering::Relaxed)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+struct Restore(Option<CancellationToken>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Run `f` with `token` as the cancellation token of the current thread
+pub fn with_cancellation<T>(token: &CancellationToken, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.borrow_mut().replace(token.clone()));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// The cancellation token of the current thread, for passing it on to worker threads
+pub fn current() -> Option<CancellationToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Whether the computation running on the current thread has been cancelled
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    })
+}
+
+/// Count a completed superstep on the cancellation token of the current thread
+pub fn record_superstep() {
+    CURRENT.with(|current| {
+        if let Some(token) = current.borrow().as_ref() {
+            token.supersteps.fetch_add(1, Ordering::Relaxed);
+        }
+    })
+}
+
+#[cfg(test)]
+mod cancellation_test {
+    use super::*;
+    use crate::{
+        algorithms::{
+            centrality::{betweenness::betweenness_centrality, pagerank::unweighted_page_rank},
+            community_detection::{louvain::louvain, modularity::ModularityUnDir},
+        },
+        prelude::*,
+    };
+    use itertools::Itertools;
+    use std::time::Duration;
+
+    #[test]
+    fn tokens_are_scoped_to_the_closure() {
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(!is_cancelled());
+        assert!(with_cancellation(&token, is_cancelled));
+        assert!(!is_cancelled());
+
+        let expired = CancellationToken::with_deadline(Instant::now() - Duration::from_secs(1));
+        assert!(expired.is_cancelled());
+    }
+
+    #[test]
+    fn cancelled_algorithms_stop_early() {
+        let graph = Graph::new();
+        for i in 0..100u64 {
+            graph.add_edge(0, i, (i + 1) % 100, NO_PROPS, None).unwrap();
+        }
+        let token = CancellationToken::new();
+        token.cancel();
+        // with a negative tolerance pagerank never converges and would run all iterations
+        let ranks = with_cancellation(&token, || {
+            unweighted_page_rank(&graph, Some(100_000_000), None, Some(-1.0), true, None)
+        });
+        assert_eq!(ranks.get_all_values().len(), 100);
+        assert_eq!(token.supersteps(), 0);
+    }
+
+    #[test]
+    fn cancelled_loops_stop_early() {
+        let graph = Graph::new();
+        for i in 0..10u64 {
+            graph.add_edge(0, i, (i + 1) % 10, NO_PROPS, None).unwrap();
+        }
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let betweenness = with_cancellation(&token, || betweenness_centrality(&graph, None, None));
+        assert!(betweenness.get_all_values().iter().all(|&v| v == 0.0));
+
+        let communities = with_cancellation(&token, || {
+            louvain::<ModularityUnDir, _>(&graph, 1.0, None, None)
+        });
+        assert_eq!(
+            communities.get_all_values().iter().unique().count(),
+            graph.count_nodes()
+        );
+    }
+
+    #[test]
+    fn supersteps_are_counted() {
+        let graph = Graph::new();
+        for i in 0..10u64 {
+            graph.add_edge(0, i, (i + 1) % 10, NO_PROPS, None).unwrap();
+        }
+        let token = CancellationToken::new();
+        with_cancellation(&token, || {
+            unweighted_page_rank(&graph, Some(5), None, Some(-1.0), true, None)
+        });
+        assert_eq!(token.supersteps(), 5);
+    }
+}
@@ -2,6 +2,7 @@ use once_cell::sync::Lazy;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::sync::Arc;
 
+pub mod cancellation;
 pub mod context;
 pub mod edge;
 mod eval_graph;
@@ -1,4 +1,5 @@
 use super::{
+    cancellation::{self, CancellationToken},
     context::{Context, GlobalState},
     custom_pool,
     task::{Job, Step, Task},
@@ -58,6 +59,7 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
         morcel_size: usize,
         morcel_id: usize,
         task: &Box<dyn Task<G, CS, S> + Send + Sync>,
+        token: Option<&CancellationToken>,
     ) -> (Shard<CS>, Global<CS>) {
         // the view for this task of the global state
         let shard_state_view = shard_state.as_cow();
@@ -70,6 +72,9 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
         let mut v_ref = morcel_id * morcel_size;
 
         for local_state in morcel {
+            if token.is_some_and(CancellationToken::is_cancelled) {
+                break;
+            }
             if g.has_node(VID(v_ref)) {
                 let eval_graph = EvalGraph {
                     ss: self.ctx.ss(),
@@ -128,6 +133,8 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
         prev_local_state: &Vec<S>,
         storage: &GraphStorage,
     ) -> (bool, Shard<CS>, Global<CS>, Vec<S>) {
+        // the token is thread local, so hand it to the threads of the pool explicitly
+        let token = cancellation::current();
         pool.install(move || {
             let mut new_shard_state = shard_state;
             let mut new_global_state = global_state;
@@ -152,6 +159,7 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
                                 morcel_size,
                                 morcel_id,
                                 task,
+                                token.as_ref(),
                             )
                         })
                         .reduce_with(|a, b| self.merge_states(a, b)),
@@ -170,6 +178,7 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
                                     morcel_size,
                                     morcel_id,
                                     task,
+                                    token.as_ref(),
                                 );
                             });
                         None
@@ -257,7 +266,8 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
         // To allow the init step to cache stuff we will copy everything from cur_local_state to prev_local_state
         prev_local_state.clone_from_slice(&cur_local_state);
 
-        while !_done && self.ctx.ss() < steps && !tasks.is_empty() {
+        while !_done && self.ctx.ss() < steps && !tasks.is_empty() && !cancellation::is_cancelled()
+        {
             (_done, shard_state, global_state, cur_local_state) = self.run_task_list(
                 &tasks,
                 &pool,
@@ -279,6 +289,7 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
 
             // Copy and reset the local states from the step that just ended
             self.ctx.increment_ss();
+            cancellation::record_superstep();
         }
 
         let ss: usize = self.ctx.ss();
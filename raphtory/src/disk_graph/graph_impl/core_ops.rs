@@ -35,6 +35,7 @@ use itertools::Itertools;
 use polars_arrow::datatypes::ArrowDataType;
 use pometry_storage::{properties::Properties, GidRef, GID};
 use rayon::prelude::*;
+use rust_decimal::Decimal;
 
 impl CoreGraphOps for DiskGraph {
     fn unfiltered_num_nodes(&self) -> usize {
@@ -242,6 +243,12 @@ where
         ArrowDataType::UInt32 => props.const_props.prop_native(index, id).map(Prop::U32),
         ArrowDataType::UInt16 => props.const_props.prop_native(index, id).map(Prop::U16),
         ArrowDataType::UInt8 => props.const_props.prop_native(index, id).map(Prop::U8),
+        ArrowDataType::Int16 => props.const_props.prop_native(index, id).map(Prop::I16),
+        ArrowDataType::Int8 => props.const_props.prop_native(index, id).map(Prop::I8),
+        ArrowDataType::Decimal(_, scale) => props
+            .const_props
+            .prop_native(index, id)
+            .map(|v: i128| Prop::Decimal(Decimal::from_i128_with_scale(v, *scale as u32))),
         ArrowDataType::Float64 => props.const_props.prop_native(index, id).map(Prop::F64),
         ArrowDataType::Float32 => props.const_props.prop_native(index, id).map(Prop::F32),
         ArrowDataType::Utf8 => props
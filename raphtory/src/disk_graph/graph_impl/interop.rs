@@ -120,5 +120,8 @@ impl GraphLike<TimeIndexEntry> for Graph {
             }),
             prop_type,
         )
+        // the properties are checked by `check_disk_props` before the conversion
+        .ok()
+        .flatten()
     }
 }
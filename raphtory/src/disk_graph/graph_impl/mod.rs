@@ -29,7 +29,10 @@ use crate::{
         mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
         view::{internal::Immutable, DynamicGraph, IntoDynamic},
     },
-    disk_graph::{graph_impl::prop_conversion::make_node_properties_from_graph, Error},
+    disk_graph::{
+        graph_impl::prop_conversion::{check_disk_props, make_node_properties_from_graph},
+        Error,
+    },
     prelude::{Graph, GraphViewOps},
 };
 
@@ -230,6 +233,7 @@ impl DiskGraph {
     }
 
     pub fn from_graph(graph: &Graph, graph_dir: impl AsRef<Path>) -> Result<Self, Error> {
+        check_disk_props(graph)?;
         let inner_graph = TemporalGraph::from_graph(graph, graph_dir.as_ref(), || {
             make_node_properties_from_graph(graph, graph_dir.as_ref())
         })?;
@@ -349,6 +353,14 @@ impl InternalAdditionOps for DiskGraph {
         unimplemented!("Diskgraph is immutable")
     }
 
+    fn read_event_id(&self) -> usize {
+        0
+    }
+
+    fn read_generation(&self) -> usize {
+        0
+    }
+
     fn resolve_layer(&self, _layer: Option<&str>) -> usize {
         // Will check this
         unimplemented!("Diskgraph is immutable")
@@ -480,9 +492,13 @@ mod test {
     use tempfile::TempDir;
 
     use crate::{
-        algorithms::components::weakly_connected_components, db::api::view::StaticGraphViewOps,
-        disk_graph::Time, prelude::*,
+        algorithms::components::weakly_connected_components,
+        core::utils::errors::GraphError,
+        db::api::view::StaticGraphViewOps,
+        disk_graph::{Error, Time},
+        prelude::*,
     };
+    use rust_decimal::Decimal;
 
     use super::DiskGraph;
 
@@ -840,4 +856,37 @@ mod test {
             "test"
         );
     }
+
+    #[test]
+    fn test_decimal_and_array_properties() {
+        let g = Graph::new();
+        g.add_node(
+            0,
+            1,
+            [("embedding", Prop::Array(vec![1.0f32, 2.0].into()))],
+            None,
+        )
+        .unwrap();
+        g.add_node(
+            1,
+            2,
+            [("embedding", Prop::Array(vec![3.0f32].into()))],
+            None,
+        )
+        .unwrap();
+        let test_dir = TempDir::new().unwrap();
+        assert!(DiskGraph::from_graph(&g, test_dir.path()).is_ok());
+
+        // no decimal column can hold both values exactly
+        let g = Graph::new();
+        g.add_node(0, 1, [("amount", Prop::Decimal(Decimal::MAX))], None)
+            .unwrap();
+        g.add_node(1, 2, [("amount", Prop::Decimal(Decimal::new(1, 10)))], None)
+            .unwrap();
+        let test_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            DiskGraph::from_graph(&g, test_dir.path()),
+            Err(Error::GraphError(GraphError::DiskPropertyConversion(_)))
+        ));
+    }
 }
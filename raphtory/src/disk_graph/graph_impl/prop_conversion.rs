@@ -1,13 +1,21 @@
 use crate::{
     arrow2::{
-        array::{Array, BooleanArray, PrimitiveArray, Utf8Array},
+        array::{
+            Array, BinaryArray, BooleanArray, ListArray, MutableListArray, MutablePrimitiveArray,
+            PrimitiveArray, TryExtend, Utf8Array,
+        },
         datatypes::{ArrowDataType as DataType, ArrowSchema as Schema, Field},
+        types::NativeType,
     },
     core::{
-        entities::{properties::props::PropMapper, VID},
-        PropType,
+        entities::{properties::props::PropMapper, EID, ELID, VID},
+        utils::errors::GraphError,
+        ArrayType, PropArray, PropType,
+    },
+    db::api::{
+        storage::{edges::edge_storage_ops::EdgeStorageOps, tprop_storage_ops::TPropOps},
+        view::internal::CoreGraphOps,
     },
-    db::api::{storage::tprop_storage_ops::TPropOps, view::internal::CoreGraphOps},
     prelude::{Graph, Prop, PropUnwrap},
 };
 use itertools::Itertools;
@@ -15,7 +23,8 @@ use pometry_storage::{
     properties::{node_ts, NodePropsBuilder, Properties},
     RAError,
 };
-use std::path::Path;
+use rust_decimal::Decimal;
+use std::{path::Path, sync::Arc};
 
 pub fn make_node_properties_from_graph(
     graph: &Graph,
@@ -72,7 +81,8 @@ pub fn make_node_properties_from_graph(
                 }),
                 prop_type,
             );
-            col.map(|col| {
+            // the properties are checked by `check_disk_props` before the conversion
+            col.ok().flatten().map(|col| {
                 let dtype = col.data_type().clone();
                 (Field::new(prop_key, dtype, true), col)
             })
@@ -86,7 +96,7 @@ pub fn make_node_properties_from_graph(
                 }),
                 prop_type,
             );
-            col.map(|col| {
+            col.ok().flatten().map(|col| {
                 let dtype = col.data_type().clone();
                 (Field::new(prop_key, dtype, true), col)
             })
@@ -95,8 +105,72 @@ pub fn make_node_properties_from_graph(
     Ok(props)
 }
 
-pub fn arrow_dtype_from_prop_type(prop_type: PropType) -> DataType {
-    match prop_type {
+/// Check that the properties of `graph` can be stored in a disk graph. Their columns are
+/// converted while the disk graph is written, where errors cannot be reported.
+pub fn check_disk_props(graph: &Graph) -> Result<(), GraphError> {
+    let node_temporal_ids = decimal_prop_ids(graph.node_meta().temporal_prop_meta())?;
+    let node_constant_ids = decimal_prop_ids(graph.node_meta().const_prop_meta())?;
+    let edge_temporal_ids = decimal_prop_ids(graph.edge_meta().temporal_prop_meta())?;
+
+    // the scale of a decimal column depends on all its values
+    let nodes = graph.0.inner().storage.nodes.read_lock();
+    let n = graph.unfiltered_num_nodes();
+    for prop_id in node_temporal_ids {
+        check_decimal_column((0..n).flat_map(|vid| {
+            let node = nodes.get(VID(vid));
+            node.temporal_property(prop_id)
+                .into_iter()
+                .flat_map(|prop| prop.iter().map(|(_, value)| value))
+                .collect::<Vec<_>>()
+        }))?;
+    }
+    for prop_id in node_constant_ids {
+        check_decimal_column(
+            (0..n).filter_map(|vid| nodes.get(VID(vid)).const_prop(prop_id).cloned()),
+        )?;
+    }
+    for layer in 0..graph.unfiltered_num_layers() {
+        for &prop_id in &edge_temporal_ids {
+            check_decimal_column((0..graph.unfiltered_num_edges()).flat_map(|eid| {
+                let edge = graph.core_edge(ELID::new(EID(eid), Some(layer)));
+                edge.temporal_prop_layer(layer, prop_id)
+                    .iter()
+                    .map(|(_, value)| value)
+                    .collect::<Vec<_>>()
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// The ids of the decimal properties of `meta`, failing if any property is not supported
+fn decimal_prop_ids(meta: &PropMapper) -> Result<Vec<usize>, GraphError> {
+    let mut ids = vec![];
+    for id in 0..meta.len() {
+        match meta.get_dtype(id).unwrap() {
+            PropType::Decimal => ids.push(id),
+            prop_type => {
+                arrow_dtype_from_prop_type(prop_type)?;
+            }
+        }
+    }
+    Ok(ids)
+}
+
+fn check_decimal_column(values: impl Iterator<Item = Prop>) -> Result<(), GraphError> {
+    let values: Vec<Decimal> = values.filter_map(|prop| prop.into_decimal()).collect();
+    if let Some(scale) = values.iter().map(|v| v.scale()).max() {
+        for value in values {
+            decimal_mantissa(value, scale)?;
+        }
+    }
+    Ok(())
+}
+
+/// The arrow type of columns of `prop_type` properties. The type of decimal columns depends on
+/// their values, see [`arrow_array_from_props`].
+pub fn arrow_dtype_from_prop_type(prop_type: PropType) -> Result<DataType, GraphError> {
+    let dtype = match prop_type {
         PropType::Str => DataType::LargeUtf8,
         PropType::U8 => DataType::UInt8,
         PropType::U16 => DataType::UInt16,
@@ -107,6 +181,23 @@ pub fn arrow_dtype_from_prop_type(prop_type: PropType) -> DataType {
         PropType::F32 => DataType::Float32,
         PropType::F64 => DataType::Float64,
         PropType::Bool => DataType::Boolean,
+        PropType::I8 => DataType::Int8,
+        PropType::I16 => DataType::Int16,
+        PropType::Bytes => DataType::LargeBinary,
+        PropType::Array(dtype) => {
+            let values = match dtype {
+                ArrayType::I32 => DataType::Int32,
+                ArrayType::I64 => DataType::Int64,
+                ArrayType::F32 => DataType::Float32,
+                ArrayType::F64 => DataType::Float64,
+            };
+            DataType::LargeList(Box::new(Field::new("item", values, true)))
+        }
+        PropType::Decimal => {
+            return Err(GraphError::DiskPropertyConversion(
+                "the arrow type of Decimal properties depends on their values".to_owned(),
+            ))
+        }
         PropType::Empty
         | PropType::List
         | PropType::Map
@@ -114,16 +205,23 @@ pub fn arrow_dtype_from_prop_type(prop_type: PropType) -> DataType {
         | PropType::Graph
         | PropType::PersistentGraph
         | PropType::Document
-        | PropType::DTime => panic!("{prop_type:?} not supported as disk_graph property"),
-    }
+        | PropType::DTime => return Err(unsupported(prop_type)),
+    };
+    Ok(dtype)
+}
+
+fn unsupported(prop_type: PropType) -> GraphError {
+    GraphError::DiskPropertyConversion(format!(
+        "{prop_type:?} not supported as disk_graph property"
+    ))
 }
 
 /// Map iterator of prop values to array (returns None if all the props are None)
 pub fn arrow_array_from_props(
     props: impl Iterator<Item = Option<Prop>>,
     prop_type: PropType,
-) -> Option<Box<dyn Array>> {
-    match prop_type {
+) -> Result<Option<Box<dyn Array>>, GraphError> {
+    let array = match prop_type {
         PropType::Str => {
             let array: Utf8Array<i64> = props.map(|prop| prop.into_str()).collect();
             array.iter().any(|v| v.is_some()).then_some(array.boxed())
@@ -164,6 +262,53 @@ pub fn arrow_array_from_props(
             let array: BooleanArray = props.map(|prop| prop.into_bool()).collect();
             array.iter().any(|v| v.is_some()).then_some(array.boxed())
         }
+        PropType::I8 => {
+            let array: PrimitiveArray<i8> = props.map(|prop| prop.into_i8()).collect();
+            array.iter().any(|v| v.is_some()).then_some(array.boxed())
+        }
+        PropType::I16 => {
+            let array: PrimitiveArray<i16> = props.map(|prop| prop.into_i16()).collect();
+            array.iter().any(|v| v.is_some()).then_some(array.boxed())
+        }
+        PropType::Bytes => {
+            let array: BinaryArray<i64> = props.map(|prop| prop.into_bytes()).collect();
+            array.iter().any(|v| v.is_some()).then_some(array.boxed())
+        }
+        PropType::Decimal => {
+            let values: Vec<Option<Decimal>> = props.map(|prop| prop.into_decimal()).collect();
+            // use the smallest scale that represents all values exactly
+            let Some(scale) = values.iter().flatten().map(|v| v.scale()).max() else {
+                return Ok(None);
+            };
+            let mantissas = values
+                .into_iter()
+                .map(|v| v.map(|v| decimal_mantissa(v, scale)).transpose())
+                .collect::<Result<Vec<_>, _>>()?;
+            let array = PrimitiveArray::<i128>::from(mantissas)
+                .to(DataType::Decimal(DECIMAL_PRECISION, scale as usize));
+            Some(array.boxed())
+        }
+        PropType::Array(dtype) => {
+            let arrays = props.map(|prop| prop.into_array());
+            match dtype {
+                ArrayType::I32 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::I32(values)) => Some(values),
+                    _ => None,
+                }))?,
+                ArrayType::I64 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::I64(values)) => Some(values),
+                    _ => None,
+                }))?,
+                ArrayType::F32 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::F32(values)) => Some(values),
+                    _ => None,
+                }))?,
+                ArrayType::F64 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::F64(values)) => Some(values),
+                    _ => None,
+                }))?,
+            }
+        }
         PropType::Empty
         | PropType::List
         | PropType::Map
@@ -171,56 +316,55 @@ pub fn arrow_array_from_props(
         | PropType::Graph
         | PropType::PersistentGraph
         | PropType::Document
-        | PropType::DTime => panic!("{prop_type:?} not supported as disk_graph property"),
+        | PropType::DTime => return Err(unsupported(prop_type)),
+    };
+    Ok(array)
+}
+
+/// Precision of arrow decimal columns (the maximum supported by `Decimal128`)
+const DECIMAL_PRECISION: usize = 38;
+
+/// The mantissa of `value` in a decimal column with `scale`
+fn decimal_mantissa(value: Decimal, scale: u32) -> Result<i128, GraphError> {
+    let max = 10u128.pow(DECIMAL_PRECISION as u32);
+    10i128
+        .checked_pow(scale - value.scale())
+        .and_then(|factor| value.mantissa().checked_mul(factor))
+        .filter(|mantissa| mantissa.unsigned_abs() < max)
+        .ok_or_else(|| {
+            GraphError::DiskPropertyConversion(format!(
+                "Decimal {value} does not fit a Decimal128 column with scale {scale}"
+            ))
+        })
+}
+
+/// Arrays of a column become a list array as their lengths may differ (returns None if all the
+/// arrays are None)
+fn list_array<T: NativeType>(
+    arrays: impl Iterator<Item = Option<Arc<[T]>>>,
+) -> Result<Option<Box<dyn Array>>, GraphError> {
+    let arrays: Vec<_> = arrays.collect();
+    if arrays.iter().all(|a| a.is_none()) {
+        return Ok(None);
     }
+    let rows = arrays
+        .iter()
+        .map(|a| a.as_ref().map(|a| a.iter().map(|v| Some(*v))));
+    let mut array = MutableListArray::<i64, MutablePrimitiveArray<T>>::new();
+    array
+        .try_extend(rows)
+        .map_err(|err| GraphError::DiskPropertyConversion(err.to_string()))?;
+    Ok(Some(ListArray::<i64>::from(array).boxed()))
 }
 
-pub fn schema_from_prop_meta(prop_map: &PropMapper) -> Schema {
+pub fn schema_from_prop_meta(prop_map: &PropMapper) -> Result<Schema, GraphError> {
     let time_field = Field::new("time", DataType::Int64, false);
     let mut schema = vec![time_field];
 
     for (id, key) in prop_map.get_keys().iter().enumerate() {
-        match prop_map.get_dtype(id).unwrap() {
-            PropType::Str => {
-                schema.push(Field::new(key, DataType::LargeUtf8, true));
-            }
-            PropType::U8 => {
-                schema.push(Field::new(key, DataType::UInt8, true));
-            }
-            PropType::U16 => {
-                schema.push(Field::new(key, DataType::UInt16, true));
-            }
-            PropType::I32 => {
-                schema.push(Field::new(key, DataType::Int32, true));
-            }
-            PropType::I64 => {
-                schema.push(Field::new(key, DataType::Int64, true));
-            }
-            PropType::U32 => {
-                schema.push(Field::new(key, DataType::UInt32, true));
-            }
-            PropType::U64 => {
-                schema.push(Field::new(key, DataType::UInt64, true));
-            }
-            PropType::F32 => {
-                schema.push(Field::new(key, DataType::Float32, true));
-            }
-            PropType::F64 => {
-                schema.push(Field::new(key, DataType::Float64, true));
-            }
-            PropType::Bool => {
-                schema.push(Field::new(key, DataType::Boolean, true));
-            }
-            prop_type @ (PropType::Empty
-            | PropType::List
-            | PropType::Map
-            | PropType::NDTime
-            | PropType::Graph
-            | PropType::PersistentGraph
-            | PropType::Document
-            | PropType::DTime) => panic!("{:?} not supported as disk_graph property", prop_type),
-        }
+        let dtype = arrow_dtype_from_prop_type(prop_map.get_dtype(id).unwrap())?;
+        schema.push(Field::new(key, dtype, true));
     }
 
-    Schema::from(schema)
+    Ok(Schema::from(schema))
 }
@@ -12,6 +12,8 @@ pub mod prelude {
 pub enum Error {
     #[error("Raphtory Arrow Error: {0}")]
     RAError(#[from] pometry_storage::RAError),
+    #[error("Graph Error: {0}")]
+    GraphError(#[from] crate::core::utils::errors::GraphError),
 }
 
 #[cfg(test)]
@@ -0,0 +1,151 @@
+//! Write graphs in the [DOT language](https://graphviz.org/doc/info/lang.html) of Graphviz
+//!
+//! The latest value of every property of a view is written as a quoted attribute, so the output
+//! can be rendered directly with `dot` or loaded by other tools that understand DOT.
+
+use crate::{
+    core::{utils::errors::GraphError, ArcStr},
+    graph_loader::formats::{invalid, LAYER, NODE_TYPE},
+    prelude::*,
+};
+use std::io::Write;
+
+const FORMAT: &str = "DOT";
+
+/// Write the latest state of a graph view as a DOT digraph
+///
+/// Node types are written as the `node_type` attribute of nodes and layers as the `layer`
+/// attribute of edges. Each layer of an edge becomes a separate DOT edge.
+///
+/// Arguments:
+///     graph: the graph view to write
+///     writer: destination of the DOT document
+pub fn write_dot<'graph, G: GraphViewOps<'graph>, W: Write>(
+    graph: &G,
+    mut writer: W,
+) -> Result<(), GraphError> {
+    writeln!(writer, "digraph {{")?;
+    let graph_attributes: Vec<_> = graph.properties().iter().collect();
+    if !graph_attributes.is_empty() {
+        writeln!(
+            writer,
+            "  graph [{}];",
+            attribute_list(
+                graph_attributes
+                    .into_iter()
+                    .map(|(k, v)| (k, v.to_string()))
+            )
+        )?;
+    }
+    for node in graph.nodes() {
+        let mut attributes = vec![];
+        let node_type = node.node_type();
+        if let Some(node_type) = &node_type {
+            attributes.push((NODE_TYPE.into(), node_type.to_string()));
+        }
+        for (name, value) in node.properties().iter() {
+            if name == NODE_TYPE && node_type.is_some() {
+                return Err(reserved_name(NODE_TYPE));
+            }
+            attributes.push((name, value.to_string()));
+        }
+        write!(writer, "  {}", quote(&node.name()))?;
+        if !attributes.is_empty() {
+            write!(writer, " [{}]", attribute_list(attributes.into_iter()))?;
+        }
+        writeln!(writer, ";")?;
+    }
+    for edge in graph.edges().explode_layers() {
+        let mut attributes = vec![];
+        let layer = edge.layer_name()?;
+        let has_layer = layer != "_default";
+        if has_layer {
+            attributes.push((LAYER.into(), layer.to_string()));
+        }
+        for (name, value) in edge.properties().iter() {
+            if name == LAYER && has_layer {
+                return Err(reserved_name(LAYER));
+            }
+            attributes.push((name, value.to_string()));
+        }
+        write!(
+            writer,
+            "  {} -> {}",
+            quote(&edge.src().name()),
+            quote(&edge.dst().name())
+        )?;
+        if !attributes.is_empty() {
+            write!(writer, " [{}]", attribute_list(attributes.into_iter()))?;
+        }
+        writeln!(writer, ";")?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+fn attribute_list(attributes: impl Iterator<Item = (ArcStr, String)>) -> String {
+    attributes
+        .map(|(name, value)| format!("{}={}", quote(&name), quote(&value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Quote a DOT identifier, escaping quotes, backslashes and line breaks
+fn quote(id: &str) -> String {
+    let mut quoted = String::with_capacity(id.len() + 2);
+    quoted.push('"');
+    for c in id.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn reserved_name(name: &str) -> GraphError {
+    invalid(
+        FORMAT,
+        format!("property '{name}' clashes with the attribute of the same name"),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_dot_with_layers_and_types() {
+        let g = Graph::new();
+        g.add_node(0, "alice", [("age", 31i64)], Some("person"))
+            .unwrap();
+        g.add_edge(
+            1,
+            "alice",
+            "bob \"the builder\"",
+            [("amount", 1.5)],
+            Some("pays"),
+        )
+        .unwrap();
+        g.add_edge(2, "alice", "bob \"the builder\"", NO_PROPS, None)
+            .unwrap();
+        g.add_constant_properties([("name", "payments")]).unwrap();
+
+        let mut buf = vec![];
+        write_dot(&g, &mut buf).unwrap();
+        let dot = String::from_utf8(buf).unwrap();
+        let expected = r#"digraph {
+  graph ["name"="payments"];
+  "alice" ["node_type"="person", "age"="31"];
+  "bob \"the builder\"";
+  "alice" -> "bob \"the builder\"";
+  "alice" -> "bob \"the builder\"" ["layer"="pays", "amount"="1.5"];
+}
+"#;
+        assert_eq!(dot, expected);
+    }
+}
@@ -0,0 +1,814 @@
+//! Read and write graphs as [GEXF](https://gexf.net/) 1.3
+//!
+//! In [`GexfMode::Dynamic`] the history of a graph view is written as spells on nodes and edges
+//! and temporal properties become dynamic attributes with time-stamped values. Times are written
+//! with the `integer` time format, i.e., as raw raphtory timestamps. Graphs with event semantics
+//! get a point spell for every update, while graphs with persistent semantics get one spell per
+//! interval in which an edge is alive. Layers are written as the edge `kind`.
+
+use crate::{
+    core::{
+        utils::{errors::GraphError, time::TryIntoTime},
+        ArcStr,
+    },
+    db::api::{
+        mutation::{
+            delete_edge_at,
+            internal::{InternalAdditionOps, InternalDeletionOps, InternalPropertyAdditionOps},
+        },
+        view::StaticGraphViewOps,
+    },
+    graph_loader::formats::{invalid, AttrKeys, AttrType, Element, NODE_TYPE},
+    prelude::*,
+};
+use itertools::Itertools;
+use quick_xml::{
+    events::{BytesDecl, BytesText, Event},
+    Writer,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{BufRead, Write},
+};
+
+const FORMAT: &str = "GEXF";
+
+/// Whether to write the history of a graph view or only its latest state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GexfMode {
+    /// Write the latest value of every property as a static attribute
+    Static,
+    /// Write spells and time-stamped values of temporal properties
+    Dynamic,
+}
+
+fn type_name(dtype: AttrType) -> &'static str {
+    match dtype {
+        AttrType::Boolean => "boolean",
+        AttrType::Int => "integer",
+        AttrType::Long => "long",
+        AttrType::Float => "float",
+        AttrType::Double => "double",
+        AttrType::String => "string",
+    }
+}
+
+fn parse_type(name: &str) -> AttrType {
+    match name {
+        "boolean" => AttrType::Boolean,
+        "integer" => AttrType::Int,
+        "long" => AttrType::Long,
+        "float" => AttrType::Float,
+        "double" => AttrType::Double,
+        _ => AttrType::String,
+    }
+}
+
+/// Static and dynamic attributes of one class of elements
+#[derive(Default)]
+struct ClassKeys {
+    constant: AttrKeys,
+    temporal: AttrKeys,
+}
+
+impl ClassKeys {
+    fn add(
+        &mut self,
+        mode: GexfMode,
+        latest: impl Iterator<Item = (ArcStr, Prop)>,
+        constant: impl Iterator<Item = (ArcStr, Prop)>,
+        temporal: impl Iterator<Item = (ArcStr, Vec<(i64, Prop)>)>,
+    ) {
+        match mode {
+            GexfMode::Static => {
+                for (name, value) in latest {
+                    self.constant.add(name, &value);
+                }
+            }
+            GexfMode::Dynamic => {
+                for (name, value) in constant {
+                    self.constant.add(name, &value);
+                }
+                for (name, updates) in temporal {
+                    for (_, value) in updates {
+                        self.temporal.add(name.clone(), &value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A single `<attvalue>` element
+struct AttValue {
+    id: String,
+    value: String,
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+/// Write a graph view as GEXF
+///
+/// Node types are stored in the static `node_type` attribute of nodes, which is why a node
+/// property with this name cannot be exported. Edges are written once per layer.
+///
+/// Arguments:
+///     graph: the graph view to write
+///     writer: destination of the GEXF document
+///     mode: whether to write the history of the view or only its latest state
+pub fn write_gexf<'graph, G: GraphViewOps<'graph>, W: Write>(
+    graph: &G,
+    writer: W,
+    mode: GexfMode,
+) -> Result<(), GraphError> {
+    let persistent = graph.include_deletions();
+
+    let mut node_keys = ClassKeys::default();
+    let mut has_node_types = false;
+    for node in graph.nodes() {
+        has_node_types |= node.node_type().is_some();
+        let props = node.properties();
+        node_keys.add(
+            mode,
+            props.iter(),
+            props.constant().iter(),
+            props
+                .temporal()
+                .iter()
+                .map(|(name, view)| (name, view.iter().collect())),
+        );
+    }
+    let mut edge_keys = ClassKeys::default();
+    for edge in graph.edges().explode_layers() {
+        let props = edge.properties();
+        edge_keys.add(
+            mode,
+            props.iter(),
+            props.constant().iter(),
+            props
+                .temporal()
+                .iter()
+                .map(|(name, view)| (name, view.iter().collect())),
+        );
+    }
+    if has_node_types && node_keys.constant.contains(NODE_TYPE) {
+        return Err(invalid(
+            FORMAT,
+            format!("property '{NODE_TYPE}' clashes with the attribute of the same name"),
+        ));
+    }
+
+    let node_constant_ids = node_keys.constant.id_map("s");
+    let node_temporal_ids = node_keys.temporal.id_map("d");
+    let edge_constant_ids = edge_keys.constant.id_map("s");
+    let edge_temporal_ids = edge_keys.temporal.id_map("d");
+
+    let mut graph_attributes = vec![("defaultedgetype", "directed")];
+    match mode {
+        GexfMode::Static => graph_attributes.push(("mode", "static")),
+        GexfMode::Dynamic => graph_attributes.extend([
+            ("mode", "dynamic"),
+            ("timeformat", "integer"),
+            ("timerepresentation", "interval"),
+        ]),
+    }
+
+    let mut xml = Writer::new_with_indent(writer, b' ', 2);
+    xml.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    xml.create_element("gexf")
+        .with_attributes([("xmlns", "http://gexf.net/1.3"), ("version", "1.3")])
+        .write_inner_content(|xml| -> Result<(), GraphError> {
+            xml.create_element("meta")
+                .write_inner_content(|xml| -> Result<(), GraphError> {
+                    xml.create_element("creator")
+                        .write_text_content(BytesText::new("raphtory"))?;
+                    Ok(())
+                })?;
+            xml.create_element("graph")
+                .with_attributes(graph_attributes)
+                .write_inner_content(|xml| -> Result<(), GraphError> {
+                    let node_type = ArcStr::from(NODE_TYPE);
+                    let node_type_key = has_node_types.then_some((
+                        NODE_TYPE.to_owned(),
+                        &node_type,
+                        AttrType::String,
+                    ));
+                    write_attributes(
+                        xml,
+                        "node",
+                        "static",
+                        node_type_key.into_iter().chain(node_keys.constant.ids("s")),
+                    )?;
+                    write_attributes(xml, "node", "dynamic", node_keys.temporal.ids("d"))?;
+                    write_attributes(xml, "edge", "static", edge_keys.constant.ids("s"))?;
+                    write_attributes(xml, "edge", "dynamic", edge_keys.temporal.ids("d"))?;
+
+                    xml.create_element("nodes").write_inner_content(
+                        |xml| -> Result<(), GraphError> {
+                            for node in graph.nodes() {
+                                let name = node.name();
+                                let mut values = vec![];
+                                if let Some(node_type) = node.node_type() {
+                                    values.push(AttValue {
+                                        id: NODE_TYPE.to_owned(),
+                                        value: node_type.to_string(),
+                                        start: None,
+                                        end: None,
+                                    });
+                                }
+                                let props = node.properties();
+                                let spells = match mode {
+                                    GexfMode::Static => {
+                                        values.extend(static_values(
+                                            props.iter(),
+                                            &node_constant_ids,
+                                        ));
+                                        vec![]
+                                    }
+                                    GexfMode::Dynamic => {
+                                        values.extend(static_values(
+                                            props.constant().iter(),
+                                            &node_constant_ids,
+                                        ));
+                                        for (prop_name, view) in props.temporal().iter() {
+                                            values.extend(dynamic_values(
+                                                &node_temporal_ids[&prop_name],
+                                                view.iter(),
+                                            ));
+                                        }
+                                        if persistent {
+                                            vec![(node.earliest_time(), None)]
+                                        } else {
+                                            point_spells(node.history())
+                                        }
+                                    }
+                                };
+                                xml.create_element("node")
+                                    .with_attributes([
+                                        ("id", name.as_str()),
+                                        ("label", name.as_str()),
+                                    ])
+                                    .write_inner_content(|xml| {
+                                        write_element_body(xml, &values, &spells)
+                                    })?;
+                            }
+                            Ok(())
+                        },
+                    )?;
+
+                    xml.create_element("edges").write_inner_content(
+                        |xml| -> Result<(), GraphError> {
+                            for (index, edge) in
+                                graph.edges().explode_layers().into_iter().enumerate()
+                            {
+                                let id = index.to_string();
+                                let src = edge.src().name();
+                                let dst = edge.dst().name();
+                                let layer = edge.layer_name()?;
+                                let mut attributes = vec![
+                                    ("id", id.as_str()),
+                                    ("source", src.as_str()),
+                                    ("target", dst.as_str()),
+                                ];
+                                if layer != "_default" {
+                                    attributes.push(("kind", &*layer));
+                                }
+                                let props = edge.properties();
+                                let mut values = vec![];
+                                let spells = match mode {
+                                    GexfMode::Static => {
+                                        values.extend(static_values(
+                                            props.iter(),
+                                            &edge_constant_ids,
+                                        ));
+                                        vec![]
+                                    }
+                                    GexfMode::Dynamic => {
+                                        values.extend(static_values(
+                                            props.constant().iter(),
+                                            &edge_constant_ids,
+                                        ));
+                                        for (prop_name, view) in props.temporal().iter() {
+                                            values.extend(dynamic_values(
+                                                &edge_temporal_ids[&prop_name],
+                                                view.iter(),
+                                            ));
+                                        }
+                                        if persistent {
+                                            alive_spells(edge.history(), edge.deletions())
+                                        } else {
+                                            point_spells(edge.history())
+                                        }
+                                    }
+                                };
+                                xml.create_element("edge")
+                                    .with_attributes(attributes)
+                                    .write_inner_content(|xml| {
+                                        write_element_body(xml, &values, &spells)
+                                    })?;
+                            }
+                            Ok(())
+                        },
+                    )?;
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn static_values<'a>(
+    props: impl Iterator<Item = (ArcStr, Prop)> + 'a,
+    ids: &'a HashMap<ArcStr, String>,
+) -> impl Iterator<Item = AttValue> + 'a {
+    props.map(|(name, value)| AttValue {
+        id: ids[&name].clone(),
+        value: value.to_string(),
+        start: None,
+        end: None,
+    })
+}
+
+/// Time-stamped values of a temporal property, each valid until the next update
+fn dynamic_values(id: &str, updates: impl Iterator<Item = (i64, Prop)>) -> Vec<AttValue> {
+    // only the last update at any given time is visible
+    let updates: Vec<_> = updates
+        .coalesce(|prev, next| {
+            if prev.0 == next.0 {
+                Ok(next)
+            } else {
+                Err((prev, next))
+            }
+        })
+        .collect();
+    let ends = updates.iter().skip(1).map(|(t, _)| Some(*t)).chain([None]);
+    updates
+        .iter()
+        .zip(ends)
+        .map(|((t, value), end)| AttValue {
+            id: id.to_owned(),
+            value: value.to_string(),
+            start: Some(*t),
+            end,
+        })
+        .collect()
+}
+
+/// A closed point spell for every distinct time of a history
+fn point_spells(history: Vec<i64>) -> Vec<(Option<i64>, Option<i64>)> {
+    history
+        .into_iter()
+        .dedup()
+        .map(|t| (Some(t), Some(t)))
+        .collect()
+}
+
+/// Intervals in which an edge is alive, closed at the start and open at the end, where `None`
+/// means unbounded
+fn alive_spells(additions: Vec<i64>, deletions: Vec<i64>) -> Vec<(Option<i64>, Option<i64>)> {
+    let events = additions
+        .into_iter()
+        .map(|t| (t, false))
+        .merge(deletions.into_iter().map(|t| (t, true)));
+    let mut spells = vec![];
+    let mut alive_since = None;
+    let mut first = true;
+    for (t, is_deletion) in events {
+        if is_deletion {
+            match alive_since.take() {
+                Some(start) => spells.push((start, Some(t))),
+                // a deletion without a previous addition means the edge was alive before
+                None if first => spells.push((None, Some(t))),
+                None => {}
+            }
+        } else if alive_since.is_none() {
+            alive_since = Some(Some(t));
+        }
+        first = false;
+    }
+    if let Some(start) = alive_since {
+        spells.push((start, None));
+    }
+    if spells.is_empty() {
+        // alive throughout the view without any updates inside it
+        spells.push((None, None));
+    }
+    spells
+}
+
+fn write_attributes<'a, W: Write>(
+    xml: &mut Writer<W>,
+    class: &str,
+    mode: &str,
+    keys: impl Iterator<Item = (String, &'a ArcStr, AttrType)>,
+) -> Result<(), GraphError> {
+    let keys: Vec<_> = keys.collect();
+    if keys.is_empty() {
+        return Ok(());
+    }
+    xml.create_element("attributes")
+        .with_attributes([("class", class), ("mode", mode)])
+        .write_inner_content(|xml| -> Result<(), GraphError> {
+            for (id, title, dtype) in keys {
+                xml.create_element("attribute")
+                    .with_attributes([
+                        ("id", id.as_str()),
+                        ("title", title),
+                        ("type", type_name(dtype)),
+                    ])
+                    .write_empty()?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+fn write_element_body<W: Write>(
+    xml: &mut Writer<W>,
+    values: &[AttValue],
+    spells: &[(Option<i64>, Option<i64>)],
+) -> Result<(), GraphError> {
+    if !values.is_empty() {
+        xml.create_element("attvalues")
+            .write_inner_content(|xml| -> Result<(), GraphError> {
+                for value in values {
+                    let start = value.start.map(|t| t.to_string());
+                    let end = value.end.map(|t| t.to_string());
+                    let mut attributes =
+                        vec![("for", value.id.as_str()), ("value", value.value.as_str())];
+                    if let Some(start) = &start {
+                        attributes.push(("start", start.as_str()));
+                    }
+                    if let Some(end) = &end {
+                        attributes.push(("endopen", end.as_str()));
+                    }
+                    xml.create_element("attvalue")
+                        .with_attributes(attributes)
+                        .write_empty()?;
+                }
+                Ok(())
+            })?;
+    }
+    if !spells.is_empty() {
+        xml.create_element("spells")
+            .write_inner_content(|xml| -> Result<(), GraphError> {
+                for &(start, end) in spells {
+                    // point spells are closed, intervals of persistent edges end when the edge is deleted
+                    let end_name = if start == end { "end" } else { "endopen" };
+                    let start = start.map(|t| t.to_string());
+                    let end = end.map(|t| t.to_string());
+                    let mut attributes = vec![];
+                    if let Some(start) = &start {
+                        attributes.push(("start", start.as_str()));
+                    }
+                    if let Some(end) = &end {
+                        attributes.push((end_name, end.as_str()));
+                    }
+                    xml.create_element("spell")
+                        .with_attributes(attributes)
+                        .write_empty()?;
+                }
+                Ok(())
+            })?;
+    }
+    Ok(())
+}
+
+/// Load a GEXF document into a graph
+///
+/// Nodes and edges are added at the start of each of their spells, or at `time` if they have no
+/// spells or start time. The end of an edge spell that is not a single point in time becomes an
+/// edge deletion. Dynamic attribute values become temporal properties at their start time and
+/// static ones become constant properties, except for the `node_type` attribute which sets the
+/// node type. The edge `kind` is used as the layer. Times are parsed according to the
+/// `timeformat` of the graph.
+///
+/// Arguments:
+///     graph: the graph to load into
+///     reader: source of the GEXF document
+///     time: the time at which nodes and edges without spells are added
+pub fn load_gexf<G>(graph: &G, reader: impl BufRead, time: i64) -> Result<(), GraphError>
+where
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps + InternalDeletionOps,
+{
+    let root = Element::parse(reader, FORMAT)?;
+    if root.name != "gexf" {
+        return Err(invalid(FORMAT, format!("unexpected root <{}>", root.name)));
+    }
+    let graph_element = root
+        .child("graph")
+        .ok_or_else(|| invalid(FORMAT, "missing <graph>"))?;
+    let time_format = graph_element.attr("timeformat").unwrap_or("integer");
+    let undirected = graph_element.attr("defaultedgetype") == Some("undirected");
+
+    let mut attributes = HashMap::new();
+    for block in graph_element.children("attributes") {
+        let class = block.attr("class").unwrap_or("node");
+        let dynamic = block.attr("mode") == Some("dynamic");
+        for attribute in block.children("attribute") {
+            let id = attribute.required_attr("id", FORMAT)?;
+            attributes.insert(
+                (class.to_owned(), id.to_owned()),
+                Attribute {
+                    title: attribute.attr("title").unwrap_or(id).to_owned(),
+                    dtype: parse_type(attribute.attr("type").unwrap_or("string")),
+                    dynamic,
+                    default: attribute.child("default").map(|d| d.text.clone()),
+                },
+            );
+        }
+    }
+    let parser = UpdateParser {
+        attributes: &attributes,
+        time_format,
+        time,
+    };
+
+    for node in graph_element.grandchildren("nodes", "node") {
+        let id = node.required_attr("id", FORMAT)?;
+        let mut updates = parser.parse(node, "node")?;
+        let node_type = updates
+            .constant
+            .iter()
+            .position(|(name, _)| name == NODE_TYPE)
+            .map(|index| updates.constant.remove(index).1.to_string());
+        let mut view = None;
+        for (t, props) in updates.additions {
+            view = Some(graph.add_node(t, id, props, node_type.as_deref())?);
+        }
+        if let Some(view) = view {
+            view.add_constant_properties(updates.constant)?;
+        }
+    }
+
+    for edge in graph_element.grandchildren("edges", "edge") {
+        let src = edge.required_attr("source", FORMAT)?;
+        let dst = edge.required_attr("target", FORMAT)?;
+        let layer = edge.attr("kind");
+        let updates = parser.parse(edge, "edge")?;
+        let mut pairs = vec![(src, dst)];
+        let edge_undirected = edge
+            .attr("type")
+            .map_or(undirected, |edge_type| edge_type == "undirected");
+        if edge_undirected && src != dst {
+            pairs.push((dst, src));
+        }
+        for (src, dst) in pairs {
+            let mut view = None;
+            for (t, props) in &updates.additions {
+                view = Some(graph.add_edge(*t, src, dst, props.clone(), layer)?);
+            }
+            for t in &updates.deletions {
+                delete_edge_at(graph, *t, src, dst, layer)?;
+            }
+            if let Some(view) = view.or_else(|| graph.edge(src, dst)) {
+                view.add_constant_properties(updates.constant.clone(), layer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Attribute {
+    title: String,
+    dtype: AttrType,
+    dynamic: bool,
+    default: Option<String>,
+}
+
+/// Additions, deletions and constant properties of a node or edge
+struct Updates {
+    additions: BTreeMap<i64, Vec<(String, Prop)>>,
+    deletions: Vec<i64>,
+    constant: Vec<(String, Prop)>,
+}
+
+struct UpdateParser<'a> {
+    attributes: &'a HashMap<(String, String), Attribute>,
+    time_format: &'a str,
+    time: i64,
+}
+
+impl<'a> UpdateParser<'a> {
+    fn parse_time(&self, value: &str) -> Result<i64, GraphError> {
+        match self.time_format {
+            "date" | "dateTime" | "datetime" => Ok(value.try_into_time()?),
+            _ => {
+                let value = value.trim();
+                value
+                    .parse::<i64>()
+                    .or_else(|_| value.parse::<f64>().map(|t| t as i64))
+                    .map_err(|_| invalid(FORMAT, format!("invalid time '{value}'")))
+            }
+        }
+    }
+
+    /// Start and end of a spell, an element or an attribute value
+    fn interval(&self, element: &Element) -> Result<(Option<i64>, Option<i64>), GraphError> {
+        if let Some(timestamp) = element.attr("timestamp") {
+            let t = self.parse_time(timestamp)?;
+            return Ok((Some(t), Some(t)));
+        }
+        let start = element.attr("start").or_else(|| element.attr("startopen"));
+        let end = element.attr("end").or_else(|| element.attr("endopen"));
+        Ok((
+            start.map(|t| self.parse_time(t)).transpose()?,
+            end.map(|t| self.parse_time(t)).transpose()?,
+        ))
+    }
+
+    fn parse(&self, element: &Element, class: &str) -> Result<Updates, GraphError> {
+        let mut updates = Updates {
+            additions: BTreeMap::new(),
+            deletions: vec![],
+            constant: vec![],
+        };
+        let mut spells = vec![];
+        for spell in element.grandchildren("spells", "spell") {
+            spells.push(self.interval(spell)?);
+        }
+        if spells.is_empty() {
+            let interval = self.interval(element)?;
+            if interval != (None, None) {
+                spells.push(interval);
+            }
+        }
+        for (start, end) in spells {
+            match start {
+                Some(start) => {
+                    updates.additions.entry(start).or_default();
+                }
+                None if end.is_none() => {
+                    updates.additions.entry(self.time).or_default();
+                }
+                None => {}
+            }
+            if let Some(end) = end {
+                if start != Some(end) {
+                    updates.deletions.push(end);
+                }
+            }
+        }
+        if updates.additions.is_empty() && updates.deletions.is_empty() {
+            updates.additions.entry(self.time).or_default();
+        }
+
+        let mut unstamped = vec![];
+        for value in element.grandchildren("attvalues", "attvalue") {
+            let id = value
+                .attr("for")
+                .or_else(|| value.attr("id"))
+                .ok_or_else(|| invalid(FORMAT, "<attvalue> is missing the 'for' attribute"))?;
+            let attribute = self
+                .attributes
+                .get(&(class.to_owned(), id.to_owned()))
+                .ok_or_else(|| invalid(FORMAT, format!("undeclared {class} attribute '{id}'")))?;
+            let raw = value.required_attr("value", FORMAT)?;
+            let prop = attribute.dtype.parse(raw).ok_or_else(|| {
+                invalid(
+                    FORMAT,
+                    format!(
+                        "invalid {:?} value '{raw}' for attribute '{id}'",
+                        attribute.dtype
+                    ),
+                )
+            })?;
+            let prop = (attribute.title.clone(), prop);
+            match self.interval(value)?.0 {
+                Some(start) => updates.additions.entry(start).or_default().push(prop),
+                None if attribute.dynamic => unstamped.push(prop),
+                None => updates.constant.push(prop),
+            }
+        }
+        if !unstamped.is_empty() {
+            let first = updates
+                .additions
+                .keys()
+                .next()
+                .copied()
+                .unwrap_or(self.time);
+            updates
+                .additions
+                .entry(first)
+                .or_default()
+                .extend(unstamped);
+        }
+
+        for ((attribute_class, _), attribute) in self.attributes {
+            if let Some(default) = &attribute.default {
+                if attribute_class == class
+                    && !attribute.dynamic
+                    && !updates
+                        .constant
+                        .iter()
+                        .any(|(name, _)| name == &attribute.title)
+                {
+                    if let Some(prop) = attribute.dtype.parse(default) {
+                        updates.constant.push((attribute.title.clone(), prop));
+                    }
+                }
+            }
+        }
+        Ok(updates)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db::graph::views::deletion_graph::PersistentGraph;
+    use std::io::Cursor;
+
+    fn to_gexf<'graph, G: GraphViewOps<'graph>>(graph: &G, mode: GexfMode) -> String {
+        let mut buf = vec![];
+        write_gexf(graph, &mut buf, mode).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn dynamic_gexf_round_trip() {
+        let g = Graph::new();
+        g.add_node(0, "alice", [("score", 1.0)], Some("person"))
+            .unwrap();
+        g.add_node(4, "alice", [("score", 2.0)], None).unwrap();
+        g.node("alice")
+            .unwrap()
+            .add_constant_properties([("team", "red")])
+            .unwrap();
+        g.add_edge(1, "alice", "bob", [("amount", 10i64)], Some("pays"))
+            .unwrap();
+        g.add_edge(3, "alice", "bob", [("amount", 20i64)], Some("pays"))
+            .unwrap();
+        g.add_edge(2, "bob", "alice", NO_PROPS, None).unwrap();
+
+        let gexf = to_gexf(&g, GexfMode::Dynamic);
+        assert!(gexf.contains(r#"mode="dynamic""#));
+        assert!(gexf.contains(r#"kind="pays""#));
+
+        let loaded = Graph::new();
+        load_gexf(&loaded, Cursor::new(gexf), 100).unwrap();
+        let alice = loaded.node("alice").unwrap();
+        assert_eq!(alice.node_type().unwrap(), "person");
+        assert_eq!(alice.earliest_time(), Some(0));
+        assert_eq!(alice.latest_time(), Some(4));
+        assert_eq!(
+            alice
+                .properties()
+                .temporal()
+                .get("score")
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![(0, Prop::F64(1.0)), (4, Prop::F64(2.0))]
+        );
+        assert_eq!(alice.properties().get("team"), Some(Prop::str("red")));
+
+        let pays = loaded.edge("alice", "bob").unwrap();
+        assert_eq!(pays.layer_names().collect::<Vec<_>>(), vec!["pays"]);
+        assert_eq!(pays.history(), vec![1, 3]);
+        assert_eq!(
+            pays.properties()
+                .temporal()
+                .get("amount")
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![(1, Prop::I64(10)), (3, Prop::I64(20))]
+        );
+        assert_eq!(loaded.edge("bob", "alice").unwrap().history(), vec![2]);
+    }
+
+    #[test]
+    fn persistent_edges_are_written_as_intervals() {
+        let g = PersistentGraph::new();
+        g.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        g.delete_edge(5, 1, 2, None).unwrap();
+        g.add_edge(7, 1, 2, NO_PROPS, None).unwrap();
+
+        let gexf = to_gexf(&g, GexfMode::Dynamic);
+        assert!(gexf.contains(r#"<spell start="1" endopen="5"/>"#));
+        assert!(gexf.contains(r#"<spell start="7"/>"#));
+
+        let loaded = PersistentGraph::new();
+        load_gexf(&loaded, Cursor::new(gexf), 0).unwrap();
+        let edge = loaded.edge(1, 2).unwrap();
+        assert_eq!(edge.history(), vec![1, 7]);
+        assert_eq!(edge.deletions(), vec![5]);
+    }
+
+    #[test]
+    fn static_gexf_has_latest_values() {
+        let g = Graph::new();
+        g.add_edge(1, "a", "b", [("weight", 1i64)], None).unwrap();
+        g.add_edge(2, "a", "b", [("weight", 3i64)], None).unwrap();
+        let gexf = to_gexf(&g.window(0, 2), GexfMode::Static);
+        assert!(gexf.contains(r#"mode="static""#));
+        assert!(!gexf.contains("<spells>"));
+
+        let loaded = Graph::new();
+        load_gexf(&loaded, Cursor::new(gexf), 9).unwrap();
+        let edge = loaded.edge("a", "b").unwrap();
+        assert_eq!(edge.history(), vec![9]);
+        assert_eq!(edge.properties().get("weight"), Some(Prop::I64(1)));
+    }
+}
@@ -0,0 +1,392 @@
+//! Read and write graphs as [GraphML](http://graphml.graphdrawing.org/)
+//!
+//! GraphML has no notion of time, so the writer stores the latest value of every property of a
+//! view and the reader adds all nodes and edges at a single time with their data as constant
+//! properties. Each layer of an edge becomes a separate GraphML edge.
+
+use crate::{
+    core::utils::errors::GraphError,
+    db::api::{
+        mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+        view::StaticGraphViewOps,
+    },
+    graph_loader::formats::{invalid, AttrKeys, AttrType, Element, LAYER, NODE_TYPE},
+    prelude::*,
+};
+use quick_xml::{
+    events::{BytesDecl, BytesText, Event},
+    Writer,
+};
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+
+const FORMAT: &str = "GraphML";
+
+fn type_name(dtype: AttrType) -> &'static str {
+    match dtype {
+        AttrType::Boolean => "boolean",
+        AttrType::Int => "int",
+        AttrType::Long => "long",
+        AttrType::Float => "float",
+        AttrType::Double => "double",
+        AttrType::String => "string",
+    }
+}
+
+fn parse_type(name: &str) -> AttrType {
+    match name {
+        "boolean" => AttrType::Boolean,
+        "int" => AttrType::Int,
+        "long" => AttrType::Long,
+        "float" => AttrType::Float,
+        "double" => AttrType::Double,
+        _ => AttrType::String,
+    }
+}
+
+/// Write the latest state of a graph view as GraphML
+///
+/// Node types are stored in the `node_type` key of nodes and layers in the `layer` key of edges,
+/// which is why properties with these names cannot be exported.
+///
+/// Arguments:
+///     graph: the graph view to write
+///     writer: destination of the GraphML document
+pub fn write_graphml<'graph, G: GraphViewOps<'graph>, W: Write>(
+    graph: &G,
+    writer: W,
+) -> Result<(), GraphError> {
+    let mut node_keys = AttrKeys::default();
+    let mut has_node_types = false;
+    for node in graph.nodes() {
+        has_node_types |= node.node_type().is_some();
+        for (name, value) in node.properties().iter() {
+            node_keys.add(name, &value);
+        }
+    }
+    let mut edge_keys = AttrKeys::default();
+    let mut has_layers = false;
+    for edge in graph.edges().explode_layers() {
+        has_layers |= edge.layer_name()? != "_default";
+        for (name, value) in edge.properties().iter() {
+            edge_keys.add(name, &value);
+        }
+    }
+    let mut graph_keys = AttrKeys::default();
+    for (name, value) in graph.properties().iter() {
+        graph_keys.add(name, &value);
+    }
+    if has_node_types && node_keys.contains(NODE_TYPE) {
+        return Err(reserved_name(NODE_TYPE));
+    }
+    if has_layers && edge_keys.contains(LAYER) {
+        return Err(reserved_name(LAYER));
+    }
+
+    let node_ids = node_keys.id_map("n");
+    let edge_ids = edge_keys.id_map("e");
+    let graph_ids = graph_keys.id_map("g");
+
+    let mut xml = Writer::new_with_indent(writer, b' ', 2);
+    xml.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    xml.create_element("graphml")
+        .with_attribute(("xmlns", "http://graphml.graphdrawing.org/xmlns"))
+        .write_inner_content(|xml| -> Result<(), GraphError> {
+            if has_node_types {
+                write_key(xml, NODE_TYPE, "node", NODE_TYPE, AttrType::String)?;
+            }
+            if has_layers {
+                write_key(xml, LAYER, "edge", LAYER, AttrType::String)?;
+            }
+            for (keys, prefix, domain) in [
+                (&node_keys, "n", "node"),
+                (&edge_keys, "e", "edge"),
+                (&graph_keys, "g", "graph"),
+            ] {
+                for (id, name, dtype) in keys.ids(prefix) {
+                    write_key(xml, &id, domain, name, dtype)?;
+                }
+            }
+            xml.create_element("graph")
+                .with_attributes([("id", "G"), ("edgedefault", "directed")])
+                .write_inner_content(|xml| -> Result<(), GraphError> {
+                    for (name, value) in graph.properties().iter() {
+                        write_data(xml, &graph_ids[&name], &value)?;
+                    }
+                    for node in graph.nodes() {
+                        let name = node.name();
+                        xml.create_element("node")
+                            .with_attribute(("id", name.as_str()))
+                            .write_inner_content(|xml| -> Result<(), GraphError> {
+                                if let Some(node_type) = node.node_type() {
+                                    write_data(xml, NODE_TYPE, &Prop::Str(node_type))?;
+                                }
+                                for (name, value) in node.properties().iter() {
+                                    write_data(xml, &node_ids[&name], &value)?;
+                                }
+                                Ok(())
+                            })?;
+                    }
+                    for edge in graph.edges().explode_layers() {
+                        let src = edge.src().name();
+                        let dst = edge.dst().name();
+                        let layer = edge.layer_name()?;
+                        xml.create_element("edge")
+                            .with_attributes([("source", src.as_str()), ("target", dst.as_str())])
+                            .write_inner_content(|xml| -> Result<(), GraphError> {
+                                if layer != "_default" {
+                                    write_data(xml, LAYER, &Prop::Str(layer))?;
+                                }
+                                for (name, value) in edge.properties().iter() {
+                                    write_data(xml, &edge_ids[&name], &value)?;
+                                }
+                                Ok(())
+                            })?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/// Load a GraphML document into a graph
+///
+/// All nodes and edges are added at `time`. Node data becomes constant node properties, except
+/// for the `node_type` key which sets the node type, and edge data becomes constant edge
+/// properties in the layer named by the `layer` key. Graph data becomes constant graph
+/// properties. Key defaults are applied to elements without a value for the key.
+///
+/// Arguments:
+///     graph: the graph to load into
+///     reader: source of the GraphML document
+///     time: the time at which all nodes and edges are added
+pub fn load_graphml<G>(graph: &G, reader: impl BufRead, time: i64) -> Result<(), GraphError>
+where
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+{
+    let root = Element::parse(reader, FORMAT)?;
+    if root.name != "graphml" {
+        return Err(invalid(FORMAT, format!("unexpected root <{}>", root.name)));
+    }
+    let mut keys = HashMap::new();
+    for key in root.children("key") {
+        let id = key.required_attr("id", FORMAT)?;
+        let name = key.attr("attr.name").unwrap_or(id).to_owned();
+        let dtype = parse_type(key.attr("attr.type").unwrap_or("string"));
+        let domain = key.attr("for").unwrap_or("all").to_owned();
+        let default = key.child("default").map(|default| default.text.clone());
+        keys.insert(
+            id.to_owned(),
+            Key {
+                name,
+                dtype,
+                domain,
+                default,
+            },
+        );
+    }
+
+    for graph_element in root.children("graph") {
+        let directed = graph_element.attr("edgedefault") != Some("undirected");
+        let props = element_data(graph_element, &keys, "graph")?;
+        graph.add_constant_properties(props)?;
+
+        for node in graph_element.children("node") {
+            let id = node.required_attr("id", FORMAT)?;
+            let mut props = element_data(node, &keys, "node")?;
+            let node_type = take_str(&mut props, NODE_TYPE);
+            graph
+                .add_node(time, id, NO_PROPS, node_type.as_deref())?
+                .add_constant_properties(props)?;
+        }
+
+        for edge in graph_element.children("edge") {
+            let src = edge.required_attr("source", FORMAT)?;
+            let dst = edge.required_attr("target", FORMAT)?;
+            let mut props = element_data(edge, &keys, "edge")?;
+            let layer = take_str(&mut props, LAYER);
+            let layer = layer.as_deref();
+            let undirected = edge
+                .attr("directed")
+                .map_or(!directed, |directed| directed == "false");
+            graph
+                .add_edge(time, src, dst, NO_PROPS, layer)?
+                .add_constant_properties(props.clone(), layer)?;
+            if undirected && src != dst {
+                graph
+                    .add_edge(time, dst, src, NO_PROPS, layer)?
+                    .add_constant_properties(props, layer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Key {
+    name: String,
+    dtype: AttrType,
+    domain: String,
+    default: Option<String>,
+}
+
+impl Key {
+    fn applies_to(&self, domain: &str) -> bool {
+        self.domain == domain || self.domain == "all"
+    }
+
+    fn parse(&self, id: &str, value: &str) -> Result<Prop, GraphError> {
+        self.dtype.parse(value).ok_or_else(|| {
+            invalid(
+                FORMAT,
+                format!("invalid {:?} value '{value}' for key '{id}'", self.dtype),
+            )
+        })
+    }
+}
+
+/// The data of an element followed by the defaults of keys it has no data for
+fn element_data(
+    element: &Element,
+    keys: &HashMap<String, Key>,
+    domain: &str,
+) -> Result<Vec<(String, Prop)>, GraphError> {
+    let mut props = vec![];
+    for data in element.children("data") {
+        let id = data.required_attr("key", FORMAT)?;
+        let key = keys
+            .get(id)
+            .ok_or_else(|| invalid(FORMAT, format!("undeclared key '{id}'")))?;
+        props.push((key.name.clone(), key.parse(id, &data.text)?));
+    }
+    for (id, key) in keys {
+        if let Some(default) = &key.default {
+            if key.applies_to(domain) && !props.iter().any(|(name, _)| name == &key.name) {
+                props.push((key.name.clone(), key.parse(id, default)?));
+            }
+        }
+    }
+    Ok(props)
+}
+
+fn take_str(props: &mut Vec<(String, Prop)>, name: &str) -> Option<String> {
+    let index = props.iter().position(|(key, _)| key == name)?;
+    Some(props.remove(index).1.to_string())
+}
+
+fn reserved_name(name: &str) -> GraphError {
+    invalid(
+        FORMAT,
+        format!("property '{name}' clashes with the key of the same name"),
+    )
+}
+
+fn write_key<W: Write>(
+    xml: &mut Writer<W>,
+    id: &str,
+    domain: &str,
+    name: &str,
+    dtype: AttrType,
+) -> Result<(), GraphError> {
+    xml.create_element("key")
+        .with_attributes([
+            ("id", id),
+            ("for", domain),
+            ("attr.name", name),
+            ("attr.type", type_name(dtype)),
+        ])
+        .write_empty()?;
+    Ok(())
+}
+
+fn write_data<W: Write>(xml: &mut Writer<W>, key: &str, value: &Prop) -> Result<(), GraphError> {
+    xml.create_element("data")
+        .with_attribute(("key", key))
+        .write_text_content(BytesText::new(&value.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn graphml_round_trip() {
+        let g = Graph::new();
+        g.add_node(0, "alice", [("age", 31i64)], Some("person"))
+            .unwrap();
+        g.add_node(0, "bob", NO_PROPS, None).unwrap();
+        g.add_edge(1, "alice", "bob", [("amount", 1.5)], Some("pays"))
+            .unwrap();
+        g.add_edge(2, "alice", "bob", [("amount", 2.5)], Some("pays"))
+            .unwrap();
+        g.add_edge(3, "bob", "alice", [("note", "hi")], None)
+            .unwrap();
+        g.add_constant_properties([("name", "payments")]).unwrap();
+
+        let mut buf = vec![];
+        write_graphml(&g, &mut buf).unwrap();
+        let loaded = Graph::new();
+        load_graphml(&loaded, Cursor::new(buf), 5).unwrap();
+
+        assert_eq!(loaded.count_nodes(), 2);
+        assert_eq!(loaded.count_edges(), 2);
+        let alice = loaded.node("alice").unwrap();
+        assert_eq!(alice.node_type().unwrap(), "person");
+        assert_eq!(alice.properties().get("age"), Some(Prop::I64(31)));
+        assert_eq!(alice.earliest_time(), Some(5));
+        let pays = loaded.edge("alice", "bob").unwrap();
+        assert_eq!(pays.layer_names().collect::<Vec<_>>(), vec!["pays"]);
+        // edge data is loaded as constant properties of the edge's layer
+        assert_eq!(
+            pays.layers("pays").unwrap().properties().get("amount"),
+            Some(Prop::F64(2.5))
+        );
+        let note = loaded.edge("bob", "alice").unwrap();
+        assert_eq!(
+            note.default_layer().properties().get("note"),
+            Some(Prop::str("hi"))
+        );
+        assert_eq!(loaded.properties().get("name"), Some(Prop::str("payments")));
+    }
+
+    #[test]
+    fn load_undirected_graphml_with_defaults() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="color" attr.type="string">
+                <default>yellow</default>
+              </key>
+              <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+              <graph id="G" edgedefault="undirected">
+                <node id="n0"><data key="d0">green</data></node>
+                <node id="n1"/>
+                <edge source="n0" target="n1"><data key="d1">1.0</data></edge>
+              </graph>
+            </graphml>"#;
+        let g = Graph::new();
+        load_graphml(&g, Cursor::new(document), 0).unwrap();
+        assert_eq!(
+            g.node("n0").unwrap().properties().get("color"),
+            Some(Prop::str("green"))
+        );
+        assert_eq!(
+            g.node("n1").unwrap().properties().get("color"),
+            Some(Prop::str("yellow"))
+        );
+        assert_eq!(
+            g.edge("n1", "n0").unwrap().properties().get("weight"),
+            Some(Prop::F64(1.0))
+        );
+    }
+
+    #[test]
+    fn property_named_like_a_key_is_an_error() {
+        let g = Graph::new();
+        g.add_edge(0, 1, 2, [("layer", "x")], Some("a")).unwrap();
+        assert!(write_graphml(&g, vec![]).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Read and write graphs in common graph exchange formats
+//!
+//! - [`graphml`] writes any graph view as GraphML and loads GraphML files,
+//! - [`gexf`] writes static or dynamic GEXF, including spells and time-stamped attribute values,
+//!   and loads GEXF files back into a graph,
+//! - [`dot`] writes graph views in the Graphviz DOT language,
+//! - [`neo4j`] writes graph views as `neo4j-admin` import CSV files or Cypher `CREATE` scripts,
+//!   flattening the history of edges into one relationship per update or per edge.
+//!
+//! Layers are written as the `layer` attribute of edges (the edge `kind` in GEXF) and node types
+//! as the `node_type` attribute of nodes. Edges in the default layer and nodes without a type
+//! carry no such attribute.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use raphtory::graph_loader::formats::{gexf::{write_gexf, GexfMode}, graphml::load_graphml};
+//! use raphtory::prelude::*;
+//! use std::{fs::File, io::BufReader};
+//!
+//! let g = Graph::new();
+//! load_graphml(&g, BufReader::new(File::open("/tmp/graph.graphml").unwrap()), 0).unwrap();
+//! write_gexf(&g, File::create("/tmp/graph.gexf").unwrap(), GexfMode::Dynamic).unwrap();
+//! ```
+
+use crate::{
+    core::{utils::errors::GraphError, ArcStr, PropType},
+    prelude::Prop,
+};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::BufRead,
+};
+
+pub mod dot;
+pub mod gexf;
+pub mod graphml;
+pub mod neo4j;
+
+/// Name of the attribute holding the node type
+pub(crate) const NODE_TYPE: &str = "node_type";
+/// Name of the attribute holding the edge layer
+pub(crate) const LAYER: &str = "layer";
+
+/// Value types of GraphML and GEXF attributes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttrType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+}
+
+impl AttrType {
+    /// The narrowest attribute type holding all values of `dtype`
+    pub(crate) fn of(dtype: PropType) -> Self {
+        match dtype {
+            PropType::Bool => AttrType::Boolean,
+            PropType::I8 | PropType::I16 | PropType::I32 | PropType::U8 | PropType::U16 => {
+                AttrType::Int
+            }
+            PropType::I64 | PropType::U32 | PropType::U64 => AttrType::Long,
+            PropType::F32 => AttrType::Float,
+            PropType::F64 => AttrType::Double,
+            _ => AttrType::String,
+        }
+    }
+
+    /// The common type of two attributes with the same name, falling back to strings
+    pub(crate) fn merge(self, other: Self) -> Self {
+        if self == other {
+            self
+        } else {
+            AttrType::String
+        }
+    }
+
+    pub(crate) fn parse(self, value: &str) -> Option<Prop> {
+        let trimmed = value.trim();
+        match self {
+            AttrType::Boolean => match trimmed.to_lowercase().as_str() {
+                "true" | "1" => Some(Prop::Bool(true)),
+                "false" | "0" => Some(Prop::Bool(false)),
+                _ => None,
+            },
+            AttrType::Int => trimmed.parse().ok().map(Prop::I32),
+            AttrType::Long => trimmed.parse().ok().map(Prop::I64),
+            AttrType::Float => trimmed.parse().ok().map(Prop::F32),
+            AttrType::Double => trimmed.parse().ok().map(Prop::F64),
+            AttrType::String => Some(Prop::str(value)),
+        }
+    }
+}
+
+/// Names and types of the properties written for one kind of element
+#[derive(Debug, Default)]
+pub(crate) struct AttrKeys(BTreeMap<ArcStr, AttrType>);
+
+impl AttrKeys {
+    pub(crate) fn add(&mut self, name: ArcStr, value: &Prop) {
+        let dtype = AttrType::of(value.dtype());
+        self.0
+            .entry(name)
+            .and_modify(|existing| *existing = existing.merge(dtype))
+            .or_insert(dtype);
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Attribute ids, names and types, with ids made from `prefix` and the position of the name
+    pub(crate) fn ids<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (String, &'a ArcStr, AttrType)> + 'a {
+        self.0
+            .iter()
+            .enumerate()
+            .map(move |(i, (name, dtype))| (format!("{prefix}{i}"), name, *dtype))
+    }
+
+    /// Map from property name to attribute id
+    pub(crate) fn id_map(&self, prefix: &str) -> HashMap<ArcStr, String> {
+        self.ids(prefix)
+            .map(|(id, name, _)| (name.clone(), id))
+            .collect()
+    }
+}
+
+/// Minimal in-memory XML element tree, enough for reading graph exchange formats
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: HashMap<String, String>,
+    pub(crate) children: Vec<Element>,
+    pub(crate) text: String,
+}
+
+impl Element {
+    /// Parse a document and return its root element
+    pub(crate) fn parse(reader: impl BufRead, format: &'static str) -> Result<Self, GraphError> {
+        let mut reader = Reader::from_reader(reader);
+        reader.trim_text(true);
+        let mut buf = vec![];
+        // the bottom of the stack collects the document's top-level elements
+        let mut stack = vec![Element::default()];
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(start) => stack.push(Element::from_start(&start)?),
+                Event::Empty(start) => {
+                    let element = Element::from_start(&start)?;
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(element);
+                    }
+                }
+                Event::End(_) => {
+                    if stack.len() < 2 {
+                        return Err(invalid(format, "unexpected closing tag"));
+                    }
+                    let element = stack.pop().unwrap();
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(element);
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(element) = stack.last_mut() {
+                        element.text.push_str(&text.unescape()?);
+                    }
+                }
+                Event::CData(data) => {
+                    if let Some(element) = stack.last_mut() {
+                        element.text.push_str(&String::from_utf8_lossy(&data));
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        if stack.len() != 1 {
+            return Err(invalid(format, "unexpected end of file"));
+        }
+        stack
+            .pop()
+            .and_then(|document| document.children.into_iter().next())
+            .ok_or_else(|| invalid(format, "empty document"))
+    }
+
+    fn from_start(start: &BytesStart) -> Result<Self, GraphError> {
+        let mut attributes = HashMap::new();
+        for attr in start.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+            attributes.insert(key, attr.unescape_value()?.into_owned());
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|value| value.as_str())
+    }
+
+    pub(crate) fn required_attr(
+        &self,
+        name: &str,
+        format: &'static str,
+    ) -> Result<&str, GraphError> {
+        self.attr(name).ok_or_else(|| {
+            invalid(
+                format,
+                format!("<{}> is missing the '{name}' attribute", self.name),
+            )
+        })
+    }
+
+    pub(crate) fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub(crate) fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// The children called `name` of the first child called `parent`
+    pub(crate) fn grandchildren<'a>(
+        &'a self,
+        parent: &'a str,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Element> + 'a {
+        self.child(parent)
+            .into_iter()
+            .flat_map(move |parent| parent.children(name))
+    }
+}
+
+pub(crate) fn invalid(format: &'static str, message: impl Into<String>) -> GraphError {
+    GraphError::InvalidFileFormat {
+        format,
+        message: message.into(),
+    }
+}
@@ -0,0 +1,506 @@
+//! Write graphs for [Neo4j](https://neo4j.com/), either as `neo4j-admin database import` CSV
+//! files or as a script of Cypher `CREATE` statements
+//!
+//! Nodes are identified by their name, which becomes the `name` property of the Neo4j node, and
+//! node types become labels. Layers become relationship types, with edges in the default layer
+//! written with the type `_default`. Neo4j relationships have no history, so the temporal history
+//! of edges is flattened according to [`Neo4jHistory`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use raphtory::graph_loader::formats::neo4j::{write_neo4j_import, Neo4jHistory};
+//! use raphtory::prelude::*;
+//!
+//! let g = Graph::new();
+//! g.add_edge(1, "alice", "bob", [("amount", 10i64)], Some("pays")).unwrap();
+//! write_neo4j_import(&g, "/tmp/neo4j", Neo4jHistory::PerEdge).unwrap();
+//! // neo4j-admin database import full --nodes=/tmp/neo4j/nodes.csv \
+//! //     --relationships=/tmp/neo4j/relationships.csv
+//! ```
+
+use crate::{
+    core::{utils::errors::GraphError, ArcStr},
+    graph_loader::formats::{invalid, AttrKeys, AttrType},
+    prelude::*,
+};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+const FORMAT: &str = "Neo4j";
+/// Property holding the name of nodes
+const NAME: &str = "name";
+/// Property holding the time of relationships written with [`Neo4jHistory::PerUpdate`]
+const TIME: &str = "time";
+/// Property holding the update times of relationships written with [`Neo4jHistory::PerEdge`]
+const HISTORY: &str = "history";
+/// Property holding the deletion times of relationships written with [`Neo4jHistory::PerEdge`]
+const DELETIONS: &str = "deletions";
+
+/// How the temporal history of edges is flattened into relationships
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Neo4jHistory {
+    /// One relationship per update of an edge, with the `time` of the update and the property
+    /// values set at that time
+    #[default]
+    PerUpdate,
+    /// One relationship per edge and layer, with the update times in a `history` array and the
+    /// latest property values. Graphs with deletions also get a `deletions` array.
+    PerEdge,
+}
+
+/// Times of a single relationship
+enum Times {
+    At(i64),
+    History {
+        additions: Vec<i64>,
+        deletions: Option<Vec<i64>>,
+    },
+}
+
+/// A relationship as it is written to Neo4j
+struct Relationship {
+    src: String,
+    dst: String,
+    rel_type: ArcStr,
+    times: Times,
+    properties: Vec<(ArcStr, Prop)>,
+}
+
+/// Write a graph view as node and relationship CSV files for `neo4j-admin database import`
+///
+/// The node file has the columns `name:ID` and `:LABEL` followed by one typed column per node
+/// property. The relationship file has the columns `:START_ID`, `:END_ID` and `:TYPE`, then
+/// either `time:long` or `history:long[]` (and `deletions:long[]` for graphs with deletions)
+/// depending on `history`, followed by one typed column per edge property. Array values are
+/// separated by `;`, the default array delimiter of the import tool.
+///
+/// Arguments:
+///     graph: the graph view to write
+///     nodes: destination of the node CSV
+///     relationships: destination of the relationship CSV
+///     history: how the history of edges is flattened
+pub fn write_neo4j_csv<'graph, G: GraphViewOps<'graph>, N: Write, R: Write>(
+    graph: &G,
+    nodes: N,
+    relationships: R,
+    history: Neo4jHistory,
+) -> Result<(), GraphError> {
+    let mut node_keys = AttrKeys::default();
+    for node in graph.nodes() {
+        for (name, value) in node.properties().iter() {
+            node_keys.add(name, &value);
+        }
+    }
+    if node_keys.contains(NAME) {
+        return Err(reserved_name(NAME));
+    }
+    let mut node_writer = csv::Writer::from_writer(nodes);
+    let mut header = vec![format!("{NAME}:ID"), ":LABEL".to_owned()];
+    header.extend(
+        node_keys
+            .ids("")
+            .map(|(_, name, dtype)| format!("{name}:{}", type_name(dtype))),
+    );
+    node_writer.write_record(&header)?;
+    for node in graph.nodes() {
+        let props = node.properties();
+        let mut row = vec![
+            node.name(),
+            node.node_type().map(|t| t.to_string()).unwrap_or_default(),
+        ];
+        row.extend(node_keys.ids("").map(|(_, name, _)| {
+            props
+                .get(name)
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        }));
+        node_writer.write_record(&row)?;
+    }
+    node_writer.flush()?;
+
+    let mut edge_keys = AttrKeys::default();
+    for_each_relationship(graph, history, |relationship| {
+        for (name, value) in relationship.properties {
+            edge_keys.add(name, &value);
+        }
+        Ok(())
+    })?;
+    let with_deletions = graph.include_deletions();
+    let time_columns: Vec<_> = match history {
+        Neo4jHistory::PerUpdate => vec![format!("{TIME}:long")],
+        Neo4jHistory::PerEdge if with_deletions => {
+            vec![format!("{HISTORY}:long[]"), format!("{DELETIONS}:long[]")]
+        }
+        Neo4jHistory::PerEdge => vec![format!("{HISTORY}:long[]")],
+    };
+    check_time_names(&edge_keys, history, with_deletions)?;
+    let mut relationship_writer = csv::Writer::from_writer(relationships);
+    let mut header = vec![
+        ":START_ID".to_owned(),
+        ":END_ID".to_owned(),
+        ":TYPE".to_owned(),
+    ];
+    header.extend(time_columns);
+    header.extend(
+        edge_keys
+            .ids("")
+            .map(|(_, name, dtype)| format!("{name}:{}", type_name(dtype))),
+    );
+    relationship_writer.write_record(&header)?;
+    for_each_relationship(graph, history, |relationship| {
+        let mut row = vec![
+            relationship.src,
+            relationship.dst,
+            relationship.rel_type.to_string(),
+        ];
+        match relationship.times {
+            Times::At(t) => row.push(t.to_string()),
+            Times::History {
+                additions,
+                deletions,
+            } => {
+                row.push(csv_array(&additions));
+                if let Some(deletions) = deletions {
+                    row.push(csv_array(&deletions));
+                }
+            }
+        }
+        row.extend(edge_keys.ids("").map(|(_, name, _)| {
+            relationship
+                .properties
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default()
+        }));
+        relationship_writer.write_record(&row)?;
+        Ok(())
+    })?;
+    relationship_writer.flush()?;
+    Ok(())
+}
+
+/// Write a graph view as a script of Cypher `CREATE` statements
+///
+/// Every node is created by its own statement, followed by one statement per relationship that
+/// matches its endpoints by name. For large graphs, create an index on the `name` property of the
+/// node labels before running the script, or use [`write_neo4j_csv`] instead.
+///
+/// Arguments:
+///     graph: the graph view to write
+///     writer: destination of the script
+///     history: how the history of edges is flattened
+pub fn write_cypher<'graph, G: GraphViewOps<'graph>, W: Write>(
+    graph: &G,
+    mut writer: W,
+    history: Neo4jHistory,
+) -> Result<(), GraphError> {
+    for node in graph.nodes() {
+        let mut properties = vec![(ArcStr::from(NAME), cypher_string(&node.name()))];
+        for (name, value) in node.properties().iter() {
+            if name == NAME {
+                return Err(reserved_name(NAME));
+            }
+            properties.push((name, cypher_value(&value)));
+        }
+        let label = node
+            .node_type()
+            .map(|t| format!(":{} ", cypher_name(&t)))
+            .unwrap_or_default();
+        writeln!(writer, "CREATE ({label}{});", cypher_map(properties))?;
+    }
+    let with_deletions = graph.include_deletions();
+    for_each_relationship(graph, history, |relationship| {
+        let mut properties = vec![];
+        match relationship.times {
+            Times::At(t) => properties.push((ArcStr::from(TIME), t.to_string())),
+            Times::History {
+                additions,
+                deletions,
+            } => {
+                properties.push((ArcStr::from(HISTORY), cypher_list(&additions)));
+                if let Some(deletions) = deletions {
+                    properties.push((ArcStr::from(DELETIONS), cypher_list(&deletions)));
+                }
+            }
+        }
+        for (name, value) in relationship.properties {
+            if is_time_name(&name, history, with_deletions) {
+                return Err(reserved_name(&name));
+            }
+            properties.push((name, cypher_value(&value)));
+        }
+        writeln!(
+            writer,
+            "MATCH (src {{{NAME}: {}}}), (dst {{{NAME}: {}}}) CREATE (src)-[:{} {}]->(dst);",
+            cypher_string(&relationship.src),
+            cypher_string(&relationship.dst),
+            cypher_name(&relationship.rel_type),
+            cypher_map(properties)
+        )?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// Write `nodes.csv`, `relationships.csv` and `create.cypher` for a graph view into `dir`
+///
+/// The directory is created if it does not exist. See [`write_neo4j_csv`] and [`write_cypher`]
+/// for the contents of the files.
+pub fn write_neo4j_import<'graph, G: GraphViewOps<'graph>, P: AsRef<Path>>(
+    graph: &G,
+    dir: P,
+    history: Neo4jHistory,
+) -> Result<(), GraphError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    write_neo4j_csv(
+        graph,
+        BufWriter::new(File::create(dir.join("nodes.csv"))?),
+        BufWriter::new(File::create(dir.join("relationships.csv"))?),
+        history,
+    )?;
+    let mut script = BufWriter::new(File::create(dir.join("create.cypher"))?);
+    write_cypher(graph, &mut script, history)?;
+    script.flush()?;
+    Ok(())
+}
+
+/// Call `f` with every relationship written for the edges of `graph`
+fn for_each_relationship<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    history: Neo4jHistory,
+    mut f: impl FnMut(Relationship) -> Result<(), GraphError>,
+) -> Result<(), GraphError> {
+    match history {
+        Neo4jHistory::PerUpdate => {
+            for edge in graph.edges().explode() {
+                f(Relationship {
+                    src: edge.src().name(),
+                    dst: edge.dst().name(),
+                    rel_type: edge.layer_name()?,
+                    times: Times::At(edge.time()?),
+                    properties: edge.properties().iter().collect(),
+                })?;
+            }
+        }
+        Neo4jHistory::PerEdge => {
+            let with_deletions = graph.include_deletions();
+            for edge in graph.edges().explode_layers() {
+                f(Relationship {
+                    src: edge.src().name(),
+                    dst: edge.dst().name(),
+                    rel_type: edge.layer_name()?,
+                    times: Times::History {
+                        additions: edge.history(),
+                        deletions: with_deletions.then(|| edge.deletions()),
+                    },
+                    properties: edge.properties().iter().collect(),
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_time_name(name: &str, history: Neo4jHistory, with_deletions: bool) -> bool {
+    match history {
+        Neo4jHistory::PerUpdate => name == TIME,
+        Neo4jHistory::PerEdge => name == HISTORY || (with_deletions && name == DELETIONS),
+    }
+}
+
+fn check_time_names(
+    keys: &AttrKeys,
+    history: Neo4jHistory,
+    with_deletions: bool,
+) -> Result<(), GraphError> {
+    for name in [TIME, HISTORY, DELETIONS] {
+        if keys.contains(name) && is_time_name(name, history, with_deletions) {
+            return Err(reserved_name(name));
+        }
+    }
+    Ok(())
+}
+
+fn type_name(dtype: AttrType) -> &'static str {
+    match dtype {
+        AttrType::Boolean => "boolean",
+        AttrType::Int => "int",
+        AttrType::Long => "long",
+        AttrType::Float => "float",
+        AttrType::Double => "double",
+        AttrType::String => "string",
+    }
+}
+
+fn csv_array(values: &[i64]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn cypher_list(values: &[i64]) -> String {
+    let values: Vec<_> = values.iter().map(|v| v.to_string()).collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn cypher_map(properties: Vec<(ArcStr, String)>) -> String {
+    let entries: Vec<_> = properties
+        .into_iter()
+        .map(|(name, value)| format!("{}: {value}", cypher_name(&name)))
+        .collect();
+    format!("{{{}}}", entries.join(", "))
+}
+
+/// A Cypher literal for a property value, falling back to strings for values without one
+fn cypher_value(value: &Prop) -> String {
+    match value {
+        Prop::Bool(v) => v.to_string(),
+        Prop::U8(v) => v.to_string(),
+        Prop::U16(v) => v.to_string(),
+        Prop::U32(v) => v.to_string(),
+        Prop::U64(v) => v.to_string(),
+        Prop::I8(v) => v.to_string(),
+        Prop::I16(v) => v.to_string(),
+        Prop::I32(v) => v.to_string(),
+        Prop::I64(v) => v.to_string(),
+        // debug formatting keeps the decimal point, so whole numbers stay floats in Cypher
+        Prop::F32(v) if v.is_finite() => format!("{v:?}"),
+        Prop::F64(v) if v.is_finite() => format!("{v:?}"),
+        Prop::F32(v) => non_finite(*v as f64),
+        Prop::F64(v) => non_finite(*v),
+        Prop::List(values) => {
+            let values: Vec<_> = values.iter().map(cypher_value).collect();
+            format!("[{}]", values.join(", "))
+        }
+        Prop::Str(v) => cypher_string(v),
+        other => cypher_string(&other.to_string()),
+    }
+}
+
+fn non_finite(value: f64) -> String {
+    if value.is_nan() {
+        "NaN"
+    } else if value > 0.0 {
+        "Infinity"
+    } else {
+        "-Infinity"
+    }
+    .to_owned()
+}
+
+/// Quote a Cypher string literal
+fn cypher_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Quote a label, relationship type or property key with backticks
+fn cypher_name(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+fn reserved_name(name: &str) -> GraphError {
+    invalid(
+        FORMAT,
+        format!("property '{name}' clashes with the column of the same name"),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db::graph::views::deletion_graph::PersistentGraph;
+
+    fn to_csv<'graph, G: GraphViewOps<'graph>>(
+        graph: &G,
+        history: Neo4jHistory,
+    ) -> (String, String) {
+        let mut nodes = vec![];
+        let mut relationships = vec![];
+        write_neo4j_csv(graph, &mut nodes, &mut relationships, history).unwrap();
+        (
+            String::from_utf8(nodes).unwrap(),
+            String::from_utf8(relationships).unwrap(),
+        )
+    }
+
+    #[test]
+    fn write_neo4j_csv_per_update_and_per_edge() {
+        let g = Graph::new();
+        g.add_node(0, "alice", [("age", 31i64)], Some("person"))
+            .unwrap();
+        g.add_edge(1, "alice", "bob", [("amount", 1.5)], Some("pays"))
+            .unwrap();
+        g.add_edge(3, "alice", "bob", [("amount", 2.5)], Some("pays"))
+            .unwrap();
+        g.add_edge(2, "bob", "alice", NO_PROPS, None).unwrap();
+
+        let (nodes, relationships) = to_csv(&g, Neo4jHistory::PerUpdate);
+        assert_eq!(nodes, "name:ID,:LABEL,age:long\nalice,person,31\nbob,,\n");
+        assert_eq!(
+            relationships,
+            ":START_ID,:END_ID,:TYPE,time:long,amount:double\n\
+             alice,bob,pays,1,1.5\n\
+             alice,bob,pays,3,2.5\n\
+             bob,alice,_default,2,\n"
+        );
+
+        let (_, relationships) = to_csv(&g, Neo4jHistory::PerEdge);
+        assert_eq!(
+            relationships,
+            ":START_ID,:END_ID,:TYPE,history:long[],amount:double\n\
+             alice,bob,pays,1;3,2.5\n\
+             bob,alice,_default,2,\n"
+        );
+
+        let persistent = PersistentGraph::new();
+        persistent.add_edge(1, "a", "b", NO_PROPS, None).unwrap();
+        persistent.delete_edge(4, "a", "b", None).unwrap();
+        let (_, relationships) = to_csv(&persistent, Neo4jHistory::PerEdge);
+        assert_eq!(
+            relationships,
+            ":START_ID,:END_ID,:TYPE,history:long[],deletions:long[]\na,b,_default,1,4\n"
+        );
+    }
+
+    #[test]
+    fn write_cypher_script() {
+        let g = Graph::new();
+        g.add_node(0, "o'brien", [("score", 1.0)], Some("person"))
+            .unwrap();
+        g.add_edge(1, "o'brien", "bob", [("note", "hi")], Some("knows"))
+            .unwrap();
+
+        let mut buf = vec![];
+        write_cypher(&g, &mut buf, Neo4jHistory::PerUpdate).unwrap();
+        let script = String::from_utf8(buf).unwrap();
+        let expected = "CREATE (:`person` {`name`: 'o\\'brien', `score`: 1.0});\n\
+                        CREATE ({`name`: 'bob'});\n\
+                        MATCH (src {name: 'o\\'brien'}), (dst {name: 'bob'}) \
+                        CREATE (src)-[:`knows` {`time`: 1, `note`: 'hi'}]->(dst);\n";
+        assert_eq!(script, expected);
+
+        g.add_node(2, "bob", [("name", "Bob")], None).unwrap();
+        assert!(write_cypher(&g, vec![], Neo4jHistory::PerUpdate).is_err());
+    }
+}
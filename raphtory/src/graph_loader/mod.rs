@@ -106,6 +106,7 @@ use std::{
 use zip::read::ZipArchive;
 
 pub mod example;
+pub mod formats;
 pub mod source;
 
 pub fn fetch_file(
@@ -61,6 +61,10 @@
 //!
 
 /// Module for loading CSV files into a graph.
+use crate::core::utils::{
+    dead_letter::{DeadLetters, ErrorPolicy, LoadReport, RejectedRecord},
+    errors::GraphError,
+};
 use bzip2::read::BzDecoder;
 use csv::StringRecord;
 use flate2; // 1.0
@@ -85,6 +89,8 @@ pub enum CsvErr {
     IoError(io::Error),
     /// A CSV parsing error that occurred while parsing the CSV data.
     CsvError(csv::Error),
+    /// A GraphError that occurred while loading the CSV data into the graph.
+    GraphError(GraphError),
 }
 
 impl From<io::Error> for CsvErr {
@@ -99,6 +105,12 @@ impl From<csv::Error> for CsvErr {
     }
 }
 
+impl From<GraphError> for CsvErr {
+    fn from(value: GraphError) -> Self {
+        Self::GraphError(value)
+    }
+}
+
 impl Display for CsvErr {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self.source() {
@@ -113,6 +125,7 @@ impl Error for CsvErr {
         match self {
             CsvErr::IoError(error) => Some(error),
             CsvErr::CsvError(error) => Some(error),
+            CsvErr::GraphError(error) => Some(error),
         }
     }
 }
@@ -130,6 +143,8 @@ pub struct CsvLoader {
     delimiter: u8,
     /// print the name of the file being loaded
     print_file_name: bool,
+    /// Handling of records that cannot be loaded
+    dead_letters: DeadLetters,
 }
 
 impl CsvLoader {
@@ -153,6 +168,7 @@ impl CsvLoader {
             header: false,
             delimiter: b',',
             print_file_name: false,
+            dead_letters: DeadLetters::default(),
         }
     }
 
@@ -226,6 +242,51 @@ impl CsvLoader {
         self
     }
 
+    /// Sets what happens to records that cannot be deserialized or loaded.
+    ///
+    /// By default the load fails on the first bad record, reporting its file and line.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The error policy to apply.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use raphtory::core::utils::dead_letter::ErrorPolicy;
+    /// use raphtory::graph_loader::source::csv_loader::CsvLoader;
+    /// let loader = CsvLoader::new("/path/to/csv_file.csv").set_error_policy(ErrorPolicy::Collect);
+    /// ```
+    pub fn set_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.dead_letters = DeadLetters::new(policy);
+        self
+    }
+
+    /// Sets the handle receiving the records that cannot be loaded, e.g. to write them to a
+    /// dead-letter file or to share one report between several loaders.
+    ///
+    /// # Arguments
+    ///
+    /// * `dead_letters` - The handle to use.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use raphtory::core::utils::dead_letter::{DeadLetters, ErrorPolicy};
+    /// use raphtory::graph_loader::source::csv_loader::CsvLoader;
+    /// let dead_letters = DeadLetters::with_file(ErrorPolicy::Skip, "/path/to/rejected.jsonl").unwrap();
+    /// let loader = CsvLoader::new("/path/to/csv_file.csv").set_dead_letters(dead_letters);
+    /// ```
+    pub fn set_dead_letters(mut self, dead_letters: DeadLetters) -> Self {
+        self.dead_letters = dead_letters;
+        self
+    }
+
+    /// Report of the records loaded and rejected so far
+    pub fn report(&self) -> LoadReport {
+        self.dead_letters.report()
+    }
+
     /// Check if the provided path is a directory or not.
     ///
     /// # Arguments
@@ -316,6 +377,9 @@ impl CsvLoader {
 
     /// Load data from all CSV files in the directory into a graph.
     ///
+    /// Records that fail to deserialize are handled according to the error policy of the loader,
+    /// see [`CsvLoader::set_error_policy`].
+    ///
     /// # Arguments
     ///
     /// * `g` - A reference to the graph object where the data should be loaded.
@@ -335,7 +399,37 @@ impl CsvLoader {
         F: Fn(REC, &G) + Send + Sync,
         G: Sync,
     {
-        //FIXME: loader function should return a result for reporting parsing errors
+        self.try_load_into_graph(g, |rec, g| {
+            loader(rec, g);
+            Ok(())
+        })
+    }
+
+    /// Load data from all CSV files in the directory into a graph with a fallible loader.
+    ///
+    /// Records that fail to deserialize or for which `loader` returns an error are handled
+    /// according to the error policy of the loader, see [`CsvLoader::set_error_policy`].
+    ///
+    /// # Arguments
+    ///
+    /// * `g` - A reference to the graph object where the data should be loaded.
+    /// * `loader` - A closure that takes a deserialized record and the graph object as arguments and adds the record to the graph.
+    ///
+    /// Returns:
+    ///
+    /// A Result containing an empty Ok value if the data is loaded successfully.
+    ///
+    /// # Errors
+    ///
+    /// An error of type CsvErr is returned if an I/O error occurs while reading the files, or
+    /// if a record is rejected with the fail-fast policy.
+    ///
+    pub fn try_load_into_graph<F, REC, G>(&self, g: &G, loader: F) -> Result<(), CsvErr>
+    where
+        REC: DeserializeOwned + Debug,
+        F: Fn(REC, &G) -> Result<(), GraphError> + Send + Sync,
+        G: Sync,
+    {
         let paths = self.files_vec()?;
         paths
             .par_iter()
@@ -363,11 +457,13 @@ impl CsvLoader {
         F: Fn(StringRecord, &G) + Send + Sync,
         G: Sync,
     {
-        //FIXME: loader function should return a result for reporting parsing errors
         let paths = self.files_vec()?;
-        paths
-            .par_iter()
-            .try_for_each(move |path| self.load_file_into_graph_record(path, g, &loader))?;
+        paths.par_iter().try_for_each(move |path| {
+            self.load_file_into_graph_record(path, g, &|rec, g| {
+                loader(rec, g);
+                Ok(())
+            })
+        })?;
         Ok(())
     }
 
@@ -391,22 +487,24 @@ impl CsvLoader {
     ) -> Result<(), CsvErr>
     where
         REC: DeserializeOwned + Debug,
-        F: Fn(REC, &G),
+        F: Fn(REC, &G) -> Result<(), GraphError>,
     {
         let file_path: PathBuf = path.into();
         if self.print_file_name {
             println!("Loading file: {:?}", file_path);
         }
-        let mut csv_reader = self.csv_reader(file_path)?;
-        let records_iter = csv_reader.deserialize::<REC>();
-
-        //TODO this needs better error handling for files without perfect data
-        for rec in records_iter {
-            let record = rec.expect("Failed to deserialize");
-            loader(record, g)
-        }
-
-        Ok(())
+        let mut csv_reader = self.csv_reader(file_path.clone())?;
+        let headers = if self.header {
+            Some(csv_reader.headers()?.clone())
+        } else {
+            None
+        };
+        self.for_each_record(&mut csv_reader, &file_path, |record| {
+            let rec = record
+                .deserialize::<REC>(headers.as_ref())
+                .map_err(|err| err.to_string())?;
+            loader(rec, g).map_err(|err| err.to_string())
+        })
     }
 
     fn load_file_into_graph_record<F, P: Into<PathBuf> + Debug, G>(
@@ -416,19 +514,62 @@ impl CsvLoader {
         loader: &F,
     ) -> Result<(), CsvErr>
     where
-        F: Fn(StringRecord, &G),
+        F: Fn(StringRecord, &G) -> Result<(), GraphError>,
     {
         let file_path: PathBuf = path.into();
 
-        let mut csv_reader = self.csv_reader(file_path)?;
-        for rec in csv_reader.records() {
-            let record = rec?;
-            loader(record, g)
-        }
+        let mut csv_reader = self.csv_reader(file_path.clone())?;
+        self.for_each_record(&mut csv_reader, &file_path, |record| {
+            loader(record.clone(), g).map_err(|err| err.to_string())
+        })
+    }
 
+    /// Read every record of a file and pass it to `load`, handing records that cannot be read
+    /// or loaded to the dead letters of the loader
+    fn for_each_record<F>(
+        &self,
+        csv_reader: &mut csv::Reader<Box<dyn io::Read>>,
+        file_path: &Path,
+        load: F,
+    ) -> Result<(), CsvErr>
+    where
+        F: Fn(&StringRecord) -> Result<(), String>,
+    {
+        let mut record = StringRecord::new();
+        loop {
+            match csv_reader.read_record(&mut record) {
+                Ok(false) => break,
+                Ok(true) => match load(&record) {
+                    Ok(()) => self.dead_letters.loaded(1),
+                    Err(error) => {
+                        let line = record.position().map(|pos| pos.line());
+                        self.dead_letters.reject(
+                            RejectedRecord::new(line, self.raw_record(&record), error)
+                                .in_file(file_path),
+                        )?
+                    }
+                },
+                Err(err) if err.is_io_error() => return Err(err.into()),
+                Err(err) => {
+                    let line = err.position().map(|pos| pos.line());
+                    self.dead_letters
+                        .reject(RejectedRecord::new(line, "", &err).in_file(file_path))?
+                }
+            }
+        }
         Ok(())
     }
 
+    /// The text of a record as it would appear in the file
+    fn raw_record(&self, record: &StringRecord) -> String {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(vec![]);
+        let _ = writer.write_record(record);
+        let raw = writer.into_inner().unwrap_or_default();
+        String::from_utf8_lossy(&raw).trim_end().to_owned()
+    }
+
     /// Returns a `csv::Reader` for the specified file path, automatically detecting and handling gzip and bzip compression.
     ///
     /// # Arguments
@@ -653,4 +794,43 @@ mod csv_loader_test {
         let delimiter = ".";
         lotr_test(g, csv_loader, has_header, delimiter, r);
     }
+
+    #[test]
+    fn test_error_policies() {
+        use crate::core::utils::dead_letter::{DeadLetters, ErrorPolicy};
+        use std::fs;
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lotr.csv");
+        fs::write(
+            &path,
+            "src_id,dst_id,time\nGandalf,Frodo,1\nGandalf,Sam,soon\nSam,Frodo,3\n",
+        )
+        .unwrap();
+        let load = |loader: CsvLoader, g: &Graph| {
+            loader
+                .set_header(true)
+                .try_load_into_graph(g, |lotr: Lotr, g: &Graph| {
+                    g.add_edge(lotr.time, lotr.src_id, lotr.dst_id, NO_PROPS, None)?;
+                    Ok(())
+                })
+        };
+
+        let err = load(CsvLoader::new(&path), &Graph::new()).unwrap_err();
+        assert!(err.to_string().contains("lotr.csv:3:"));
+
+        let rejected = dir.path().join("rejected.jsonl");
+        let dead_letters = DeadLetters::with_file(ErrorPolicy::Collect, &rejected).unwrap();
+        let g = Graph::new();
+        let loader = CsvLoader::new(&path).set_dead_letters(dead_letters.clone());
+        load(loader, &g).unwrap();
+        assert_eq!(g.count_edges(), 2);
+        let report = dead_letters.report();
+        assert_eq!(report.loaded, 2);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.rejected[0].line, Some(3));
+        assert_eq!(report.rejected[0].record, "Gandalf,Sam,soon");
+        assert_eq!(fs::read_to_string(&rejected).unwrap().lines().count(), 1);
+    }
 }
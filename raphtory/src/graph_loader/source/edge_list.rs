@@ -0,0 +1,621 @@
+//! Load temporal edge lists such as the SNAP `sx-*` and `CollegeMsg` datasets
+//!
+//! Temporal network datasets are mostly published as plain text files with one edge per line,
+//! `src dst time`, sometimes with a header, comment lines, extra columns or a different
+//! delimiter. [`EdgeListLoader`] detects these from the start of every file:
+//!
+//! - the delimiter, one of tab, `,`, `;`, `|` or runs of whitespace,
+//! - a header, when the first line names its columns,
+//! - the time column, named `time`, `timestamp`, `t`, `ts`, `date` or `datetime` in the header,
+//!   or otherwise the last of at least three columns if all its values are times,
+//! - the unit of the times, see [`TimeUnit`].
+//!
+//! Lines starting with `#`, `%` or `//` are comments. Files in the Trivial Graph Format (TGF),
+//! with node declarations before a line containing only `#`, are detected too. Files ending in
+//! `.gz` or `.bz2` are decompressed and directories are loaded file by file.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use raphtory::graph_loader::source::edge_list::load_edge_list;
+//! use raphtory::prelude::*;
+//!
+//! let g = load_edge_list("/tmp/sx-superuser.txt.gz").unwrap();
+//! println!("{} edges", g.count_edges());
+//! ```
+
+use crate::{
+    core::utils::{
+        dead_letter::{DeadLetters, ErrorPolicy, LoadReport, RejectedRecord},
+        errors::GraphError,
+        time::TryIntoTime,
+    },
+    db::api::{
+        mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+        view::StaticGraphViewOps,
+    },
+    graph_loader::{
+        formats::invalid,
+        source::mapping::{data_files, open_data_file},
+    },
+    prelude::*,
+};
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+const FORMAT: &str = "edge list";
+/// Number of data lines used to detect the format of a file
+const SAMPLE_SIZE: usize = 1000;
+const COMMENT_PREFIXES: [&str; 3] = ["#", "%", "//"];
+const TIME_NAMES: [&str; 6] = ["time", "timestamp", "t", "ts", "date", "datetime"];
+const HEADER_NAMES: [&str; 8] = [
+    "src", "source", "from", "dst", "target", "to", "weight", "label",
+];
+
+/// How the fields of a line are separated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Any run of spaces and tabs
+    Whitespace,
+    Char(char),
+}
+
+impl Delimiter {
+    fn split<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        match self {
+            Delimiter::Whitespace => line.split_whitespace().collect(),
+            Delimiter::Char(c) => line.split(*c).map(|field| field.trim()).collect(),
+        }
+    }
+}
+
+impl From<char> for Delimiter {
+    fn from(c: char) -> Self {
+        if c == ' ' {
+            Delimiter::Whitespace
+        } else {
+            Delimiter::Char(c)
+        }
+    }
+}
+
+/// Unit of the times in an edge list
+///
+/// Integer times are taken as milliseconds from 1e11 (March 1973 in milliseconds) and as
+/// seconds from 1e8, which covers Unix timestamps of the last decades, and otherwise as plain
+/// event counters. Raphtory times are milliseconds, so times in seconds are scaled by 1000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    /// Integers used as they are, e.g. steps of a simulation
+    Raw,
+    /// Unix timestamps in seconds, possibly fractional
+    Seconds,
+    /// Unix timestamps in milliseconds
+    Milliseconds,
+    /// RFC 3339, RFC 2822 or ISO 8601 date times
+    DateTime,
+}
+
+impl TimeUnit {
+    fn parse(self, value: &str) -> Result<i64, GraphError> {
+        let invalid_time = || GraphError::InvalidRecord(format!("invalid time '{value}'"));
+        match self {
+            TimeUnit::Raw | TimeUnit::Milliseconds => value
+                .parse::<i64>()
+                .or_else(|_| value.parse::<f64>().map(|t| t as i64))
+                .map_err(|_| invalid_time()),
+            TimeUnit::Seconds => match value.parse::<i64>() {
+                Ok(t) => Ok(t * 1000),
+                Err(_) => value
+                    .parse::<f64>()
+                    .map(|t| (t * 1000.0).round() as i64)
+                    .map_err(|_| invalid_time()),
+            },
+            TimeUnit::DateTime => Ok(value.try_into_time()?),
+        }
+    }
+
+    /// The unit of most `values`, if any of them are times
+    fn detect<'a>(values: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut numbers = vec![];
+        let mut date_times = 0;
+        for value in values {
+            if let Ok(number) = value.parse::<f64>() {
+                numbers.push(number);
+            } else if value.try_into_time().is_ok() {
+                date_times += 1;
+            }
+        }
+        if numbers.is_empty() && date_times == 0 {
+            None
+        } else if date_times > numbers.len() {
+            Some(TimeUnit::DateTime)
+        } else {
+            let max = numbers.iter().fold(0f64, |max, t| max.max(t.abs()));
+            if max >= 1e11 {
+                Some(TimeUnit::Milliseconds)
+            } else if max >= 1e8 {
+                Some(TimeUnit::Seconds)
+            } else {
+                Some(TimeUnit::Raw)
+            }
+        }
+    }
+}
+
+impl FromStr for TimeUnit {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(TimeUnit::Raw),
+            "s" | "seconds" => Ok(TimeUnit::Seconds),
+            "ms" | "milliseconds" => Ok(TimeUnit::Milliseconds),
+            "datetime" | "iso" => Ok(TimeUnit::DateTime),
+            other => Err(GraphError::InvalidTimeUnit(other.to_owned())),
+        }
+    }
+}
+
+/// The detected layout of an edge list file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeListFormat {
+    pub delimiter: Delimiter,
+    /// Column names read from the header, if the file has one
+    pub columns: Option<Vec<String>>,
+    /// Index of the time column. Without one, all edges are added at time 0.
+    pub time_column: Option<usize>,
+    pub time_unit: TimeUnit,
+    /// Whether the file is in the Trivial Graph Format
+    pub tgf: bool,
+}
+
+impl EdgeListFormat {
+    fn column_name(&self, index: usize) -> String {
+        self.columns
+            .as_ref()
+            .and_then(|columns| columns.get(index).cloned())
+            .unwrap_or_else(|| format!("column_{index}"))
+    }
+}
+
+/// A loader for temporal edge lists, detecting the format of each file unless it is set
+/// explicitly
+///
+/// The first two columns are the source and destination of edges. Columns other than these and
+/// the time column are added as edge properties, named after the header or `column_<index>`.
+#[derive(Debug)]
+pub struct EdgeListLoader {
+    path: PathBuf,
+    delimiter: Option<Delimiter>,
+    header: Option<bool>,
+    comment_prefixes: Vec<String>,
+    time_column: Option<Option<usize>>,
+    time_unit: Option<TimeUnit>,
+    layer: Option<String>,
+    dead_letters: DeadLetters,
+}
+
+impl EdgeListLoader {
+    /// Creates a loader for the file at `path` or all files in the directory at `path`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use raphtory::graph_loader::source::edge_list::{EdgeListLoader, TimeUnit};
+    /// use raphtory::prelude::*;
+    ///
+    /// let g = EdgeListLoader::new("/tmp/CollegeMsg.txt")
+    ///     .set_time_unit(TimeUnit::Seconds)
+    ///     .graph()
+    ///     .unwrap();
+    /// ```
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            delimiter: None,
+            header: None,
+            comment_prefixes: COMMENT_PREFIXES.iter().map(|p| p.to_string()).collect(),
+            time_column: None,
+            time_unit: None,
+            layer: None,
+            dead_letters: DeadLetters::default(),
+        }
+    }
+
+    /// Sets the delimiter instead of detecting it.
+    pub fn set_delimiter(mut self, delimiter: impl Into<Delimiter>) -> Self {
+        self.delimiter = Some(delimiter.into());
+        self
+    }
+
+    /// Sets whether the files have a header instead of detecting it.
+    pub fn set_header(mut self, header: bool) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// Sets the prefix of comment lines, replacing the default `#`, `%` and `//`.
+    pub fn set_comment_prefix(mut self, prefix: &str) -> Self {
+        self.comment_prefixes = vec![prefix.to_owned()];
+        self
+    }
+
+    /// Sets the index of the time column instead of detecting it, `None` adding all edges at
+    /// time 0.
+    pub fn set_time_column(mut self, column: Option<usize>) -> Self {
+        self.time_column = Some(column);
+        self
+    }
+
+    /// Sets the unit of the times instead of detecting it.
+    pub fn set_time_unit(mut self, unit: TimeUnit) -> Self {
+        self.time_unit = Some(unit);
+        self
+    }
+
+    /// Sets the layer of the loaded edges.
+    pub fn set_layer(mut self, layer: &str) -> Self {
+        self.layer = Some(layer.to_owned());
+        self
+    }
+
+    /// Sets what happens to lines that cannot be loaded, failing on the first one by default.
+    pub fn set_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.dead_letters = DeadLetters::new(policy);
+        self
+    }
+
+    /// Sets the handle receiving the lines that cannot be loaded.
+    pub fn set_dead_letters(mut self, dead_letters: DeadLetters) -> Self {
+        self.dead_letters = dead_letters;
+        self
+    }
+
+    /// Report of the lines loaded and rejected so far
+    pub fn report(&self) -> LoadReport {
+        self.dead_letters.report()
+    }
+
+    /// The format of the first file, as it would be loaded
+    pub fn detect(&self) -> Result<EdgeListFormat, GraphError> {
+        let file = data_files(&self.path)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| invalid(FORMAT, format!("no files in {}", self.path.display())))?;
+        self.detect_file(&file)
+    }
+
+    /// Load all edges into a new graph
+    pub fn graph(&self) -> Result<Graph, GraphError> {
+        let graph = Graph::new();
+        self.load_into_graph(&graph)?;
+        Ok(graph)
+    }
+
+    /// Load all edges into `graph`
+    pub fn load_into_graph<G>(&self, graph: &G) -> Result<LoadReport, GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        for file in data_files(&self.path)? {
+            let format = self.detect_file(&file)?;
+            let mut skip_header = format.columns.is_some();
+            let mut in_tgf_nodes = format.tgf;
+            for (index, line) in open_data_file(&file)?.lines().enumerate() {
+                let line = line?;
+                let trimmed = line.trim();
+                if format.tgf && trimmed == "#" {
+                    in_tgf_nodes = false;
+                    continue;
+                }
+                if trimmed.is_empty() || self.is_comment(trimmed) {
+                    continue;
+                }
+                if skip_header {
+                    skip_header = false;
+                    continue;
+                }
+                let result = if in_tgf_nodes {
+                    self.load_tgf_node(graph, trimmed)
+                } else if format.tgf {
+                    self.load_tgf_edge(graph, trimmed)
+                } else {
+                    self.load_edge(graph, &format, &format.delimiter.split(&line))
+                };
+                match result {
+                    Ok(()) => self.dead_letters.loaded(1),
+                    Err(error) => self.dead_letters.reject(
+                        RejectedRecord::new(Some(index as u64 + 1), line, error).in_file(&file),
+                    )?,
+                }
+            }
+        }
+        Ok(self.report())
+    }
+
+    fn is_comment(&self, line: &str) -> bool {
+        self.comment_prefixes
+            .iter()
+            .any(|prefix| line.starts_with(prefix.as_str()))
+    }
+
+    fn detect_file(&self, file: &Path) -> Result<EdgeListFormat, GraphError> {
+        let mut sample = vec![];
+        let mut tgf = false;
+        for line in open_data_file(file)?.lines() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed == "#" && !sample.is_empty() {
+                tgf = true;
+                break;
+            }
+            if trimmed.is_empty() || self.is_comment(trimmed) {
+                continue;
+            }
+            sample.push(line);
+            if sample.len() >= SAMPLE_SIZE {
+                break;
+            }
+        }
+        if tgf {
+            return Ok(EdgeListFormat {
+                delimiter: Delimiter::Whitespace,
+                columns: None,
+                time_column: None,
+                time_unit: TimeUnit::Raw,
+                tgf,
+            });
+        }
+
+        let delimiter = match self.delimiter {
+            Some(delimiter) => delimiter,
+            None => detect_delimiter(&sample).ok_or_else(|| {
+                invalid(
+                    FORMAT,
+                    format!("could not detect the delimiter of {}", file.display()),
+                )
+            })?,
+        };
+        let rows: Vec<_> = sample.iter().map(|line| delimiter.split(line)).collect();
+        let header = self.header.unwrap_or_else(|| match rows.split_first() {
+            Some((first, rest)) => looks_like_header(first, rest),
+            None => false,
+        });
+        let columns: Option<Vec<String>> =
+            header.then(|| rows.first().map_or(vec![], |first| to_strings(first)));
+        let data = if header && !rows.is_empty() {
+            &rows[1..]
+        } else {
+            &rows[..]
+        };
+        let time_column = match self.time_column {
+            Some(column) => column,
+            None => detect_time_column(columns.as_deref(), data),
+        };
+        let time_unit = match (self.time_unit, time_column) {
+            (Some(unit), _) => unit,
+            (None, Some(column)) => {
+                TimeUnit::detect(data.iter().filter_map(|row| row.get(column).copied()))
+                    .unwrap_or(TimeUnit::Raw)
+            }
+            (None, None) => TimeUnit::Raw,
+        };
+        Ok(EdgeListFormat {
+            delimiter,
+            columns,
+            time_column,
+            time_unit,
+            tgf,
+        })
+    }
+
+    fn load_edge<G>(
+        &self,
+        graph: &G,
+        format: &EdgeListFormat,
+        fields: &[&str],
+    ) -> Result<(), GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        if fields.len() < 2 {
+            return Err(GraphError::InvalidRecord(
+                "expected at least a source and a destination".to_owned(),
+            ));
+        }
+        let time = match format.time_column {
+            Some(column) => {
+                let value = fields.get(column).ok_or_else(|| {
+                    GraphError::InvalidRecord(format!("missing time column {column}"))
+                })?;
+                format.time_unit.parse(value)?
+            }
+            None => 0,
+        };
+        let props: Vec<_> = fields
+            .iter()
+            .enumerate()
+            .filter(|(index, value)| {
+                *index > 1 && Some(*index) != format.time_column && !value.is_empty()
+            })
+            .map(|(index, value)| (format.column_name(index), infer_prop(value)))
+            .collect();
+        graph.add_edge(time, fields[0], fields[1], props, self.layer.as_deref())?;
+        Ok(())
+    }
+
+    fn load_tgf_node<G>(&self, graph: &G, line: &str) -> Result<(), GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        let (id, label) = split_label(line);
+        let node = graph.add_node(0, id, NO_PROPS, None)?;
+        if let Some(label) = label {
+            node.update_constant_properties([("label", label)])?;
+        }
+        Ok(())
+    }
+
+    fn load_tgf_edge<G>(&self, graph: &G, line: &str) -> Result<(), GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        let (src, rest) = split_label(line);
+        let (dst, label) = rest.map(split_label).ok_or_else(|| {
+            GraphError::InvalidRecord("expected a source and a destination".to_owned())
+        })?;
+        let props: Vec<_> = label.map(|label| ("label", label)).into_iter().collect();
+        graph.add_edge(0, src, dst, props, self.layer.as_deref())?;
+        Ok(())
+    }
+}
+
+/// Load the edge list at `path` into a new graph, detecting its format
+pub fn load_edge_list<P: Into<PathBuf>>(path: P) -> Result<Graph, GraphError> {
+    EdgeListLoader::new(path).graph()
+}
+
+/// The first delimiter splitting every line into the same number of at least two fields,
+/// falling back to whitespace
+fn detect_delimiter(lines: &[String]) -> Option<Delimiter> {
+    for c in ['\t', ',', ';', '|'] {
+        let mut counts = lines.iter().map(|line| line.split(c).count());
+        if let Some(first) = counts.next() {
+            if first >= 2 && counts.all(|count| count == first) {
+                return Some(Delimiter::Char(c));
+            }
+        }
+    }
+    lines
+        .iter()
+        .all(|line| line.split_whitespace().count() >= 2)
+        .then_some(Delimiter::Whitespace)
+}
+
+/// Whether the first row names the columns, because it contains a well-known column name or
+/// because it has no numbers while the other rows do
+fn looks_like_header(first: &[&str], rest: &[Vec<&str>]) -> bool {
+    let known = |field: &&str| {
+        let field = field.to_lowercase();
+        TIME_NAMES.contains(&field.as_str()) || HEADER_NAMES.contains(&field.as_str())
+    };
+    let numeric = |field: &&str| field.parse::<f64>().is_ok();
+    first.iter().any(known)
+        || (!first.iter().any(numeric) && rest.iter().any(|row| row.iter().any(numeric)))
+}
+
+fn detect_time_column(columns: Option<&[String]>, rows: &[Vec<&str>]) -> Option<usize> {
+    if let Some(columns) = columns {
+        let named = columns
+            .iter()
+            .position(|column| TIME_NAMES.contains(&column.to_lowercase().as_str()));
+        if named.is_some() {
+            return named;
+        }
+    }
+    let width = rows.first()?.len();
+    if width < 3 {
+        return None;
+    }
+    let last = width - 1;
+    rows.iter()
+        .all(|row| row.get(last).is_some_and(|value| is_time(value)))
+        .then_some(last)
+}
+
+fn is_time(value: &str) -> bool {
+    value.parse::<f64>().is_ok() || value.try_into_time().is_ok()
+}
+
+/// Split off the first whitespace-separated field, returning the rest of the line if not empty
+fn split_label(line: &str) -> (&str, Option<&str>) {
+    match line.split_once(char::is_whitespace) {
+        Some((first, rest)) if !rest.trim().is_empty() => (first, Some(rest.trim())),
+        Some((first, _)) => (first, None),
+        None => (line, None),
+    }
+}
+
+fn infer_prop(value: &str) -> Prop {
+    if let Ok(v) = value.parse::<i64>() {
+        Prop::I64(v)
+    } else if let Ok(v) = value.parse::<f64>() {
+        Prop::F64(v)
+    } else if let Ok(v) = value.parse::<bool>() {
+        Prop::Bool(v)
+    } else {
+        Prop::str(value)
+    }
+}
+
+fn to_strings(fields: &[&str]) -> Vec<String> {
+    fields.iter().map(|field| field.to_string()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::{fs, io::Write};
+    use tempfile::tempdir;
+
+    #[test]
+    fn detect_snap_and_csv_edge_lists() {
+        let dir = tempdir().unwrap();
+        let snap = dir.path().join("sx-test.txt.gz");
+        let mut gz = GzEncoder::new(fs::File::create(&snap).unwrap(), Compression::default());
+        gz.write_all(b"# Directed graph\n# FromNodeId ToNodeId Time\n1 2 1217567877\n1 3  1217573801\n2 3 1217606247\n")
+            .unwrap();
+        gz.finish().unwrap();
+
+        let loader = EdgeListLoader::new(&snap);
+        let format = loader.detect().unwrap();
+        assert_eq!(format.delimiter, Delimiter::Whitespace);
+        assert_eq!(format.columns, None);
+        assert_eq!(format.time_column, Some(2));
+        assert_eq!(format.time_unit, TimeUnit::Seconds);
+        let g = loader.graph().unwrap();
+        assert_eq!(g.count_edges(), 3);
+        assert_eq!(g.edge(1, 3).unwrap().earliest_time(), Some(1217573801000));
+
+        let csv = dir.path().join("messages.csv");
+        fs::write(
+            &csv,
+            "from,to,weight,timestamp\nalice,bob,2.5,2024-01-01T10:00:00Z\nbob,alice,1,oops\n",
+        )
+        .unwrap();
+        let loader = EdgeListLoader::new(&csv).set_error_policy(ErrorPolicy::Collect);
+        let format = loader.detect().unwrap();
+        assert_eq!(format.delimiter, Delimiter::Char(','));
+        assert_eq!(format.time_column, Some(3));
+        assert_eq!(format.time_unit, TimeUnit::DateTime);
+        let g = Graph::new();
+        let report = loader.load_into_graph(&g).unwrap();
+        assert_eq!(report.loaded, 1);
+        assert_eq!(report.rejected[0].line, Some(3));
+        let edge = g.edge("alice", "bob").unwrap();
+        assert_eq!(edge.properties().get("weight"), Some(Prop::F64(2.5)));
+        assert_eq!(edge.earliest_time(), Some(1704103200000));
+    }
+
+    #[test]
+    fn load_tgf() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("graph.tgf");
+        fs::write(&path, "1 First node\n2\n#\n1 2 an edge\n2 1\n").unwrap();
+        let g = load_edge_list(&path).unwrap();
+        assert_eq!(
+            g.node(1).unwrap().properties().get("label"),
+            Some(Prop::str("First node"))
+        );
+        assert_eq!(
+            g.edge(1, 2).unwrap().properties().get("label"),
+            Some(Prop::str("an edge"))
+        );
+        assert!(g.has_edge(2, 1));
+    }
+}
@@ -1,11 +1,13 @@
-use crate::core::utils::errors::GraphError;
+use crate::core::utils::{
+    dead_letter::{DeadLetters, ErrorPolicy, LoadReport, RejectedRecord},
+    errors::GraphError,
+};
 use bzip2::read::BzDecoder;
 use flate2; // 1.0
 use flate2::read::GzDecoder;
 use rayon::prelude::*;
 use regex::Regex;
 use serde::de::DeserializeOwned;
-use serde_json::{de::IoRead, Deserializer};
 use std::{
     collections::VecDeque,
     error::Error,
@@ -13,7 +15,7 @@ use std::{
     fs,
     fs::File,
     io,
-    io::BufReader,
+    io::{BufRead, BufReader},
     path::{Path, PathBuf},
 };
 
@@ -74,6 +76,8 @@ pub struct JsonLinesLoader<REC: DeserializeOwned> {
     _a: std::marker::PhantomData<REC>,
     /// print the name of the file being loaded
     print_file_name: bool,
+    /// Handling of records that cannot be loaded
+    dead_letters: DeadLetters,
 }
 
 impl<REC: DeserializeOwned + std::fmt::Debug + Sync> JsonLinesLoader<REC> {
@@ -84,6 +88,7 @@ impl<REC: DeserializeOwned + std::fmt::Debug + Sync> JsonLinesLoader<REC> {
             regex_filter,
             _a: std::marker::PhantomData,
             print_file_name: false,
+            dead_letters: DeadLetters::default(),
         }
     }
 
@@ -98,6 +103,26 @@ impl<REC: DeserializeOwned + std::fmt::Debug + Sync> JsonLinesLoader<REC> {
         self
     }
 
+    /// Sets what happens to lines that cannot be deserialized or loaded.
+    ///
+    /// By default the load fails on the first bad line, reporting its file and line number.
+    pub fn set_error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.dead_letters = DeadLetters::new(policy);
+        self
+    }
+
+    /// Sets the handle receiving the lines that cannot be loaded, e.g. to write them to a
+    /// dead-letter file or to share one report between several loaders.
+    pub fn set_dead_letters(mut self, dead_letters: DeadLetters) -> Self {
+        self.dead_letters = dead_letters;
+        self
+    }
+
+    /// Report of the records loaded and rejected so far
+    pub fn report(&self) -> LoadReport {
+        self.dead_letters.report()
+    }
+
     /// Check if the provided path is a directory or not.
     ///
     /// # Arguments
@@ -207,7 +232,6 @@ impl<REC: DeserializeOwned + std::fmt::Debug + Sync> JsonLinesLoader<REC> {
         F: Fn(REC, &G) -> Result<(), GraphError> + Send + Sync,
         G: Sync,
     {
-        //FIXME: loader function should return a result for reporting parsing errors
         let paths = self.files_vec()?;
         paths
             .par_iter()
@@ -215,10 +239,8 @@ impl<REC: DeserializeOwned + std::fmt::Debug + Sync> JsonLinesLoader<REC> {
         Ok(())
     }
 
-    fn json_reader(
-        &self,
-        file_path: PathBuf,
-    ) -> Result<Deserializer<IoRead<BufReader<Box<dyn io::Read>>>>, JsonErr> {
+    /// Opens a JSON lines file, automatically detecting and handling gzip and bzip compression.
+    fn json_reader(&self, file_path: &Path) -> Result<BufReader<Box<dyn io::Read>>, JsonErr> {
         let is_gziped = file_path
             .file_name()
             .and_then(|name| name.to_str())
@@ -231,32 +253,32 @@ impl<REC: DeserializeOwned + std::fmt::Debug + Sync> JsonLinesLoader<REC> {
             .filter(|name| name.ends_with(".bz2"))
             .is_some();
 
-        let f = File::open(&file_path)?;
+        let f = File::open(file_path)?;
 
         if is_gziped {
-            Ok(Deserializer::from_reader(BufReader::new(Box::new(
-                GzDecoder::new(f),
-            ))))
+            Ok(BufReader::new(Box::new(GzDecoder::new(f))))
         } else if is_bziped {
-            Ok(Deserializer::from_reader(BufReader::new(Box::new(
-                BzDecoder::new(f),
-            ))))
+            Ok(BufReader::new(Box::new(BzDecoder::new(f))))
         } else {
-            Ok(Deserializer::from_reader(BufReader::new(Box::new(f))))
+            Ok(BufReader::new(Box::new(f)))
         }
     }
 
     /// Loads a JSON file into a graph using the specified loader function.
     ///
+    /// Every non-empty line of the file is deserialized as one record. Lines that fail to
+    /// deserialize or for which `loader` returns an error are handled according to the error
+    /// policy of the loader.
+    ///
     /// # Arguments
     ///
     /// * `path` - The path to the JSON file to load.
     /// * `g` - A reference to the graph to load the data into.
-    /// * `loader` - The function to use for loading the CSV records into the graph.
+    /// * `loader` - The function to use for loading the JSON records into the graph.
     ///
     /// Returns:
     ///
-    /// Returns `Ok(())` if the operation was successful, or a `CsvErr` if there was an error.
+    /// Returns `Ok(())` if the operation was successful, or a `JsonErr` if there was an error.
     ///
     fn load_file_into_graph<F, P: Into<PathBuf> + std::fmt::Debug, G>(
         &self,
@@ -272,16 +294,22 @@ impl<REC: DeserializeOwned + std::fmt::Debug + Sync> JsonLinesLoader<REC> {
             println!("Loading file: {:?}", file_path);
         }
 
-        let json_reader = self.json_reader(file_path)?;
+        let json_reader = self.json_reader(&file_path)?;
 
-        let records_iter = json_reader.into_iter::<REC>();
-
-        //TODO this needs better error handling for files without perfect data
-        for rec in records_iter {
-            if let Ok(record) = rec {
-                loader(record, g)?
-            } else {
-                println!("Error parsing record: {:?}", rec);
+        for (line_number, line) in json_reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let result = serde_json::from_str::<REC>(&line)
+                .map_err(|err| err.to_string())
+                .and_then(|record| loader(record, g).map_err(|err| err.to_string()));
+            match result {
+                Ok(()) => self.dead_letters.loaded(1),
+                Err(error) => self.dead_letters.reject(
+                    RejectedRecord::new(Some(line_number as u64 + 1), line, error)
+                        .in_file(&file_path),
+                )?,
             }
         }
 
@@ -350,4 +378,33 @@ mod tests {
         let loader = JsonLinesLoader::<TestRecord>::new(dir.path().to_path_buf(), None);
         test_json_rec(g, loader);
     }
+
+    #[test]
+    fn test_skip_bad_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.json");
+        File::create(&path)
+            .unwrap()
+            .write_all(b"{\"name\": \"a\", \"time\": 1}\n{\"name\": \"b\"}\n\n{\"name\": \"c\", \"time\": 3}\n")
+            .unwrap();
+        let load = |loader: &JsonLinesLoader<TestRecord>, g: &Graph| {
+            loader.load_into_graph(g, |rec: TestRecord, g: &Graph| {
+                g.add_node(rec.time, rec.name, NO_PROPS, None)?;
+                Ok(())
+            })
+        };
+
+        let loader = JsonLinesLoader::new(path.clone(), None);
+        let err = load(&loader, &Graph::new()).unwrap_err();
+        assert!(err.to_string().contains("test.json:2:"));
+
+        let g = Graph::new();
+        let loader = JsonLinesLoader::new(path, None).set_error_policy(ErrorPolicy::Skip);
+        load(&loader, &g).unwrap();
+        let report = loader.report();
+        assert_eq!(g.count_nodes(), 2);
+        assert_eq!(report.loaded, 2);
+        assert_eq!(report.skipped, 1);
+        assert!(report.rejected.is_empty());
+    }
 }
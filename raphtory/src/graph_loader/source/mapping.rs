@@ -0,0 +1,885 @@
+//! Declarative mapping of records to node and edge updates
+//!
+//! A [`RecordMapping`] names the fields of a record holding the time, node ids, layer, node type
+//! and properties, so that records can be added to a graph without writing a loader closure.
+//! Records are JSON values; rows of CSV files are converted to JSON objects keyed by the column
+//! names with [`csv_record`]. Nested fields of JSON objects are addressed with dotted paths such
+//! as `"payload.amount"`.
+//!
+//! A [`MappingSpec`] adds the format of the data files to a mapping and can be written as JSON,
+//! YAML or TOML, so new datasets can be loaded with [`load_with_mapping`] without any code:
+//!
+//! ```yaml
+//! format: csv
+//! delimiter: ";"
+//! time: timestamp
+//! time_format: "%Y-%m-%d %H:%M:%S"
+//! casts:
+//!   amount: f64
+//! edge:
+//!   src: sender
+//!   dst: receiver
+//!   layer_name: transfers
+//!   properties: [amount]
+//!   constant_properties: [currency]
+//! node:
+//!   id: sender
+//!   node_type_name: account
+//! ```
+//!
+//! # Example
+//! ```
+//! use raphtory::graph_loader::source::mapping::{EdgeMapping, RecordMapping};
+//! use raphtory::prelude::*;
+//!
+//! let mapping = RecordMapping {
+//!     time: "ts".to_owned(),
+//!     edge: Some(EdgeMapping {
+//!         src: "from".to_owned(),
+//!         dst: "to".to_owned(),
+//!         properties: vec!["amount".to_owned()],
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//! let g = Graph::new();
+//! let record = serde_json::json!({"ts": 1, "from": "alice", "to": "bob", "amount": 2.5});
+//! mapping.apply(&g, &record).unwrap();
+//! assert_eq!(g.edge("alice", "bob").unwrap().properties().get("amount"), Some(Prop::F64(2.5)));
+//! ```
+
+use crate::{
+    core::utils::{
+        dead_letter::{DeadLetters, ErrorPolicy, LoadReport, RejectedRecord},
+        dedup::{DedupKey, Deduplicator},
+        errors::GraphError,
+        time::{IntoTimeWithFormat, TryIntoTime},
+    },
+    db::api::{
+        mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+        view::StaticGraphViewOps,
+    },
+    prelude::*,
+};
+use bzip2::read::BzDecoder;
+use chrono::DateTime;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    fs,
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Describes how the fields of a record become updates of a graph
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordMapping {
+    /// Field holding the time of the updates, either a number or a datetime string
+    pub time: String,
+    /// `strftime`-style format of string times, by default RFC 3339 and common ISO 8601 formats
+    /// are accepted
+    #[serde(default)]
+    pub time_format: Option<String>,
+    /// Types that fields are converted to before they are added, by field name
+    #[serde(default)]
+    pub casts: HashMap<String, FieldType>,
+    /// Add an edge for every record
+    #[serde(default)]
+    pub edge: Option<EdgeMapping>,
+    /// Add a node for every record
+    #[serde(default)]
+    pub node: Option<NodeMapping>,
+}
+
+/// Fields of a record describing an edge update
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EdgeMapping {
+    /// Field holding the id of the source node
+    pub src: String,
+    /// Field holding the id of the destination node
+    pub dst: String,
+    /// Field holding the layer of the edge
+    #[serde(default)]
+    pub layer: Option<String>,
+    /// Layer of all edges, used if `layer` is not set
+    #[serde(default)]
+    pub layer_name: Option<String>,
+    /// Fields added as temporal properties
+    #[serde(default)]
+    pub properties: Vec<String>,
+    /// Fields added as constant properties
+    #[serde(default)]
+    pub constant_properties: Vec<String>,
+    /// Constant properties added to every edge
+    #[serde(default)]
+    pub shared_constant_properties: HashMap<String, Value>,
+}
+
+/// Fields of a record describing a node update
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodeMapping {
+    /// Field holding the id of the node
+    pub id: String,
+    /// Field holding the type of the node
+    #[serde(default)]
+    pub node_type: Option<String>,
+    /// Type of all nodes, used if `node_type` is not set
+    #[serde(default)]
+    pub node_type_name: Option<String>,
+    /// Fields added as temporal properties
+    #[serde(default)]
+    pub properties: Vec<String>,
+    /// Fields added as constant properties
+    #[serde(default)]
+    pub constant_properties: Vec<String>,
+    /// Constant properties added to every node
+    #[serde(default)]
+    pub shared_constant_properties: HashMap<String, Value>,
+}
+
+/// Type a field is converted to before it is added as a property
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    Str,
+    Bool,
+    I32,
+    I64,
+    U64,
+    F32,
+    F64,
+    /// A datetime, parsed like the time of the record
+    DateTime,
+}
+
+impl RecordMapping {
+    /// Add the updates described by a record to a graph
+    ///
+    /// Missing or `null` property fields are skipped, while missing time, node id, layer or node
+    /// type fields are an error. Constant properties are overwritten by later records.
+    pub fn apply<G>(&self, graph: &G, record: &Value) -> Result<(), GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        self.apply_with(graph, record, None).map(|_| ())
+    }
+
+    /// Add the updates described by a record to a graph, unless `dedup` has seen its edge update
+    /// before
+    ///
+    /// Returns whether the record was added. Records without an edge are always added.
+    pub fn apply_once<G>(
+        &self,
+        graph: &G,
+        record: &Value,
+        dedup: &Deduplicator,
+    ) -> Result<bool, GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        self.apply_with(graph, record, Some(dedup))
+    }
+
+    fn apply_with<G>(
+        &self,
+        graph: &G,
+        record: &Value,
+        dedup: Option<&Deduplicator>,
+    ) -> Result<bool, GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        let time = self.time_of(required(record, &self.time)?)?;
+        // read the edge first, so that duplicates are skipped before anything is added
+        let edge = match &self.edge {
+            Some(edge) => {
+                let src = id_value(required(record, &edge.src)?)?;
+                let dst = id_value(required(record, &edge.dst)?)?;
+                let layer = match &edge.layer {
+                    Some(field) => Some(str_value(required(record, field)?)?),
+                    None => edge.layer_name.clone(),
+                };
+                let props = self.properties(record, &edge.properties)?;
+                let mut constant = self.properties(record, &edge.constant_properties)?;
+                constant.extend(shared_properties(&edge.shared_constant_properties));
+                Some((src, dst, layer, props, constant))
+            }
+            None => None,
+        };
+        let key = match (dedup, &edge) {
+            (Some(dedup), Some((src, dst, layer, props, _))) => {
+                let key = dedup.edge_key(time, src, dst, layer.as_deref(), props)?;
+                if !dedup.insert(key) {
+                    return Ok(false);
+                }
+                Some((dedup, key))
+            }
+            _ => None,
+        };
+        // the edge update was not added after all, so it must not count as seen
+        let forget = || {
+            if let Some((dedup, key)) = key {
+                dedup.remove(key);
+            }
+        };
+
+        if let Some(node) = &self.node {
+            if let Err(error) = self.add_node(graph, record, time, node) {
+                forget();
+                return Err(error);
+            }
+        }
+        if let Some((src, dst, layer, props, constant)) = edge {
+            let view = graph
+                .add_edge(time, src, dst, props, layer.as_deref())
+                .inspect_err(|_| forget())?;
+            if !constant.is_empty() {
+                view.update_constant_properties(constant, layer.as_deref())?;
+            }
+        }
+        Ok(true)
+    }
+
+    fn add_node<G>(
+        &self,
+        graph: &G,
+        record: &Value,
+        time: i64,
+        node: &NodeMapping,
+    ) -> Result<(), GraphError>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        let id = id_value(required(record, &node.id)?)?;
+        let node_type = match &node.node_type {
+            Some(field) => Some(str_value(required(record, field)?)?),
+            None => node.node_type_name.clone(),
+        };
+        let view = graph.add_node(
+            time,
+            id,
+            self.properties(record, &node.properties)?,
+            node_type.as_deref(),
+        )?;
+        let mut constant = self.properties(record, &node.constant_properties)?;
+        constant.extend(shared_properties(&node.shared_constant_properties));
+        if !constant.is_empty() {
+            view.update_constant_properties(constant)?;
+        }
+        Ok(())
+    }
+
+    fn properties(
+        &self,
+        record: &Value,
+        fields: &[String],
+    ) -> Result<Vec<(String, Prop)>, GraphError> {
+        let mut props = Vec::with_capacity(fields.len());
+        for name in fields {
+            let Some(value) = field(record, name).filter(|value| !value.is_null()) else {
+                continue;
+            };
+            let prop = match self.casts.get(name) {
+                Some(dtype) => Some(self.cast(name, value, *dtype)?),
+                None => Prop::from_json(value),
+            };
+            if let Some(prop) = prop {
+                props.push((name.clone(), prop));
+            }
+        }
+        Ok(props)
+    }
+
+    fn cast(&self, name: &str, value: &Value, dtype: FieldType) -> Result<Prop, GraphError> {
+        let text = match value {
+            Value::String(text) => text.trim().to_owned(),
+            other => other.to_string(),
+        };
+        let prop = match dtype {
+            FieldType::Str => Some(Prop::str(text.as_str())),
+            FieldType::Bool => match text.to_lowercase().as_str() {
+                "true" | "1" => Some(Prop::Bool(true)),
+                "false" | "0" => Some(Prop::Bool(false)),
+                _ => None,
+            },
+            FieldType::I32 => text.parse().ok().map(Prop::I32),
+            FieldType::I64 => text.parse().ok().map(Prop::I64),
+            FieldType::U64 => text.parse().ok().map(Prop::U64),
+            FieldType::F32 => text.parse().ok().map(Prop::F32),
+            FieldType::F64 => text.parse().ok().map(Prop::F64),
+            FieldType::DateTime => self
+                .time_of(value)
+                .ok()
+                .and_then(DateTime::from_timestamp_millis)
+                .map(Prop::DTime),
+        };
+        prop.ok_or_else(|| {
+            GraphError::InvalidRecord(format!("cannot convert {value} in '{name}' to {dtype:?}"))
+        })
+    }
+
+    fn time_of(&self, value: &Value) -> Result<i64, GraphError> {
+        match (value, &self.time_format) {
+            (Value::String(time), Some(format)) => Ok(time.trim().parse_time(format)?),
+            _ => parse_time(value),
+        }
+    }
+}
+
+/// Format of data files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFormat {
+    /// Delimited text with one record per row
+    Csv,
+    /// One JSON object per line
+    NdJson,
+}
+
+impl DataFormat {
+    /// Guess the format from a file name, ignoring `.gz` and `.bz2` extensions
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        let name = name
+            .strip_suffix(".gz")
+            .or_else(|| name.strip_suffix(".bz2"))
+            .unwrap_or(name);
+        match Path::new(name).extension()?.to_str()? {
+            "csv" | "tsv" | "txt" => Some(DataFormat::Csv),
+            "json" | "jsonl" | "ndjson" => Some(DataFormat::NdJson),
+            _ => None,
+        }
+    }
+}
+
+/// A [`RecordMapping`] together with the format of the files it applies to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappingSpec {
+    /// Format of the data files, guessed from their extension if not set
+    #[serde(default)]
+    pub format: Option<DataFormat>,
+    /// Delimiter of CSV files
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    /// Column names of CSV files without a header row
+    #[serde(default)]
+    pub columns: Option<Vec<String>>,
+    /// What to do with records that cannot be loaded
+    #[serde(default)]
+    pub error_policy: ErrorPolicy,
+    /// File the rejected records are written to as JSON lines
+    #[serde(default)]
+    pub dead_letter_file: Option<PathBuf>,
+    /// Skip edge updates that are already in the graph, comparing their time, nodes, layer and
+    /// properties
+    #[serde(default)]
+    pub deduplicate: bool,
+    /// Edge property holding a unique id of every update, skipping updates whose id is already
+    /// in the graph
+    #[serde(default)]
+    pub event_id: Option<String>,
+    #[serde(flatten)]
+    pub mapping: RecordMapping,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+impl MappingSpec {
+    /// The deduplicator requested by the spec, seeded with the edge updates of `graph`
+    pub fn deduplicator<G: StaticGraphViewOps>(
+        &self,
+        graph: &G,
+    ) -> Result<Option<Deduplicator>, GraphError> {
+        let key = match &self.event_id {
+            Some(event_id) => {
+                let is_property = self
+                    .mapping
+                    .edge
+                    .as_ref()
+                    .is_some_and(|edge| edge.properties.contains(event_id));
+                if !is_property {
+                    return Err(GraphError::InvalidMapping(format!(
+                        "event id '{event_id}' is not an edge property"
+                    )));
+                }
+                DedupKey::EventId(event_id.clone())
+            }
+            None if self.deduplicate => DedupKey::Content,
+            None => return Ok(None),
+        };
+        Ok(Some(Deduplicator::for_graph(key, graph)))
+    }
+
+    /// Parse a spec written as JSON, YAML or TOML
+    pub fn parse(spec: &str) -> Result<Self, GraphError> {
+        serde_json::from_str(spec)
+            .or_else(|_| serde_yaml_ng::from_str(spec))
+            .or_else(|_| toml::from_str(spec))
+            .map_err(|err| GraphError::InvalidMapping(err.to_string()))
+    }
+
+    /// Read a spec from a `.json`, `.yaml`, `.yml` or `.toml` file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, GraphError> {
+        let path = path.as_ref();
+        let spec = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&spec).map_err(|err| err.to_string()),
+            Some("yaml" | "yml") => serde_yaml_ng::from_str(&spec).map_err(|err| err.to_string()),
+            Some("toml") => toml::from_str(&spec).map_err(|err| err.to_string()),
+            _ => return Self::parse(&spec),
+        }
+        .map_err(GraphError::InvalidMapping)
+    }
+
+    /// Read a spec from a file if `spec` is the path of an existing file, otherwise parse it
+    pub fn from_path_or_str(spec: &str) -> Result<Self, GraphError> {
+        if Path::new(spec).is_file() {
+            Self::from_file(spec)
+        } else {
+            Self::parse(spec)
+        }
+    }
+}
+
+/// Load a file, or all files in a directory, into a graph as described by a mapping spec
+///
+/// Files compressed with gzip or bzip2 are decompressed based on their `.gz` or `.bz2`
+/// extension. Files in a directory are loaded in the order of their paths. Records that cannot
+/// be parsed or loaded are handled according to the error policy of the spec.
+///
+/// Arguments:
+///     path: the data file or a directory of data files
+///     spec: the mapping of the records to the graph
+///     graph: the graph to load into
+///
+/// Returns:
+///     the numbers of loaded and rejected records
+pub fn load_with_mapping<G, P: AsRef<Path>>(
+    path: P,
+    spec: &MappingSpec,
+    graph: &G,
+) -> Result<LoadReport, GraphError>
+where
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+{
+    let dead_letters = match &spec.dead_letter_file {
+        Some(file) => DeadLetters::with_file(spec.error_policy, file)?,
+        None => DeadLetters::new(spec.error_policy),
+    };
+    let dedup = spec.deduplicator(graph)?;
+    let apply = |record: &Value| match &dedup {
+        Some(dedup) => spec.mapping.apply_once(graph, record, dedup),
+        None => spec.mapping.apply(graph, record).map(|()| true),
+    };
+    let handle = |file: &Path, line: Option<u64>, raw: String, result: Result<bool, GraphError>| {
+        match result {
+            Ok(true) => {
+                dead_letters.loaded(1);
+                Ok(())
+            }
+            Ok(false) => {
+                dead_letters.duplicate();
+                Ok(())
+            }
+            Err(error) => dead_letters.reject(RejectedRecord::new(line, raw, error).in_file(file)),
+        }
+    };
+    for file in data_files(path.as_ref())? {
+        let format = spec
+            .format
+            .or_else(|| DataFormat::from_path(&file))
+            .ok_or_else(|| {
+                GraphError::InvalidMapping(format!("unknown format of {}", file.display()))
+            })?;
+        let reader = open_data_file(&file)?;
+        match format {
+            DataFormat::Csv => {
+                let delimiter = u8::try_from(spec.delimiter).map_err(|_| {
+                    GraphError::InvalidMapping(format!("invalid delimiter {:?}", spec.delimiter))
+                })?;
+                let mut csv = csv::ReaderBuilder::new()
+                    .has_headers(spec.columns.is_none())
+                    .delimiter(delimiter)
+                    .flexible(true)
+                    .from_reader(reader);
+                let columns = match &spec.columns {
+                    Some(columns) => columns.clone(),
+                    None => csv_error(csv.headers())?
+                        .iter()
+                        .map(|column| column.trim().to_owned())
+                        .collect(),
+                };
+                for row in csv.records() {
+                    let row = match row {
+                        Ok(row) => row,
+                        Err(err) if err.is_io_error() => return csv_error(Err(err)),
+                        Err(err) => {
+                            let line = err.position().map(|pos| pos.line());
+                            handle(&file, line, String::new(), csv_error(Err(err)))?;
+                            continue;
+                        }
+                    };
+                    let mut record = csv_record(&columns, row.iter());
+                    // keep the raw text of cast columns so that e.g. "007" can become a string
+                    for (column, value) in columns.iter().zip(row.iter()) {
+                        if spec.mapping.casts.contains_key(column) && !value.trim().is_empty() {
+                            record[column] = Value::String(value.to_owned());
+                        }
+                    }
+                    let line = row.position().map(|pos| pos.line());
+                    let raw = row
+                        .iter()
+                        .collect::<Vec<_>>()
+                        .join(&spec.delimiter.to_string());
+                    handle(&file, line, raw, apply(&record))?;
+                }
+            }
+            DataFormat::NdJson => {
+                for (line_number, line) in reader.lines().enumerate() {
+                    let line = line?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let result = serde_json::from_str::<Value>(&line)
+                        .map_err(|err| GraphError::InvalidRecord(err.to_string()))
+                        .and_then(|record| apply(&record));
+                    handle(&file, Some(line_number as u64 + 1), line, result)?;
+                }
+            }
+        }
+    }
+    Ok(dead_letters.report())
+}
+
+fn csv_error<T>(result: Result<T, csv::Error>) -> Result<T, GraphError> {
+    result.map_err(|err| GraphError::InvalidRecord(err.to_string()))
+}
+
+/// The file at `path` or all files below it, sorted by path
+pub(crate) fn data_files(path: &Path) -> Result<Vec<PathBuf>, GraphError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = vec![];
+    for entry in fs::read_dir(path)? {
+        files.extend(data_files(&entry?.path())?);
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Open a file, decompressing it based on its `.gz` or `.bz2` extension
+pub(crate) fn open_data_file(path: &Path) -> Result<BufReader<Box<dyn Read>>, GraphError> {
+    let file = File::open(path)?;
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    let reader: Box<dyn Read> = if name.ends_with(".gz") {
+        Box::new(GzDecoder::new(file))
+    } else if name.ends_with(".bz2") {
+        Box::new(BzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    Ok(BufReader::new(reader))
+}
+
+/// Convert a CSV row to a JSON object keyed by the column names
+///
+/// Values that parse as integers, floats or booleans become JSON numbers and booleans, empty
+/// values become `null` and everything else is kept as a string.
+pub fn csv_record<'a>(columns: &[String], values: impl IntoIterator<Item = &'a str>) -> Value {
+    let mut record = Map::new();
+    for (column, value) in columns.iter().zip(values) {
+        record.insert(column.clone(), infer_value(value));
+    }
+    Value::Object(record)
+}
+
+fn infer_value(value: &str) -> Value {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        Value::Null
+    } else if let Ok(value) = trimmed.parse::<i64>() {
+        value.into()
+    } else if let Ok(value) = trimmed.parse::<f64>() {
+        serde_json::Number::from_f64(value).map_or_else(|| value.to_string().into(), Value::Number)
+    } else if let Ok(value) = trimmed.parse::<bool>() {
+        value.into()
+    } else {
+        value.into()
+    }
+}
+
+/// Look up a field, following dots into nested objects
+pub(crate) fn field<'a>(record: &'a Value, name: &str) -> Option<&'a Value> {
+    if let Some(value) = record.get(name) {
+        return Some(value);
+    }
+    name.split('.')
+        .try_fold(record, |value, part| value.get(part))
+        .filter(|value| !value.is_null())
+}
+
+fn required<'a>(record: &'a Value, name: &str) -> Result<&'a Value, GraphError> {
+    field(record, name)
+        .filter(|value| !value.is_null())
+        .ok_or_else(|| GraphError::InvalidRecord(format!("missing field '{name}'")))
+}
+
+fn shared_properties(props: &HashMap<String, Value>) -> impl Iterator<Item = (String, Prop)> + '_ {
+    props
+        .iter()
+        .filter_map(|(name, value)| Some((name.clone(), Prop::from_json(value)?)))
+}
+
+pub(crate) fn parse_time(value: &Value) -> Result<i64, GraphError> {
+    match value {
+        Value::Number(number) => number
+            .as_i64()
+            .or_else(|| number.as_f64().map(|t| t as i64))
+            .ok_or_else(|| GraphError::InvalidRecord(format!("invalid time {number}"))),
+        Value::String(time) => match time.trim().parse::<i64>() {
+            Ok(time) => Ok(time),
+            Err(_) => Ok(time.as_str().try_into_time()?),
+        },
+        other => Err(GraphError::InvalidRecord(format!("invalid time {other}"))),
+    }
+}
+
+fn id_value(value: &Value) -> Result<String, GraphError> {
+    match value {
+        Value::String(id) => Ok(id.clone()),
+        Value::Number(id) => Ok(id.to_string()),
+        other => Err(GraphError::InvalidRecord(format!(
+            "invalid node id {other}"
+        ))),
+    }
+}
+
+fn str_value(value: &Value) -> Result<String, GraphError> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Number(_) | Value::Bool(_) => Ok(value.to_string()),
+        other => Err(GraphError::InvalidRecord(format!(
+            "expected a string but got {other}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn apply_node_and_edge_mapping() {
+        let mapping = RecordMapping {
+            time: "time".to_owned(),
+            edge: Some(EdgeMapping {
+                src: "src".to_owned(),
+                dst: "dst".to_owned(),
+                layer: Some("kind".to_owned()),
+                properties: vec!["payload.amount".to_owned()],
+                constant_properties: vec!["currency".to_owned()],
+                ..Default::default()
+            }),
+            node: Some(NodeMapping {
+                id: "src".to_owned(),
+                node_type: Some("src_type".to_owned()),
+                properties: vec!["balance".to_owned()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let g = Graph::new();
+        let record = json!({
+            "time": "1970-01-01T00:00:01Z",
+            "src": 1,
+            "dst": 2,
+            "kind": "pays",
+            "src_type": "account",
+            "balance": null,
+            "currency": "EUR",
+            "payload": {"amount": 3}
+        });
+        mapping.apply(&g, &record).unwrap();
+
+        let node = g.node(1).unwrap();
+        assert_eq!(node.node_type().unwrap(), "account");
+        assert_eq!(node.earliest_time(), Some(1000));
+        assert!(node.properties().get("balance").is_none());
+        let edge = g.edge(1, 2).unwrap();
+        assert_eq!(edge.layer_names().collect::<Vec<_>>(), vec!["pays"]);
+        assert_eq!(edge.properties().get("payload.amount"), Some(Prop::I64(3)));
+        assert_eq!(
+            edge.layers("pays").unwrap().properties().get("currency"),
+            Some(Prop::str("EUR"))
+        );
+
+        let missing = json!({"time": 2, "dst": 2, "kind": "pays", "src_type": "a"});
+        assert!(matches!(
+            mapping.apply(&g, &missing),
+            Err(GraphError::InvalidRecord(_))
+        ));
+    }
+
+    #[test]
+    fn csv_values_are_inferred() {
+        let columns = vec![
+            "a".to_owned(),
+            "b".to_owned(),
+            "c".to_owned(),
+            "d".to_owned(),
+        ];
+        let record = csv_record(&columns, ["1", "2.5", "true", "x y"]);
+        assert_eq!(record, json!({"a": 1, "b": 2.5, "c": true, "d": "x y"}));
+    }
+
+    #[test]
+    fn specs_in_all_syntaxes_are_equal() {
+        let yaml = r#"
+format: csv
+delimiter: ";"
+time: ts
+time_format: "%Y-%m-%d %H:%M"
+casts:
+  amount: f32
+edge:
+  src: from
+  dst: to
+  layer_name: transfers
+  properties: [amount]
+"#;
+        let toml = r#"
+format = "csv"
+delimiter = ";"
+time = "ts"
+time_format = "%Y-%m-%d %H:%M"
+
+[casts]
+amount = "f32"
+
+[edge]
+src = "from"
+dst = "to"
+layer_name = "transfers"
+properties = ["amount"]
+"#;
+        let json = r#"{"format": "csv", "delimiter": ";", "time": "ts",
+            "time_format": "%Y-%m-%d %H:%M", "casts": {"amount": "f32"},
+            "edge": {"src": "from", "dst": "to", "layer_name": "transfers",
+            "properties": ["amount"]}}"#;
+        let spec = MappingSpec::parse(yaml).unwrap();
+        assert_eq!(spec, MappingSpec::parse(toml).unwrap());
+        assert_eq!(spec, MappingSpec::parse(json).unwrap());
+        assert_eq!(spec.format, Some(DataFormat::Csv));
+        assert_eq!(spec.mapping.casts["amount"], FieldType::F32);
+        assert!(MappingSpec::parse("edge: 1").is_err());
+    }
+
+    #[test]
+    fn load_csv_and_ndjson_with_mapping() {
+        let dir = tempdir().unwrap();
+        let spec_path = dir.path().join("spec.yaml");
+        fs::write(
+            &spec_path,
+            r#"
+delimiter: ";"
+error_policy: collect
+time: ts
+time_format: "%Y-%m-%d %H:%M"
+casts:
+  amount: f32
+  id: str
+edge:
+  src: from
+  dst: to
+  layer_name: transfers
+  properties: [amount, id]
+  shared_constant_properties:
+    source: bank
+"#,
+        )
+        .unwrap();
+        let data = dir.path().join("data");
+        fs::create_dir(&data).unwrap();
+        fs::write(
+            data.join("a.csv"),
+            "ts;from;to;amount;id\n2024-01-01 00:00;a;b;1.5;007\n",
+        )
+        .unwrap();
+        fs::write(
+            data.join("c.csv"),
+            "ts;from;to;amount;id\n2024-01-01 00:02;c;d;lots;1\n",
+        )
+        .unwrap();
+        fs::write(
+            data.join("b.ndjson"),
+            r#"{"ts": "2024-01-01 00:01", "from": "b", "to": "c", "amount": 2}"#,
+        )
+        .unwrap();
+
+        let spec = MappingSpec::from_file(&spec_path).unwrap();
+        let g = Graph::new();
+        let report = load_with_mapping(&data, &spec, &g).unwrap();
+        assert_eq!(report.loaded, 2);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].line, Some(2));
+        assert_eq!(report.rejected[0].record, "2024-01-01 00:02;c;d;lots;1");
+        assert!(g.edge("c", "d").is_none());
+
+        let ab = g.edge("a", "b").unwrap();
+        assert_eq!(ab.layer_names().collect::<Vec<_>>(), vec!["transfers"]);
+        assert_eq!(ab.earliest_time(), Some(1704067200000));
+        assert_eq!(ab.properties().get("amount"), Some(Prop::F32(1.5)));
+        assert_eq!(ab.properties().get("id"), Some(Prop::str("007")));
+        assert_eq!(
+            ab.layers("transfers").unwrap().properties().get("source"),
+            Some(Prop::str("bank"))
+        );
+        let bc = g.edge("b", "c").unwrap();
+        assert_eq!(bc.properties().get("amount"), Some(Prop::F32(2.0)));
+        assert_eq!(bc.earliest_time(), Some(1704067260000));
+    }
+
+    #[test]
+    fn replaying_files_is_idempotent_with_deduplication() {
+        let dir = tempdir().unwrap();
+        let data = dir.path().join("events.csv");
+        fs::write(&data, "id,ts,from,to\ne1,1,a,b\ne2,2,a,b\ne2,2,a,b\n").unwrap();
+
+        let by_id = MappingSpec::parse(
+            r#"{"event_id": "id", "time": "ts", "edge": {"src": "from", "dst": "to", "properties": ["id"]}}"#,
+        )
+        .unwrap();
+        let g = Graph::new();
+        let report = load_with_mapping(&data, &by_id, &g).unwrap();
+        assert_eq!((report.loaded, report.duplicates), (2, 1));
+        let report = load_with_mapping(&data, &by_id, &g).unwrap();
+        assert_eq!((report.loaded, report.duplicates), (0, 3));
+        assert_eq!(g.edge("a", "b").unwrap().history(), vec![1, 2]);
+
+        let by_content = MappingSpec::parse(
+            r#"{"deduplicate": true, "time": "ts", "edge": {"src": "from", "dst": "to"}}"#,
+        )
+        .unwrap();
+        let g = Graph::new();
+        load_with_mapping(&data, &by_content, &g).unwrap();
+        load_with_mapping(&data, &by_content, &g).unwrap();
+        assert_eq!(g.edge("a", "b").unwrap().history(), vec![1, 2]);
+
+        let invalid = MappingSpec::parse(
+            r#"{"event_id": "id", "time": "ts", "edge": {"src": "from", "dst": "to"}}"#,
+        )
+        .unwrap();
+        assert!(load_with_mapping(&data, &invalid, &Graph::new()).is_err());
+    }
+}
@@ -1,3 +1,6 @@
 pub mod csv_loader;
+pub mod edge_list;
 pub mod json_loader;
+pub mod mapping;
 pub mod neo4j_loader;
+pub mod stream;
@@ -0,0 +1,729 @@
+//! Streaming ingestion of newline-delimited JSON or CSV records
+//!
+//! A [`StreamIngestor`] reads records continuously from a TCP socket, a named pipe or a file that
+//! is being appended to, maps them to node and edge updates with a [`RecordMapping`] and adds them
+//! to a graph in batches. A background thread reads lines into a bounded buffer, so a slow graph
+//! applies back-pressure to the source instead of buffering without limit; the time the reader
+//! spends waiting is reported in the [`IngestStats`].
+//!
+//! When following a file, the byte offset after the last applied batch can be written to a
+//! checkpoint file, so that a restarted ingestor resumes where the previous one stopped. Files
+//! that are truncated or replaced (e.g. by log rotation) are reopened from the start.
+//!
+//! Records are split on newlines, so CSV fields cannot contain line breaks.
+//!
+//! # Example
+//! ```no_run
+//! use raphtory::graph_loader::source::{
+//!     mapping::{EdgeMapping, RecordMapping},
+//!     stream::{RecordFormat, StreamIngestor, StreamSource},
+//! };
+//! use raphtory::prelude::*;
+//!
+//! let mapping = RecordMapping {
+//!     time: "time".to_owned(),
+//!     edge: Some(EdgeMapping {
+//!         src: "src".to_owned(),
+//!         dst: "dst".to_owned(),
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//! let g = Graph::new();
+//! let stats = StreamIngestor::new(mapping, RecordFormat::NdJson)
+//!     .set_batch_size(10_000)
+//!     .set_checkpoint("/tmp/events.offset")
+//!     .run(&g, StreamSource::Tail("/var/log/events.ndjson".into()))
+//!     .expect("Failed to ingest events");
+//! println!("added {} records", stats.records);
+//! ```
+
+use crate::{
+    db::api::{
+        mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+        view::StaticGraphViewOps,
+    },
+    graph_loader::source::mapping::{csv_record, RecordMapping},
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    fs::File,
+    io,
+    io::{BufRead, BufReader, Seek, SeekFrom},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{sync_channel, RecvTimeoutError, SyncSender, TrySendError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug)]
+pub enum StreamErr {
+    /// An IO error that occurred while reading the source or writing the checkpoint.
+    IoError(io::Error),
+    /// A JSON error that occurred while reading the checkpoint.
+    JsonError(serde_json::Error),
+}
+
+impl From<io::Error> for StreamErr {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl From<serde_json::Error> for StreamErr {
+    fn from(value: serde_json::Error) -> Self {
+        Self::JsonError(value)
+    }
+}
+
+impl Display for StreamErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.source() {
+            Some(error) => write!(f, "Stream ingestion failed with error: {}", error),
+            None => write!(f, "Stream ingestion failed with unknown error"),
+        }
+    }
+}
+
+impl Error for StreamErr {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamErr::IoError(error) => Some(error),
+            StreamErr::JsonError(error) => Some(error),
+        }
+    }
+}
+
+/// Where records are read from
+#[derive(Debug, Clone)]
+pub enum StreamSource {
+    /// Connect to a TCP server and read until it closes the connection
+    TcpConnect(String),
+    /// Listen on an address and read from every client connection in turn until stopped
+    TcpListen(String),
+    /// Read a named pipe, or any other file, once until its end
+    Pipe(PathBuf),
+    /// Follow a file as it grows until stopped, reopening it when it is rotated or truncated
+    Tail(PathBuf),
+}
+
+/// How lines are parsed into records
+#[derive(Debug, Clone)]
+pub enum RecordFormat {
+    /// Every line is a JSON object
+    NdJson,
+    /// Every line is a CSV row. If `columns` is `None`, the first line of every file or
+    /// connection is the header.
+    Csv {
+        delimiter: u8,
+        columns: Option<Vec<String>>,
+    },
+}
+
+/// Progress of a stream ingestion
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IngestStats {
+    /// Records added to the graph
+    pub records: u64,
+    /// Lines that could not be parsed as records
+    pub parse_errors: u64,
+    /// Records rejected by the mapping or the graph
+    pub graph_errors: u64,
+    /// Batches added to the graph
+    pub batches: u64,
+    /// Byte offset in the current file or connection after the last applied record
+    pub offset: u64,
+    /// Number of times the reader waited because the buffer of pending lines was full
+    pub backpressure_waits: u64,
+    /// Total time the reader spent waiting on a full buffer
+    pub backpressure_time: Duration,
+    /// The most recent record error
+    pub last_error: Option<String>,
+}
+
+/// Position in a followed file, persisted between runs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Checkpoint {
+    offset: u64,
+    file_id: Option<u64>,
+}
+
+impl Checkpoint {
+    fn read(path: &Path) -> Result<Option<Self>, StreamErr> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn write(&self, path: &Path) -> Result<(), StreamErr> {
+        // write to a temporary file first so a crash never leaves a partial checkpoint
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+enum Message {
+    /// A new file or connection was opened, the following lines start at `offset`
+    Opened { file_id: Option<u64>, offset: u64 },
+    /// The CSV header of the current file or connection
+    Header(String),
+    /// A record and the offset just after it
+    Line { line: String, end: u64 },
+    /// Reading the source failed
+    Failed(io::Error),
+}
+
+/// Reads records from a stream and adds them to a graph in batches
+#[derive(Debug)]
+pub struct StreamIngestor {
+    mapping: RecordMapping,
+    format: RecordFormat,
+    batch_size: usize,
+    buffer_size: usize,
+    flush_interval: Duration,
+    poll_interval: Duration,
+    idle_timeout: Option<Duration>,
+    checkpoint: Option<PathBuf>,
+    stop: Arc<AtomicBool>,
+    stats: Arc<Mutex<IngestStats>>,
+}
+
+impl StreamIngestor {
+    /// Creates a new ingestor adding records of the given format with `mapping`
+    pub fn new(mapping: RecordMapping, format: RecordFormat) -> Self {
+        Self {
+            mapping,
+            format,
+            batch_size: 1000,
+            buffer_size: 100_000,
+            flush_interval: Duration::from_secs(1),
+            poll_interval: Duration::from_millis(100),
+            idle_timeout: None,
+            checkpoint: None,
+            stop: Arc::new(AtomicBool::new(false)),
+            stats: Default::default(),
+        }
+    }
+
+    /// Maximum number of records added to the graph at once
+    pub fn set_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Maximum number of lines read ahead of the graph before the reader blocks
+    pub fn set_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+        self
+    }
+
+    /// Add incomplete batches after waiting this long for more records
+    pub fn set_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// How often to check for new data in a followed file and for a stop request
+    pub fn set_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Stop once no new records arrived for this long
+    pub fn set_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Record the position in a followed file after every batch and resume from it
+    pub fn set_checkpoint<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.checkpoint = Some(path.into());
+        self
+    }
+
+    /// Flag that stops a running ingestion when set to `true`
+    pub fn stop_handle(&self) -> Arc<AtomicBool> {
+        self.stop.clone()
+    }
+
+    /// Snapshot of the progress of the current or last run
+    pub fn stats(&self) -> IngestStats {
+        self.stats.lock().clone()
+    }
+
+    /// Read records from `source` and add them to `graph` until the source ends or the
+    /// ingestion is stopped
+    ///
+    /// Records that fail to parse or to be added are counted and skipped. Returns the progress
+    /// at the end of the run.
+    pub fn run<G>(&self, graph: &G, source: StreamSource) -> Result<IngestStats, StreamErr>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        let checkpoint = match &source {
+            StreamSource::Tail(_) => self.checkpoint.as_deref(),
+            _ => None,
+        };
+        let resume = match checkpoint {
+            Some(path) => Checkpoint::read(path)?,
+            None => None,
+        };
+        self.stop.store(false, Ordering::Relaxed);
+        *self.stats.lock() = IngestStats::default();
+        let mut columns = match &self.format {
+            RecordFormat::Csv {
+                columns: Some(columns),
+                ..
+            } => Some(columns.clone()),
+            _ => None,
+        };
+        let (sender, receiver) = sync_channel(self.buffer_size);
+        let reader = Reader {
+            sender,
+            stop: self.stop.clone(),
+            stats: self.stats.clone(),
+            poll_interval: self.poll_interval,
+            header: matches!(self.format, RecordFormat::Csv { columns: None, .. }),
+        };
+        let handle = thread::spawn(move || {
+            if let Err(err) = reader.run(source, resume) {
+                let _ = reader.sender.send(Message::Failed(err));
+            }
+        });
+
+        let mut batch = vec![];
+        let mut file_id = None;
+        let mut last_record = Instant::now();
+        let mut result = Ok(());
+        loop {
+            match receiver.recv_timeout(self.flush_interval) {
+                Ok(Message::Opened {
+                    file_id: id,
+                    offset,
+                }) => {
+                    result = self.flush(graph, &mut batch, &columns, file_id, checkpoint);
+                    file_id = id;
+                    self.stats.lock().offset = offset;
+                }
+                Ok(Message::Header(line)) => {
+                    result = self.flush(graph, &mut batch, &columns, file_id, checkpoint);
+                    columns = Some(
+                        self.csv_row(&line)
+                            .map(|row| row.iter().map(|c| c.trim().to_owned()).collect())
+                            .unwrap_or_default(),
+                    );
+                }
+                Ok(Message::Line { line, end }) => {
+                    last_record = Instant::now();
+                    batch.push((line, end));
+                    if batch.len() >= self.batch_size {
+                        result = self.flush(graph, &mut batch, &columns, file_id, checkpoint);
+                    }
+                }
+                Ok(Message::Failed(err)) => result = Err(err.into()),
+                Err(RecvTimeoutError::Timeout) => {
+                    result = self.flush(graph, &mut batch, &columns, file_id, checkpoint);
+                    if self
+                        .idle_timeout
+                        .filter(|timeout| last_record.elapsed() >= *timeout)
+                        .is_some()
+                    {
+                        self.stop.store(true, Ordering::Relaxed);
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if result.is_err() {
+                self.stop.store(true, Ordering::Relaxed);
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = self.flush(graph, &mut batch, &columns, file_id, checkpoint);
+        }
+        // unblock the reader if it is waiting on a full buffer
+        drop(receiver);
+        let _ = handle.join();
+        result.map(|_| self.stats())
+    }
+
+    fn csv_row(&self, line: &str) -> Option<csv::StringRecord> {
+        let delimiter = match &self.format {
+            RecordFormat::Csv { delimiter, .. } => *delimiter,
+            RecordFormat::NdJson => b',',
+        };
+        csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(delimiter)
+            .from_reader(line.as_bytes())
+            .records()
+            .next()?
+            .ok()
+    }
+
+    fn parse(&self, line: &str, columns: &Option<Vec<String>>) -> Result<Value, String> {
+        match &self.format {
+            RecordFormat::NdJson => serde_json::from_str(line).map_err(|err| err.to_string()),
+            RecordFormat::Csv { .. } => {
+                let columns = columns.as_ref().ok_or("missing CSV header")?;
+                let row = self
+                    .csv_row(line)
+                    .ok_or_else(|| format!("invalid CSV row {line:?}"))?;
+                Ok(csv_record(columns, row.iter()))
+            }
+        }
+    }
+
+    fn flush<G>(
+        &self,
+        graph: &G,
+        batch: &mut Vec<(String, u64)>,
+        columns: &Option<Vec<String>>,
+        file_id: Option<u64>,
+        checkpoint: Option<&Path>,
+    ) -> Result<(), StreamErr>
+    where
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    {
+        let Some((_, offset)) = batch.last().cloned() else {
+            return Ok(());
+        };
+        let mut records = 0;
+        let mut parse_errors = 0;
+        let mut graph_errors = 0;
+        let mut last_error = None;
+        for (line, _) in batch.drain(..) {
+            match self.parse(&line, columns) {
+                Ok(record) => match self.mapping.apply(graph, &record) {
+                    Ok(()) => records += 1,
+                    Err(err) => {
+                        graph_errors += 1;
+                        last_error = Some(format!("{err} in {line:?}"));
+                    }
+                },
+                Err(err) => {
+                    parse_errors += 1;
+                    last_error = Some(format!("{err} in {line:?}"));
+                }
+            }
+        }
+        {
+            let mut stats = self.stats.lock();
+            stats.records += records;
+            stats.parse_errors += parse_errors;
+            stats.graph_errors += graph_errors;
+            stats.batches += 1;
+            stats.offset = offset;
+            if last_error.is_some() {
+                stats.last_error = last_error;
+            }
+        }
+        if let Some(path) = checkpoint {
+            Checkpoint { offset, file_id }.write(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Background half of an ingestion, forwarding lines of the source to the ingestor
+struct Reader {
+    sender: SyncSender<Message>,
+    stop: Arc<AtomicBool>,
+    stats: Arc<Mutex<IngestStats>>,
+    poll_interval: Duration,
+    header: bool,
+}
+
+/// Line splitting state of one file or connection
+struct Lines {
+    offset: u64,
+    partial: Vec<u8>,
+    expect_header: bool,
+}
+
+enum Flow {
+    /// Reached the current end of the source
+    End,
+    /// The ingestion was stopped
+    Stop,
+}
+
+impl Reader {
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Send a message, waiting while the buffer is full. Returns `false` if the ingestor is gone.
+    fn send(&self, message: Message) -> bool {
+        match self.sender.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(message)) => {
+                let start = Instant::now();
+                let sent = self.sender.send(message).is_ok();
+                let mut stats = self.stats.lock();
+                stats.backpressure_waits += 1;
+                stats.backpressure_time += start.elapsed();
+                sent
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+
+    fn open(&self, file_id: Option<u64>, offset: u64) -> Option<Lines> {
+        self.send(Message::Opened { file_id, offset })
+            .then(|| Lines {
+                offset,
+                partial: vec![],
+                expect_header: self.header,
+            })
+    }
+
+    fn emit(&self, lines: &mut Lines) -> bool {
+        let line = String::from_utf8_lossy(&lines.partial).trim().to_owned();
+        lines.partial.clear();
+        if line.is_empty() {
+            return true;
+        }
+        if lines.expect_header {
+            lines.expect_header = false;
+            self.send(Message::Header(line))
+        } else {
+            self.send(Message::Line {
+                line,
+                end: lines.offset,
+            })
+        }
+    }
+
+    /// Forward complete lines until the current end of the source
+    fn forward(&self, reader: &mut impl BufRead, lines: &mut Lines) -> io::Result<Flow> {
+        loop {
+            if self.stopped() {
+                return Ok(Flow::Stop);
+            }
+            match reader.read_until(b'\n', &mut lines.partial) {
+                Ok(0) => return Ok(Flow::End),
+                Ok(_) => {
+                    if lines.partial.ends_with(b"\n") {
+                        lines.offset += lines.partial.len() as u64;
+                        if !self.emit(lines) {
+                            return Ok(Flow::Stop);
+                        }
+                    }
+                }
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock
+                            | io::ErrorKind::TimedOut
+                            | io::ErrorKind::Interrupted
+                    ) => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Forward all lines of a stream that ends, including a last line without a line break
+    fn forward_to_end(&self, reader: &mut impl BufRead, file_id: Option<u64>) -> io::Result<Flow> {
+        let Some(mut lines) = self.open(file_id, 0) else {
+            return Ok(Flow::Stop);
+        };
+        let flow = self.forward(reader, &mut lines)?;
+        if matches!(flow, Flow::End) && !lines.partial.is_empty() {
+            lines.offset += lines.partial.len() as u64;
+            self.emit(&mut lines);
+        }
+        Ok(flow)
+    }
+
+    fn run(&self, source: StreamSource, resume: Option<Checkpoint>) -> io::Result<()> {
+        match source {
+            StreamSource::TcpConnect(address) => {
+                let stream = TcpStream::connect(address)?;
+                self.read_connection(stream)?;
+            }
+            StreamSource::TcpListen(address) => {
+                let listener = TcpListener::bind(address)?;
+                listener.set_nonblocking(true)?;
+                while !self.stopped() {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            stream.set_nonblocking(false)?;
+                            if let Flow::Stop = self.read_connection(stream)? {
+                                break;
+                            }
+                        }
+                        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            thread::sleep(self.poll_interval)
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+            StreamSource::Pipe(path) => {
+                let mut reader = BufReader::new(File::open(path)?);
+                self.forward_to_end(&mut reader, None)?;
+            }
+            StreamSource::Tail(path) => self.tail(&path, resume)?,
+        }
+        Ok(())
+    }
+
+    fn read_connection(&self, stream: TcpStream) -> io::Result<Flow> {
+        // time out reads regularly to notice stop requests
+        stream.set_read_timeout(Some(self.poll_interval))?;
+        self.forward_to_end(&mut BufReader::new(stream), None)
+    }
+
+    fn tail(&self, path: &Path, resume: Option<Checkpoint>) -> io::Result<()> {
+        let mut file = File::open(path)?;
+        let mut current_id = file_id(&file.metadata()?);
+        let len = file.metadata()?.len();
+        let offset = resume
+            .filter(|checkpoint| checkpoint.file_id == current_id && checkpoint.offset <= len)
+            .map_or(0, |checkpoint| checkpoint.offset);
+        let Some(mut lines) = self.open(current_id, offset) else {
+            return Ok(());
+        };
+        if offset > 0 && lines.expect_header {
+            // the header was consumed in a previous run
+            let mut header = vec![];
+            BufReader::new(&file).read_until(b'\n', &mut header)?;
+            lines.partial = header;
+            if !self.emit(&mut lines) {
+                return Ok(());
+            }
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut reader = BufReader::new(file);
+        loop {
+            if let Flow::Stop = self.forward(&mut reader, &mut lines)? {
+                return Ok(());
+            }
+            thread::sleep(self.poll_interval);
+            let Ok(metadata) = fs::metadata(path) else {
+                // the file is being rotated
+                continue;
+            };
+            let consumed = lines.offset + lines.partial.len() as u64;
+            if file_id(&metadata) != current_id || metadata.len() < consumed {
+                reader = BufReader::new(File::open(path)?);
+                current_id = file_id(&reader.get_ref().metadata()?);
+                match self.open(current_id, 0) {
+                    Some(new_lines) => lines = new_lines,
+                    None => return Ok(()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{graph_loader::source::mapping::EdgeMapping, prelude::*};
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn mapping() -> RecordMapping {
+        RecordMapping {
+            time: "time".to_owned(),
+            edge: Some(EdgeMapping {
+                src: "src".to_owned(),
+                dst: "dst".to_owned(),
+                properties: vec!["weight".to_owned()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn ingestor(format: RecordFormat) -> StreamIngestor {
+        StreamIngestor::new(mapping(), format)
+            .set_batch_size(2)
+            .set_poll_interval(Duration::from_millis(10))
+            .set_flush_interval(Duration::from_millis(20))
+            .set_idle_timeout(Duration::from_millis(200))
+    }
+
+    #[test]
+    fn tail_file_and_resume_from_checkpoint() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.ndjson");
+        let checkpoint = dir.path().join("events.offset");
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, r#"{{"time": 1, "src": 1, "dst": 2, "weight": 1.0}}"#).unwrap();
+        writeln!(file, "not json").unwrap();
+        writeln!(file, r#"{{"time": 2, "src": 2, "dst": 3}}"#).unwrap();
+        writeln!(file, r#"{{"time": 3, "src": 3}}"#).unwrap();
+
+        let g = Graph::new();
+        let stats = ingestor(RecordFormat::NdJson)
+            .set_checkpoint(&checkpoint)
+            .run(&g, StreamSource::Tail(path.clone()))
+            .unwrap();
+        assert_eq!(stats.records, 2);
+        assert_eq!(stats.parse_errors, 1);
+        assert_eq!(stats.graph_errors, 1);
+        assert_eq!(stats.offset, fs::metadata(&path).unwrap().len());
+        assert_eq!(g.count_edges(), 2);
+
+        writeln!(file, r#"{{"time": 4, "src": 1, "dst": 2, "weight": 2.0}}"#).unwrap();
+        let stats = ingestor(RecordFormat::NdJson)
+            .set_checkpoint(&checkpoint)
+            .run(&g, StreamSource::Tail(path.clone()))
+            .unwrap();
+        assert_eq!(stats.records, 1);
+        assert_eq!(g.edge(1, 2).unwrap().history(), vec![1, 4]);
+    }
+
+    #[test]
+    fn read_csv_from_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream
+                .write_all(b"time;src;dst;weight\n1;a;b;0.5\n2;b;c;1.5")
+                .unwrap();
+        });
+        let g = Graph::new();
+        let stats = ingestor(RecordFormat::Csv {
+            delimiter: b';',
+            columns: None,
+        })
+        .run(&g, StreamSource::TcpConnect(address))
+        .unwrap();
+        server.join().unwrap();
+        assert_eq!(stats.records, 2);
+        assert_eq!(
+            g.edge("b", "c").unwrap().properties().get("weight"),
+            Some(Prop::F64(1.5))
+        );
+    }
+}
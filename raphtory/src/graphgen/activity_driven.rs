@@ -0,0 +1,158 @@
+//! Generates a temporal graph using the activity-driven model
+//!
+//! This function is a graph generation model based upon:
+//! Perra, Nicola, et al. "Activity driven modeling of time varying networks."
+//! Scientific Reports 2.1 (2012): 469.
+//!
+//! # Examples
+//!
+//! ```
+//! use raphtory::prelude::*;
+//! use raphtory::graphgen::{activity_driven::{activity_driven, ActivityDriven}, synthesis::Synthesis};
+//! let graph = Graph::new();
+//! let model = ActivityDriven { nodes: 500, steps: 50, ..Default::default() };
+//! activity_driven(&graph, &model, &Synthesis::default(), None).unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    graphgen::synthesis::{rng, GraphSink, Synthesis},
+};
+use rand::{seq::index::sample, Rng};
+
+/// Parameters of the activity-driven model
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityDriven {
+    /// Number of nodes
+    pub nodes: usize,
+    /// Number of time steps
+    pub steps: usize,
+    /// Number of distinct nodes an active node connects to in a step
+    pub edges_per_activation: usize,
+    /// Exponent of the power-law distribution of activities
+    pub gamma: f64,
+    /// Smallest activity, activities are drawn from `[epsilon, 1]`
+    pub epsilon: f64,
+    /// Factor turning activities into the probability of being active in a step
+    pub eta: f64,
+    /// Time between consecutive steps
+    pub step_duration: i64,
+}
+
+impl Default for ActivityDriven {
+    fn default() -> Self {
+        Self {
+            nodes: 1000,
+            steps: 100,
+            edges_per_activation: 2,
+            gamma: 2.1,
+            epsilon: 1e-3,
+            eta: 10.0,
+            step_duration: 1,
+        }
+    }
+}
+
+impl ActivityDriven {
+    fn validate(&self) -> Result<(), GraphError> {
+        let invalid =
+            |message: &str| Err(GraphError::InvalidGeneratorParameter(message.to_owned()));
+        if self.edges_per_activation >= self.nodes {
+            return invalid("edges_per_activation must be smaller than the number of nodes");
+        }
+        if !(self.epsilon > 0.0 && self.epsilon <= 1.0) {
+            return invalid("epsilon must be in (0, 1]");
+        }
+        if !(self.gamma.is_finite() && self.eta >= 0.0 && self.eta.is_finite()) {
+            return invalid("gamma and eta must be finite and eta non-negative");
+        }
+        if self.step_duration <= 0 {
+            return invalid("step_duration must be positive");
+        }
+        Ok(())
+    }
+
+    /// Draw an activity from the power law `F(a) ∝ a^-gamma` on `[epsilon, 1]` by inverting its
+    /// cumulative distribution
+    fn activity<R: Rng>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.gen();
+        let exponent = 1.0 - self.gamma;
+        if exponent.abs() < f64::EPSILON {
+            self.epsilon.powf(1.0 - u)
+        } else {
+            let low = self.epsilon.powf(exponent);
+            (low + u * (1.0 - low)).powf(1.0 / exponent)
+        }
+    }
+}
+
+/// Adds `model.nodes` nodes with ids `0..model.nodes`, each with an activity drawn from a
+/// power law. In every step each node becomes active with probability `eta` times its activity
+/// and then connects to `edges_per_activation` distinct nodes chosen uniformly. Edges of step
+/// `i` are added at `start + i * step_duration`, where `start` is the start of the time range of
+/// `synthesis` or 0.
+///
+/// # Arguments
+/// * `sink` - The graph (or file) you wish to write nodes and edges to
+/// * `model` - The parameters of the model
+/// * `synthesis` - Layers and properties of the edges
+/// * `seed` - (Optional) An array of u8 bytes to be used as the input seed, Default None
+pub fn activity_driven<S: GraphSink + ?Sized>(
+    sink: &S,
+    model: &ActivityDriven,
+    synthesis: &Synthesis,
+    seed: Option<[u8; 32]>,
+) -> Result<(), GraphError> {
+    model.validate()?;
+    synthesis.validate()?;
+    let mut rng = rng(seed);
+    let start = synthesis.start();
+    let mut probabilities = Vec::with_capacity(model.nodes);
+    for id in 0..model.nodes as u64 {
+        sink.write_node(start, id, None)?;
+        probabilities.push((model.eta * model.activity(&mut rng)).min(1.0));
+    }
+    for step in 0..model.steps as i64 {
+        let time = start + step * model.step_duration;
+        for (src, &p) in probabilities.iter().enumerate() {
+            if !rng.gen_bool(p) {
+                continue;
+            }
+            for dst in sample(&mut rng, model.nodes - 1, model.edges_per_activation) {
+                let dst = if dst >= src { dst + 1 } else { dst };
+                synthesis.add_edge(sink, &mut rng, time, src as u64, dst as u64)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn edges_are_added_at_step_times() {
+        let graph = Graph::new();
+        let model = ActivityDriven {
+            nodes: 50,
+            steps: 10,
+            epsilon: 0.5,
+            eta: 100.0,
+            step_duration: 5,
+            ..Default::default()
+        };
+        activity_driven(&graph, &model, &Synthesis::default(), Some([2; 32])).unwrap();
+        assert_eq!(graph.count_nodes(), 50);
+        // every node is active in every step
+        assert_eq!(graph.count_temporal_edges(), 50 * 10 * 2);
+        assert_eq!(graph.earliest_time(), Some(0));
+        assert_eq!(graph.latest_time(), Some(45));
+        assert!(graph
+            .edges()
+            .explode()
+            .iter()
+            .all(|e| e.time().unwrap() % 5 == 0));
+    }
+}
@@ -0,0 +1,86 @@
+//! Generates a graph using the configuration model
+//!
+//! This function is a graph generation model based upon:
+//! Newman, Mark EJ. "The structure and function of complex networks."
+//! SIAM Review 45.2 (2003): 167-256.
+//!
+//! # Examples
+//!
+//! ```
+//! use raphtory::prelude::*;
+//! use raphtory::graphgen::{configuration_model::configuration_model, synthesis::Synthesis};
+//! let graph = Graph::new();
+//! configuration_model(&graph, &[3, 2, 2, 1], &Synthesis::default(), None).unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    graphgen::synthesis::{rng, GraphSink, Synthesis},
+};
+use rand::seq::SliceRandom;
+
+/// Adds one node per entry of `degrees`, with ids `0..degrees.len()`, gives every node as many
+/// edge stubs as its degree and pairs the stubs uniformly at random.
+///
+/// Every pair of stubs becomes one edge update, so the number of updates of every node matches
+/// its degree exactly. Self-loops and repeated updates between the same nodes are kept.
+///
+/// # Arguments
+/// * `sink` - The graph (or file) you wish to write nodes and edges to
+/// * `degrees` - The degree of every node, summing to an even number
+/// * `synthesis` - Times, layers and properties of the edges
+/// * `seed` - (Optional) An array of u8 bytes to be used as the input seed, Default None
+pub fn configuration_model<S: GraphSink + ?Sized>(
+    sink: &S,
+    degrees: &[usize],
+    synthesis: &Synthesis,
+    seed: Option<[u8; 32]>,
+) -> Result<(), GraphError> {
+    let total: usize = degrees.iter().sum();
+    if total % 2 != 0 {
+        return Err(GraphError::InvalidGeneratorParameter(format!(
+            "the degrees must sum to an even number, got {total}"
+        )));
+    }
+    synthesis.validate()?;
+    let mut rng = rng(seed);
+    let start = synthesis.start();
+    let mut stubs = Vec::with_capacity(total);
+    for (id, &degree) in degrees.iter().enumerate() {
+        sink.write_node(start, id as u64, None)?;
+        stubs.extend(std::iter::repeat(id as u64).take(degree));
+    }
+    stubs.shuffle(&mut rng);
+    for pair in stubs.chunks_exact(2) {
+        let time = synthesis.static_time(&mut rng);
+        synthesis.add_edge(sink, &mut rng, time, pair[0], pair[1])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn updates_match_degrees() {
+        let graph = Graph::new();
+        let degrees = [3, 1, 2, 2, 4];
+        configuration_model(&graph, &degrees, &Synthesis::default(), Some([5; 32])).unwrap();
+        assert_eq!(graph.count_nodes(), 5);
+        assert_eq!(graph.count_temporal_edges(), 6);
+        for (id, &degree) in degrees.iter().enumerate() {
+            let node = graph.node(id as u64).unwrap();
+            let updates: usize = node
+                .out_edges()
+                .iter()
+                .chain(node.in_edges().iter())
+                .map(|e| e.explode().iter().count())
+                .sum();
+            assert_eq!(updates, degree);
+        }
+
+        assert!(configuration_model(&graph, &[1, 2], &Synthesis::default(), None).is_err());
+    }
+}
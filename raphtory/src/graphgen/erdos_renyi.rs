@@ -0,0 +1,92 @@
+//! Generates a graph using the Erdős–Rényi model
+//!
+//! This function is a graph generation model based upon:
+//! Gilbert, Edgar N. "Random graphs."
+//! The Annals of Mathematical Statistics 30.4 (1959): 1141-1144.
+//!
+//! # Examples
+//!
+//! ```
+//! use raphtory::prelude::*;
+//! use raphtory::graphgen::{erdos_renyi::erdos_renyi, synthesis::Synthesis};
+//! let graph = Graph::new();
+//! erdos_renyi(&graph, 1000, 0.01, &Synthesis::default(), None).unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    graphgen::synthesis::{check_probability, for_each_success, rng, GraphSink, Synthesis},
+};
+
+/// Adds `nodes` nodes with ids `0..nodes` and connects every ordered pair of distinct nodes
+/// with probability `p`.
+///
+/// # Arguments
+/// * `sink` - The graph (or file) you wish to write nodes and edges to
+/// * `nodes` - The number of nodes
+/// * `p` - The probability of every directed edge
+/// * `synthesis` - Times, layers and properties of the edges
+/// * `seed` - (Optional) An array of u8 bytes to be used as the input seed, Default None
+pub fn erdos_renyi<S: GraphSink + ?Sized>(
+    sink: &S,
+    nodes: usize,
+    p: f64,
+    synthesis: &Synthesis,
+    seed: Option<[u8; 32]>,
+) -> Result<(), GraphError> {
+    check_probability("p", p)?;
+    synthesis.validate()?;
+    let mut rng = rng(seed);
+    let start = synthesis.start();
+    for id in 0..nodes as u64 {
+        sink.write_node(start, id, None)?;
+    }
+    if nodes < 2 {
+        return Ok(());
+    }
+    let n = nodes as u64;
+    for_each_success(&mut rng, n * (n - 1), p, |rng, index| {
+        let src = index / (n - 1);
+        let mut dst = index % (n - 1);
+        if dst >= src {
+            dst += 1;
+        }
+        let time = synthesis.static_time(rng);
+        synthesis.add_edge(sink, rng, time, src, dst)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn complete_and_empty() {
+        let graph = Graph::new();
+        erdos_renyi(&graph, 10, 1.0, &Synthesis::default(), Some([1; 32])).unwrap();
+        assert_eq!(graph.count_nodes(), 10);
+        assert_eq!(graph.count_edges(), 90);
+        assert!(graph.edges().iter().all(|e| e.src().id() != e.dst().id()));
+
+        let graph = Graph::new();
+        erdos_renyi(&graph, 10, 0.0, &Synthesis::default(), Some([1; 32])).unwrap();
+        assert_eq!(graph.count_nodes(), 10);
+        assert_eq!(graph.count_edges(), 0);
+    }
+
+    #[test]
+    fn seeded_generation_is_deterministic() {
+        let synthesis = Synthesis {
+            time_range: Some(0..100),
+            layers: vec!["a".to_owned(), "b".to_owned()],
+            properties: vec![],
+        };
+        let g1 = Graph::new();
+        let g2 = Graph::new();
+        erdos_renyi(&g1, 100, 0.1, &synthesis, Some([7; 32])).unwrap();
+        erdos_renyi(&g2, 100, 0.1, &synthesis, Some([7; 32])).unwrap();
+        assert_eq!(g1, g2);
+        assert!(g1.count_edges() > 0);
+    }
+}
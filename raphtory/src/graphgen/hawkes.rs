@@ -0,0 +1,175 @@
+//! Generates a temporal graph of bursty interactions using Hawkes processes
+//!
+//! The interactions of every pair of nodes follow a self-exciting point process with an
+//! exponential kernel, simulated with Ogata's thinning algorithm:
+//! Ogata, Yosihiko. "On Lewis' simulation method for point processes."
+//! IEEE Transactions on Information Theory 27.1 (1981): 23-31.
+//!
+//! # Examples
+//!
+//! ```
+//! use raphtory::prelude::*;
+//! use raphtory::graphgen::{hawkes::{hawkes, Hawkes}, synthesis::Synthesis};
+//! let graph = Graph::new();
+//! hawkes(&graph, &Hawkes::default(), &Synthesis::default(), None).unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    graphgen::synthesis::{rng, GraphSink, Synthesis},
+};
+use rand::Rng;
+use rand_distr::{Distribution, Exp};
+use std::collections::HashSet;
+
+/// Parameters of the Hawkes model
+///
+/// The rate of interactions of a pair at time `t` is
+/// `baseline + excitation * sum(exp(-decay * (t - t_i)))` over its previous interactions `t_i`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hawkes {
+    /// Number of nodes
+    pub nodes: usize,
+    /// Number of distinct ordered pairs of nodes that interact
+    pub pairs: usize,
+    /// Rate of spontaneous interactions of a pair per unit of time
+    pub baseline: f64,
+    /// Increase of the rate after every interaction
+    pub excitation: f64,
+    /// Rate at which the increase decays, larger than `excitation` to keep the process stable
+    pub decay: f64,
+    /// Length of the simulated period
+    pub duration: i64,
+}
+
+impl Default for Hawkes {
+    fn default() -> Self {
+        Self {
+            nodes: 100,
+            pairs: 200,
+            baseline: 0.01,
+            excitation: 0.5,
+            decay: 1.0,
+            duration: 1000,
+        }
+    }
+}
+
+impl Hawkes {
+    fn validate(&self) -> Result<(), GraphError> {
+        let invalid =
+            |message: &str| Err(GraphError::InvalidGeneratorParameter(message.to_owned()));
+        let possible_pairs = (self.nodes as u128) * (self.nodes.saturating_sub(1) as u128);
+        if self.pairs as u128 > possible_pairs {
+            return invalid("more pairs than distinct ordered pairs of nodes");
+        }
+        if !(self.baseline > 0.0 && self.baseline.is_finite()) {
+            return invalid("baseline must be positive");
+        }
+        if !(self.excitation >= 0.0 && self.excitation < self.decay && self.decay.is_finite()) {
+            return invalid("excitation must be non-negative and smaller than decay");
+        }
+        if self.duration < 0 {
+            return invalid("duration must not be negative");
+        }
+        Ok(())
+    }
+
+    /// Interaction times of one pair in `[0, duration)`
+    fn events<R: Rng>(&self, rng: &mut R) -> Vec<f64> {
+        let mut events = vec![];
+        let mut t = 0.0;
+        // excitation left from previous events at time t
+        let mut excited = 0.0;
+        loop {
+            // the rate only decays until the next event, so the current rate bounds it
+            let bound = self.baseline + excited;
+            let wait = Exp::new(bound).expect("rate is positive").sample(rng);
+            t += wait;
+            if t >= self.duration as f64 {
+                break;
+            }
+            excited *= (-self.decay * wait).exp();
+            if rng.gen::<f64>() * bound <= self.baseline + excited {
+                events.push(t);
+                excited += self.excitation;
+            }
+        }
+        events
+    }
+}
+
+/// Adds `model.nodes` nodes with ids `0..model.nodes`, chooses `model.pairs` distinct ordered
+/// pairs uniformly and adds an edge update for every interaction of a pair, at
+/// `start + floor(t)` where `start` is the start of the time range of `synthesis` or 0.
+///
+/// # Arguments
+/// * `sink` - The graph (or file) you wish to write nodes and edges to
+/// * `model` - The parameters of the model
+/// * `synthesis` - Layers and properties of the edges
+/// * `seed` - (Optional) An array of u8 bytes to be used as the input seed, Default None
+pub fn hawkes<S: GraphSink + ?Sized>(
+    sink: &S,
+    model: &Hawkes,
+    synthesis: &Synthesis,
+    seed: Option<[u8; 32]>,
+) -> Result<(), GraphError> {
+    model.validate()?;
+    synthesis.validate()?;
+    let mut rng = rng(seed);
+    let start = synthesis.start();
+    let n = model.nodes as u64;
+    for id in 0..n {
+        sink.write_node(start, id, None)?;
+    }
+    let mut pairs = HashSet::with_capacity(model.pairs);
+    while pairs.len() < model.pairs {
+        let src = rng.gen_range(0..n);
+        let dst = rng.gen_range(0..n - 1);
+        let dst = if dst >= src { dst + 1 } else { dst };
+        if !pairs.insert((src, dst)) {
+            continue;
+        }
+        for t in model.events(&mut rng) {
+            synthesis.add_edge(sink, &mut rng, start + t as i64, src, dst)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn events_fall_in_duration() {
+        let graph = Graph::new();
+        let model = Hawkes {
+            nodes: 20,
+            pairs: 30,
+            baseline: 0.05,
+            excitation: 0.8,
+            decay: 1.0,
+            duration: 500,
+        };
+        let synthesis = Synthesis {
+            time_range: Some(1000..2000),
+            ..Default::default()
+        };
+        hawkes(&graph, &model, &synthesis, Some([4; 32])).unwrap();
+        assert_eq!(graph.count_nodes(), 20);
+        assert_eq!(graph.count_edges(), 30);
+        assert!(graph
+            .edges()
+            .explode()
+            .iter()
+            .all(|e| (1000..1500).contains(&e.time().unwrap())));
+
+        let unstable = Hawkes {
+            excitation: 2.0,
+            ..model
+        };
+        assert!(hawkes(&graph, &unstable, &synthesis, None).is_err());
+    }
+}
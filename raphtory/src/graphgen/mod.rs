@@ -1,4 +1,17 @@
 //! Provides functionality for generating graphs for testing and benchmarking.
+//!
+//! Besides the growth models ([`preferential_attachment`] and [`random_attachment`]), which
+//! extend an existing [`Graph`](crate::prelude::Graph), the static models ([`erdos_renyi`],
+//! [`watts_strogatz`], [`stochastic_block_model`] and [`configuration_model`]) and the temporal
+//! models ([`activity_driven`] and [`hawkes`]) write to any [`GraphSink`](synthesis::GraphSink),
+//! decorating edges as described by a [`Synthesis`](synthesis::Synthesis).
 
+pub mod activity_driven;
+pub mod configuration_model;
+pub mod erdos_renyi;
+pub mod hawkes;
 pub mod preferential_attachment;
 pub mod random_attachment;
+pub mod stochastic_block_model;
+pub mod synthesis;
+pub mod watts_strogatz;
@@ -0,0 +1,128 @@
+//! Generates a graph using the stochastic block model
+//!
+//! This function is a graph generation model based upon:
+//! Holland, Paul W., Kathryn Blackmond Laskey, and Samuel Leinhardt.
+//! "Stochastic blockmodels: First steps." Social Networks 5.2 (1983): 109-137.
+//!
+//! # Examples
+//!
+//! ```
+//! use raphtory::prelude::*;
+//! use raphtory::graphgen::{stochastic_block_model::stochastic_block_model, synthesis::Synthesis};
+//! let graph = Graph::new();
+//! let probabilities = vec![vec![0.1, 0.01], vec![0.01, 0.2]];
+//! stochastic_block_model(&graph, &[100, 50], &probabilities, &Synthesis::default(), None).unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    graphgen::synthesis::{check_probability, for_each_success, rng, GraphSink, Synthesis},
+};
+
+/// Adds one block of nodes per entry of `sizes`, with consecutive ids starting at 0 and node
+/// type `block_<i>`, and connects every ordered pair of distinct nodes in blocks `i` and `j`
+/// with probability `probabilities[i][j]`.
+///
+/// # Arguments
+/// * `sink` - The graph (or file) you wish to write nodes and edges to
+/// * `sizes` - The number of nodes in every block
+/// * `probabilities` - The square matrix of edge probabilities between blocks
+/// * `synthesis` - Times, layers and properties of the edges
+/// * `seed` - (Optional) An array of u8 bytes to be used as the input seed, Default None
+pub fn stochastic_block_model<S: GraphSink + ?Sized>(
+    sink: &S,
+    sizes: &[usize],
+    probabilities: &[Vec<f64>],
+    synthesis: &Synthesis,
+    seed: Option<[u8; 32]>,
+) -> Result<(), GraphError> {
+    if probabilities.len() != sizes.len()
+        || probabilities.iter().any(|row| row.len() != sizes.len())
+    {
+        return Err(GraphError::InvalidGeneratorParameter(format!(
+            "probabilities must be a {0}x{0} matrix",
+            sizes.len()
+        )));
+    }
+    for &p in probabilities.iter().flatten() {
+        check_probability("block probability", p)?;
+    }
+    synthesis.validate()?;
+    let mut rng = rng(seed);
+    let start = synthesis.start();
+
+    let mut offsets = Vec::with_capacity(sizes.len());
+    let mut next = 0u64;
+    for (block, &size) in sizes.iter().enumerate() {
+        offsets.push(next);
+        let node_type = format!("block_{block}");
+        for id in next..next + size as u64 {
+            sink.write_node(start, id, Some(&node_type))?;
+        }
+        next += size as u64;
+    }
+
+    for (a, row) in probabilities.iter().enumerate() {
+        for (b, &p) in row.iter().enumerate() {
+            let (size_a, size_b) = (sizes[a] as u64, sizes[b] as u64);
+            let (offset_a, offset_b) = (offsets[a], offsets[b]);
+            if a == b {
+                if size_a < 2 {
+                    continue;
+                }
+                for_each_success(&mut rng, size_a * (size_a - 1), p, |rng, index| {
+                    let src = index / (size_a - 1);
+                    let mut dst = index % (size_a - 1);
+                    if dst >= src {
+                        dst += 1;
+                    }
+                    let time = synthesis.static_time(rng);
+                    synthesis.add_edge(sink, rng, time, offset_a + src, offset_a + dst)
+                })?;
+            } else {
+                for_each_success(&mut rng, size_a * size_b, p, |rng, index| {
+                    let src = offset_a + index / size_b;
+                    let dst = offset_b + index % size_b;
+                    let time = synthesis.static_time(rng);
+                    synthesis.add_edge(sink, rng, time, src, dst)
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn disconnected_blocks() {
+        let graph = Graph::new();
+        let probabilities = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        stochastic_block_model(
+            &graph,
+            &[3, 4],
+            &probabilities,
+            &Synthesis::default(),
+            Some([1; 32]),
+        )
+        .unwrap();
+        assert_eq!(graph.count_nodes(), 7);
+        assert_eq!(graph.count_edges(), 3 * 2 + 4 * 3);
+        assert!(graph
+            .edges()
+            .iter()
+            .all(|e| e.src().node_type() == e.dst().node_type()));
+        assert_eq!(
+            graph.node(0).unwrap().node_type().map(|t| t.to_string()),
+            Some("block_0".to_owned())
+        );
+
+        assert!(
+            stochastic_block_model(&graph, &[3], &probabilities, &Synthesis::default(), None)
+                .is_err()
+        );
+    }
+}
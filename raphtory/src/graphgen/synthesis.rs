@@ -0,0 +1,312 @@
+//! Times, layers and properties of generated edges, and the destinations generators write to
+//!
+//! The generators in [`crate::graphgen`] decide which nodes are connected. A [`Synthesis`]
+//! decorates every generated edge update with a time (for models without a notion of time), a
+//! layer and properties, all drawn from the seeded random number generator of the model, so
+//! that the same seed always produces the same graph.
+//!
+//! Generated updates are written to a [`GraphSink`], which is either a graph or, with the `io`
+//! feature, a [`CsvSink`] writing them straight to disk.
+//!
+//! # Examples
+//!
+//! ```
+//! use raphtory::graphgen::{erdos_renyi::erdos_renyi, synthesis::{PropSynth, Synthesis}};
+//! use raphtory::prelude::*;
+//!
+//! let synthesis = Synthesis {
+//!     time_range: Some(0..1000),
+//!     layers: vec!["email".to_owned(), "chat".to_owned()],
+//!     properties: vec![("weight".to_owned(), PropSynth::Float(0.0..1.0))],
+//! };
+//! let graph = Graph::new();
+//! erdos_renyi(&graph, 100, 0.05, &synthesis, Some([1; 32])).unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    db::{api::mutation::AdditionOps, graph::views::deletion_graph::PersistentGraph},
+    prelude::*,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::ops::Range;
+
+/// How the values of a generated property are drawn
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropSynth {
+    /// The same value for every update
+    Constant(Prop),
+    /// An integer drawn uniformly from the range
+    Int(Range<i64>),
+    /// A float drawn uniformly from the range
+    Float(Range<f64>),
+    /// A value drawn uniformly from the list
+    Choice(Vec<Prop>),
+}
+
+impl PropSynth {
+    fn sample(&self, rng: &mut StdRng) -> Prop {
+        match self {
+            PropSynth::Constant(value) => value.clone(),
+            PropSynth::Int(range) => Prop::I64(rng.gen_range(range.clone())),
+            PropSynth::Float(range) => Prop::F64(rng.gen_range(range.clone())),
+            PropSynth::Choice(values) => values
+                .choose(rng)
+                .cloned()
+                .expect("choices are checked to be non-empty"),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            PropSynth::Constant(_) => false,
+            PropSynth::Int(range) => range.is_empty(),
+            PropSynth::Float(range) => range.is_empty(),
+            PropSynth::Choice(values) => values.is_empty(),
+        }
+    }
+}
+
+/// Decoration of generated edge updates
+///
+/// The default adds edges of static models at time 0, in the default layer and without
+/// properties.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Synthesis {
+    /// Range the times of edges of static models are drawn from uniformly. Temporal models
+    /// generate their own times and ignore it.
+    pub time_range: Option<Range<i64>>,
+    /// Layers edges are assigned to uniformly at random, the default layer if empty
+    pub layers: Vec<String>,
+    /// Properties added to every edge update
+    pub properties: Vec<(String, PropSynth)>,
+}
+
+impl Synthesis {
+    /// Check that all ranges and choices are non-empty
+    pub(crate) fn validate(&self) -> Result<(), GraphError> {
+        if self
+            .time_range
+            .as_ref()
+            .is_some_and(|range| range.is_empty())
+        {
+            return Err(GraphError::InvalidGeneratorParameter(
+                "time range is empty".to_owned(),
+            ));
+        }
+        if let Some((name, _)) = self.properties.iter().find(|(_, synth)| synth.is_empty()) {
+            return Err(GraphError::InvalidGeneratorParameter(format!(
+                "no values to draw for property '{name}'"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Time of the first nodes and edges
+    pub(crate) fn start(&self) -> i64 {
+        self.time_range.as_ref().map_or(0, |range| range.start)
+    }
+
+    /// A time for an edge of a static model
+    pub(crate) fn static_time(&self, rng: &mut StdRng) -> i64 {
+        match &self.time_range {
+            Some(range) => rng.gen_range(range.clone()),
+            None => 0,
+        }
+    }
+
+    /// Add an edge update at `time` with a random layer and properties
+    pub(crate) fn add_edge<S: GraphSink + ?Sized>(
+        &self,
+        sink: &S,
+        rng: &mut StdRng,
+        time: i64,
+        src: u64,
+        dst: u64,
+    ) -> Result<(), GraphError> {
+        let layer = self.layers.choose(rng).map(|layer| layer.as_str());
+        let props = self
+            .properties
+            .iter()
+            .map(|(name, synth)| (name.clone(), synth.sample(rng)))
+            .collect();
+        sink.write_edge(time, src, dst, layer, props)
+    }
+}
+
+/// Random number generator of a model, seeded with `seed` or from the operating system
+pub(crate) fn rng(seed: Option<[u8; 32]>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::from_seed(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Check that `value` is a probability
+pub(crate) fn check_probability(name: &str, value: f64) -> Result<(), GraphError> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(GraphError::InvalidGeneratorParameter(format!(
+            "{name} must be between 0 and 1, got {value}"
+        )))
+    }
+}
+
+/// Call `f` with every index in `0..total` that succeeds in an independent Bernoulli trial with
+/// probability `p`
+///
+/// Runs of failed trials are skipped by drawing their geometrically distributed length, so the
+/// cost is proportional to the number of successes rather than to `total`.
+pub(crate) fn for_each_success(
+    rng: &mut StdRng,
+    total: u64,
+    p: f64,
+    mut f: impl FnMut(&mut StdRng, u64) -> Result<(), GraphError>,
+) -> Result<(), GraphError> {
+    if p <= 0.0 {
+        return Ok(());
+    }
+    if p >= 1.0 {
+        for index in 0..total {
+            f(rng, index)?;
+        }
+        return Ok(());
+    }
+    let log_q = (1.0 - p).ln();
+    let mut index = 0u64;
+    while index < total {
+        let u: f64 = rng.gen();
+        let skip = ((1.0 - u).ln() / log_q).floor();
+        if !skip.is_finite() || skip >= (total - index) as f64 {
+            break;
+        }
+        index += skip as u64;
+        f(rng, index)?;
+        index += 1;
+    }
+    Ok(())
+}
+
+/// Destination of generated nodes and edges
+pub trait GraphSink {
+    fn write_node(&self, time: i64, id: u64, node_type: Option<&str>) -> Result<(), GraphError>;
+
+    fn write_edge(
+        &self,
+        time: i64,
+        src: u64,
+        dst: u64,
+        layer: Option<&str>,
+        props: Vec<(String, Prop)>,
+    ) -> Result<(), GraphError>;
+}
+
+macro_rules! impl_graph_sink {
+    ($graph:ty) => {
+        impl GraphSink for $graph {
+            fn write_node(
+                &self,
+                time: i64,
+                id: u64,
+                node_type: Option<&str>,
+            ) -> Result<(), GraphError> {
+                AdditionOps::add_node(self, time, id, NO_PROPS, node_type)?;
+                Ok(())
+            }
+
+            fn write_edge(
+                &self,
+                time: i64,
+                src: u64,
+                dst: u64,
+                layer: Option<&str>,
+                props: Vec<(String, Prop)>,
+            ) -> Result<(), GraphError> {
+                AdditionOps::add_edge(self, time, src, dst, props, layer)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_graph_sink!(Graph);
+impl_graph_sink!(PersistentGraph);
+
+#[cfg(feature = "io")]
+pub use csv_sink::CsvSink;
+
+#[cfg(feature = "io")]
+mod csv_sink {
+    use super::{GraphSink, Synthesis};
+    use crate::{core::utils::errors::GraphError, prelude::Prop};
+    use parking_lot::Mutex;
+    use std::io::Write;
+
+    /// Writes generated edges as CSV with the columns `src`, `dst`, `time`, `layer` and one
+    /// column per synthesised property
+    ///
+    /// The file can be loaded back with
+    /// [`EdgeListLoader`](crate::graph_loader::source::edge_list::EdgeListLoader), which adds
+    /// the layer column as a property. Nodes are not written, so nodes without edges are lost.
+    pub struct CsvSink<W: Write> {
+        writer: Mutex<csv::Writer<W>>,
+        properties: Vec<String>,
+    }
+
+    impl<W: Write> CsvSink<W> {
+        /// A sink for the edges decorated by `synthesis`, writing the header right away
+        pub fn new(writer: W, synthesis: &Synthesis) -> Result<Self, GraphError> {
+            let properties: Vec<_> = synthesis
+                .properties
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect();
+            let mut writer = csv::Writer::from_writer(writer);
+            let mut header = vec!["src", "dst", "time", "layer"];
+            header.extend(properties.iter().map(|name| name.as_str()));
+            writer.write_record(&header)?;
+            Ok(Self {
+                writer: Mutex::new(writer),
+                properties,
+            })
+        }
+
+        /// Flush the written edges
+        pub fn flush(&self) -> Result<(), GraphError> {
+            self.writer.lock().flush()?;
+            Ok(())
+        }
+    }
+
+    impl<W: Write> GraphSink for CsvSink<W> {
+        fn write_node(&self, _: i64, _: u64, _: Option<&str>) -> Result<(), GraphError> {
+            Ok(())
+        }
+
+        fn write_edge(
+            &self,
+            time: i64,
+            src: u64,
+            dst: u64,
+            layer: Option<&str>,
+            props: Vec<(String, Prop)>,
+        ) -> Result<(), GraphError> {
+            let mut row = vec![
+                src.to_string(),
+                dst.to_string(),
+                time.to_string(),
+                layer.unwrap_or_default().to_owned(),
+            ];
+            row.extend(self.properties.iter().map(|name| {
+                props
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.to_string())
+                    .unwrap_or_default()
+            }));
+            self.writer.lock().write_record(&row)?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Generates a graph using the Watts–Strogatz small-world model
+//!
+//! This function is a graph generation model based upon:
+//! Watts, Duncan J., and Steven H. Strogatz. "Collective dynamics of 'small-world' networks."
+//! Nature 393.6684 (1998): 440-442.
+//!
+//! # Examples
+//!
+//! ```
+//! use raphtory::prelude::*;
+//! use raphtory::graphgen::{synthesis::Synthesis, watts_strogatz::watts_strogatz};
+//! let graph = Graph::new();
+//! watts_strogatz(&graph, 1000, 4, 0.1, &Synthesis::default(), None).unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    graphgen::synthesis::{check_probability, rng, GraphSink, Synthesis},
+};
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Attempts to find a new neighbour for a rewired edge before keeping the original one
+const REWIRE_ATTEMPTS: usize = 100;
+
+fn undirected(a: u64, b: u64) -> (u64, u64) {
+    (a.min(b), a.max(b))
+}
+
+/// Adds `nodes` nodes with ids `0..nodes` arranged in a ring where every node is connected to
+/// its `k / 2` successors, then rewires every edge to a uniformly chosen node with probability
+/// `beta`, avoiding self-loops and duplicate edges.
+///
+/// # Arguments
+/// * `sink` - The graph (or file) you wish to write nodes and edges to
+/// * `nodes` - The number of nodes
+/// * `k` - The even number of neighbours of every node in the ring lattice
+/// * `beta` - The probability of rewiring every edge
+/// * `synthesis` - Times, layers and properties of the edges
+/// * `seed` - (Optional) An array of u8 bytes to be used as the input seed, Default None
+pub fn watts_strogatz<S: GraphSink + ?Sized>(
+    sink: &S,
+    nodes: usize,
+    k: usize,
+    beta: f64,
+    synthesis: &Synthesis,
+    seed: Option<[u8; 32]>,
+) -> Result<(), GraphError> {
+    check_probability("beta", beta)?;
+    if k % 2 != 0 || k >= nodes {
+        return Err(GraphError::InvalidGeneratorParameter(format!(
+            "k must be even and smaller than the number of nodes, got {k}"
+        )));
+    }
+    synthesis.validate()?;
+    let mut rng = rng(seed);
+    let start = synthesis.start();
+    let n = nodes as u64;
+    for id in 0..n {
+        sink.write_node(start, id, None)?;
+    }
+
+    let mut lattice = Vec::with_capacity(nodes * k / 2);
+    let mut edges = HashSet::with_capacity(nodes * k / 2);
+    for src in 0..n {
+        for offset in 1..=(k / 2) as u64 {
+            let dst = (src + offset) % n;
+            lattice.push((src, dst));
+            edges.insert(undirected(src, dst));
+        }
+    }
+
+    for (src, dst) in lattice {
+        let mut target = dst;
+        if rng.gen_bool(beta) {
+            let candidate = (0..REWIRE_ATTEMPTS)
+                .map(|_| rng.gen_range(0..n))
+                .find(|&w| w != src && !edges.contains(&undirected(src, w)));
+            if let Some(w) = candidate {
+                edges.remove(&undirected(src, dst));
+                edges.insert(undirected(src, w));
+                target = w;
+            }
+        }
+        let time = synthesis.static_time(&mut rng);
+        synthesis.add_edge(sink, &mut rng, time, src, target)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn ring_lattice_without_rewiring() {
+        let graph = Graph::new();
+        watts_strogatz(&graph, 20, 4, 0.0, &Synthesis::default(), Some([1; 32])).unwrap();
+        assert_eq!(graph.count_edges(), 40);
+        assert!(graph.nodes().iter().all(|node| node.degree() == 4));
+    }
+
+    #[test]
+    fn rewiring_keeps_edge_count() {
+        let graph = Graph::new();
+        watts_strogatz(&graph, 50, 6, 0.5, &Synthesis::default(), Some([3; 32])).unwrap();
+        assert_eq!(graph.count_edges(), 150);
+        assert!(watts_strogatz(&graph, 5, 3, 0.5, &Synthesis::default(), None).is_err());
+    }
+}
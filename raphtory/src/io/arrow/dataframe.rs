@@ -0,0 +1,168 @@
+use crate::core::utils::errors::GraphError;
+
+use polars_arrow::{
+    array::{Array, BooleanArray, PrimitiveArray, Utf8Array},
+    compute::cast::{self, CastOptions},
+    datatypes::{ArrowDataType as DataType, TimeUnit},
+    offset::Offset,
+    types::NativeType,
+};
+
+use itertools::Itertools;
+
+/// Columnar data made up of named columns split into chunks of arrow arrays
+///
+/// Every chunk holds one array per column, in the same order as `names`.
+#[derive(Debug, Clone)]
+pub struct PretendDF {
+    pub(crate) names: Vec<String>,
+    pub(crate) arrays: Vec<Vec<Box<dyn Array>>>,
+}
+
+impl PretendDF {
+    /// Create a dataframe from column names and chunks of arrays, e.g. the record batches of an
+    /// arrow stream
+    pub fn new(names: Vec<String>, arrays: Vec<Vec<Box<dyn Array>>>) -> Result<Self, GraphError> {
+        for chunk in arrays.iter() {
+            if chunk.len() != names.len() {
+                return Err(GraphError::LoadFailure(format!(
+                    "Expected {} columns in every chunk but found {}",
+                    names.len(),
+                    chunk.len()
+                )));
+            }
+            if !chunk.iter().map(|arr| arr.len()).all_equal() {
+                return Err(GraphError::LoadFailure(
+                    "All columns in a chunk must have the same length".to_string(),
+                ));
+            }
+        }
+        Ok(Self { names, arrays })
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Number of rows in the dataframe
+    pub fn len(&self) -> usize {
+        self.arrays
+            .iter()
+            .map(|chunk| chunk.first().map_or(0, |arr| arr.len()))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn check_cols_exist(&self, cols: &[&str]) -> Result<(), GraphError> {
+        let non_cols: Vec<&&str> = cols
+            .iter()
+            .filter(|c| !self.names.contains(&c.to_string()))
+            .collect();
+        if !non_cols.is_empty() {
+            return Err(GraphError::ColumnDoesNotExist(non_cols.iter().join(", ")));
+        }
+
+        Ok(())
+    }
+
+    /// Split the dataframe into single-chunk views of at most `chunk_size` rows
+    ///
+    /// Slicing arrow arrays does not copy the underlying data.
+    pub(crate) fn split(&self, chunk_size: usize) -> Vec<PretendDF> {
+        let chunk_size = chunk_size.max(1);
+        self.arrays
+            .iter()
+            .flat_map(|chunk| {
+                let len = chunk.first().map_or(0, |arr| arr.len());
+                (0..len).step_by(chunk_size).map(move |offset| {
+                    let length = chunk_size.min(len - offset);
+                    PretendDF {
+                        names: self.names.clone(),
+                        arrays: vec![chunk.iter().map(|arr| arr.sliced(offset, length)).collect()],
+                    }
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn iter_col<T: NativeType>(
+        &self,
+        name: &str,
+    ) -> Option<impl Iterator<Item = Option<&T>> + '_> {
+        let idx = self.names.iter().position(|n| n == name)?;
+
+        let _ = (&self.arrays[0])[idx]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<T>>()?;
+
+        let iter = self.arrays.iter().flat_map(move |arr| {
+            let arr = &arr[idx];
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+            arr.iter()
+        });
+
+        Some(iter)
+    }
+
+    pub fn utf8<O: Offset>(&self, name: &str) -> Option<impl Iterator<Item = Option<&str>> + '_> {
+        let idx = self.names.iter().position(|n| n == name)?;
+        // test that it's actually a utf8 array
+        let _ = (&self.arrays[0])[idx]
+            .as_any()
+            .downcast_ref::<Utf8Array<O>>()?;
+
+        let iter = self.arrays.iter().flat_map(move |arr| {
+            let arr = &arr[idx];
+            let arr = arr.as_any().downcast_ref::<Utf8Array<O>>().unwrap();
+            arr.iter()
+        });
+
+        Some(iter)
+    }
+
+    pub fn time_iter_col(&self, name: &str) -> Option<impl Iterator<Item = Option<i64>> + '_> {
+        let idx = self.names.iter().position(|n| n == name)?;
+
+        let _ = (&self.arrays[0])[idx]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()?;
+
+        let iter = self.arrays.iter().flat_map(move |arr| {
+            let arr = &arr[idx];
+            let arr = if let DataType::Timestamp(_, _) = arr.data_type() {
+                cast::cast(
+                    &*arr.clone(),
+                    &DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".to_string())),
+                    CastOptions::default(),
+                )
+                .unwrap()
+            } else {
+                arr.clone()
+            };
+
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<i64>>().unwrap();
+            arr.clone().into_iter()
+        });
+
+        Some(iter)
+    }
+
+    pub(crate) fn bool_col(&self, name: &str) -> Option<impl Iterator<Item = Option<bool>> + '_> {
+        let idx = self.names.iter().position(|n| n == name)?;
+
+        let _ = (&self.arrays[0])[idx]
+            .as_any()
+            .downcast_ref::<BooleanArray>()?;
+
+        let iter = self.arrays.iter().flat_map(move |arr| {
+            let arr = &arr[idx];
+            let arr = arr.as_any().downcast_ref::<BooleanArray>().unwrap();
+            arr.iter()
+        });
+
+        Some(iter)
+    }
+}
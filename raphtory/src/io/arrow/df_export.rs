@@ -0,0 +1,841 @@
+//! Export graphs and graph views as arrow tables
+//!
+//! [`GraphTables`] holds one dataframe per kind of update in a graph view. The tables use the
+//! column layout expected by the loaders in [`super::df_loaders`], so writing them with
+//! [`GraphTables::to_parquet`] and loading them back with [`GraphTables::load_into`] recreates
+//! the view as a new graph.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use raphtory::{
+//!     io::arrow::{
+//!         df_export::{GraphExportOps, GraphTables},
+//!         df_loaders::LoadOptions,
+//!     },
+//!     prelude::*,
+//! };
+//!
+//! let g = Graph::new();
+//! g.add_edge(1, 1, 2, [("weight", 1.0)], Some("trade")).unwrap();
+//! g.window(0, 10).to_parquet("/tmp/trades").unwrap();
+//!
+//! let copy = Graph::new();
+//! GraphTables::from_parquet("/tmp/trades")
+//!     .unwrap()
+//!     .load_into(&copy, &LoadOptions::default())
+//!     .unwrap();
+//! ```
+
+use crate::{
+    core::{utils::errors::GraphError, ArcStr, ArrayType, PropArray, PropType},
+    db::api::{
+        mutation::internal::{
+            InternalAdditionOps, InternalDeletionOps, InternalPropertyAdditionOps,
+        },
+        view::StaticGraphViewOps,
+    },
+    io::{
+        arrow::{
+            dataframe::PretendDF,
+            df_loaders::{
+                load_edge_deletions_from_arrow, load_edges_from_arrow, load_edges_props_from_df,
+                load_node_props_from_df, load_nodes_from_arrow, EdgeColumns, LoadOptions,
+                NodeColumns,
+            },
+            prop_handler::get_prop_rows,
+        },
+        parquet_loaders::{read_parquet_table, write_parquet},
+    },
+    prelude::*,
+};
+use polars_arrow::{
+    array::{
+        Array, BinaryArray, BooleanArray, FixedSizeListArray, ListArray, MutableFixedSizeListArray,
+        MutableListArray, MutablePrimitiveArray, PrimitiveArray, TryExtend, Utf8Array,
+    },
+    datatypes::{ArrowDataType as DataType, TimeUnit},
+    types::NativeType,
+};
+use std::{collections::BTreeMap, fs, path::Path, sync::Arc};
+
+const ID: &str = "id";
+const TIME: &str = "time";
+const NODE_TYPE: &str = "node_type";
+const SRC: &str = "src";
+const DST: &str = "dst";
+const LAYER: &str = "layer";
+
+/// The tables making up a graph view
+///
+/// Nodes are identified by their id if every node name is the string form of its id, and by
+/// their name otherwise. Edges in the default layer have a null `layer`. Every other column of
+/// a table is a property, with nulls where a row does not update that property.
+#[derive(Debug, Clone)]
+pub struct GraphTables {
+    /// Node updates: `id`, `time`, `node_type` and the temporal node properties, with one row
+    /// per point in the history of each node
+    pub nodes: PretendDF,
+    /// Constant node properties: `id` and one column per property
+    pub node_properties: PretendDF,
+    /// Exploded edge updates: `src`, `dst`, `time`, `layer` and the temporal edge properties
+    pub edges: PretendDF,
+    /// Edge deletions: `src`, `dst`, `time` and `layer`
+    pub edge_deletions: PretendDF,
+    /// Constant edge properties: `src`, `dst`, `layer` and one column per property
+    pub edge_properties: PretendDF,
+    /// Temporal graph properties: `time` and one column per property
+    pub graph_properties: PretendDF,
+    /// Constant graph properties as a single row
+    pub graph_constant_properties: PretendDF,
+}
+
+impl GraphTables {
+    /// Convert a graph view into tables
+    pub fn from_graph<'graph, G: GraphViewOps<'graph>>(graph: &G) -> Result<Self, GraphError> {
+        let numeric_ids = graph
+            .nodes()
+            .iter()
+            .all(|node| node.name() == node.id().to_string());
+        let (nodes, node_properties) = node_tables(graph, numeric_ids)?;
+        let (edges, edge_deletions, edge_properties) = edge_tables(graph, numeric_ids)?;
+        let (graph_properties, graph_constant_properties) = graph_tables(graph)?;
+        Ok(Self {
+            nodes,
+            node_properties,
+            edges,
+            edge_deletions,
+            edge_properties,
+            graph_properties,
+            graph_constant_properties,
+        })
+    }
+
+    fn tables(&self) -> [(&'static str, &PretendDF); 7] {
+        [
+            ("nodes", &self.nodes),
+            ("node_properties", &self.node_properties),
+            ("edges", &self.edges),
+            ("edge_deletions", &self.edge_deletions),
+            ("edge_properties", &self.edge_properties),
+            ("graph_properties", &self.graph_properties),
+            ("graph_constant_properties", &self.graph_constant_properties),
+        ]
+    }
+
+    /// Write every table to `<dir>/<table>.parquet`, creating `dir` if needed
+    ///
+    /// Tables without any columns, i.e. graphs without constant properties, are not written.
+    pub fn to_parquet(&self, dir: impl AsRef<Path>) -> Result<(), GraphError> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        for (name, table) in self.tables() {
+            if !table.names().is_empty() {
+                write_parquet(table, dir.join(format!("{name}.parquet")))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the tables written by [`GraphTables::to_parquet`]
+    pub fn from_parquet(dir: impl AsRef<Path>) -> Result<Self, GraphError> {
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(GraphError::LoadFailure(format!(
+                "{} is not a directory",
+                dir.display()
+            )));
+        }
+        let read = |name: &str| {
+            let path = dir.join(format!("{name}.parquet"));
+            if path.exists() {
+                read_parquet_table(path)
+            } else {
+                PretendDF::new(vec![], vec![])
+            }
+        };
+        Ok(Self {
+            nodes: read("nodes")?,
+            node_properties: read("node_properties")?,
+            edges: read("edges")?,
+            edge_deletions: read("edge_deletions")?,
+            edge_properties: read("edge_properties")?,
+            graph_properties: read("graph_properties")?,
+            graph_constant_properties: read("graph_constant_properties")?,
+        })
+    }
+
+    /// Add all updates in the tables to `graph`
+    pub fn load_into<
+        G: StaticGraphViewOps
+            + InternalAdditionOps
+            + InternalPropertyAdditionOps
+            + InternalDeletionOps,
+    >(
+        &self,
+        graph: &G,
+        options: &LoadOptions,
+    ) -> Result<(), GraphError> {
+        if !self.graph_properties.is_empty() {
+            let df = &self.graph_properties;
+            let times = df
+                .time_iter_col(TIME)
+                .ok_or_else(|| GraphError::ColumnDoesNotExist(TIME.to_string()))?;
+            let (rows, _) = get_prop_rows(df, Some(property_columns(df, &[TIME])), None)?;
+            for (time, row) in times.zip(rows) {
+                if let Some(time) = time {
+                    graph.add_properties(time, row)?;
+                }
+            }
+        }
+        if !self.graph_constant_properties.is_empty() {
+            let df = &self.graph_constant_properties;
+            let (_, rows) = get_prop_rows(df, None, Some(property_columns(df, &[])))?;
+            for row in rows {
+                graph.add_constant_properties(row)?;
+            }
+        }
+
+        let node_columns = NodeColumns::new(ID, TIME)
+            .properties(property_columns(&self.nodes, &[ID, TIME, NODE_TYPE]))
+            .node_type_column(NODE_TYPE);
+        load_nodes_from_arrow(graph, &self.nodes, &node_columns, options)?;
+        load_node_props_from_df(
+            &self.node_properties,
+            ID,
+            Some(property_columns(&self.node_properties, &[ID])),
+            None,
+            graph,
+            options,
+        )?;
+
+        let edge_columns = EdgeColumns::new(SRC, DST, TIME)
+            .properties(property_columns(&self.edges, &[SRC, DST, TIME, LAYER]))
+            .layer_column(LAYER);
+        load_edges_from_arrow(graph, &self.edges, &edge_columns, options)?;
+        load_edge_deletions_from_arrow(
+            graph,
+            &self.edge_deletions,
+            &EdgeColumns::new(SRC, DST, TIME).layer_column(LAYER),
+            options,
+        )?;
+        load_edges_props_from_df(
+            &self.edge_properties,
+            SRC,
+            DST,
+            Some(property_columns(&self.edge_properties, &[SRC, DST, LAYER])),
+            None,
+            Some(LAYER),
+            true,
+            graph,
+            options,
+        )
+    }
+}
+
+/// Export a graph view as arrow tables or parquet files
+pub trait GraphExportOps<'graph>: GraphViewOps<'graph> {
+    /// Convert the graph view into arrow tables
+    fn to_arrow(&self) -> Result<GraphTables, GraphError> {
+        GraphTables::from_graph(self)
+    }
+
+    /// Write the graph view to `dir` as one parquet file per table, see [`GraphTables`]
+    fn to_parquet(&self, dir: impl AsRef<Path>) -> Result<(), GraphError> {
+        self.to_arrow()?.to_parquet(dir)
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> GraphExportOps<'graph> for G {}
+
+fn node_tables<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    numeric_ids: bool,
+) -> Result<(PretendDF, PretendDF), GraphError> {
+    let mut ids = IdColumn::new(numeric_ids);
+    let mut times = vec![];
+    let mut node_types = vec![];
+    let mut props = PropColumns::default();
+    let mut const_ids = IdColumn::new(numeric_ids);
+    let mut const_props = PropColumns::default();
+
+    for node in graph.nodes().iter() {
+        let mut rows = rows_by_time(
+            node.properties()
+                .temporal()
+                .iter()
+                .map(|(name, values)| (name, values.iter().collect())),
+        );
+        for t in node.history() {
+            rows.entry(t).or_insert_with(|| vec![vec![]]);
+        }
+        if rows.is_empty() {
+            rows.insert(node.earliest_time().unwrap_or_default(), vec![vec![]]);
+        }
+        let node_type = node.node_type();
+        for (t, rows_at_t) in rows {
+            for row in rows_at_t {
+                ids.push(node.id(), || node.name());
+                times.push(t);
+                node_types.push(node_type.clone());
+                props.push(row);
+            }
+        }
+
+        let constant = node.properties().constant().iter().collect::<Vec<_>>();
+        if !constant.is_empty() {
+            const_ids.push(node.id(), || node.name());
+            const_props.push(constant);
+        }
+    }
+
+    let nodes = table(
+        vec![
+            (ID, ids.into_array()),
+            (TIME, PrimitiveArray::from_vec(times).boxed()),
+            (
+                NODE_TYPE,
+                node_types.into_iter().collect::<Utf8Array<i64>>().boxed(),
+            ),
+        ],
+        props,
+    )?;
+    let node_properties = table(vec![(ID, const_ids.into_array())], const_props)?;
+    Ok((nodes, node_properties))
+}
+
+fn edge_tables<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    numeric_ids: bool,
+) -> Result<(PretendDF, PretendDF, PretendDF), GraphError> {
+    let mut updates = EdgeColumnsBuilder::new(numeric_ids);
+    let mut props = PropColumns::default();
+    let mut deletions = EdgeColumnsBuilder::new(numeric_ids);
+    let mut const_edges = EdgeColumnsBuilder::new(numeric_ids);
+    let mut const_props = PropColumns::default();
+
+    for layer in graph.unique_layers() {
+        let layer_graph = graph.layers(layer.clone())?;
+        let layer = (layer != "_default").then_some(layer);
+        for edge in layer_graph.edges() {
+            let (src, dst) = (edge.src(), edge.dst());
+            let src_name = || src.name();
+            let dst_name = || dst.name();
+            for exploded in edge.explode() {
+                updates.push(src.id(), src_name, dst.id(), dst_name, &layer);
+                updates.times.push(exploded.time()?);
+                props.push(exploded.properties().temporal().iter_latest());
+            }
+            for t in edge.deletions() {
+                deletions.push(src.id(), src_name, dst.id(), dst_name, &layer);
+                deletions.times.push(t);
+            }
+            let constant = edge.properties().constant().iter().collect::<Vec<_>>();
+            if !constant.is_empty() {
+                const_edges.push(src.id(), src_name, dst.id(), dst_name, &layer);
+                const_props.push(constant);
+            }
+        }
+    }
+
+    let edges = table(updates.into_columns(true), props)?;
+    let edge_deletions = table(deletions.into_columns(true), PropColumns::default())?;
+    let edge_properties = table(const_edges.into_columns(false), const_props)?;
+    Ok((edges, edge_deletions, edge_properties))
+}
+
+fn graph_tables<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+) -> Result<(PretendDF, PretendDF), GraphError> {
+    let properties = graph.properties();
+    let rows = rows_by_time(
+        properties
+            .temporal()
+            .iter()
+            .map(|(name, values)| (name, values.iter().collect())),
+    );
+    let mut times = vec![];
+    let mut props = PropColumns::default();
+    for (t, rows_at_t) in rows {
+        for row in rows_at_t {
+            times.push(t);
+            props.push(row);
+        }
+    }
+    let graph_properties = table(vec![(TIME, PrimitiveArray::from_vec(times).boxed())], props)?;
+
+    let mut const_props = PropColumns::default();
+    let constant = properties.constant().iter().collect::<Vec<_>>();
+    if !constant.is_empty() {
+        const_props.push(constant);
+    }
+    let graph_constant_properties = table(vec![], const_props)?;
+    Ok((graph_properties, graph_constant_properties))
+}
+
+/// Group temporal property updates into rows by time
+///
+/// Updates of different properties at the same time share a row. A property updated more than
+/// once at the same time gets an extra row for each repeated update.
+fn rows_by_time(
+    props: impl Iterator<Item = (ArcStr, Vec<(i64, Prop)>)>,
+) -> BTreeMap<i64, Vec<Vec<(ArcStr, Prop)>>> {
+    let mut rows: BTreeMap<i64, Vec<Vec<(ArcStr, Prop)>>> = BTreeMap::new();
+    for (name, updates) in props {
+        let mut previous = None;
+        for (t, prop) in updates {
+            let index = match previous {
+                Some((prev_t, prev_index)) if prev_t == t => prev_index + 1,
+                _ => 0,
+            };
+            previous = Some((t, index));
+            let rows_at_t = rows.entry(t).or_default();
+            if rows_at_t.len() <= index {
+                rows_at_t.push(vec![]);
+            }
+            rows_at_t[index].push((name.clone(), prop));
+        }
+    }
+    rows
+}
+
+/// Names of all columns of `df` except `fixed`
+fn property_columns<'a>(df: &'a PretendDF, fixed: &[&str]) -> Vec<&'a str> {
+    df.names()
+        .iter()
+        .map(|name| name.as_str())
+        .filter(|name| !fixed.contains(name))
+        .collect()
+}
+
+fn table(fixed: Vec<(&str, Box<dyn Array>)>, props: PropColumns) -> Result<PretendDF, GraphError> {
+    let reserved = fixed.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    let props = props.into_columns(&reserved)?;
+    let (names, arrays): (Vec<_>, Vec<_>) = fixed
+        .into_iter()
+        .map(|(name, array)| (name.to_string(), array))
+        .chain(props)
+        .unzip();
+    PretendDF::new(names, vec![arrays])
+}
+
+/// Node ids, or node names if not all nodes are identified by their id
+struct IdColumn {
+    numeric: bool,
+    ids: Vec<u64>,
+    names: Vec<String>,
+}
+
+impl IdColumn {
+    fn new(numeric: bool) -> Self {
+        Self {
+            numeric,
+            ids: vec![],
+            names: vec![],
+        }
+    }
+
+    fn push(&mut self, id: u64, name: impl FnOnce() -> String) {
+        if self.numeric {
+            self.ids.push(id);
+        } else {
+            self.names.push(name());
+        }
+    }
+
+    fn into_array(self) -> Box<dyn Array> {
+        if self.numeric {
+            PrimitiveArray::from_vec(self.ids).boxed()
+        } else {
+            Utf8Array::<i64>::from_iter_values(self.names.iter()).boxed()
+        }
+    }
+}
+
+struct EdgeColumnsBuilder {
+    src: IdColumn,
+    dst: IdColumn,
+    times: Vec<i64>,
+    layers: Vec<Option<ArcStr>>,
+}
+
+impl EdgeColumnsBuilder {
+    fn new(numeric_ids: bool) -> Self {
+        Self {
+            src: IdColumn::new(numeric_ids),
+            dst: IdColumn::new(numeric_ids),
+            times: vec![],
+            layers: vec![],
+        }
+    }
+
+    fn push(
+        &mut self,
+        src: u64,
+        src_name: impl FnOnce() -> String,
+        dst: u64,
+        dst_name: impl FnOnce() -> String,
+        layer: &Option<ArcStr>,
+    ) {
+        self.src.push(src, src_name);
+        self.dst.push(dst, dst_name);
+        self.layers.push(layer.clone());
+    }
+
+    fn into_columns(self, with_time: bool) -> Vec<(&'static str, Box<dyn Array>)> {
+        let mut columns: Vec<(&'static str, Box<dyn Array>)> =
+            vec![(SRC, self.src.into_array()), (DST, self.dst.into_array())];
+        if with_time {
+            columns.push((TIME, PrimitiveArray::from_vec(self.times).boxed()));
+        }
+        columns.push((
+            LAYER,
+            self.layers.into_iter().collect::<Utf8Array<i64>>().boxed(),
+        ));
+        columns
+    }
+}
+
+/// Property values of the rows of a table, one column per property name
+#[derive(Default)]
+struct PropColumns {
+    len: usize,
+    columns: BTreeMap<ArcStr, Vec<Option<Prop>>>,
+}
+
+impl PropColumns {
+    fn push(&mut self, row: impl IntoIterator<Item = (ArcStr, Prop)>) {
+        for (name, prop) in row {
+            let len = self.len;
+            self.columns
+                .entry(name)
+                .or_insert_with(|| vec![None; len])
+                .push(Some(prop));
+        }
+        self.len += 1;
+        for column in self.columns.values_mut() {
+            column.resize(self.len, None);
+        }
+    }
+
+    /// Convert the properties to arrow arrays, dropping columns without any values
+    fn into_columns(self, reserved: &[&str]) -> Result<Vec<(String, Box<dyn Array>)>, GraphError> {
+        let mut columns = vec![];
+        for (name, values) in self.columns {
+            if reserved.contains(&name.as_ref()) {
+                return Err(GraphError::ExportFailure(format!(
+                    "Property {name} has the same name as the {name} column"
+                )));
+            }
+            if let Some(array) = prop_array(&name, values)? {
+                columns.push((name.to_string(), array));
+            }
+        }
+        Ok(columns)
+    }
+}
+
+/// Convert property values to an arrow array, or `None` if all values are null
+///
+/// Lists, maps, graphs and documents have no arrow equivalent that the loaders understand and
+/// are written as their string representation.
+fn prop_array(name: &str, values: Vec<Option<Prop>>) -> Result<Option<Box<dyn Array>>, GraphError> {
+    let mut dtypes = values.iter().flatten().map(|prop| prop.dtype());
+    let Some(dtype) = dtypes.next() else {
+        return Ok(None);
+    };
+    if let Some(actual) = dtypes.find(|other| other != &dtype) {
+        return Err(GraphError::PropertyTypeError {
+            name: name.to_string(),
+            expected: dtype,
+            actual,
+        });
+    }
+
+    let array: Box<dyn Array> = match dtype {
+        PropType::Str => values
+            .into_iter()
+            .map(|v| v.and_then(|v| v.into_str()))
+            .collect::<Utf8Array<i64>>()
+            .boxed(),
+        PropType::U8 => primitive_array(values, Prop::into_u8),
+        PropType::U16 => primitive_array(values, Prop::into_u16),
+        PropType::U32 => primitive_array(values, Prop::into_u32),
+        PropType::U64 => primitive_array(values, Prop::into_u64),
+        PropType::I8 => primitive_array(values, Prop::into_i8),
+        PropType::I16 => primitive_array(values, Prop::into_i16),
+        PropType::I32 => primitive_array(values, Prop::into_i32),
+        PropType::I64 => primitive_array(values, Prop::into_i64),
+        PropType::F32 => primitive_array(values, Prop::into_f32),
+        PropType::F64 => primitive_array(values, Prop::into_f64),
+        PropType::Bool => values
+            .into_iter()
+            .map(|v| v.and_then(|v| v.into_bool()))
+            .collect::<BooleanArray>()
+            .boxed(),
+        PropType::DTime => timestamp_array(values, Some("UTC".to_string())),
+        PropType::NDTime => timestamp_array(values, None),
+        PropType::Decimal => {
+            let decimals = values
+                .into_iter()
+                .map(|v| v.and_then(|v| v.into_decimal()))
+                .collect::<Vec<_>>();
+            let scale = decimals
+                .iter()
+                .flatten()
+                .map(|d| d.scale())
+                .max()
+                .unwrap_or_default();
+            let mantissas = decimals
+                .into_iter()
+                .map(|d| {
+                    d.map(|mut d| {
+                        d.rescale(scale);
+                        d.mantissa()
+                    })
+                })
+                .collect::<Vec<_>>();
+            PrimitiveArray::<i128>::from(mantissas)
+                .to(DataType::Decimal(38, scale as usize))
+                .boxed()
+        }
+        PropType::Bytes => values
+            .into_iter()
+            .map(|v| v.and_then(|v| v.into_bytes()))
+            .collect::<BinaryArray<i64>>()
+            .boxed(),
+        PropType::Array(array_type) => {
+            let arrays = values.into_iter().map(|v| v.and_then(|v| v.into_array()));
+            match array_type {
+                ArrayType::I32 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::I32(values)) => Some(values),
+                    _ => None,
+                }))?,
+                ArrayType::I64 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::I64(values)) => Some(values),
+                    _ => None,
+                }))?,
+                ArrayType::F32 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::F32(values)) => Some(values),
+                    _ => None,
+                }))?,
+                ArrayType::F64 => list_array(arrays.map(|a| match a {
+                    Some(PropArray::F64(values)) => Some(values),
+                    _ => None,
+                }))?,
+            }
+        }
+        PropType::List
+        | PropType::Map
+        | PropType::Graph
+        | PropType::PersistentGraph
+        | PropType::Document
+        | PropType::Empty => values
+            .into_iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect::<Utf8Array<i64>>()
+            .boxed(),
+    };
+    Ok(Some(array))
+}
+
+fn primitive_array<T: NativeType>(
+    values: Vec<Option<Prop>>,
+    unwrap: impl Fn(Prop) -> Option<T>,
+) -> Box<dyn Array> {
+    let values = values
+        .into_iter()
+        .map(|v| v.and_then(&unwrap))
+        .collect::<Vec<_>>();
+    PrimitiveArray::<T>::from(values).boxed()
+}
+
+fn timestamp_array(values: Vec<Option<Prop>>, timezone: Option<String>) -> Box<dyn Array> {
+    let millis = values
+        .into_iter()
+        .map(|v| match v {
+            Some(Prop::DTime(dt)) => Some(dt.timestamp_millis()),
+            Some(Prop::NDTime(dt)) => Some(dt.and_utc().timestamp_millis()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    PrimitiveArray::<i64>::from(millis)
+        .to(DataType::Timestamp(TimeUnit::Millisecond, timezone))
+        .boxed()
+}
+
+/// Arrays of equal length become fixed-size lists, which load back as arrays. Otherwise they
+/// become lists, which load back as lists of numbers.
+fn list_array<T: NativeType>(
+    arrays: impl Iterator<Item = Option<Arc<[T]>>>,
+) -> Result<Box<dyn Array>, GraphError> {
+    let arrays = arrays.collect::<Vec<_>>();
+    let rows = arrays
+        .iter()
+        .map(|a| a.as_ref().map(|a| a.iter().map(|v| Some(*v))));
+    let mut lengths = arrays.iter().flatten().map(|a| a.len());
+    let fixed_size = lengths
+        .next()
+        .filter(|size| lengths.all(|len| len == *size));
+    let export_error = |e| GraphError::ExportFailure(format!("{e}"));
+    match fixed_size {
+        Some(size) => {
+            let mut array = MutableFixedSizeListArray::new(MutablePrimitiveArray::<T>::new(), size);
+            array.try_extend(rows).map_err(export_error)?;
+            Ok(FixedSizeListArray::from(array).boxed())
+        }
+        None => {
+            let mut array = MutableListArray::<i32, MutablePrimitiveArray<T>>::new();
+            array.try_extend(rows).map_err(export_error)?;
+            Ok(ListArray::<i32>::from(array).boxed())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db::graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph};
+    use chrono::{DateTime, Utc};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    fn test_graph() -> PersistentGraph {
+        let g = PersistentGraph::new();
+        let time = DateTime::<Utc>::from_timestamp_millis(1_700_000_000_000).unwrap();
+        g.add_node(0, "alice", [("score", Prop::F64(1.5))], Some("person"))
+            .unwrap();
+        g.add_node(0, "alice", [("score", Prop::F64(2.5))], Some("person"))
+            .unwrap();
+        g.add_node(3, "bob", [("active", Prop::Bool(true))], None)
+            .unwrap();
+        g.node("alice")
+            .unwrap()
+            .add_constant_properties([("joined", Prop::DTime(time))])
+            .unwrap();
+        g.add_edge(
+            1,
+            "alice",
+            "bob",
+            [
+                ("amount", Prop::Decimal(Decimal::from_str("12.50").unwrap())),
+                ("tag", Prop::str("a")),
+            ],
+            Some("pays"),
+        )
+        .unwrap();
+        g.add_edge(
+            2,
+            "alice",
+            "bob",
+            [(
+                "embedding",
+                Prop::Array(PropArray::F32(vec![1.0, 2.0].into())),
+            )],
+            None,
+        )
+        .unwrap();
+        g.add_edge(
+            4,
+            "bob",
+            "carol",
+            [("bytes", Prop::bytes(&b"xyz"[..]))],
+            None,
+        )
+        .unwrap();
+        g.delete_edge(5, "bob", "carol", None).unwrap();
+        g.edge("alice", "bob")
+            .unwrap()
+            .add_constant_properties([("currency", Prop::str("GBP"))], Some("pays"))
+            .unwrap();
+        g.add_properties(1, [("version", Prop::I64(1))]).unwrap();
+        g.add_constant_properties([("name", Prop::str("payments"))])
+            .unwrap();
+        g
+    }
+
+    fn assert_round_trip(g: &PersistentGraph, copy: &PersistentGraph) {
+        assert_graph_equal(g, copy);
+        let alice = copy.node("alice").unwrap();
+        assert_eq!(alice.node_type().unwrap().as_ref(), "person");
+        assert_eq!(
+            alice.properties().temporal().get("score").unwrap().values(),
+            vec![Prop::F64(1.5), Prop::F64(2.5)]
+        );
+        assert_eq!(
+            alice.properties().get("joined"),
+            g.node("alice").unwrap().properties().get("joined")
+        );
+        let pays = copy.layers("pays").unwrap().edge("alice", "bob").unwrap();
+        assert_eq!(pays.properties().get("currency"), Some(Prop::str("GBP")));
+        assert_eq!(
+            pays.properties().get("amount"),
+            Some(Prop::Decimal(Decimal::from_str("12.50").unwrap()))
+        );
+        assert_eq!(
+            copy.default_layer()
+                .edge("alice", "bob")
+                .unwrap()
+                .properties()
+                .get("embedding"),
+            Some(Prop::Array(PropArray::F32(vec![1.0, 2.0].into())))
+        );
+        assert_eq!(
+            copy.edge("bob", "carol").unwrap().deletions(),
+            g.edge("bob", "carol").unwrap().deletions()
+        );
+        assert_eq!(copy.properties().get("version"), Some(Prop::I64(1)));
+        assert_eq!(copy.properties().get("name"), Some(Prop::str("payments")));
+    }
+
+    #[test]
+    fn arrow_round_trip() {
+        let g = test_graph();
+        let tables = g.to_arrow().unwrap();
+        assert_eq!(tables.edges.len(), 3);
+        assert_eq!(tables.edge_deletions.len(), 1);
+        let copy = PersistentGraph::new();
+        tables.load_into(&copy, &LoadOptions::sequential()).unwrap();
+        assert_round_trip(&g, &copy);
+    }
+
+    #[test]
+    fn parquet_round_trip() {
+        let g = test_graph();
+        let dir = TempDir::new().unwrap();
+        g.to_parquet(dir.path()).unwrap();
+        let copy = PersistentGraph::new();
+        GraphTables::from_parquet(dir.path())
+            .unwrap()
+            .load_into(&copy, &LoadOptions::default())
+            .unwrap();
+        assert_round_trip(&g, &copy);
+    }
+
+    #[test]
+    fn window_exports_only_updates_in_view() {
+        let g = Graph::new();
+        g.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        g.add_edge(5, 2, 3, [("w", 1u64)], None).unwrap();
+        let tables = g.window(3, 10).to_arrow().unwrap();
+        assert_eq!(tables.edges.len(), 1);
+        assert_eq!(
+            tables.edges.utf8::<i64>("layer").unwrap().next(),
+            Some(None)
+        );
+        assert!(tables.edges.iter_col::<u64>("src").is_some());
+
+        let copy = Graph::new();
+        tables.load_into(&copy, &LoadOptions::default()).unwrap();
+        assert_graph_equal(&g.window(3, 10), &copy);
+        assert!(!copy.has_node(1));
+    }
+
+    #[test]
+    fn property_named_like_a_column_is_an_error() {
+        let g = Graph::new();
+        g.add_edge(1, 1, 2, [("time", 1i64)], None).unwrap();
+        assert!(matches!(g.to_arrow(), Err(GraphError::ExportFailure(_))));
+    }
+}
@@ -0,0 +1,1090 @@
+use crate::{
+    core::{
+        entities::nodes::{input_node::InputNode, node_ref::AsNodeRef},
+        utils::{
+            dead_letter::{DeadLetters, RejectedRecord},
+            dedup::Deduplicator,
+            errors::GraphError,
+        },
+    },
+    db::api::{
+        mutation::{
+            delete_edge_at,
+            internal::{InternalAdditionOps, InternalDeletionOps, InternalPropertyAdditionOps},
+        },
+        view::StaticGraphViewOps,
+    },
+    io::arrow::{
+        dataframe::PretendDF,
+        prop_handler::{get_prop_rows, lift_layer},
+    },
+    prelude::*,
+};
+use kdam::{tqdm, BarExt};
+use parking_lot::Mutex;
+use rayon::prelude::*;
+use std::{collections::HashMap, iter};
+
+/// Options controlling how a dataframe is ingested
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadOptions {
+    /// Ingest chunks of rows in parallel. Updates within a chunk are always applied in order,
+    /// but updates with the same timestamp in different chunks may be applied in any order.
+    pub parallel: bool,
+    /// Maximum number of rows processed as a single unit of work
+    pub chunk_size: usize,
+    /// Show a progress bar while loading
+    pub progress: bool,
+    /// Handling of rows that cannot be loaded, by default the load fails on the first bad row
+    pub dead_letters: DeadLetters,
+    /// Skip edge updates that were loaded before, counting them as duplicates
+    pub deduplicator: Option<Deduplicator>,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            parallel: true,
+            chunk_size: 100_000,
+            progress: false,
+            dead_letters: DeadLetters::default(),
+            deduplicator: None,
+        }
+    }
+}
+
+impl LoadOptions {
+    /// Ordered ingestion with a progress bar, as used by the python loaders
+    pub fn sequential() -> Self {
+        Self {
+            parallel: false,
+            progress: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    pub fn progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    pub fn dead_letters(mut self, dead_letters: DeadLetters) -> Self {
+        self.dead_letters = dead_letters;
+        self
+    }
+
+    pub fn deduplicate(mut self, deduplicator: Deduplicator) -> Self {
+        self.deduplicator = Some(deduplicator);
+        self
+    }
+}
+
+/// Mapping from dataframe columns to node updates
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeColumns {
+    pub id: String,
+    pub time: String,
+    pub properties: Vec<String>,
+    pub constant_properties: Vec<String>,
+    pub shared_constant_properties: HashMap<String, Prop>,
+    pub node_type: Option<String>,
+    /// If true, `node_type` is the name of a column holding the node type of each row
+    pub node_type_in_df: bool,
+}
+
+impl NodeColumns {
+    pub fn new(id: impl Into<String>, time: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            time: time.into(),
+            properties: vec![],
+            constant_properties: vec![],
+            shared_constant_properties: HashMap::new(),
+            node_type: None,
+            node_type_in_df: false,
+        }
+    }
+
+    /// Columns loaded as temporal properties
+    pub fn properties<S: Into<String>>(mut self, columns: impl IntoIterator<Item = S>) -> Self {
+        self.properties = columns.into_iter().map(|c| c.into()).collect();
+        self
+    }
+
+    /// Columns loaded as constant properties
+    pub fn constant_properties<S: Into<String>>(
+        mut self,
+        columns: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.constant_properties = columns.into_iter().map(|c| c.into()).collect();
+        self
+    }
+
+    /// Constant properties added to every node
+    pub fn shared_constant_properties<S: Into<String>, P: Into<Prop>>(
+        mut self,
+        properties: impl IntoIterator<Item = (S, P)>,
+    ) -> Self {
+        self.shared_constant_properties = properties
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self
+    }
+
+    /// Use the same node type for every node
+    pub fn node_type(mut self, node_type: impl Into<String>) -> Self {
+        self.node_type = Some(node_type.into());
+        self.node_type_in_df = false;
+        self
+    }
+
+    /// Read the node type of each row from a column
+    pub fn node_type_column(mut self, column: impl Into<String>) -> Self {
+        self.node_type = Some(column.into());
+        self.node_type_in_df = true;
+        self
+    }
+
+    pub(crate) fn columns(&self) -> Vec<&str> {
+        let mut cols = vec![self.id.as_str(), self.time.as_str()];
+        cols.extend(self.properties.iter().map(|c| c.as_str()));
+        cols.extend(self.constant_properties.iter().map(|c| c.as_str()));
+        if self.node_type_in_df {
+            cols.extend(self.node_type.as_deref());
+        }
+        cols
+    }
+}
+
+/// Mapping from dataframe columns to edge updates
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeColumns {
+    pub src: String,
+    pub dst: String,
+    pub time: String,
+    pub properties: Vec<String>,
+    pub constant_properties: Vec<String>,
+    pub shared_constant_properties: HashMap<String, Prop>,
+    pub layer: Option<String>,
+    /// If true, `layer` is the name of a column holding the layer of each row
+    pub layer_in_df: bool,
+    /// Boolean column marking rows that are edge deletions rather than additions
+    pub deletions: Option<String>,
+}
+
+impl EdgeColumns {
+    pub fn new(src: impl Into<String>, dst: impl Into<String>, time: impl Into<String>) -> Self {
+        Self {
+            src: src.into(),
+            dst: dst.into(),
+            time: time.into(),
+            properties: vec![],
+            constant_properties: vec![],
+            shared_constant_properties: HashMap::new(),
+            layer: None,
+            layer_in_df: false,
+            deletions: None,
+        }
+    }
+
+    /// Columns loaded as temporal properties
+    pub fn properties<S: Into<String>>(mut self, columns: impl IntoIterator<Item = S>) -> Self {
+        self.properties = columns.into_iter().map(|c| c.into()).collect();
+        self
+    }
+
+    /// Columns loaded as constant properties
+    pub fn constant_properties<S: Into<String>>(
+        mut self,
+        columns: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.constant_properties = columns.into_iter().map(|c| c.into()).collect();
+        self
+    }
+
+    /// Constant properties added to every edge
+    pub fn shared_constant_properties<S: Into<String>, P: Into<Prop>>(
+        mut self,
+        properties: impl IntoIterator<Item = (S, P)>,
+    ) -> Self {
+        self.shared_constant_properties = properties
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self
+    }
+
+    /// Add every edge to the same layer
+    pub fn layer(mut self, layer: impl Into<String>) -> Self {
+        self.layer = Some(layer.into());
+        self.layer_in_df = false;
+        self
+    }
+
+    /// Read the layer of each row from a column
+    pub fn layer_column(mut self, column: impl Into<String>) -> Self {
+        self.layer = Some(column.into());
+        self.layer_in_df = true;
+        self
+    }
+
+    /// Treat rows where the boolean `column` is true as edge deletions
+    pub fn deletions(mut self, column: impl Into<String>) -> Self {
+        self.deletions = Some(column.into());
+        self
+    }
+
+    pub(crate) fn columns(&self) -> Vec<&str> {
+        let mut cols = vec![self.src.as_str(), self.dst.as_str(), self.time.as_str()];
+        cols.extend(self.properties.iter().map(|c| c.as_str()));
+        cols.extend(self.constant_properties.iter().map(|c| c.as_str()));
+        if self.layer_in_df {
+            cols.extend(self.layer.as_deref());
+        }
+        cols.extend(self.deletions.as_deref());
+        cols
+    }
+}
+
+/// Load node updates from a dataframe
+///
+/// # Example
+///
+/// ```
+/// use polars_arrow::array::{PrimitiveArray, Utf8Array};
+/// use raphtory::{
+///     io::arrow::{
+///         dataframe::PretendDF,
+///         df_loaders::{load_nodes_from_arrow, LoadOptions, NodeColumns},
+///     },
+///     prelude::*,
+/// };
+///
+/// let df = PretendDF::new(
+///     vec!["id".to_string(), "time".to_string(), "kind".to_string()],
+///     vec![vec![
+///         Box::new(PrimitiveArray::<u64>::from_vec(vec![1, 2])),
+///         Box::new(PrimitiveArray::<i64>::from_vec(vec![10, 20])),
+///         Box::new(Utf8Array::<i32>::from_slice(["user", "bot"])),
+///     ]],
+/// )
+/// .unwrap();
+/// let g = Graph::new();
+/// let columns = NodeColumns::new("id", "time").node_type_column("kind");
+/// load_nodes_from_arrow(&g, &df, &columns, &LoadOptions::default()).unwrap();
+/// assert_eq!(g.node(2).unwrap().node_type().unwrap().as_ref(), "bot");
+/// ```
+pub fn load_nodes_from_arrow<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+>(
+    graph: &G,
+    df: &PretendDF,
+    columns: &NodeColumns,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    df.check_cols_exist(&columns.columns())?;
+    load_nodes_from_df(
+        df,
+        &columns.id,
+        &columns.time,
+        Some(columns.properties.iter().map(|c| c.as_str()).collect()),
+        Some(
+            columns
+                .constant_properties
+                .iter()
+                .map(|c| c.as_str())
+                .collect(),
+        ),
+        Some(columns.shared_constant_properties.clone()),
+        columns.node_type.as_deref(),
+        columns.node_type_in_df,
+        graph,
+        options,
+    )
+}
+
+/// Load edge additions, and deletions if `columns` has a deletion column, from a dataframe
+///
+/// Deletions are recorded on any graph but are only visible through a `PersistentGraph`.
+pub fn load_edges_from_arrow<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps + InternalDeletionOps,
+>(
+    graph: &G,
+    df: &PretendDF,
+    columns: &EdgeColumns,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    df.check_cols_exist(&columns.columns())?;
+    load_edges_from_df(
+        df,
+        &columns.src,
+        &columns.dst,
+        &columns.time,
+        Some(columns.properties.iter().map(|c| c.as_str()).collect()),
+        Some(
+            columns
+                .constant_properties
+                .iter()
+                .map(|c| c.as_str())
+                .collect(),
+        ),
+        Some(columns.shared_constant_properties.clone()),
+        columns.layer.as_deref(),
+        columns.layer_in_df,
+        columns.deletions.as_deref(),
+        graph,
+        options,
+    )
+}
+
+/// Load every row of a dataframe as an edge deletion. Property columns are ignored.
+pub fn load_edge_deletions_from_arrow<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalDeletionOps,
+>(
+    graph: &G,
+    df: &PretendDF,
+    columns: &EdgeColumns,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    let mut cols = vec![
+        columns.src.as_str(),
+        columns.dst.as_str(),
+        columns.time.as_str(),
+    ];
+    if columns.layer_in_df {
+        cols.extend(columns.layer.as_deref());
+    }
+    df.check_cols_exist(&cols)?;
+    load_edges_deletions_from_df(
+        df,
+        &columns.src,
+        &columns.dst,
+        &columns.time,
+        columns.layer.as_deref(),
+        columns.layer_in_df,
+        graph,
+        options,
+    )
+}
+
+/// Split `df` into chunks and apply `f` to each of them and the index of its first row, in
+/// parallel if requested. `f` returns the number of rows it loaded.
+fn for_each_chunk<F>(
+    df: &PretendDF,
+    desc: &'static str,
+    options: &LoadOptions,
+    f: F,
+) -> Result<(), GraphError>
+where
+    F: Fn(&PretendDF, usize) -> Result<usize, GraphError> + Send + Sync,
+{
+    let chunks = df.split(options.chunk_size);
+    let offsets: Vec<_> = chunks
+        .iter()
+        .scan(0, |offset, chunk| {
+            let start = *offset;
+            *offset += chunk.len();
+            Some(start)
+        })
+        .collect();
+    let bar = options.progress.then(|| {
+        Mutex::new(tqdm!(
+            desc = desc,
+            total = df.len(),
+            animation = kdam::Animation::FillUp,
+            unit_scale = true
+        ))
+    });
+    let process = |(chunk, offset): (&PretendDF, &usize)| -> Result<(), GraphError> {
+        let loaded = f(chunk, *offset)?;
+        options.dead_letters.loaded(loaded);
+        if let Some(bar) = &bar {
+            let _ = bar.lock().update(chunk.len());
+        }
+        Ok(())
+    };
+    if options.parallel {
+        chunks
+            .par_iter()
+            .zip(offsets.par_iter())
+            .try_for_each(process)
+    } else {
+        chunks.iter().zip(offsets.iter()).try_for_each(process)
+    }
+}
+
+/// Hand a row that could not be loaded to the dead letters
+fn reject_row(
+    dead_letters: &DeadLetters,
+    row: usize,
+    record: String,
+    error: GraphError,
+) -> Result<(), GraphError> {
+    dead_letters.reject(RejectedRecord::new(Some(row as u64), record, error))
+}
+
+fn node_label<V: InputNode>(node: &V) -> String {
+    node.id_str()
+        .map_or_else(|| node.id().to_string(), |name| name.to_owned())
+}
+
+pub(crate) fn load_nodes_from_df<
+    'a,
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+>(
+    df: &'a PretendDF,
+    node_id: &str,
+    time: &str,
+    properties: Option<Vec<&str>>,
+    const_properties: Option<Vec<&str>>,
+    shared_const_properties: Option<HashMap<String, Prop>>,
+    node_type: Option<&str>,
+    node_type_in_df: bool,
+    graph: &G,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    for_each_chunk(df, "Loading nodes", options, |df, offset| {
+        let (prop_iter, const_prop_iter) =
+            get_prop_rows(df, properties.clone(), const_properties.clone())?;
+
+        let node_type: Box<dyn Iterator<Item = Option<&str>> + '_> = match node_type {
+            Some(node_type) => {
+                if node_type_in_df {
+                    let iter_res: Result<Box<dyn Iterator<Item = Option<&str>> + '_>, GraphError> =
+                        if let Some(node_types) = df.utf8::<i32>(node_type) {
+                            Ok(Box::new(node_types))
+                        } else if let Some(node_types) = df.utf8::<i64>(node_type) {
+                            Ok(Box::new(node_types))
+                        } else {
+                            Err(GraphError::LoadFailure(
+                                "Unable to convert / find node_type column in dataframe."
+                                    .to_string(),
+                            ))
+                        };
+                    iter_res?
+                } else {
+                    Box::new(iter::repeat(Some(node_type)))
+                }
+            }
+            None => Box::new(iter::repeat(None)),
+        };
+        let shared_const_properties = shared_const_properties.as_ref();
+
+        if let (Some(node_id), Some(time)) = (df.iter_col::<u64>(node_id), df.time_iter_col(time)) {
+            let iter = node_id.map(|i| i.copied()).zip(time).zip(node_type);
+            load_nodes_from_iter(
+                graph,
+                iter,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let (Some(node_id), Some(time)) =
+            (df.iter_col::<i64>(node_id), df.time_iter_col(time))
+        {
+            let iter = node_id.map(i64_opt_into_u64_opt).zip(time).zip(node_type);
+            load_nodes_from_iter(
+                graph,
+                iter,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let (Some(node_id), Some(time)) =
+            (df.utf8::<i32>(node_id), df.time_iter_col(time))
+        {
+            let iter = node_id.zip(time).zip(node_type);
+            load_nodes_from_iter(
+                graph,
+                iter,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let (Some(node_id), Some(time)) =
+            (df.utf8::<i64>(node_id), df.time_iter_col(time))
+        {
+            let iter = node_id.zip(time).zip(node_type);
+            load_nodes_from_iter(
+                graph,
+                iter,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else {
+            Err(GraphError::LoadFailure(
+                "node id column must be either u64 or text, time column must be i64. Ensure these contain no NaN, Null or None values.".to_string(),
+            ))
+        }
+    })
+}
+
+fn extract_out_default_type(n_t: Option<&str>) -> Option<&str> {
+    if n_t == Some("_default") {
+        None
+    } else {
+        n_t
+    }
+}
+
+pub(crate) fn load_edges_from_df<
+    'a,
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps + InternalDeletionOps,
+    S: AsRef<str> + Sync,
+>(
+    df: &'a PretendDF,
+    src: &str,
+    dst: &str,
+    time: &str,
+    properties: Option<Vec<&str>>,
+    const_properties: Option<Vec<&str>>,
+    shared_const_properties: Option<HashMap<String, Prop>>,
+    layer: Option<S>,
+    layer_in_df: bool,
+    deletions: Option<&str>,
+    graph: &G,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    for_each_chunk(df, "Loading edges", options, |df, offset| {
+        let (prop_iter, const_prop_iter) =
+            get_prop_rows(df, properties.clone(), const_properties.clone())?;
+        let layer = lift_layer(layer.as_ref(), layer_in_df, df);
+        let deletions = lift_deletions(deletions, df)?;
+        let shared_const_properties = shared_const_properties.as_ref();
+
+        if let (Some(src), Some(dst), Some(time)) = (
+            df.iter_col::<u64>(src),
+            df.iter_col::<u64>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src
+                .map(|i| i.copied())
+                .zip(dst.map(|i| i.copied()))
+                .zip(time);
+            load_edges_from_iter(
+                graph,
+                triplets,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                deletions,
+                offset,
+                options,
+            )
+        } else if let (Some(src), Some(dst), Some(time)) = (
+            df.iter_col::<i64>(src),
+            df.iter_col::<i64>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src
+                .map(i64_opt_into_u64_opt)
+                .zip(dst.map(i64_opt_into_u64_opt))
+                .zip(time);
+            load_edges_from_iter(
+                graph,
+                triplets,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                deletions,
+                offset,
+                options,
+            )
+        } else if let (Some(src), Some(dst), Some(time)) = (
+            df.utf8::<i32>(src),
+            df.utf8::<i32>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src.zip(dst).zip(time);
+            load_edges_from_iter(
+                graph,
+                triplets,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                deletions,
+                offset,
+                options,
+            )
+        } else if let (Some(src), Some(dst), Some(time)) = (
+            df.utf8::<i64>(src),
+            df.utf8::<i64>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src.zip(dst).zip(time);
+            load_edges_from_iter(
+                graph,
+                triplets,
+                prop_iter,
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                deletions,
+                offset,
+                options,
+            )
+        } else {
+            Err(GraphError::LoadFailure(
+                "Source and Target columns must be either u64 or text, Time column must be i64. Ensure these contain no NaN, Null or None values."
+                    .to_string(),
+            ))
+        }
+    })
+}
+
+pub(crate) fn load_edges_deletions_from_df<
+    'a,
+    G: StaticGraphViewOps + InternalAdditionOps + InternalDeletionOps,
+    S: AsRef<str> + Sync,
+>(
+    df: &'a PretendDF,
+    src: &str,
+    dst: &str,
+    time: &str,
+    layer: Option<S>,
+    layer_in_df: bool,
+    graph: &G,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    for_each_chunk(df, "Loading edges", options, |df, offset| {
+        let layer = lift_layer(layer.as_ref(), layer_in_df, df);
+
+        if let (Some(src), Some(dst), Some(time)) = (
+            df.iter_col::<u64>(src),
+            df.iter_col::<u64>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src
+                .map(|i| i.copied())
+                .zip(dst.map(|i| i.copied()))
+                .zip(time);
+            delete_edges_from_iter(graph, triplets, layer, offset, &options.dead_letters)
+        } else if let (Some(src), Some(dst), Some(time)) = (
+            df.iter_col::<i64>(src),
+            df.iter_col::<i64>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src
+                .map(i64_opt_into_u64_opt)
+                .zip(dst.map(i64_opt_into_u64_opt))
+                .zip(time);
+            delete_edges_from_iter(graph, triplets, layer, offset, &options.dead_letters)
+        } else if let (Some(src), Some(dst), Some(time)) = (
+            df.utf8::<i32>(src),
+            df.utf8::<i32>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src.zip(dst).zip(time);
+            delete_edges_from_iter(graph, triplets, layer, offset, &options.dead_letters)
+        } else if let (Some(src), Some(dst), Some(time)) = (
+            df.utf8::<i64>(src),
+            df.utf8::<i64>(dst),
+            df.time_iter_col(time),
+        ) {
+            let triplets = src.zip(dst).zip(time);
+            delete_edges_from_iter(graph, triplets, layer, offset, &options.dead_letters)
+        } else {
+            Err(GraphError::LoadFailure(
+                "Source and Target columns must be either u64 or text, Time column must be i64. Ensure these contain no NaN, Null or None values."
+                    .to_string(),
+            ))
+        }
+    })
+}
+
+pub(crate) fn load_node_props_from_df<
+    'a,
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+>(
+    df: &'a PretendDF,
+    node_id: &str,
+    const_properties: Option<Vec<&str>>,
+    shared_const_properties: Option<HashMap<String, Prop>>,
+    graph: &G,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    for_each_chunk(df, "Loading node properties", options, |df, offset| {
+        let (_, const_prop_iter) = get_prop_rows(df, None, const_properties.clone())?;
+        let shared_const_properties = shared_const_properties.as_ref();
+
+        if let Some(node_id) = df.iter_col::<u64>(node_id) {
+            let iter = node_id.map(|i| i.copied());
+            load_node_props_from_iter(
+                graph,
+                iter,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let Some(node_id) = df.iter_col::<i64>(node_id) {
+            let iter = node_id.map(i64_opt_into_u64_opt);
+            load_node_props_from_iter(
+                graph,
+                iter,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let Some(node_id) = df.utf8::<i32>(node_id) {
+            load_node_props_from_iter(
+                graph,
+                node_id,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let Some(node_id) = df.utf8::<i64>(node_id) {
+            load_node_props_from_iter(
+                graph,
+                node_id,
+                const_prop_iter,
+                shared_const_properties,
+                offset,
+                &options.dead_letters,
+            )
+        } else {
+            Err(GraphError::LoadFailure(
+                "node id column must be either u64 or text, time column must be i64. Ensure these contain no NaN, Null or None values.".to_string(),
+            ))
+        }
+    })
+}
+
+pub(crate) fn load_edges_props_from_df<
+    'a,
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    S: AsRef<str> + Sync,
+>(
+    df: &'a PretendDF,
+    src: &str,
+    dst: &str,
+    const_properties: Option<Vec<&str>>,
+    shared_const_properties: Option<HashMap<String, Prop>>,
+    layer: Option<S>,
+    layer_in_df: bool,
+    graph: &G,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    for_each_chunk(df, "Loading edge properties", options, |df, offset| {
+        let (_, const_prop_iter) = get_prop_rows(df, None, const_properties.clone())?;
+        let layer = lift_layer(layer.as_ref(), layer_in_df, df);
+        let shared_const_properties = shared_const_properties.as_ref();
+
+        if let (Some(src), Some(dst)) = (df.iter_col::<u64>(src), df.iter_col::<u64>(dst)) {
+            let pairs = src.map(|i| i.copied()).zip(dst.map(|i| i.copied()));
+            load_edge_props_from_iter(
+                graph,
+                pairs,
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let (Some(src), Some(dst)) = (df.iter_col::<i64>(src), df.iter_col::<i64>(dst)) {
+            let pairs = src
+                .map(i64_opt_into_u64_opt)
+                .zip(dst.map(i64_opt_into_u64_opt));
+            load_edge_props_from_iter(
+                graph,
+                pairs,
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let (Some(src), Some(dst)) = (df.utf8::<i32>(src), df.utf8::<i32>(dst)) {
+            load_edge_props_from_iter(
+                graph,
+                src.zip(dst),
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                offset,
+                &options.dead_letters,
+            )
+        } else if let (Some(src), Some(dst)) = (df.utf8::<i64>(src), df.utf8::<i64>(dst)) {
+            load_edge_props_from_iter(
+                graph,
+                src.zip(dst),
+                const_prop_iter,
+                shared_const_properties,
+                layer,
+                offset,
+                &options.dead_letters,
+            )
+        } else {
+            Err(GraphError::LoadFailure(
+                "Source and Target columns must be either u64 or text, Time column must be i64. Ensure these contain no NaN, Null or None values."
+                    .to_string(),
+            ))
+        }
+    })
+}
+
+fn i64_opt_into_u64_opt(x: Option<&i64>) -> Option<u64> {
+    x.map(|x| (*x).try_into().unwrap())
+}
+
+fn lift_deletions<'a>(
+    deletions: Option<&str>,
+    df: &'a PretendDF,
+) -> Result<Box<dyn Iterator<Item = bool> + 'a>, GraphError> {
+    match deletions {
+        None => Ok(Box::new(iter::repeat(false))),
+        Some(name) => {
+            let col = df.bool_col(name).ok_or_else(|| {
+                GraphError::LoadFailure(format!("Deletion column '{name}' must be boolean"))
+            })?;
+            Ok(Box::new(col.map(|v| v.unwrap_or(false))))
+        }
+    }
+}
+
+fn load_edges_from_iter<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps + InternalDeletionOps,
+    V: InputNode,
+    S: AsRef<str>,
+>(
+    graph: &G,
+    edges: impl Iterator<Item = ((Option<V>, Option<V>), Option<i64>)>,
+    properties: impl Iterator<Item = Vec<(S, Prop)>>,
+    const_properties: impl Iterator<Item = Vec<(S, Prop)>>,
+    shared_const_properties: Option<&HashMap<String, Prop>>,
+    layer: impl Iterator<Item = Option<String>>,
+    deletions: impl Iterator<Item = bool>,
+    offset: usize,
+    options: &LoadOptions,
+) -> Result<usize, GraphError> {
+    let mut loaded = 0;
+    for (row, ((((((src, dst), time), edge_props), const_props), layer), deleted)) in edges
+        .zip(properties)
+        .zip(const_properties)
+        .zip(layer)
+        .zip(deletions)
+        .enumerate()
+    {
+        if let (Some(src), Some(dst), Some(time)) = (src, dst, time) {
+            let src_label = node_label(&src);
+            let dst_label = node_label(&dst);
+            let record = format!("src={src_label}, dst={dst_label}, time={time}");
+            // returns whether the row was loaded, or skipped as a duplicate
+            let add = || -> Result<bool, GraphError> {
+                if deleted {
+                    delete_edge_at(graph, time, src, dst, layer.as_deref())?;
+                    return Ok(true);
+                }
+                let key = match &options.deduplicator {
+                    Some(dedup) => {
+                        let key = dedup.edge_key(
+                            time,
+                            &src_label,
+                            &dst_label,
+                            layer.as_deref(),
+                            &edge_props,
+                        )?;
+                        if !dedup.insert(key) {
+                            return Ok(false);
+                        }
+                        Some((dedup, key))
+                    }
+                    None => None,
+                };
+                let e = match graph.add_edge(time, src, dst, edge_props, layer.as_deref()) {
+                    Ok(e) => e,
+                    Err(error) => {
+                        if let Some((dedup, key)) = key {
+                            dedup.remove(key);
+                        }
+                        return Err(error);
+                    }
+                };
+                e.add_constant_properties(const_props, layer.as_deref())?;
+                if let Some(shared_const_props) = shared_const_properties {
+                    e.add_constant_properties(shared_const_props.iter(), layer.as_deref())?;
+                }
+                Ok(true)
+            };
+            match add() {
+                Ok(true) => loaded += 1,
+                Ok(false) => options.dead_letters.duplicate(),
+                Err(error) => reject_row(&options.dead_letters, offset + row, record, error)?,
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+fn delete_edges_from_iter<G: InternalAdditionOps + InternalDeletionOps, V: InputNode>(
+    graph: &G,
+    edges: impl Iterator<Item = ((Option<V>, Option<V>), Option<i64>)>,
+    layer: impl Iterator<Item = Option<String>>,
+    offset: usize,
+    dead_letters: &DeadLetters,
+) -> Result<usize, GraphError> {
+    let mut loaded = 0;
+    for (row, (((src, dst), time), layer)) in edges.zip(layer).enumerate() {
+        if let (Some(src), Some(dst), Some(time)) = (src, dst, time) {
+            let record = format!(
+                "src={}, dst={}, time={time}",
+                node_label(&src),
+                node_label(&dst)
+            );
+            match delete_edge_at(graph, time, src, dst, layer.as_deref()) {
+                Ok(()) => loaded += 1,
+                Err(error) => reject_row(dead_letters, offset + row, record, error)?,
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+fn load_nodes_from_iter<
+    'a,
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    V: InputNode,
+    S: AsRef<str>,
+>(
+    graph: &G,
+    nodes: impl Iterator<Item = ((Option<V>, Option<i64>), Option<&'a str>)>,
+    properties: impl Iterator<Item = Vec<(S, Prop)>>,
+    const_properties: impl Iterator<Item = Vec<(S, Prop)>>,
+    shared_const_properties: Option<&HashMap<String, Prop>>,
+    offset: usize,
+    dead_letters: &DeadLetters,
+) -> Result<usize, GraphError> {
+    let mut loaded = 0;
+    for (row, ((((node, time), node_type), props), const_props)) in
+        nodes.zip(properties).zip(const_properties).enumerate()
+    {
+        if let (Some(v), Some(t)) = (node, time) {
+            let record = format!("id={}, time={t}", node_label(&v));
+            let add = || -> Result<(), GraphError> {
+                let actual_node_type = extract_out_default_type(node_type);
+                let v = graph.add_node(t, v, props, actual_node_type)?;
+                v.add_constant_properties(const_props)?;
+
+                if let Some(shared_const_props) = shared_const_properties {
+                    v.add_constant_properties(shared_const_props.iter())?;
+                }
+                Ok(())
+            };
+            match add() {
+                Ok(()) => loaded += 1,
+                Err(error) => reject_row(dead_letters, offset + row, record, error)?,
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+fn load_node_props_from_iter<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    V: InputNode + AsNodeRef,
+    S: AsRef<str>,
+>(
+    graph: &G,
+    nodes: impl Iterator<Item = Option<V>>,
+    const_properties: impl Iterator<Item = Vec<(S, Prop)>>,
+    shared_const_properties: Option<&HashMap<String, Prop>>,
+    offset: usize,
+    dead_letters: &DeadLetters,
+) -> Result<usize, GraphError> {
+    let mut loaded = 0;
+    for (row, (node_id, const_props)) in nodes.zip(const_properties).enumerate() {
+        if let Some(node_id) = node_id {
+            let record = format!("id={}", node_label(&node_id));
+            let add = || -> Result<(), GraphError> {
+                let v = graph.node(&node_id).ok_or_else(|| match node_id.id_str() {
+                    Some(name) => GraphError::NodeNameError(name.to_owned()),
+                    None => GraphError::NodeIdError(node_id.id()),
+                })?;
+                v.add_constant_properties(const_props)?;
+                if let Some(shared_const_props) = shared_const_properties {
+                    v.add_constant_properties(shared_const_props.iter())?;
+                }
+                Ok(())
+            };
+            match add() {
+                Ok(()) => loaded += 1,
+                Err(error) => reject_row(dead_letters, offset + row, record, error)?,
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+fn load_edge_props_from_iter<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+    V: InputNode + AsNodeRef,
+    S: AsRef<str>,
+>(
+    graph: &G,
+    edges: impl Iterator<Item = (Option<V>, Option<V>)>,
+    const_properties: impl Iterator<Item = Vec<(S, Prop)>>,
+    shared_const_properties: Option<&HashMap<String, Prop>>,
+    layer: impl Iterator<Item = Option<String>>,
+    offset: usize,
+    dead_letters: &DeadLetters,
+) -> Result<usize, GraphError> {
+    let mut loaded = 0;
+    for (row, (((src, dst), const_props), layer)) in
+        edges.zip(const_properties).zip(layer).enumerate()
+    {
+        if let (Some(src), Some(dst)) = (src, dst) {
+            let record = format!("src={}, dst={}", node_label(&src), node_label(&dst));
+            let add = || -> Result<(), GraphError> {
+                let e =
+                    graph
+                        .edge(&src, &dst)
+                        .ok_or_else(|| match (src.id_str(), dst.id_str()) {
+                            (Some(src), Some(dst)) => GraphError::EdgeNameError {
+                                src: src.to_owned(),
+                                dst: dst.to_owned(),
+                            },
+                            _ => GraphError::EdgeIdError {
+                                src: src.id(),
+                                dst: dst.id(),
+                            },
+                        })?;
+                e.add_constant_properties(const_props, layer.as_deref())?;
+                if let Some(shared_const_props) = shared_const_properties {
+                    e.add_constant_properties(shared_const_props.iter(), layer.as_deref())?;
+                }
+                Ok(())
+            };
+            match add() {
+                Ok(()) => loaded += 1,
+                Err(error) => reject_row(dead_letters, offset + row, record, error)?,
+            }
+        }
+    }
+    Ok(loaded)
+}
@@ -0,0 +1,271 @@
+//! Load graphs from columnar data held in arrow arrays
+//!
+//! The loaders take a [`dataframe::PretendDF`], i.e. a list of column names and chunks of arrow
+//! arrays, together with a mapping from columns to node or edge updates. Parquet files are read
+//! into the same representation by [`crate::io::parquet_loaders`], and graph views can be
+//! exported to it with [`df_export::GraphExportOps`].
+
+pub mod dataframe;
+pub mod df_export;
+pub mod df_loaders;
+mod prop_handler;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::ArcStr,
+        db::graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph},
+        io::arrow::{
+            dataframe::PretendDF,
+            df_loaders::{
+                load_edges_from_arrow, load_edges_from_df, load_nodes_from_df, EdgeColumns,
+                LoadOptions,
+            },
+        },
+        prelude::*,
+    };
+    use polars_arrow::array::{BooleanArray, PrimitiveArray, Utf8Array};
+
+    #[test]
+    fn load_edges_from_pretend_df() {
+        let df = PretendDF {
+            names: vec!["src", "dst", "time", "prop1", "prop2"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            arrays: vec![
+                vec![
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(1)])),
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(2)])),
+                    Box::new(PrimitiveArray::<i64>::from(vec![Some(1)])),
+                    Box::new(PrimitiveArray::<f64>::from(vec![Some(1.0)])),
+                    Box::new(Utf8Array::<i32>::from(vec![Some("a")])),
+                ],
+                vec![
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(2), Some(3)])),
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(3), Some(4)])),
+                    Box::new(PrimitiveArray::<i64>::from(vec![Some(2), Some(3)])),
+                    Box::new(PrimitiveArray::<f64>::from(vec![Some(2.0), Some(3.0)])),
+                    Box::new(Utf8Array::<i32>::from(vec![Some("b"), Some("c")])),
+                ],
+            ],
+        };
+        let graph = Graph::new();
+        let layer: Option<&str> = None;
+        let layer_in_df: bool = true;
+        load_edges_from_df(
+            &df,
+            "src",
+            "dst",
+            "time",
+            Some(vec!["prop1", "prop2"]),
+            None,
+            None,
+            layer,
+            layer_in_df,
+            None,
+            &graph,
+            &LoadOptions::default().chunk_size(1),
+        )
+        .expect("failed to load edges from pretend df");
+
+        let actual = graph
+            .edges()
+            .iter()
+            .map(|e| {
+                (
+                    e.src().id(),
+                    e.dst().id(),
+                    e.latest_time(),
+                    e.properties()
+                        .temporal()
+                        .get("prop1")
+                        .and_then(|v| v.latest()),
+                    e.properties()
+                        .temporal()
+                        .get("prop2")
+                        .and_then(|v| v.latest()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                (1, 2, Some(1), Some(Prop::F64(1.0)), Some(Prop::str("a"))),
+                (2, 3, Some(2), Some(Prop::F64(2.0)), Some(Prop::str("b"))),
+                (3, 4, Some(3), Some(Prop::F64(3.0)), Some(Prop::str("c"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_nodes_from_pretend_df() {
+        let df = PretendDF {
+            names: vec!["id", "name", "time", "node_type"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            arrays: vec![
+                vec![
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(1)])),
+                    Box::new(Utf8Array::<i32>::from(vec![Some("a")])),
+                    Box::new(PrimitiveArray::<i64>::from(vec![Some(1)])),
+                    Box::new(Utf8Array::<i32>::from(vec![Some("atype")])),
+                ],
+                vec![
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(2)])),
+                    Box::new(Utf8Array::<i32>::from(vec![Some("b")])),
+                    Box::new(PrimitiveArray::<i64>::from(vec![Some(2)])),
+                    Box::new(Utf8Array::<i32>::from(vec![Some("btype")])),
+                ],
+            ],
+        };
+        let graph = Graph::new();
+
+        load_nodes_from_df(
+            &df,
+            "id",
+            "time",
+            Some(vec!["name"]),
+            None,
+            None,
+            Some("node_type"),
+            false,
+            &graph,
+            &LoadOptions::sequential().progress(false),
+        )
+        .expect("failed to load nodes from pretend df");
+
+        let actual = graph
+            .nodes()
+            .iter()
+            .map(|v| {
+                (
+                    v.id(),
+                    v.latest_time(),
+                    v.properties()
+                        .temporal()
+                        .get("name")
+                        .and_then(|v| v.latest()),
+                    v.node_type(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    1,
+                    Some(1),
+                    Some(Prop::str("a")),
+                    Some(ArcStr::from("node_type"))
+                ),
+                (
+                    2,
+                    Some(2),
+                    Some(Prop::str("b")),
+                    Some(ArcStr::from("node_type"))
+                ),
+            ]
+        );
+    }
+
+    fn edge_df() -> PretendDF {
+        let n = 1000u64;
+        PretendDF::new(
+            vec!["src", "dst", "time", "weight", "layer", "deleted"]
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            vec![vec![
+                Box::new(PrimitiveArray::<u64>::from_vec(
+                    (0..n).map(|i| i % 10).collect(),
+                )),
+                Box::new(PrimitiveArray::<u64>::from_vec(
+                    (0..n).map(|i| (i + 1) % 10).collect(),
+                )),
+                Box::new(PrimitiveArray::<i64>::from_vec((0..n as i64).collect())),
+                Box::new(PrimitiveArray::<f64>::from_vec(
+                    (0..n).map(|i| i as f64).collect(),
+                )),
+                Box::new(Utf8Array::<i32>::from_iter_values((0..n).map(|i| {
+                    if i % 2 == 0 {
+                        "even"
+                    } else {
+                        "odd"
+                    }
+                }))),
+                Box::new(BooleanArray::from_slice(
+                    (0..n).map(|i| i >= n - 10).collect::<Vec<_>>(),
+                )),
+            ]],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parallel_load_matches_sequential_load() {
+        let df = edge_df();
+        let columns = EdgeColumns::new("src", "dst", "time")
+            .properties(["weight"])
+            .layer_column("layer");
+        let sequential = Graph::new();
+        load_edges_from_arrow(
+            &sequential,
+            &df,
+            &columns,
+            &LoadOptions::default().parallel(false),
+        )
+        .unwrap();
+        let parallel = Graph::new();
+        load_edges_from_arrow(
+            &parallel,
+            &df,
+            &columns,
+            &LoadOptions::default().chunk_size(7),
+        )
+        .unwrap();
+
+        assert_eq!(parallel.count_edges(), 10);
+        assert_eq!(
+            parallel
+                .edge(0, 1)
+                .unwrap()
+                .layer_names()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>(),
+            vec!["even"]
+        );
+        assert_graph_equal(&parallel, &sequential);
+    }
+
+    #[test]
+    fn deletion_column_deletes_edges() {
+        let df = edge_df();
+        let columns = EdgeColumns::new("src", "dst", "time")
+            .properties(["weight"])
+            .deletions("deleted");
+        let graph = PersistentGraph::new();
+        load_edges_from_arrow(&graph, &df, &columns, &LoadOptions::default()).unwrap();
+
+        let expected = PersistentGraph::new();
+        for i in 0..1000u64 {
+            if i >= 990 {
+                expected
+                    .delete_edge(i as i64, i % 10, (i + 1) % 10, None)
+                    .unwrap();
+            } else {
+                expected
+                    .add_edge(i as i64, i % 10, (i + 1) % 10, [("weight", i as f64)], None)
+                    .unwrap();
+            }
+        }
+        assert_graph_equal(&graph, &expected);
+        assert!(graph.at(1000).edges().is_empty());
+
+        let missing = EdgeColumns::new("src", "dst", "time").deletions("weight");
+        assert!(load_edges_from_arrow(&graph, &df, &missing, &LoadOptions::default()).is_err());
+    }
+}
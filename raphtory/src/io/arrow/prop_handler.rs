@@ -1,13 +1,16 @@
 use chrono::{DateTime, Utc};
 use polars_arrow::{
-    array::{Array, BooleanArray, FixedSizeListArray, ListArray, PrimitiveArray, Utf8Array},
+    array::{
+        Array, BinaryArray, BooleanArray, FixedSizeListArray, ListArray, PrimitiveArray, Utf8Array,
+    },
     datatypes::{ArrowDataType as DataType, TimeUnit},
 };
+use rust_decimal::Decimal;
 
 use crate::{
-    core::{utils::errors::GraphError, IntoPropList},
+    core::{utils::errors::GraphError, IntoPropList, PropArray},
+    io::arrow::dataframe::PretendDF,
     prelude::Prop,
-    python::graph::pandas::dataframe::PretendDF,
 };
 
 pub struct PropIter<'a> {
@@ -59,6 +62,14 @@ fn arr_as_prop(arr: Box<dyn Array>) -> Prop {
             let arr = arr.as_any().downcast_ref::<BooleanArray>().unwrap();
             arr.iter().flatten().into_prop_list()
         }
+        DataType::Int8 => {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<i8>>().unwrap();
+            arr.iter().flatten().copied().into_prop_list()
+        }
+        DataType::Int16 => {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<i16>>().unwrap();
+            arr.iter().flatten().copied().into_prop_list()
+        }
         DataType::Int32 => {
             let arr = arr.as_any().downcast_ref::<PrimitiveArray<i32>>().unwrap();
             arr.iter().flatten().copied().into_prop_list()
@@ -127,6 +138,8 @@ fn arr_as_prop(arr: Box<dyn Array>) -> Prop {
 fn validate_data_types(dt: &DataType) -> Result<(), GraphError> {
     match dt {
         DataType::Boolean => {}
+        DataType::Int8 => {}
+        DataType::Int16 => {}
         DataType::Int32 => {}
         DataType::Int64 => {}
         DataType::UInt8 => {}
@@ -137,6 +150,9 @@ fn validate_data_types(dt: &DataType) -> Result<(), GraphError> {
         DataType::Float64 => {}
         DataType::Utf8 => {}
         DataType::LargeUtf8 => {}
+        DataType::Binary => {}
+        DataType::LargeBinary => {}
+        DataType::Decimal(_, _) => {}
         DataType::List(v) => validate_data_types(v.data_type())?,
         DataType::FixedSizeList(v, _) => validate_data_types(v.data_type())?,
         DataType::LargeList(v) => validate_data_types(v.data_type())?,
@@ -167,6 +183,14 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                 let arr = arr.as_any().downcast_ref::<BooleanArray>().unwrap();
                 iter_as_prop(name, arr.iter())
             }
+            DataType::Int8 => {
+                let arr = arr.as_any().downcast_ref::<PrimitiveArray<i8>>().unwrap();
+                iter_as_prop(name, arr.iter().map(|i| i.copied()))
+            }
+            DataType::Int16 => {
+                let arr = arr.as_any().downcast_ref::<PrimitiveArray<i16>>().unwrap();
+                iter_as_prop(name, arr.iter().map(|i| i.copied()))
+            }
             DataType::Int32 => {
                 let arr = arr.as_any().downcast_ref::<PrimitiveArray<i32>>().unwrap();
                 iter_as_prop(name, arr.iter().map(|i| i.copied()))
@@ -207,6 +231,31 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                 let arr = arr.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
                 iter_as_prop(name, arr.iter())
             }
+            DataType::Binary => {
+                let arr = arr.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
+                iter_as_prop(name, arr.iter().map(|v| v.map(Prop::bytes)))
+            }
+            DataType::LargeBinary => {
+                let arr = arr.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+                iter_as_prop(name, arr.iter().map(|v| v.map(Prop::bytes)))
+            }
+            DataType::Decimal(_, scale) => {
+                let scale = *scale as u32;
+                let arr = arr.as_any().downcast_ref::<PrimitiveArray<i128>>().unwrap();
+                iter_as_prop(
+                    name,
+                    arr.iter()
+                        .map(move |v| v.map(|v| Decimal::from_i128_with_scale(*v, scale))),
+                )
+            }
+            DataType::FixedSizeList(field, _) if is_numeric_array(field.data_type()) => {
+                let arr = arr.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+                iter_as_prop(
+                    name,
+                    arr.iter()
+                        .map(|v| v.and_then(|v| arr_as_prop_array(v.as_ref()))),
+                )
+            }
             DataType::List(_) => {
                 let arr = arr.as_any().downcast_ref::<ListArray<i32>>().unwrap();
                 iter_as_arr_prop(name, arr.iter())
@@ -224,7 +273,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                 match timezone {
                     Some(_) => match timeunit {
                         TimeUnit::Second => {
-                            println!("Timestamp(Second, Some({:?})); ", timezone);
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -242,7 +290,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                             r
                         }
                         TimeUnit::Millisecond => {
-                            println!("Timestamp(Millisecond, Some({:?})); ", timezone);
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -260,7 +307,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                             r
                         }
                         TimeUnit::Microsecond => {
-                            println!("Timestamp(Microsecond, Some({:?})); ", timezone);
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -278,7 +324,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                             r
                         }
                         TimeUnit::Nanosecond => {
-                            println!("Timestamp(Nanosecond, Some({:?})); ", timezone);
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -297,7 +342,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                     },
                     None => match timeunit {
                         TimeUnit::Second => {
-                            println!("Timestamp(Second, None); ");
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -316,7 +360,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                             r
                         }
                         TimeUnit::Millisecond => {
-                            println!("Timestamp(Millisecond, None); ");
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -335,7 +378,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                             r
                         }
                         TimeUnit::Microsecond => {
-                            println!("Timestamp(Microsecond, None); ");
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -354,7 +396,6 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
                             r
                         }
                         TimeUnit::Nanosecond => {
-                            println!("Timestamp(Nanosecond, None); ");
                             let r: Box<dyn Iterator<Item = Vec<(&'b str, Prop)>> + 'b> =
                                 Box::new(arr.iter().map(move |val| {
                                     val.into_iter()
@@ -413,6 +454,36 @@ fn iter_as_prop<'a, T: Into<Prop> + 'a, I: Iterator<Item = Option<T>> + 'a>(
     }))
 }
 
+fn is_numeric_array(dt: &DataType) -> bool {
+    matches!(
+        dt,
+        DataType::Int32 | DataType::Int64 | DataType::Float32 | DataType::Float64
+    )
+}
+
+/// Fixed-size lists of numbers are loaded as dense arrays rather than lists
+fn arr_as_prop_array(arr: &dyn Array) -> Option<PropArray> {
+    match arr.data_type() {
+        DataType::Int32 => {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<i32>>()?;
+            Some(PropArray::I32(arr.values().as_slice().into()))
+        }
+        DataType::Int64 => {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<i64>>()?;
+            Some(PropArray::I64(arr.values().as_slice().into()))
+        }
+        DataType::Float32 => {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<f32>>()?;
+            Some(PropArray::F32(arr.values().as_slice().into()))
+        }
+        DataType::Float64 => {
+            let arr = arr.as_any().downcast_ref::<PrimitiveArray<f64>>()?;
+            Some(PropArray::F64(arr.values().as_slice().into()))
+        }
+        _ => None,
+    }
+}
+
 fn iter_as_arr_prop<'a, I: Iterator<Item = Option<Box<dyn Array>>> + 'a>(
     name: &'a str,
     is: I,
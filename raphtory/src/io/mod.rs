@@ -0,0 +1,3 @@
+//! Bulk loading and export of graphs as columnar data
+pub mod arrow;
+pub mod parquet_loaders;
@@ -0,0 +1,298 @@
+//! Load graphs from parquet files and write dataframes to them
+//!
+//! Only the columns referenced by the column mapping are read. Paths can point to a single
+//! parquet file or to a directory, in which case all `.parquet` files in it are loaded in name
+//! order.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use raphtory::{
+//!     io::{
+//!         arrow::df_loaders::{EdgeColumns, LoadOptions, NodeColumns},
+//!         parquet_loaders::{load_edges_from_parquet, load_nodes_from_parquet},
+//!     },
+//!     prelude::*,
+//! };
+//!
+//! let g = Graph::new();
+//! let options = LoadOptions::default();
+//! load_edges_from_parquet(
+//!     &g,
+//!     "/tmp/transactions",
+//!     &EdgeColumns::new("sender", "receiver", "timestamp")
+//!         .properties(["amount"])
+//!         .layer_column("currency"),
+//!     &options,
+//! )
+//! .unwrap();
+//! load_nodes_from_parquet(
+//!     &g,
+//!     "/tmp/accounts.parquet",
+//!     &NodeColumns::new("id", "opened").node_type("account"),
+//!     &options,
+//! )
+//! .unwrap();
+//! ```
+
+use crate::{
+    core::utils::errors::GraphError,
+    db::api::{
+        mutation::internal::{
+            InternalAdditionOps, InternalDeletionOps, InternalPropertyAdditionOps,
+        },
+        view::StaticGraphViewOps,
+    },
+    io::arrow::{
+        dataframe::PretendDF,
+        df_loaders::{
+            load_edge_deletions_from_arrow, load_edges_from_arrow, load_nodes_from_arrow,
+            EdgeColumns, LoadOptions, NodeColumns,
+        },
+    },
+};
+use polars_arrow::{
+    array::{Array, ListArray},
+    bitmap::Bitmap,
+    compute::cast::{cast, CastOptions},
+    datatypes::{ArrowDataType as DataType, ArrowSchema, Field, Metadata},
+    record_batch::RecordBatch,
+};
+use polars_parquet::{read, write};
+use std::{
+    fs,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+/// Load node updates from parquet
+pub fn load_nodes_from_parquet<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+>(
+    graph: &G,
+    path: impl AsRef<Path>,
+    columns: &NodeColumns,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    for file in parquet_files(path.as_ref())? {
+        let df = read_parquet(&file, &columns.columns())?;
+        load_nodes_from_arrow(graph, &df, columns, options)?;
+    }
+    Ok(())
+}
+
+/// Load edge additions, and deletions if `columns` has a deletion column, from parquet
+pub fn load_edges_from_parquet<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps + InternalDeletionOps,
+>(
+    graph: &G,
+    path: impl AsRef<Path>,
+    columns: &EdgeColumns,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    for file in parquet_files(path.as_ref())? {
+        let df = read_parquet(&file, &columns.columns())?;
+        load_edges_from_arrow(graph, &df, columns, options)?;
+    }
+    Ok(())
+}
+
+/// Load every row of the parquet data as an edge deletion
+pub fn load_edge_deletions_from_parquet<
+    G: StaticGraphViewOps + InternalAdditionOps + InternalDeletionOps,
+>(
+    graph: &G,
+    path: impl AsRef<Path>,
+    columns: &EdgeColumns,
+    options: &LoadOptions,
+) -> Result<(), GraphError> {
+    let mut cols = vec![
+        columns.src.as_str(),
+        columns.dst.as_str(),
+        columns.time.as_str(),
+    ];
+    if columns.layer_in_df {
+        cols.extend(columns.layer.as_deref());
+    }
+    for file in parquet_files(path.as_ref())? {
+        let df = read_parquet(&file, &cols)?;
+        load_edge_deletions_from_arrow(graph, &df, columns, options)?;
+    }
+    Ok(())
+}
+
+/// Read the given columns of a parquet file. Each row group becomes a chunk of the dataframe.
+pub fn read_parquet(path: impl AsRef<Path>, columns: &[&str]) -> Result<PretendDF, GraphError> {
+    let df = read_parquet_columns(path.as_ref(), |name| columns.contains(&name))?;
+    df.check_cols_exist(columns)?;
+    Ok(df)
+}
+
+/// Field metadata with the size of a fixed-size list column written as a large list
+///
+/// Null entries of fixed-size lists do not survive a parquet round trip, so these columns are
+/// written as large lists and converted back when read.
+const FIXED_SIZE_LIST_KEY: &str = "raphtory:fixed_size_list";
+
+/// Read all columns of a parquet file
+pub fn read_parquet_table(path: impl AsRef<Path>) -> Result<PretendDF, GraphError> {
+    read_parquet_columns(path.as_ref(), |_| true)
+}
+
+fn read_parquet_columns(path: &Path, keep: impl Fn(&str) -> bool) -> Result<PretendDF, GraphError> {
+    let parquet_error = |e| {
+        GraphError::LoadFailure(format!(
+            "Failed to read parquet file {}: {e:?}",
+            path.display()
+        ))
+    };
+    let mut file = File::open(path)?;
+    let metadata = read::read_metadata(&mut file).map_err(parquet_error)?;
+    let schema = read::infer_schema(&metadata).map_err(parquet_error)?;
+    // string and binary columns are inferred as view types, which the loaders don't handle
+    let fields = schema
+        .fields
+        .into_iter()
+        .filter(|field| keep(&field.name))
+        .map(|field| match field.data_type {
+            DataType::Utf8View => Field::new(field.name, DataType::LargeUtf8, field.is_nullable),
+            DataType::BinaryView => {
+                Field::new(field.name, DataType::LargeBinary, field.is_nullable)
+            }
+            _ => field,
+        })
+        .collect::<Vec<_>>();
+    let names = fields.iter().map(|field| field.name.clone()).collect();
+    let fixed_size_lists = fields
+        .iter()
+        .map(
+            |field| match (&field.data_type, field.metadata.get(FIXED_SIZE_LIST_KEY)) {
+                (DataType::LargeList(inner), Some(size)) => size
+                    .parse()
+                    .ok()
+                    .map(|size| DataType::FixedSizeList(inner.clone(), size)),
+                _ => None,
+            },
+        )
+        .collect::<Vec<_>>();
+    let schema = ArrowSchema::from(fields).with_metadata(schema.metadata);
+
+    let arrays = read::FileReader::new(file, metadata.row_groups, schema, None, None, None)
+        .map(|chunk| {
+            chunk?
+                .into_arrays()
+                .into_iter()
+                .zip(&fixed_size_lists)
+                .map(|(array, fixed_size_list)| match fixed_size_list {
+                    Some(dtype) => cast(
+                        empty_lists_as_nulls(array).as_ref(),
+                        dtype,
+                        CastOptions::default(),
+                    ),
+                    None => Ok(array),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(parquet_error)?;
+    PretendDF::new(names, arrays)
+}
+
+/// Null lists of a column written from a fixed-size list are read back as empty lists, which
+/// a fixed-size list cannot contain, so turn them back into nulls
+fn empty_lists_as_nulls(array: Box<dyn Array>) -> Box<dyn Array> {
+    match array.as_any().downcast_ref::<ListArray<i64>>() {
+        Some(list) => {
+            let validity = list
+                .offsets()
+                .lengths()
+                .enumerate()
+                .map(|(i, len)| len > 0 && list.is_valid(i))
+                .collect::<Bitmap>();
+            list.clone().with_validity(Some(validity)).boxed()
+        }
+        None => array,
+    }
+}
+
+/// Write a dataframe to a parquet file, one row group per chunk
+pub fn write_parquet(df: &PretendDF, path: impl AsRef<Path>) -> Result<(), GraphError> {
+    let path = path.as_ref();
+    let parquet_error = |e| {
+        GraphError::ExportFailure(format!(
+            "Failed to write parquet file {}: {e:?}",
+            path.display()
+        ))
+    };
+    let first_chunk = df.arrays.first().ok_or_else(|| {
+        GraphError::ExportFailure("Cannot write a dataframe without any chunks".to_string())
+    })?;
+    let fields = df
+        .names
+        .iter()
+        .zip(first_chunk)
+        .map(|(name, array)| match array.data_type() {
+            DataType::FixedSizeList(inner, size) => {
+                Field::new(name.clone(), DataType::LargeList(inner.clone()), true).with_metadata(
+                    Metadata::from([(FIXED_SIZE_LIST_KEY.to_string(), size.to_string())]),
+                )
+            }
+            dtype => Field::new(name.clone(), dtype.clone(), true),
+        })
+        .collect::<Vec<_>>();
+    let encodings = fields
+        .iter()
+        .map(|field| write::transverse(&field.data_type, |_| write::Encoding::Plain))
+        .collect();
+    let dtypes = fields
+        .iter()
+        .map(|field| field.data_type.clone())
+        .collect::<Vec<_>>();
+    let schema = ArrowSchema::from(fields);
+    let options = write::WriteOptions {
+        write_statistics: true,
+        version: write::Version::V2,
+        compression: write::CompressionOptions::Snappy,
+        data_pagesize_limit: None,
+    };
+
+    let batches = df.arrays.iter().map(|chunk| {
+        chunk
+            .iter()
+            .zip(&dtypes)
+            .map(|(array, dtype)| match dtype {
+                DataType::LargeList(_) if array.data_type() != dtype => {
+                    cast(array.as_ref(), dtype, CastOptions::default())
+                }
+                _ => Ok(array.clone()),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(RecordBatch::new)
+    });
+    let row_groups = write::RowGroupIterator::try_new(batches, &schema, options, encodings)
+        .map_err(parquet_error)?;
+    let mut writer =
+        write::FileWriter::try_new(File::create(path)?, schema, options).map_err(parquet_error)?;
+    for row_group in row_groups {
+        writer
+            .write(row_group.map_err(parquet_error)?)
+            .map_err(parquet_error)?;
+    }
+    writer.end(None).map_err(parquet_error)?;
+    Ok(())
+}
+
+fn parquet_files(path: &Path) -> Result<Vec<PathBuf>, GraphError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = vec![];
+    for entry in fs::read_dir(path)? {
+        let file = entry?.path();
+        if file.extension().is_some_and(|ext| ext == "parquet") {
+            files.push(file);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -98,6 +98,9 @@ pub mod python;
 #[cfg(feature = "io")]
 pub mod graph_loader;
 
+#[cfg(feature = "arrow")]
+pub mod io;
+
 #[cfg(feature = "search")]
 pub mod search;
 
@@ -18,6 +18,7 @@ use crate::{
         query::{ast::Query, executors::rayon2, state::StaticGraphHopState, NodeSource},
         Error,
     },
+    io::arrow::dataframe::PretendDF,
     prelude::{EdgeViewOps, GraphViewOps, NodeViewOps, TimeOps},
     python::{
         graph::{edge::PyDirection, graph::PyGraph, views::graph_view::PyGraphView},
@@ -33,7 +34,7 @@ use pyo3::{
     types::{IntoPyDict, PyDict, PyList, PyString},
 };
 
-use super::pandas::dataframe::{process_pandas_py_df, PretendDF};
+use super::pandas::dataframe::process_pandas_py_df;
 
 impl From<Error> for PyErr {
     fn from(value: Error) -> Self {
@@ -6,11 +6,17 @@
 use super::utils;
 use crate::{
     algorithms::components::LargestConnectedComponent,
-    core::{entities::nodes::node_ref::NodeRef, utils::errors::GraphError, ArcStr},
+    core::{
+        entities::nodes::node_ref::NodeRef,
+        utils::{dead_letter::LoadReport, errors::GraphError},
+        ArcStr,
+    },
     db::{
         api::view::internal::{CoreGraphOps, DynamicGraph, IntoDynamic, MaterializedGraph},
         graph::{edge::EdgeView, node::NodeView, views::node_subgraph::NodeSubgraph},
     },
+    graph_loader::source::mapping::{load_with_mapping, MappingSpec},
+    io::arrow::df_loaders::LoadOptions,
     prelude::*,
     python::{
         graph::{
@@ -24,7 +30,7 @@ use pyo3::{prelude::*, types::PyBytes};
 use std::{
     collections::HashMap,
     fmt::{Debug, Formatter},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 /// A temporal graph.
@@ -442,6 +448,8 @@ impl PyGraph {
                 node_properties,
                 node_const_properties,
                 node_shared_const_properties,
+                None,
+                None,
             )?;
         }
         graph.load_edges_from_pandas(
@@ -454,6 +462,10 @@ impl PyGraph {
             edge_shared_const_properties,
             edge_layer,
             layer_in_df,
+            None,
+            None,
+            false,
+            None,
         )?;
         Ok(graph.graph)
     }
@@ -469,9 +481,12 @@ impl PyGraph {
     ///     properties (List<str>): List of node property column names. Defaults to None. (optional)
     ///     const_properties (List<str>): List of constant node property column names. Defaults to None.  (optional)
     ///     shared_const_properties (Dictionary/Hashmap of properties): A dictionary of constant properties that will be added to every node. Defaults to None. (optional)
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
+    ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, id, time, node_type = None, node_type_in_df = true, properties = None, const_properties = None, shared_const_properties = None))]
+    ///     dict: A report with the number of rows `loaded` and `skipped` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, id, time, node_type = None, node_type_in_df = true, properties = None, const_properties = None, shared_const_properties = None, error_policy = None, dead_letter_file = None))]
     fn load_nodes_from_pandas(
         &self,
         df: &PyAny,
@@ -482,7 +497,10 @@ impl PyGraph {
         properties: Option<Vec<&str>>,
         const_properties: Option<Vec<&str>>,
         shared_const_properties: Option<HashMap<String, Prop>>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
         utils::load_nodes_from_pandas(
             &self.graph.0,
             df,
@@ -493,7 +511,9 @@ impl PyGraph {
             properties,
             const_properties,
             shared_const_properties,
-        )
+            &LoadOptions::sequential().dead_letters(dead_letters.clone()),
+        )?;
+        Ok(dead_letters.report())
     }
 
     /// Load edges from a Pandas DataFrame into the graph.
@@ -508,10 +528,14 @@ impl PyGraph {
     ///     shared_const_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
     ///     layer (str): The edge layer name (optional) Defaults to None.
     ///     layer_in_df (bool): Whether the layer name should be used to look up the values in a column of the dateframe or if it should be used directly as the layer for all edges (optional) defaults to True.
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
+    ///     deduplicate (bool): Whether to skip edge updates with the same time, nodes, layer and properties as an update already in the graph. Defaults to False. (optional)
+    ///     event_id (str): Edge property column holding a unique id of every update, skipping updates whose id is already in the graph. Must be one of `properties`. (optional)
     ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, src, dst, time, properties = None, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true))]
+    ///     dict: A report with the number of rows `loaded`, `skipped` and skipped as `duplicates` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, src, dst, time, properties = None, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true, error_policy = None, dead_letter_file = None, deduplicate = false, event_id = None))]
     fn load_edges_from_pandas(
         &self,
         df: &PyAny,
@@ -523,7 +547,16 @@ impl PyGraph {
         shared_const_properties: Option<HashMap<String, Prop>>,
         layer: Option<&str>,
         layer_in_df: Option<bool>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+        deduplicate: bool,
+        event_id: Option<&str>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
+        let mut options = LoadOptions::sequential().dead_letters(dead_letters.clone());
+        if let Some(dedup) = utils::deduplicator(&self.graph, deduplicate, event_id) {
+            options = options.deduplicate(dedup);
+        }
         utils::load_edges_from_pandas(
             &self.graph.0,
             df,
@@ -535,7 +568,9 @@ impl PyGraph {
             shared_const_properties,
             layer,
             layer_in_df,
-        )
+            &options,
+        )?;
+        Ok(dead_letters.report())
     }
 
     /// Load node properties from a Pandas DataFrame.
@@ -545,24 +580,31 @@ impl PyGraph {
     ///     id(str): The column name for the node IDs.
     ///     const_properties (List<str>): List of constant node property column names. Defaults to None. (optional)
     ///     shared_const_properties (<HashMap<String, Prop>>):  A dictionary of constant properties that will be added to every node. Defaults to None. (optional)
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
     ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, id, const_properties = None, shared_const_properties = None))]
+    ///     dict: A report with the number of rows `loaded` and `skipped` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, id, const_properties = None, shared_const_properties = None, error_policy = None, dead_letter_file = None))]
     fn load_node_props_from_pandas(
         &self,
         df: &PyAny,
         id: &str,
         const_properties: Option<Vec<&str>>,
         shared_const_properties: Option<HashMap<String, Prop>>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
         utils::load_node_props_from_pandas(
             &self.graph.0,
             df,
             id,
             const_properties,
             shared_const_properties,
-        )
+            &LoadOptions::sequential().dead_letters(dead_letters.clone()),
+        )?;
+        Ok(dead_letters.report())
     }
 
     /// Load edge properties from a Pandas DataFrame.
@@ -575,10 +617,12 @@ impl PyGraph {
     ///     shared_const_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
     ///     layer (str): Layer name. Defaults to None.  (optional)
     ///     layer_in_df (bool): Whether the layer name should be used to look up the values in a column of the data frame or if it should be used directly as the layer for all edges (optional) defaults to True.
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
     ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, src, dst, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true))]
+    ///     dict: A report with the number of rows `loaded` and `skipped` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, src, dst, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true, error_policy = None, dead_letter_file = None))]
     fn load_edge_props_from_pandas(
         &self,
         df: &PyAny,
@@ -588,7 +632,10 @@ impl PyGraph {
         shared_const_properties: Option<HashMap<String, Prop>>,
         layer: Option<&str>,
         layer_in_df: Option<bool>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
         utils::load_edge_props_from_pandas(
             &self.graph.0,
             df,
@@ -598,6 +645,26 @@ impl PyGraph {
             shared_const_properties,
             layer,
             layer_in_df,
-        )
+            &LoadOptions::sequential().dead_letters(dead_letters.clone()),
+        )?;
+        Ok(dead_letters.report())
+    }
+
+    /// Load a file, or a directory of files, into the graph as described by a mapping spec.
+    ///
+    /// The spec is written as JSON, YAML or TOML and names the columns holding the time, source,
+    /// destination, layer, node type and properties of the updates, see the documentation of
+    /// `raphtory::graph_loader::source::mapping` for the available options.
+    ///
+    /// Arguments:
+    ///     path (str): The data file or directory of data files (CSV or NDJSON, optionally gzip or bzip2 compressed).
+    ///     spec (str): The path of a spec file or the spec itself.
+    ///
+    /// Returns:
+    ///     dict: A report with the number of records `loaded` and `skipped` and the `rejected` records (with the "collect" error policy of the spec).
+    #[pyo3(signature = (path, spec))]
+    fn load_with_mapping(&self, path: PathBuf, spec: &str) -> Result<LoadReport, GraphError> {
+        let spec = MappingSpec::from_path_or_str(spec)?;
+        load_with_mapping(path, &spec, &self.graph)
     }
 }
@@ -6,7 +6,11 @@
 //! It is a wrapper around a set of shards, which are the actual graph data structures.
 //! In Python, this class wraps around the rust graph.
 use crate::{
-    core::{entities::nodes::node_ref::NodeRef, utils::errors::GraphError, ArcStr, Prop},
+    core::{
+        entities::nodes::node_ref::NodeRef,
+        utils::{dead_letter::LoadReport, errors::GraphError},
+        ArcStr, Prop,
+    },
     db::{
         api::{
             mutation::{AdditionOps, PropertyAdditionOps},
@@ -14,6 +18,8 @@ use crate::{
         },
         graph::{edge::EdgeView, node::NodeView, views::deletion_graph::PersistentGraph},
     },
+    graph_loader::source::mapping::{load_with_mapping, MappingSpec},
+    io::arrow::df_loaders::{load_edges_deletions_from_df, LoadOptions},
     prelude::{DeletionOps, GraphViewOps, ImportOps},
     python::{
         graph::{edge::PyEdge, node::PyNode, views::graph_view::PyGraphView},
@@ -32,10 +38,7 @@ use std::{
 
 use super::{
     graph::PyGraph,
-    pandas::{
-        dataframe::{process_pandas_py_df, GraphLoadException},
-        loaders::load_edges_deletions_from_df,
-    },
+    pandas::dataframe::{process_pandas_py_df, GraphLoadException},
     utils,
 };
 
@@ -439,6 +442,10 @@ impl PyPersistentGraph {
             edge_shared_const_properties,
             edge_layer,
             layer_in_df,
+            None,
+            None,
+            false,
+            None,
         )?;
         if let (Some(node_df), Some(node_id), Some(node_time)) = (node_df, node_id, node_time) {
             graph.load_nodes_from_pandas(
@@ -450,6 +457,8 @@ impl PyPersistentGraph {
                 node_properties,
                 node_const_properties,
                 node_shared_const_properties,
+                None,
+                None,
             )?;
         }
         Ok(graph.graph)
@@ -466,9 +475,12 @@ impl PyPersistentGraph {
     ///     properties (List<str>): List of node property column names. Defaults to None. (optional)
     ///     const_properties (List<str>): List of constant node property column names. Defaults to None.  (optional)
     ///     shared_const_properties (Dictionary/Hashmap of properties): A dictionary of constant properties that will be added to every node. Defaults to None. (optional)
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
+    ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, id, time, node_type = None, node_type_in_df = true, properties = None, const_properties = None, shared_const_properties = None))]
+    ///     dict: A report with the number of rows `loaded` and `skipped` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, id, time, node_type = None, node_type_in_df = true, properties = None, const_properties = None, shared_const_properties = None, error_policy = None, dead_letter_file = None))]
     fn load_nodes_from_pandas(
         &self,
         df: &PyAny,
@@ -479,7 +491,10 @@ impl PyPersistentGraph {
         properties: Option<Vec<&str>>,
         const_properties: Option<Vec<&str>>,
         shared_const_properties: Option<HashMap<String, Prop>>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
         utils::load_nodes_from_pandas(
             &self.graph.0,
             df,
@@ -490,7 +505,9 @@ impl PyPersistentGraph {
             properties,
             const_properties,
             shared_const_properties,
-        )
+            &LoadOptions::sequential().dead_letters(dead_letters.clone()),
+        )?;
+        Ok(dead_letters.report())
     }
 
     /// Load edges from a Pandas DataFrame into the graph.
@@ -505,10 +522,14 @@ impl PyPersistentGraph {
     ///     shared_const_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
     ///     layer (str): The edge layer name (optional) Defaults to None.
     ///     layer_in_df (bool): Whether the layer name should be used to look up the values in a column of the dateframe or if it should be used directly as the layer for all edges (optional) defaults to True.
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
+    ///     deduplicate (bool): Whether to skip edge updates with the same time, nodes, layer and properties as an update already in the graph. Defaults to False. (optional)
+    ///     event_id (str): Edge property column holding a unique id of every update, skipping updates whose id is already in the graph. Must be one of `properties`. (optional)
     ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, src, dst, time, properties = None, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true))]
+    ///     dict: A report with the number of rows `loaded`, `skipped` and skipped as `duplicates` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, src, dst, time, properties = None, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true, error_policy = None, dead_letter_file = None, deduplicate = false, event_id = None))]
     fn load_edges_from_pandas(
         &self,
         df: &PyAny,
@@ -520,7 +541,16 @@ impl PyPersistentGraph {
         shared_const_properties: Option<HashMap<String, Prop>>,
         layer: Option<&str>,
         layer_in_df: Option<bool>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+        deduplicate: bool,
+        event_id: Option<&str>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
+        let mut options = LoadOptions::sequential().dead_letters(dead_letters.clone());
+        if let Some(dedup) = utils::deduplicator(&self.graph, deduplicate, event_id) {
+            options = options.deduplicate(dedup);
+        }
         utils::load_edges_from_pandas(
             &self.graph.0,
             df,
@@ -532,7 +562,9 @@ impl PyPersistentGraph {
             shared_const_properties,
             layer,
             layer_in_df,
-        )
+            &options,
+        )?;
+        Ok(dead_letters.report())
     }
 
     /// Load edges deletions from a Pandas DataFrame into the graph.
@@ -544,10 +576,12 @@ impl PyPersistentGraph {
     ///     time (str): The column name for the update timestamps.
     ///     layer (str): The edge layer name (optional) Defaults to None.
     ///     layer_in_df (bool): Whether the layer name should be used to look up the values in a column of the dateframe or if it should be used directly as the layer for all edges (optional) defaults to True.
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
     ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, src, dst, time, layer = None, layer_in_df = true))]
+    ///     dict: A report with the number of rows `loaded` and `skipped` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, src, dst, time, layer = None, layer_in_df = true, error_policy = None, dead_letter_file = None))]
     fn load_edges_deletions_from_pandas(
         &self,
         df: &PyAny,
@@ -556,8 +590,11 @@ impl PyPersistentGraph {
         time: &str,
         layer: Option<&str>,
         layer_in_df: Option<bool>,
-    ) -> Result<(), GraphError> {
-        let graph = &self.graph.0;
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+    ) -> Result<LoadReport, GraphError> {
+        let graph = &self.graph;
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
         Python::with_gil(|py| {
             let size: usize = py
                 .eval(
@@ -579,20 +616,20 @@ impl PyPersistentGraph {
             df.check_cols_exist(&cols_to_check)?;
             load_edges_deletions_from_df(
                 &df,
-                size,
                 src,
                 dst,
                 time,
                 layer,
                 layer_in_df.unwrap_or(true),
                 graph,
+                &LoadOptions::sequential().dead_letters(dead_letters.clone()),
             )
             .map_err(|e| GraphLoadException::new_err(format!("{:?}", e)))?;
 
             Ok::<(), PyErr>(())
         })
         .map_err(|e| GraphError::LoadFailure(format!("Failed to load graph {e:?}")))?;
-        Ok(())
+        Ok(dead_letters.report())
     }
 
     /// Load node properties from a Pandas DataFrame.
@@ -602,24 +639,31 @@ impl PyPersistentGraph {
     ///     id(str): The column name for the node IDs.
     ///     const_properties (List<str>): List of constant node property column names. Defaults to None. (optional)
     ///     shared_const_properties (<HashMap<String, Prop>>):  A dictionary of constant properties that will be added to every node. Defaults to None. (optional)
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
     ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, id, const_properties = None, shared_const_properties = None))]
+    ///     dict: A report with the number of rows `loaded` and `skipped` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, id, const_properties = None, shared_const_properties = None, error_policy = None, dead_letter_file = None))]
     fn load_node_props_from_pandas(
         &self,
         df: &PyAny,
         id: &str,
         const_properties: Option<Vec<&str>>,
         shared_const_properties: Option<HashMap<String, Prop>>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
         utils::load_node_props_from_pandas(
             &self.graph.0,
             df,
             id,
             const_properties,
             shared_const_properties,
-        )
+            &LoadOptions::sequential().dead_letters(dead_letters.clone()),
+        )?;
+        Ok(dead_letters.report())
     }
 
     /// Load edge properties from a Pandas DataFrame.
@@ -632,10 +676,12 @@ impl PyPersistentGraph {
     ///     shared_const_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
     ///     layer (str): Layer name. Defaults to None.  (optional)
     ///     layer_in_df (bool): Whether the layer name should be used to look up the values in a column of the data frame or if it should be used directly as the layer for all edges (optional) defaults to True.
+    ///     error_policy (str): What to do with rows that cannot be loaded: "fail" (default) aborts the load, "skip" skips them and "collect" skips them and lists them in the report. (optional)
+    ///     dead_letter_file (str): Path of a file the rejected rows are written to as JSON lines. (optional)
     ///
     /// Returns:
-    ///     Result<(), GraphError>: Result of the operation.
-    #[pyo3(signature = (df, src, dst, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true))]
+    ///     dict: A report with the number of rows `loaded` and `skipped` and the `rejected` rows (with the "collect" policy).
+    #[pyo3(signature = (df, src, dst, const_properties = None, shared_const_properties = None, layer = None, layer_in_df = true, error_policy = None, dead_letter_file = None))]
     fn load_edge_props_from_pandas(
         &self,
         df: &PyAny,
@@ -645,7 +691,10 @@ impl PyPersistentGraph {
         shared_const_properties: Option<HashMap<String, Prop>>,
         layer: Option<&str>,
         layer_in_df: Option<bool>,
-    ) -> Result<(), GraphError> {
+        error_policy: Option<&str>,
+        dead_letter_file: Option<PathBuf>,
+    ) -> Result<LoadReport, GraphError> {
+        let dead_letters = utils::dead_letters(error_policy, dead_letter_file)?;
         utils::load_edge_props_from_pandas(
             &self.graph.0,
             df,
@@ -655,6 +704,26 @@ impl PyPersistentGraph {
             shared_const_properties,
             layer,
             layer_in_df,
-        )
+            &LoadOptions::sequential().dead_letters(dead_letters.clone()),
+        )?;
+        Ok(dead_letters.report())
+    }
+
+    /// Load a file, or a directory of files, into the graph as described by a mapping spec.
+    ///
+    /// The spec is written as JSON, YAML or TOML and names the columns holding the time, source,
+    /// destination, layer, node type and properties of the updates, see the documentation of
+    /// `raphtory::graph_loader::source::mapping` for the available options.
+    ///
+    /// Arguments:
+    ///     path (str): The data file or directory of data files (CSV or NDJSON, optionally gzip or bzip2 compressed).
+    ///     spec (str): The path of a spec file or the spec itself.
+    ///
+    /// Returns:
+    ///     dict: A report with the number of records `loaded` and `skipped` and the `rejected` records (with the "collect" error policy of the spec).
+    #[pyo3(signature = (path, spec))]
+    fn load_with_mapping(&self, path: PathBuf, spec: &str) -> Result<LoadReport, GraphError> {
+        let spec = MappingSpec::from_path_or_str(spec)?;
+        load_with_mapping(path, &spec, &self.graph)
     }
 }
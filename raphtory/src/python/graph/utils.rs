@@ -1,13 +1,23 @@
-use crate::core::{entities::graph::tgraph::InternalGraph, utils::errors::GraphError, Prop};
-use pyo3::{prelude::*, types::IntoPyDict};
-use std::collections::HashMap;
-
-use super::pandas::{
-    dataframe::{process_pandas_py_df, GraphLoadException},
-    loaders::{
+use crate::{
+    core::{
+        entities::graph::tgraph::InternalGraph,
+        utils::{
+            dead_letter::{DeadLetters, LoadReport},
+            dedup::{DedupKey, Deduplicator},
+            errors::GraphError,
+        },
+        Prop,
+    },
+    db::api::view::StaticGraphViewOps,
+    io::arrow::df_loaders::{
         load_edges_from_df, load_edges_props_from_df, load_node_props_from_df, load_nodes_from_df,
+        LoadOptions,
     },
 };
+use pyo3::{prelude::*, types::IntoPyDict};
+use std::{collections::HashMap, path::PathBuf};
+
+use super::pandas::dataframe::{process_pandas_py_df, GraphLoadException};
 
 pub fn load_nodes_from_pandas(
     graph: &InternalGraph,
@@ -19,6 +29,7 @@ pub fn load_nodes_from_pandas(
     properties: Option<Vec<&str>>,
     const_properties: Option<Vec<&str>>,
     shared_const_properties: Option<HashMap<String, Prop>>,
+    options: &LoadOptions,
 ) -> Result<(), GraphError> {
     Python::with_gil(|py| {
         let size: usize = py
@@ -43,7 +54,6 @@ pub fn load_nodes_from_pandas(
 
         load_nodes_from_df(
             &df,
-            size,
             id,
             time,
             properties,
@@ -52,6 +62,7 @@ pub fn load_nodes_from_pandas(
             node_type,
             node_type_in_df.unwrap_or(true),
             graph,
+            options,
         )
         .map_err(|e| GraphLoadException::new_err(format!("{:?}", e)))?;
         Ok::<(), PyErr>(())
@@ -71,6 +82,7 @@ pub fn load_edges_from_pandas(
     shared_const_properties: Option<HashMap<String, Prop>>,
     layer: Option<&str>,
     layer_in_df: Option<bool>,
+    options: &LoadOptions,
 ) -> Result<(), GraphError> {
     Python::with_gil(|py| {
         let size: usize = py
@@ -95,7 +107,6 @@ pub fn load_edges_from_pandas(
         df.check_cols_exist(&cols_to_check)?;
         load_edges_from_df(
             &df,
-            size,
             src,
             dst,
             time,
@@ -104,7 +115,9 @@ pub fn load_edges_from_pandas(
             shared_const_properties,
             layer,
             layer_in_df.unwrap_or(true),
+            None,
             graph,
+            options,
         )
         .map_err(|e| GraphLoadException::new_err(format!("{:?}", e)))?;
 
@@ -120,6 +133,7 @@ pub fn load_node_props_from_pandas(
     id: &str,
     const_properties: Option<Vec<&str>>,
     shared_const_properties: Option<HashMap<String, Prop>>,
+    options: &LoadOptions,
 ) -> Result<(), GraphError> {
     Python::with_gil(|py| {
         let size: usize = py
@@ -136,11 +150,11 @@ pub fn load_node_props_from_pandas(
 
         load_node_props_from_df(
             &df,
-            size,
             id,
             const_properties,
             shared_const_properties,
             graph,
+            options,
         )
         .map_err(|e| GraphLoadException::new_err(format!("{:?}", e)))?;
 
@@ -159,6 +173,7 @@ pub fn load_edge_props_from_pandas(
     shared_const_properties: Option<HashMap<String, Prop>>,
     layer: Option<&str>,
     layer_in_df: Option<bool>,
+    options: &LoadOptions,
 ) -> Result<(), GraphError> {
     Python::with_gil(|py| {
         let size: usize = py
@@ -179,7 +194,6 @@ pub fn load_edge_props_from_pandas(
         df.check_cols_exist(&cols_to_check)?;
         load_edges_props_from_df(
             &df,
-            size,
             src,
             dst,
             const_properties,
@@ -187,6 +201,7 @@ pub fn load_edge_props_from_pandas(
             layer,
             layer_in_df.unwrap_or(true),
             graph,
+            options,
         )
         .map_err(|e| GraphLoadException::new_err(format!("{:?}", e)))?;
         df.check_cols_exist(&cols_to_check)?;
@@ -195,3 +210,65 @@ pub fn load_edge_props_from_pandas(
     .map_err(|e| GraphError::LoadFailure(format!("Failed to load graph {e:?}")))?;
     Ok(())
 }
+
+/// Dead letters for the `error_policy` and `dead_letter_file` arguments of the pandas loaders
+pub(crate) fn dead_letters(
+    error_policy: Option<&str>,
+    dead_letter_file: Option<PathBuf>,
+) -> Result<DeadLetters, GraphError> {
+    let policy = error_policy
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or_default();
+    match dead_letter_file {
+        Some(path) => DeadLetters::with_file(policy, path),
+        None => Ok(DeadLetters::new(policy)),
+    }
+}
+
+/// Deduplicator for the `deduplicate` and `event_id` arguments of the pandas edge loaders
+pub(crate) fn deduplicator<G: StaticGraphViewOps>(
+    graph: &G,
+    deduplicate: bool,
+    event_id: Option<&str>,
+) -> Option<Deduplicator> {
+    let key = match event_id {
+        Some(event_id) => DedupKey::EventId(event_id.to_owned()),
+        None if deduplicate => DedupKey::Content,
+        None => return None,
+    };
+    Some(Deduplicator::for_graph(key, graph))
+}
+
+impl IntoPy<PyObject> for LoadReport {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        let rejected: Vec<PyObject> = self
+            .rejected
+            .into_iter()
+            .map(|record| {
+                [
+                    (
+                        "file",
+                        record
+                            .file
+                            .map(|file| file.display().to_string())
+                            .into_py(py),
+                    ),
+                    ("line", record.line.into_py(py)),
+                    ("record", record.record.into_py(py)),
+                    ("error", record.error.into_py(py)),
+                ]
+                .into_py_dict(py)
+                .into_py(py)
+            })
+            .collect();
+        [
+            ("loaded", self.loaded.into_py(py)),
+            ("skipped", self.skipped.into_py(py)),
+            ("duplicates", self.duplicates.into_py(py)),
+            ("rejected", rejected.into_py(py)),
+        ]
+        .into_py_dict(py)
+        .into_py(py)
+    }
+}
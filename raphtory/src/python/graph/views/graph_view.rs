@@ -4,7 +4,14 @@ use rayon::prelude::*;
 use std::collections::HashMap;
 
 use crate::{
-    core::{entities::nodes::node_ref::NodeRef, utils::errors::GraphError, ArcStr},
+    core::{
+        entities::{
+            graph::memory::{MemoryStats, MemoryUsage},
+            nodes::node_ref::NodeRef,
+        },
+        utils::errors::GraphError,
+        ArcStr,
+    },
     db::{
         api::{
             properties::Properties,
@@ -25,6 +32,7 @@ use crate::{
             },
         },
     },
+    io::arrow::df_export::GraphExportOps,
     prelude::*,
     python::{
         graph::{edge::PyEdge, node::PyNode},
@@ -46,6 +54,52 @@ impl IntoPy<PyObject> for MaterializedGraph {
     }
 }
 
+impl IntoPy<PyObject> for MemoryUsage {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        HashMap::from([("bytes", self.bytes), ("count", self.count)]).into_py(py)
+    }
+}
+
+impl IntoPy<PyObject> for MemoryStats {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        let mut stats: HashMap<&str, PyObject> = self
+            .components()
+            .into_iter()
+            .map(|(name, usage)| (name, usage.into_py(py)))
+            .collect();
+        stats.insert("total_bytes", self.total_bytes().into_py(py));
+        let layers: HashMap<ArcStr, PyObject> = self
+            .layers
+            .into_iter()
+            .map(|layer| {
+                let usage = HashMap::from([
+                    ("adjacency", layer.adjacency),
+                    ("edge_updates", layer.edge_updates),
+                    ("edge_properties", layer.edge_properties),
+                ]);
+                (layer.name, usage.into_py(py))
+            })
+            .collect();
+        stats.insert("layers", layers.into_py(py));
+        let properties: Vec<PyObject> = self
+            .properties
+            .into_iter()
+            .map(|property| {
+                HashMap::from([
+                    ("entity", property.entity.to_string().into_py(py)),
+                    ("name", property.name.into_py(py)),
+                    ("constant", property.constant.into_py(py)),
+                    ("bytes", property.usage.bytes.into_py(py)),
+                    ("count", property.usage.count.into_py(py)),
+                ])
+                .into_py(py)
+            })
+            .collect();
+        stats.insert("properties", properties.into_py(py));
+        stats.into_py(py)
+    }
+}
+
 impl IntoPy<PyObject> for DynamicGraph {
     fn into_py(self, py: Python<'_>) -> PyObject {
         PyGraphView::from(self).into_py(py)
@@ -351,6 +405,29 @@ impl PyGraphView {
         self.graph.materialize()
     }
 
+    /// Estimate the memory used by the graph
+    ///
+    /// Views report the memory of the whole underlying graph.
+    ///
+    /// Returns:
+    ///    dict: bytes and number of entries (or updates) for each storage component, with breakdowns
+    ///    per layer (`layers`) and per property key (`properties`), or None if the graph is not stored in memory
+    fn memory_stats(&self) -> Option<MemoryStats> {
+        self.graph.memory_stats()
+    }
+
+    /// Write the graph view to a directory as parquet files
+    ///
+    /// The directory gets one file per table: nodes, node_properties, edges, edge_deletions,
+    /// edge_properties, graph_properties and graph_constant_properties. The files use the same
+    /// column names as the parquet loaders.
+    ///
+    /// Arguments:
+    ///    path (str): the directory to write to
+    fn to_parquet(&self, path: &str) -> Result<(), GraphError> {
+        self.graph.to_parquet(path)
+    }
+
     /// Get bincode encoded graph
     pub fn bincode<'py>(&'py self, py: Python<'py>) -> Result<&'py PyBytes, GraphError> {
         let bytes = self.graph.materialize()?.bincode()?;
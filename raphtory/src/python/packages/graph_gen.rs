@@ -1,10 +1,19 @@
 //! Provides functionality for generating graphs for testing and benchmarking.
-//! Allows us to generate graphs using the preferential attachment model and
-//! the random attachment model.
+//! Allows us to generate graphs using the preferential attachment, random attachment,
+//! Erdős–Rényi, Watts–Strogatz, stochastic block, configuration, activity-driven and Hawkes
+//! models.
 use crate::{
+    core::utils::errors::GraphError,
     graphgen::{
+        activity_driven::{activity_driven as ad, ActivityDriven},
+        configuration_model::configuration_model as cm,
+        erdos_renyi::erdos_renyi as er,
+        hawkes::{hawkes as hp, Hawkes},
         preferential_attachment::ba_preferential_attachment as pa,
         random_attachment::random_attachment as ra,
+        stochastic_block_model::stochastic_block_model as sbm,
+        synthesis::Synthesis,
+        watts_strogatz::watts_strogatz as ws,
     },
     python::graph::graph::PyGraph,
 };
@@ -67,3 +76,231 @@ pub fn ba_preferential_attachment(
 ) {
     pa(&g.graph, nodes_to_add, edges_per_step, seed);
 }
+
+fn synthesis(time_range: Option<(i64, i64)>, layers: Option<Vec<String>>) -> Synthesis {
+    Synthesis {
+        time_range: time_range.map(|(start, end)| start..end),
+        layers: layers.unwrap_or_default(),
+        properties: vec![],
+    }
+}
+
+/// Generates a graph using the Erdős–Rényi model.
+///
+/// Adds `nodes` nodes with ids `0..nodes` and connects every ordered pair of distinct nodes
+/// with probability `p`.
+///
+/// Arguments:
+///    g: The graph you wish to add nodes and edges to
+///    nodes: The number of nodes
+///    p: The probability of every directed edge
+///    seed: The seed used in rng, an array of length 32 containing ints (ints must have a max size of u8)
+///    time_range: The (start, end) range edge times are drawn from uniformly, all edges are added at 0 if None
+///    layers: The layers edges are assigned to uniformly at random, the default layer if None
+///
+/// Returns:
+///
+/// None
+#[pyfunction]
+#[pyo3[signature = (g, nodes, p, seed=None, time_range=None, layers=None)]]
+pub fn erdos_renyi(
+    g: &PyGraph,
+    nodes: usize,
+    p: f64,
+    seed: Option<[u8; 32]>,
+    time_range: Option<(i64, i64)>,
+    layers: Option<Vec<String>>,
+) -> Result<(), GraphError> {
+    er(&g.graph, nodes, p, &synthesis(time_range, layers), seed)
+}
+
+/// Generates a graph using the Watts–Strogatz small-world model.
+///
+/// Adds `nodes` nodes with ids `0..nodes` arranged in a ring where every node is connected to
+/// its `k / 2` successors, then rewires every edge with probability `beta`.
+///
+/// Arguments:
+///    g: The graph you wish to add nodes and edges to
+///    nodes: The number of nodes
+///    k: The even number of neighbours of every node in the ring
+///    beta: The probability of rewiring every edge
+///    seed: The seed used in rng, an array of length 32 containing ints (ints must have a max size of u8)
+///    time_range: The (start, end) range edge times are drawn from uniformly, all edges are added at 0 if None
+///    layers: The layers edges are assigned to uniformly at random, the default layer if None
+///
+/// Returns:
+///
+/// None
+#[pyfunction]
+#[pyo3[signature = (g, nodes, k, beta, seed=None, time_range=None, layers=None)]]
+pub fn watts_strogatz(
+    g: &PyGraph,
+    nodes: usize,
+    k: usize,
+    beta: f64,
+    seed: Option<[u8; 32]>,
+    time_range: Option<(i64, i64)>,
+    layers: Option<Vec<String>>,
+) -> Result<(), GraphError> {
+    ws(
+        &g.graph,
+        nodes,
+        k,
+        beta,
+        &synthesis(time_range, layers),
+        seed,
+    )
+}
+
+/// Generates a graph using the stochastic block model.
+///
+/// Adds one block of nodes per entry of `sizes` with node type `block_<i>` and connects every
+/// ordered pair of nodes in blocks `i` and `j` with probability `probabilities[i][j]`.
+///
+/// Arguments:
+///    g: The graph you wish to add nodes and edges to
+///    sizes: The number of nodes in every block
+///    probabilities: The square matrix of edge probabilities between blocks
+///    seed: The seed used in rng, an array of length 32 containing ints (ints must have a max size of u8)
+///    time_range: The (start, end) range edge times are drawn from uniformly, all edges are added at 0 if None
+///    layers: The layers edges are assigned to uniformly at random, the default layer if None
+///
+/// Returns:
+///
+/// None
+#[pyfunction]
+#[pyo3[signature = (g, sizes, probabilities, seed=None, time_range=None, layers=None)]]
+pub fn stochastic_block_model(
+    g: &PyGraph,
+    sizes: Vec<usize>,
+    probabilities: Vec<Vec<f64>>,
+    seed: Option<[u8; 32]>,
+    time_range: Option<(i64, i64)>,
+    layers: Option<Vec<String>>,
+) -> Result<(), GraphError> {
+    sbm(
+        &g.graph,
+        &sizes,
+        &probabilities,
+        &synthesis(time_range, layers),
+        seed,
+    )
+}
+
+/// Generates a graph using the configuration model.
+///
+/// Adds one node per entry of `degrees` and pairs their edge stubs uniformly at random, keeping
+/// self-loops and repeated edges.
+///
+/// Arguments:
+///    g: The graph you wish to add nodes and edges to
+///    degrees: The degree of every node, summing to an even number
+///    seed: The seed used in rng, an array of length 32 containing ints (ints must have a max size of u8)
+///    time_range: The (start, end) range edge times are drawn from uniformly, all edges are added at 0 if None
+///    layers: The layers edges are assigned to uniformly at random, the default layer if None
+///
+/// Returns:
+///
+/// None
+#[pyfunction]
+#[pyo3[signature = (g, degrees, seed=None, time_range=None, layers=None)]]
+pub fn configuration_model(
+    g: &PyGraph,
+    degrees: Vec<usize>,
+    seed: Option<[u8; 32]>,
+    time_range: Option<(i64, i64)>,
+    layers: Option<Vec<String>>,
+) -> Result<(), GraphError> {
+    cm(&g.graph, &degrees, &synthesis(time_range, layers), seed)
+}
+
+/// Generates a temporal graph using the activity-driven model.
+///
+/// Every node gets an activity drawn from a power law. In each step a node is active with
+/// probability `eta` times its activity and connects to `edges_per_activation` random nodes.
+///
+/// Arguments:
+///    g: The graph you wish to add nodes and edges to
+///    nodes: The number of nodes
+///    steps: The number of time steps
+///    edges_per_activation: The number of edges an active node adds
+///    gamma: The exponent of the activity distribution
+///    epsilon: The smallest activity
+///    eta: The factor turning activities into activation probabilities
+///    step_duration: The time between consecutive steps
+///    seed: The seed used in rng, an array of length 32 containing ints (ints must have a max size of u8)
+///    layers: The layers edges are assigned to uniformly at random, the default layer if None
+///
+/// Returns:
+///
+/// None
+#[pyfunction]
+#[pyo3[signature = (g, nodes, steps, edges_per_activation=2, gamma=2.1, epsilon=0.001, eta=10.0, step_duration=1, seed=None, layers=None)]]
+#[allow(clippy::too_many_arguments)]
+pub fn activity_driven(
+    g: &PyGraph,
+    nodes: usize,
+    steps: usize,
+    edges_per_activation: usize,
+    gamma: f64,
+    epsilon: f64,
+    eta: f64,
+    step_duration: i64,
+    seed: Option<[u8; 32]>,
+    layers: Option<Vec<String>>,
+) -> Result<(), GraphError> {
+    let model = ActivityDriven {
+        nodes,
+        steps,
+        edges_per_activation,
+        gamma,
+        epsilon,
+        eta,
+        step_duration,
+    };
+    ad(&g.graph, &model, &synthesis(None, layers), seed)
+}
+
+/// Generates a temporal graph of bursty interactions using Hawkes processes.
+///
+/// Chooses `pairs` random ordered pairs of nodes whose interactions follow a self-exciting
+/// process with rate `baseline + excitation * sum(exp(-decay * (t - t_i)))`.
+///
+/// Arguments:
+///    g: The graph you wish to add nodes and edges to
+///    nodes: The number of nodes
+///    pairs: The number of interacting pairs
+///    duration: The length of the simulated period
+///    baseline: The rate of spontaneous interactions
+///    excitation: The increase of the rate after every interaction
+///    decay: The rate at which the increase decays, larger than excitation
+///    seed: The seed used in rng, an array of length 32 containing ints (ints must have a max size of u8)
+///    layers: The layers edges are assigned to uniformly at random, the default layer if None
+///
+/// Returns:
+///
+/// None
+#[pyfunction]
+#[pyo3[signature = (g, nodes, pairs, duration, baseline=0.01, excitation=0.5, decay=1.0, seed=None, layers=None)]]
+#[allow(clippy::too_many_arguments)]
+pub fn hawkes(
+    g: &PyGraph,
+    nodes: usize,
+    pairs: usize,
+    duration: i64,
+    baseline: f64,
+    excitation: f64,
+    decay: f64,
+    seed: Option<[u8; 32]>,
+    layers: Option<Vec<String>>,
+) -> Result<(), GraphError> {
+    let model = Hawkes {
+        nodes,
+        pairs,
+        baseline,
+        excitation,
+        decay,
+        duration,
+    };
+    hp(&g.graph, &model, &synthesis(None, layers), seed)
+}
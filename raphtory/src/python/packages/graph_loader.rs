@@ -1,6 +1,9 @@
 //! `GraphLoader` provides some default implementations for loading a pre-built graph.
 //! This base class is used to load in-built graphs such as the LOTR, reddit and StackOverflow.
-use crate::python::graph::graph::PyGraph;
+use crate::{
+    graph_loader::source::edge_list::{EdgeListLoader, TimeUnit},
+    python::graph::graph::PyGraph,
+};
 use pyo3::prelude::*;
 use std::path::PathBuf;
 use tokio::runtime::Runtime;
@@ -133,3 +136,48 @@ pub fn neo4j_movie_graph(
 pub fn karate_club_graph() -> PyResult<Py<PyGraph>> {
     PyGraph::py_from_db_graph(crate::graph_loader::example::karate_club::karate_club_graph())
 }
+
+/// Load a temporal edge list, such as the SNAP `sx-*` and `CollegeMsg` datasets, into a graph.
+///
+/// The delimiter, header, comment lines, time column and time unit are detected from the start
+/// of every file unless they are given. Files ending in `.gz` or `.bz2` are decompressed and
+/// directories are loaded file by file. Files in the Trivial Graph Format (TGF) are supported too.
+///
+/// Arguments:
+///     path: the file or directory to load
+///     delimiter: the delimiter of the fields, `" "` for any whitespace
+///     header: whether the files have a header
+///     time_column: the index of the time column
+///     time_unit: one of "raw", "seconds", "milliseconds" or "datetime"
+///     layer: the layer of the loaded edges
+///
+/// Returns:
+///     A Graph containing the edges
+#[pyfunction]
+#[pyo3(signature = (path, delimiter=None, header=None, time_column=None, time_unit=None, layer=None))]
+pub fn edge_list_graph(
+    path: PathBuf,
+    delimiter: Option<char>,
+    header: Option<bool>,
+    time_column: Option<usize>,
+    time_unit: Option<&str>,
+    layer: Option<&str>,
+) -> PyResult<Py<PyGraph>> {
+    let mut loader = EdgeListLoader::new(path);
+    if let Some(delimiter) = delimiter {
+        loader = loader.set_delimiter(delimiter);
+    }
+    if let Some(header) = header {
+        loader = loader.set_header(header);
+    }
+    if let Some(time_column) = time_column {
+        loader = loader.set_time_column(Some(time_column));
+    }
+    if let Some(time_unit) = time_unit {
+        loader = loader.set_time_unit(time_unit.parse::<TimeUnit>()?);
+    }
+    if let Some(layer) = layer {
+        loader = loader.set_layer(layer);
+    }
+    PyGraph::py_from_db_graph(loader.graph()?)
+}
@@ -90,6 +90,18 @@ impl Repr for u64 {
     }
 }
 
+impl Repr for i8 {
+    fn repr(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Repr for i16 {
+    fn repr(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl Repr for i32 {
     fn repr(&self) -> String {
         self.to_string()
@@ -1,13 +1,15 @@
 use super::document::PyDocument;
 use crate::{
-    core::{DocumentInput, Prop},
+    core::{DocumentInput, Prop, PropArray},
     db::graph::views::deletion_graph::PersistentGraph,
     python::{graph::views::graph_view::PyGraphView, types::repr::Repr},
 };
 use pyo3::{
-    exceptions::PyTypeError, types::PyBool, FromPyObject, IntoPy, PyAny, PyObject, PyResult,
-    Python, ToPyObject,
+    exceptions::PyTypeError,
+    types::{PyBool, PyBytes},
+    FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject,
 };
+use rust_decimal::Decimal;
 use std::{ops::Deref, sync::Arc};
 
 impl ToPyObject for Prop {
@@ -30,6 +32,11 @@ impl ToPyObject for Prop {
             Prop::F32(v) => v.into_py(py),
             Prop::List(v) => v.deref().clone().into_py(py), // Fixme: optimise the clone here?
             Prop::Map(v) => v.deref().clone().into_py(py),
+            Prop::I8(v) => v.into_py(py),
+            Prop::I16(v) => v.into_py(py),
+            Prop::Decimal(v) => v.into_py(py),
+            Prop::Bytes(v) => PyBytes::new(py, v).into_py(py),
+            Prop::Array(v) => v.clone().into_py(py),
         }
     }
 }
@@ -54,16 +61,61 @@ impl IntoPy<PyObject> for Prop {
             Prop::F32(v) => v.into_py(py),
             Prop::List(v) => v.deref().clone().into_py(py), // Fixme: optimise the clone here?
             Prop::Map(v) => v.deref().clone().into_py(py),
+            Prop::I8(v) => v.into_py(py),
+            Prop::I16(v) => v.into_py(py),
+            Prop::Decimal(v) => v.into_py(py),
+            Prop::Bytes(v) => PyBytes::new(py, &v).into_py(py),
+            Prop::Array(v) => v.into_py(py),
         }
     }
 }
 
+impl IntoPy<PyObject> for PropArray {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            PropArray::I32(v) => v.to_vec().into_py(py),
+            PropArray::I64(v) => v.to_vec().into_py(py),
+            PropArray::F32(v) => v.to_vec().into_py(py),
+            PropArray::F64(v) => v.to_vec().into_py(py),
+        }
+    }
+}
+
+/// Extract 1-dimensional numpy arrays of supported numeric types as dense arrays
+fn extract_numpy_array(ob: &PyAny) -> Option<PropArray> {
+    if ob.get_type().name().ok()? != "ndarray"
+        || ob.getattr("ndim").ok()?.extract::<usize>().ok()? != 1
+    {
+        return None;
+    }
+    let dtype: String = ob.getattr("dtype").ok()?.str().ok()?.extract().ok()?;
+    let values = ob.call_method0("tolist").ok()?;
+    match dtype.as_str() {
+        "int32" => values.extract::<Vec<i32>>().ok().map(|v| v.into()),
+        "int64" => values.extract::<Vec<i64>>().ok().map(|v| v.into()),
+        "float32" => values.extract::<Vec<f32>>().ok().map(|v| v.into()),
+        "float64" => values.extract::<Vec<f64>>().ok().map(|v| v.into()),
+        _ => None,
+    }
+}
+
 // Manually implemented to make sure we don't end up with f32/i32/u32 from python ints/floats
 impl<'source> FromPyObject<'source> for Prop {
     fn extract(ob: &'source PyAny) -> PyResult<Self> {
         if ob.is_instance_of::<PyBool>() {
             return Ok(Prop::Bool(ob.extract()?));
         }
+        if let Ok(bytes) = ob.downcast::<PyBytes>() {
+            return Ok(Prop::bytes(bytes.as_bytes()));
+        }
+        // checked before floats as decimals can be converted to floats
+        let decimal_type = ob.py().import("decimal")?.getattr("Decimal")?;
+        if ob.is_instance(decimal_type)? {
+            return Ok(Prop::Decimal(ob.extract::<Decimal>()?));
+        }
+        if let Some(array) = extract_numpy_array(ob) {
+            return Ok(Prop::Array(array));
+        }
         if let Ok(v) = ob.extract() {
             return Ok(Prop::I64(v));
         }
@@ -118,6 +170,11 @@ impl Repr for Prop {
             Prop::F32(v) => v.repr(),
             Prop::List(v) => v.repr(),
             Prop::Map(v) => v.repr(),
+            Prop::I8(v) => v.repr(),
+            Prop::I16(v) => v.repr(),
+            Prop::Decimal(v) => format!("Decimal('{}')", v),
+            Prop::Bytes(v) => format!("b'{}'", v.escape_ascii()),
+            Prop::Array(v) => v.to_string(),
         }
     }
 }
@@ -5,6 +5,7 @@ pub mod into_indexed;
 use std::{collections::HashSet, ops::Deref, path::Path, sync::Arc};
 
 use rayon::{prelude::ParallelIterator, slice::ParallelSlice};
+use rust_decimal::prelude::ToPrimitive;
 use tantivy::{
     collector::TopDocs,
     schema::{Field, Schema, SchemaBuilder, Value, FAST, INDEXED, STORED, TEXT},
@@ -167,13 +168,13 @@ impl<'graph, G: GraphViewOps<'graph>> IndexedGraph<G> {
             Prop::I64(_) => {
                 schema.add_i64_field(prop, INDEXED);
             }
-            Prop::I32(_) => {
+            Prop::I32(_) | Prop::I16(_) | Prop::I8(_) => {
                 schema.add_i64_field(prop, INDEXED);
             }
             Prop::F64(_) => {
                 schema.add_f64_field(prop, INDEXED);
             }
-            Prop::F32(_) => {
+            Prop::F32(_) | Prop::Decimal(_) => {
                 schema.add_f64_field(prop, INDEXED);
             }
             Prop::Bool(_) => {
@@ -328,12 +329,22 @@ impl<'graph, G: GraphViewOps<'graph>> IndexedGraph<G> {
             Prop::I32(prop_i32) => {
                 document.add_i64(prop_field, i64::from(prop_i32));
             }
+            Prop::I16(prop_i16) => {
+                document.add_i64(prop_field, i64::from(prop_i16));
+            }
+            Prop::I8(prop_i8) => {
+                document.add_i64(prop_field, i64::from(prop_i8));
+            }
             Prop::F64(prop_f64) => {
                 document.add_f64(prop_field, prop_f64);
             }
             Prop::F32(prop_f32) => {
                 document.add_f64(prop_field, f64::from(prop_f32));
             }
+            Prop::Decimal(prop_decimal) => {
+                // indexed as float, exact values are kept in the graph
+                document.add_f64(prop_field, prop_decimal.to_f64().unwrap_or(f64::NAN));
+            }
             Prop::Bool(prop_bool) => {
                 document.add_bool(prop_field, prop_bool);
             }
@@ -761,6 +772,14 @@ impl<G: StaticGraphViewOps + InternalAdditionOps> InternalAdditionOps for Indexe
         self.graph.next_event_id()
     }
     #[inline]
+    fn read_event_id(&self) -> usize {
+        self.graph.read_event_id()
+    }
+    #[inline]
+    fn read_generation(&self) -> usize {
+        self.graph.read_generation()
+    }
+    #[inline]
     fn resolve_layer(&self, layer: Option<&str>) -> usize {
         self.graph.resolve_layer(layer)
     }